@@ -9,9 +9,9 @@ mod circuits {
     // =========================================================================
 
     // Type aliases for readability
-    type PlanetState = Pack<[u32; 15]>;
+    type PlanetState = Pack<[u32; 23]>;
 
-    // PlanetState field indices (into [u32; 15])
+    // PlanetState field indices (into [u32; 23])
     const PS_BODY_TYPE: usize = 0;
     const PS_SIZE: usize = 1;
     const PS_MAX_SHIP_CAP: usize = 2;
@@ -27,6 +27,18 @@ mod circuits {
     const PD_METAL: usize = 12;
     const PD_OWNER_EXISTS: usize = 13;
     const PD_OWNER_ID: usize = 14;
+    // Pre-comet capacities (level upgrades applied); comets multiply on top
+    const PS_BASE_SHIP_CAP: usize = 15;
+    const PS_BASE_METAL_CAP: usize = 16;
+    // Slot the current owner captured the planet in (0 = never captured)
+    const PD_LAST_CAPTURE_SLOT: usize = 17;
+    // Pre-comet gen speeds, range and velocity (level upgrades applied)
+    const PS_BASE_SHIP_GEN: usize = 18;
+    const PS_BASE_METAL_GEN: usize = 19;
+    const PS_BASE_RANGE: usize = 20;
+    const PS_BASE_VELOCITY: usize = 21;
+    // High 32 bits of the 64-bit owner id (PD_OWNER_ID holds the low 32)
+    const PD_OWNER_ID_HI: usize = 22;
 
     // =========================================================================
     // Input structs
@@ -46,13 +58,25 @@ mod circuits {
     pub struct SpawnInput {
         pub x: u64,
         pub y: u64,
-        pub player_id: u32,
+        pub player_id: u64,
         pub source_planet_id: u32,
     }
 
-    // ProcessMoveInput: packed as [u32; 8] for compact encryption (2 CTs).
+    /// Spawn coordinates kept on the spawn planet (Enc<Mxe>) so later spawns
+    /// can be checked against them without revealing either location.
+    /// Stored in biased space (see bias_coord).
+    pub struct SpawnCoords {
+        pub x: u64,
+        pub y: u64,
+    }
+
+    /// Number of existing spawns init_spawn_planet can check separation against.
+    const MAX_SEPARATION_CHECKS: usize = 8;
+
+    // ProcessMoveInput: packed as [u32; 11] for compact encryption (2 CTs).
     // Coordinates are biased (coord + COORD_BIAS) to be positive u32.
-    type ProcessMoveInputPacked = Pack<[u32; 8]>;
+    // 64-bit player ids are split into a low word and a *_HI word.
+    type ProcessMoveInputPacked = Pack<[u32; 11]>;
     const MI_PLAYER_ID: usize = 0;
     const MI_SOURCE_PLANET_ID: usize = 1;
     const MI_SHIPS_TO_SEND: usize = 2;
@@ -61,23 +85,67 @@ mod circuits {
     const MI_SOURCE_Y: usize = 5;
     const MI_TARGET_X: usize = 6;
     const MI_TARGET_Y: usize = 7;
-
-    pub struct FlushTimingInput {
-        pub current_slot: u32,
-        pub game_speed: u32,
-        pub last_updated_slot: u32,
-        pub flush_count: u32,
-    }
+    /// Ally whose command the fleet joins (0 = none). Only honoured when the
+    /// on-chain handler verified the payer's alliance membership.
+    const MI_ALLY_PLAYER_ID: usize = 8;
+    const MI_PLAYER_ID_HI: usize = 9;
+    const MI_ALLY_PLAYER_ID_HI: usize = 10;
+    const COORD_BIAS: u64 = 1 << 31;
+    /// determine_body_type result for coordinates with no celestial body.
+    const BODY_DEAD_SPACE: u32 = 4;
+    /// Max slots a client-supplied landing_slot may differ from the MPC-computed one.
+    const LANDING_SLOT_TOLERANCE: u64 = 5;
 
     pub struct UpgradePlanetInput {
-        pub player_id: u32,
+        pub player_id: u64,
+        /// 0 = Range, 1 = LaunchVelocity, 2 = ShipGenSpeed, 3 = MetalGenSpeed,
+        /// 4 = Balanced. Anything else fails the upgrade.
         pub focus: u32,
-        pub current_slot: u32,
-        pub game_speed: u32,
-        pub last_updated_slot: u32,
         pub metal_upgrade_cost: u32,
     }
 
+    pub struct SurrenderInput {
+        pub player_id: u64,
+    }
+
+    /// new_owner_id is only honoured because the on-chain handler has verified
+    /// that both players belong to the same alliance.
+    pub struct TransferOwnerInput {
+        pub player_id: u64,
+        pub new_owner_id: u64,
+    }
+
+    /// ally_player_id is only honoured when the on-chain handler has verified
+    /// alliance membership (allied == 1 plaintext param).
+    pub struct TransferInput {
+        pub player_id: u64,
+        pub ally_player_id: u64,
+        pub ships_amount: u32,
+        pub metal_amount: u32,
+    }
+
+    /// Coordinates are biased by 2^31 like ProcessMoveInputPacked.
+    pub struct SpyInput {
+        pub player_id: u64,
+        pub source_x: u32,
+        pub source_y: u32,
+        pub target_x: u32,
+        pub target_y: u32,
+    }
+
+    pub struct OwnershipInput {
+        pub player_id: u64,
+    }
+
+    pub struct WormholeInput {
+        pub player_id: u64,
+        pub ships_to_jump: u32,
+    }
+
+    pub struct MineInput {
+        pub player_id: u64,
+    }
+
     // =========================================================================
     // Revealed output structs
     // =========================================================================
@@ -91,11 +159,34 @@ mod circuits {
         pub planet_hash: u64,
         pub valid: u32,
         pub is_spawn_valid: u32,
+        pub is_center_valid: u8,
+    }
+
+    /// Reconnaissance result. All zeros unless valid == 1.
+    pub struct SpyRevealed {
+        pub valid: u8,
+        pub body_type: u8,
+        pub size: u8,
+        pub owner_exists: u8,
+    }
+
+    /// Resources on the planet after lazy generation, before the flushed move lands.
+    pub struct FlushRevealed {
+        pub gen_ships_before_flush: u64,
+        pub gen_metal_before_flush: u64,
     }
 
+    /// at_max_level = 1 when the planet was already at the game's level cap.
     pub struct UpgradeRevealed {
         pub success: u32,
         pub new_level: u32,
+        pub at_max_level: u32,
+    }
+
+    /// metal_yield = metal gained (lazy generation + mining bonus, after cap).
+    pub struct MineRevealed {
+        pub success: u32,
+        pub metal_yield: u32,
     }
 
     // PendingMoveData: encrypted data about a move in transit (4 fields)
@@ -103,7 +194,7 @@ mod circuits {
         pub ships_arriving: u32,
         pub metal_arriving: u32,
         pub attacking_planet_id: u32,
-        pub attacking_player_id: u32,
+        pub attacking_player_id: u64,
     }
 
     // =========================================================================
@@ -125,23 +216,33 @@ mod circuits {
 
     const MAX_HASH_ROUNDS: usize = 200;
 
-    /// Compute SHA3-256 hash of (x || y || game_id) with iterated rounds.
+    /// Single SHA3-256 of (a || b || c).
     /// Input: 32 bytes (24 bytes of data + 8 zero padding bytes).
-    /// Must match client-side sha3PropertyHash in sdk/core.
-    fn compute_property_hash(x: u64, y: u64, game_id: u64, hash_rounds: u64) -> [u8; 32] {
-        let xb = u64_to_le_bytes(x);
-        let yb = u64_to_le_bytes(y);
-        let gb = u64_to_le_bytes(game_id);
+    /// Placeholder MPC mixer for property derivation and drift entropy only; it
+    /// is independent of the on-chain planet hash (Game.hash_algorithm).
+    /// Every input word goes through the hash (never a linear combination), so
+    /// game_id already separates domains: game N and N + 1 share no structure
+    /// at the same (x, y). sdk/core pins this with an avalanche test.
+    fn mix_hash(a: u64, b: u64, c: u64) -> [u8; 32] {
+        let ab = u64_to_le_bytes(a);
+        let bb = u64_to_le_bytes(b);
+        let cb = u64_to_le_bytes(c);
 
         let input: [u8; 32] = [
-            xb[0], xb[1], xb[2], xb[3], xb[4], xb[5], xb[6], xb[7],
-            yb[0], yb[1], yb[2], yb[3], yb[4], yb[5], yb[6], yb[7],
-            gb[0], gb[1], gb[2], gb[3], gb[4], gb[5], gb[6], gb[7],
+            ab[0], ab[1], ab[2], ab[3], ab[4], ab[5], ab[6], ab[7],
+            bb[0], bb[1], bb[2], bb[3], bb[4], bb[5], bb[6], bb[7],
+            cb[0], cb[1], cb[2], cb[3], cb[4], cb[5], cb[6], cb[7],
             0, 0, 0, 0, 0, 0, 0, 0,
         ];
 
         let hasher = SHA3_256::new();
-        let mut hash: [u8; 32] = hasher.digest(&input);
+        hasher.digest(&input)
+    }
+
+    /// Compute SHA3-256 hash of (x || y || game_id) with iterated rounds.
+    /// Must match client-side sha3PropertyHash in sdk/core.
+    fn compute_property_hash(x: u64, y: u64, game_id: u64, hash_rounds: u64) -> [u8; 32] {
+        let mut hash: [u8; 32] = mix_hash(x, y, game_id);
 
         for r in 1..MAX_HASH_ROUNDS {
             let hasher2 = SHA3_256::new();
@@ -152,44 +253,110 @@ mod circuits {
         hash
     }
 
-    /// Determine body type from hash byte.
+    /// 16-bit noise value from a high and low hash byte. Callers pair hash[i]
+    /// with hash[i + 6], so a legacy 8-bit threshold t compares the same as t << 8.
+    fn noise_u16(hi: u8, lo: u8) -> u64 {
+        (hi as u64) * 256 + (lo as u64)
+    }
+
+    /// Determine body type from 16-bit noise: noise0 gates dead space, noise1
+    /// picks the type. Returns 0=Planet, 1=Quasar, 2=SpacetimeRip, 3=AsteroidBelt
+    /// or BODY_DEAD_SPACE when noise0 < dead_space_threshold or
+    /// noise1 >= asteroid_belt_threshold.
     fn determine_body_type(
-        byte1: u64,
+        noise0: u64,
+        noise1: u64,
+        dead_space_threshold: u64,
         planet_threshold: u64,
         quasar_threshold: u64,
         spacetime_rip_threshold: u64,
+        asteroid_belt_threshold: u64,
     ) -> u32 {
-        if byte1 < planet_threshold {
+        if noise0 < dead_space_threshold {
+            BODY_DEAD_SPACE
+        } else if noise1 < planet_threshold {
             0
-        } else if byte1 < quasar_threshold {
+        } else if noise1 < quasar_threshold {
             1
-        } else if byte1 < spacetime_rip_threshold {
+        } else if noise1 < spacetime_rip_threshold {
             2
-        } else {
+        } else if noise1 < asteroid_belt_threshold {
             3
+        } else {
+            BODY_DEAD_SPACE
         }
     }
 
-    /// Determine size (1-6) from hash byte and thresholds.
+    /// Determine size (1-6) from 16-bit noise and thresholds.
     fn determine_size(
-        byte2: u64,
+        noise2: u64,
         t1: u64, t2: u64, t3: u64, t4: u64, t5: u64,
     ) -> u32 {
-        if byte2 < t1 {
+        if noise2 < t1 {
             1
-        } else if byte2 < t2 {
+        } else if noise2 < t2 {
             2
-        } else if byte2 < t3 {
+        } else if noise2 < t3 {
             3
-        } else if byte2 < t4 {
+        } else if noise2 < t4 {
             4
-        } else if byte2 < t5 {
+        } else if noise2 < t5 {
             5
         } else {
             6
         }
     }
 
+    /// Player id bound to a wallet: the XOR of the pubkey's four little-endian
+    /// u64 words. All 64 bits are kept so a colliding key can't be ground out.
+    /// Mirrors playerIdFromPubkey in sdk/core.
+    fn player_id_from_key(k0: u64, k1: u64, k2: u64, k3: u64) -> u64 {
+        k0 ^ k1 ^ k2 ^ k3
+    }
+
+    /// 64-bit player id from its packed low and high u32 words.
+    fn join_id(lo: u32, hi: u32) -> u64 {
+        (lo as u64) | ((hi as u64) << 32)
+    }
+
+    /// Low u32 word of a 64-bit player id.
+    fn id_lo(id: u64) -> u32 {
+        (id & 0xFFFF_FFFF) as u32
+    }
+
+    /// High u32 word of a 64-bit player id.
+    fn id_hi(id: u64) -> u32 {
+        (id >> 32) as u32
+    }
+
+    /// Whether body_type's bit is set in the valid_spawn_body_types mask
+    /// (bit 0 = Planet, 1 = Quasar, 2 = SpacetimeRip, 3 = AsteroidBelt).
+    /// The mask is plaintext, so each bit is extracted with a constant shift.
+    fn spawn_type_allowed(body_type: u32, valid_spawn_body_types: u64) -> u32 {
+        let bit = if body_type == 0 {
+            valid_spawn_body_types & 1
+        } else if body_type == 1 {
+            (valid_spawn_body_types >> 1) & 1
+        } else if body_type == 2 {
+            (valid_spawn_body_types >> 2) & 1
+        } else if body_type == 3 {
+            (valid_spawn_body_types >> 3) & 1
+        } else {
+            0
+        };
+        if bit == 1 { 1 } else { 0 }
+    }
+
+    /// Comets from three hash bytes: byte3 picks the count (> 216 one,
+    /// > 242 two), byte4/byte5 the boosts. Returns (comet_0, comet_1), 0 = none.
+    fn comets_from_bytes(byte3: u64, byte4: u64, byte5: u64) -> (u32, u32) {
+        let comet_0_raw = comet_from_byte(byte4);
+        let comet_1_raw = comet_from_byte_avoiding(byte5, comet_0_raw);
+        let comet_0 = if byte3 > 216 { comet_0_raw } else { 0u32 };
+        let comet_1 = if byte3 > 242 { comet_1_raw } else { 0u32 };
+        (comet_0, comet_1)
+    }
+
     /// Comet value from byte. Returns 1-6 (never 0; 0 means "no comet").
     fn comet_from_byte(b: u64) -> u32 {
         ((b % 6) + 1) as u32
@@ -334,15 +501,44 @@ mod circuits {
         if a > b { a - b } else { b - a }
     }
 
-    fn compute_distance(sx: u64, sy: u64, tx: u64, ty: u64) -> u64 {
+    /// method: 0 = ChebyshevApprox (max + min / 2), 1 = Manhattan (dx + dy),
+    /// matching the on-chain DistanceMethod discriminant.
+    fn compute_distance(sx: u64, sy: u64, tx: u64, ty: u64, method: u64) -> u64 {
         let dx = compute_abs_diff(sx, tx);
         let dy = compute_abs_diff(sy, ty);
         let max_d = if dx > dy { dx } else { dy };
         let min_d = if dx > dy { dy } else { dx };
-        max_d + min_d / 2
+        // Saturate instead of wrapping so far-away targets never look close
+        let min_d_part = if method == 0 { min_d / 2 } else { min_d };
+        if max_d > u64::MAX - min_d_part {
+            u64::MAX
+        } else {
+            max_d + min_d_part
+        }
     }
 
-    /// Apply distance decay. No early return.
+    /// Map a two's-complement u64 coordinate (as clients encode SpawnInput.x/y)
+    /// into the biased space process_move uses, so the map center is
+    /// (COORD_BIAS, COORD_BIAS) and negative coordinates measure correctly.
+    /// Coordinates below -COORD_BIAS clamp to 0.
+    fn bias_coord(raw: u64) -> u64 {
+        let neg_offset = u64::MAX - COORD_BIAS + 1;
+        if raw >= neg_offset {
+            raw - neg_offset
+        } else if raw >= (1u64 << 63) {
+            0
+        } else {
+            raw + COORD_BIAS
+        }
+    }
+
+    /// Biased coordinate lies within [-half, half] of the map center.
+    fn in_map_bounds(biased: u64, half: u64) -> bool {
+        let lo = if COORD_BIAS > half { COORD_BIAS - half } else { 0 };
+        biased >= lo && biased <= COORD_BIAS + half
+    }
+
+    /// Apply distance decay to ships or metal in flight. No early return.
     fn apply_distance_decay(ships: u32, distance: u64, range: u32) -> u32 {
         if range == 0 {
             0
@@ -358,6 +554,8 @@ mod circuits {
 
     /// Compute landing slot. game_speed=10000 is 1x; lower = faster.
     /// Formula: current_slot + distance * game_speed / (velocity * 10000)
+    /// A zero velocity or game_speed never lands (create_game rejects game_speed 0,
+    /// but the circuit must not rely on that).
     fn compute_landing_slot(
         current_slot: u32,
         distance: u64,
@@ -366,6 +564,8 @@ mod circuits {
     ) -> u32 {
         if velocity == 0 {
             current_slot + 999999999
+        } else if game_speed == 0 {
+            current_slot + 999999999
         } else {
             let travel_time = distance * (game_speed as u64) / ((velocity as u64) * 10000);
             let landing = (current_slot as u64) + travel_time;
@@ -408,7 +608,7 @@ mod circuits {
         comet_0: u32,
         comet_1: u32,
         owner_exists: u32,
-        owner_id: u32,
+        owner_id: u64,
     ) -> PlanetState {
         let (ship_cap, ship_gen, metal_cap, metal_gen, range, velocity, native_ships) =
             base_stats(body_type, size);
@@ -436,15 +636,51 @@ mod circuits {
             ship_count,
             0,
             owner_exists,
-            owner_id,
+            id_lo(owner_id),
+            ship_cap,
+            metal_cap,
+            0,
+            ship_gen,
+            metal_gen,
+            range,
+            velocity,
+            id_hi(owner_id),
         ])
     }
 
+    /// Multiplier from the planet's comets for one boost type (1=ShipCapacity,
+    /// 2=MetalCapacity, 3=ShipGenSpeed, 4=MetalGenSpeed, 5=Range,
+    /// 6=LaunchVelocity): x2 per matching comet.
+    fn comet_cap_multiplier(comet_0: u32, comet_1: u32, boost: u32) -> u32 {
+        let m0 = if comet_0 == boost { 2 } else { 1 };
+        let m1 = if comet_1 == boost { 2 } else { 1 };
+        m0 * m1
+    }
+
+    /// QuasarPenalty: ships arriving at a Quasar (body_type 1) lose
+    /// penalty_bps / 10000 of their number before combat.
+    fn apply_quasar_penalty(ships: u32, body_type: u32, penalty_bps: u64) -> u32 {
+        if body_type == 1 {
+            let bps = if penalty_bps > 10000 { 10000 } else { penalty_bps };
+            ((ships as u64) * (10000 - bps) / 10000) as u32
+        } else {
+            ships
+        }
+    }
+
     /// Cap a value at a maximum.
     fn cap_at(val: u32, max: u32) -> u32 {
         if val > max { max } else { val }
     }
 
+    /// val + add, with add clamped to the room left under max so the sum can
+    /// never wrap (a plain cap_at(val + add, max) would pass a wrapped sum).
+    fn add_capped(val: u32, add: u32, max: u32) -> u32 {
+        let remaining_cap = if val > max { 0 } else { max - val };
+        let added = if add > remaining_cap { remaining_cap } else { add };
+        val + added
+    }
+
     /// Apply a single move to planet state (combat resolution).
     /// Returns (ships, metal, owner_exists, owner_id).
     fn apply_combat(
@@ -453,16 +689,16 @@ mod circuits {
         max_ship_cap: u32,
         max_metal_cap: u32,
         owner_exists: u32,
-        owner_id: u32,
+        owner_id: u64,
         m_ships: u32,
         m_metal: u32,
-        m_player_id: u32,
-    ) -> (u32, u32, u32, u32) {
+        m_player_id: u64,
+    ) -> (u32, u32, u32, u64) {
         let is_friendly: u32 = if owner_exists == 1 && owner_id == m_player_id { 1 } else { 0 };
 
         if is_friendly == 1 {
-            let new_ships = cap_at(ships + m_ships, max_ship_cap);
-            let new_metal = cap_at(metal + m_metal, max_metal_cap);
+            let new_ships = add_capped(ships, m_ships, max_ship_cap);
+            let new_metal = add_capped(metal, m_metal, max_metal_cap);
             (new_ships, new_metal, owner_exists, owner_id)
         } else if m_ships > ships {
             let remaining = cap_at(m_ships - ships, max_ship_cap);
@@ -488,6 +724,7 @@ mod circuits {
         planet_threshold: u64,
         quasar_threshold: u64,
         spacetime_rip_threshold: u64,
+        asteroid_belt_threshold: u64,
         size_threshold_1: u64,
         size_threshold_2: u64,
         size_threshold_3: u64,
@@ -502,29 +739,26 @@ mod circuits {
         let hash = compute_property_hash(inp.x, inp.y, game_id, hash_rounds);
         let planet_hash_val = (hash[0] as u64) + (hash[1] as u64) * 3 + (hash[2] as u64) * 7 + (hash[3] as u64) * 11;
 
-        let byte0 = hash[0] as u64;
-        let byte1 = hash[1] as u64;
-        let byte2 = hash[2] as u64;
+        let noise0 = noise_u16(hash[0], hash[6]);
+        let noise1 = noise_u16(hash[1], hash[7]);
+        let noise2 = noise_u16(hash[2], hash[8]);
         let byte3 = hash[3] as u64;
         let byte4 = hash[4] as u64;
         let byte5 = hash[5] as u64;
 
-        let is_body: u32 = if byte0 >= dead_space_threshold { 1 } else { 0 };
-
         let body_type = determine_body_type(
-            byte1, planet_threshold, quasar_threshold, spacetime_rip_threshold,
+            noise0, noise1, dead_space_threshold, planet_threshold,
+            quasar_threshold, spacetime_rip_threshold, asteroid_belt_threshold,
         );
+        let is_body: u32 = if body_type == BODY_DEAD_SPACE { 0 } else { 1 };
 
         let size = determine_size(
-            byte2, size_threshold_1, size_threshold_2,
+            noise2, size_threshold_1, size_threshold_2,
             size_threshold_3, size_threshold_4, size_threshold_5,
         );
 
         // Comet determination: byte3 thresholds, values 1-6 (0 = none)
-        let comet_0_raw = comet_from_byte(byte4);
-        let comet_1_raw = comet_from_byte_avoiding(byte5, comet_0_raw);
-        let comet_0 = if byte3 > 216 { comet_0_raw } else { 0u32 };
-        let comet_1 = if byte3 > 242 { comet_1_raw } else { 0u32 };
+        let (comet_0, comet_1) = comets_from_bytes(byte3, byte4, byte5);
 
         let state = build_planet_state(
             body_type, size, comet_0, comet_1,
@@ -543,7 +777,18 @@ mod circuits {
     }
 
     /// 2. init_spawn_planet: Create planet + validate spawn + set owner.
-    /// Output: (PlanetState, SpawnPlanetRevealed)
+    /// Spawns closer than min_spawn_distance to the map center (map_center_x/y,
+    /// in biased coordinates) are invalid (min_spawn_distance is 0 unless the
+    /// game is RaceToCenter), as are spawns
+    /// within min_spawn_separation of any of the first spawn_count existing spawns
+    /// (all of the game's placed spawns, supplied on-chain).
+    /// Both checks measure distance with distance_method. The body type must be
+    /// allowed by the valid_spawn_body_types bitmask (Planet only by default).
+    /// player_id, the new owner, must equal player_id_from_key of the payer key
+    /// (player_key_0..3, supplied on-chain); otherwise the placement is invalid.
+    /// Output: (PlanetState, SpawnPlanetRevealed, is_placement_valid, reached_center,
+    /// SpawnCoords). The two flags are revealed so the callback can gate has_spawned
+    /// and detect a RaceToCenter victory.
     #[instruction]
     pub fn init_spawn_planet(
         input: Enc<Shared, SpawnInput>,
@@ -552,50 +797,101 @@ mod circuits {
         planet_threshold: u64,
         quasar_threshold: u64,
         spacetime_rip_threshold: u64,
+        asteroid_belt_threshold: u64,
         size_threshold_1: u64,
         size_threshold_2: u64,
         size_threshold_3: u64,
         size_threshold_4: u64,
         size_threshold_5: u64,
         hash_rounds: u64,
+        map_center_x: u64,
+        map_center_y: u64,
+        min_spawn_distance: u64,
+        min_spawn_separation: u64,
+        distance_method: u64,
+        valid_spawn_body_types: u64,
+        spawn_count: u64,
+        player_key_0: u64,
+        player_key_1: u64,
+        player_key_2: u64,
+        player_key_3: u64,
+        s0: Enc<Mxe, SpawnCoords>,
+        s1: Enc<Mxe, SpawnCoords>,
+        s2: Enc<Mxe, SpawnCoords>,
+        s3: Enc<Mxe, SpawnCoords>,
+        s4: Enc<Mxe, SpawnCoords>,
+        s5: Enc<Mxe, SpawnCoords>,
+        s6: Enc<Mxe, SpawnCoords>,
+        s7: Enc<Mxe, SpawnCoords>,
         planet_key: Shared,
         observer: Shared,
-    ) -> (Enc<Shared, PlanetState>, Enc<Shared, SpawnPlanetRevealed>) {
+    ) -> (
+        Enc<Shared, PlanetState>,
+        Enc<Shared, SpawnPlanetRevealed>,
+        u8,
+        u8,
+        Enc<Mxe, SpawnCoords>,
+    ) {
         let inp = input.to_arcis();
 
         let hash = compute_property_hash(inp.x, inp.y, game_id, hash_rounds);
         let planet_hash_val = (hash[0] as u64) + (hash[1] as u64) * 3 + (hash[2] as u64) * 7 + (hash[3] as u64) * 11;
 
-        let byte0 = hash[0] as u64;
-        let byte1 = hash[1] as u64;
-        let byte2 = hash[2] as u64;
+        let noise0 = noise_u16(hash[0], hash[6]);
+        let noise1 = noise_u16(hash[1], hash[7]);
+        let noise2 = noise_u16(hash[2], hash[8]);
         let byte3 = hash[3] as u64;
         let byte4 = hash[4] as u64;
         let byte5 = hash[5] as u64;
 
-        let is_body: u32 = if byte0 >= dead_space_threshold { 1 } else { 0 };
-
         let body_type = determine_body_type(
-            byte1, planet_threshold, quasar_threshold, spacetime_rip_threshold,
+            noise0, noise1, dead_space_threshold, planet_threshold,
+            quasar_threshold, spacetime_rip_threshold, asteroid_belt_threshold,
         );
+        let is_body: u32 = if body_type == BODY_DEAD_SPACE { 0 } else { 1 };
 
         let size = determine_size(
-            byte2, size_threshold_1, size_threshold_2,
+            noise2, size_threshold_1, size_threshold_2,
             size_threshold_3, size_threshold_4, size_threshold_5,
         );
 
         // Comet determination: byte3 thresholds, values 1-6 (0 = none)
-        let comet_0_raw = comet_from_byte(byte4);
-        let comet_1_raw = comet_from_byte_avoiding(byte5, comet_0_raw);
-        let comet_0 = if byte3 > 216 { comet_0_raw } else { 0u32 };
-        let comet_1 = if byte3 > 242 { comet_1_raw } else { 0u32 };
+        let (comet_0, comet_1) = comets_from_bytes(byte3, byte4, byte5);
 
-        let is_planet: u32 = if body_type == 0 { 1 } else { 0 };
+        let is_valid_type: u32 = spawn_type_allowed(body_type, valid_spawn_body_types);
         let is_miniscule: u32 = if size == 1 { 1 } else { 0 };
-        let is_spawn_valid = is_body * is_planet * is_miniscule;
+        let bx = bias_coord(inp.x);
+        let by = bias_coord(inp.y);
+        let center_distance = compute_distance(bx, by, map_center_x, map_center_y, distance_method);
+        let is_center_valid: u8 = if center_distance >= min_spawn_distance { 1 } else { 0 };
+
+        // Unused slots (index >= spawn_count) hold padding and are ignored
+        let others = [
+            s0.to_arcis(), s1.to_arcis(), s2.to_arcis(), s3.to_arcis(),
+            s4.to_arcis(), s5.to_arcis(), s6.to_arcis(), s7.to_arcis(),
+        ];
+        let mut is_separated: u8 = 1;
+        for i in 0..MAX_SEPARATION_CHECKS {
+            let d = compute_distance(bx, by, others[i].x, others[i].y, distance_method);
+            if (i as u64) < spawn_count && min_spawn_separation > 0 && d <= min_spawn_separation {
+                is_separated = 0;
+            }
+        }
+
+        let key_match: u8 = if inp.player_id
+            == player_id_from_key(player_key_0, player_key_1, player_key_2, player_key_3)
+        {
+            1
+        } else {
+            0
+        };
+
+        let is_placement_valid: u8 = is_center_valid * is_separated * key_match;
+        let is_spawn_valid = is_body * is_valid_type * is_miniscule * (is_placement_valid as u32);
+        let reached_center: u8 = if is_spawn_valid == 1 && center_distance == 0 { 1 } else { 0 };
 
         let owner_exists = is_spawn_valid;
-        let oid = if is_spawn_valid == 1 { inp.player_id } else { 0u32 };
+        let oid = if is_spawn_valid == 1 { inp.player_id } else { 0u64 };
 
         let state = build_planet_state(
             body_type, size, comet_0, comet_1,
@@ -606,35 +902,108 @@ mod circuits {
             planet_hash: planet_hash_val,
             valid: is_body,
             is_spawn_valid,
+            is_center_valid,
         };
 
+        let coords = SpawnCoords { x: bx, y: by };
+
         (
             planet_key.from_arcis(state),
             observer.from_arcis(revealed),
+            is_placement_valid.reveal(),
+            reached_center.reveal(),
+            Mxe::get().from_arcis(coords),
         )
     }
 
     /// 3. process_move: Validate and process a ship movement from source planet.
-    /// Input: (PlanetState, ProcessMoveInputPacked) + plaintext resource counts
-    /// Output: (PlanetState, PendingMoveData)
+    /// Moves with either endpoint outside map_diameter, a target beyond the source's
+    /// range, no surviving ships, fewer than min_ships_per_move ships, ships or
+    /// metal above the source's caps, or whose
+    /// client_landing_slot is more than
+    /// LANDING_SLOT_TOLERANCE from the computed landing slot, are invalid.
+    /// With alliance_valid set, a non-zero ally_player_id sends the fleet to
+    /// reinforce that ally instead of attacking. Sources captured less than
+    /// capture_cooldown_slots ago can't launch. Distance uses the game's
+    /// distance_method. The move's player_id must equal player_id_from_key of
+    /// the payer's pubkey (process_player_key_0..3, supplied on-chain), and the
+    /// distance must not exceed the client's public max_observable_distance.
+    /// Input: (PlanetState, ProcessMoveInputPacked) + plaintext timing; current
+    /// ships/metal are derived in MPC from the stored counts
+    /// Output: (PlanetState, PendingMoveData, landing_slot, valid, within_range,
+    /// ships_sent) -- landing_slot and valid are revealed so the callback can record
+    /// the MPC-computed arrival; within_range is a diagnostic. ships_sent is the
+    /// valid move's ship count, revealed only when reveal_ships_sent == 1 (the payer
+    /// attached a PlayerProfile to be credited) and 0 otherwise.
     /// planet_input.owner re-encrypts updated state.
     #[instruction]
     pub fn process_move(
         planet_input: Enc<Shared, PlanetState>,
         move_input: Enc<Shared, ProcessMoveInputPacked>,
-        current_ships: u64,
-        current_metal: u64,
         current_slot: u64,
         game_speed: u64,
-    ) -> (Enc<Shared, PlanetState>, Enc<Mxe, PendingMoveData>) {
-        let state_data: [u32; 15] = planet_input.to_arcis().unpack();
-        let mv: [u32; 8] = move_input.to_arcis().unpack();
+        last_updated_slot: u64,
+        map_diameter: u64,
+        client_landing_slot: u64,
+        alliance_valid: u64,
+        capture_cooldown_slots: u64,
+        distance_method: u64,
+        process_player_key_0: u64,
+        process_player_key_1: u64,
+        process_player_key_2: u64,
+        process_player_key_3: u64,
+        min_ships_per_move: u64,
+        max_observable_distance: u64,
+        reveal_ships_sent: u64,
+    ) -> (Enc<Shared, PlanetState>, Enc<Mxe, PendingMoveData>, u64, u8, u8, u64) {
+        let state_data: [u32; 23] = planet_input.to_arcis().unpack();
+        let mv: [u32; 11] = move_input.to_arcis().unpack();
+
+        // Lazy generation up to current_slot, from the on-chain last_updated_slot
+        let cs = if state_data[PD_OWNER_EXISTS] == 1 {
+            compute_current_resource(
+                state_data[PD_SHIPS],
+                state_data[PS_MAX_SHIP_CAP],
+                state_data[PS_SHIP_GEN],
+                last_updated_slot as u32,
+                current_slot as u32,
+                game_speed as u32,
+            )
+        } else {
+            state_data[PD_SHIPS]
+        };
+        let cm = if state_data[PD_OWNER_EXISTS] == 1 {
+            compute_current_resource(
+                state_data[PD_METAL],
+                state_data[PS_MAX_METAL_CAP],
+                state_data[PS_METAL_GEN],
+                last_updated_slot as u32,
+                current_slot as u32,
+                game_speed as u32,
+            )
+        } else {
+            state_data[PD_METAL]
+        };
 
-        let cs = current_ships as u32;
-        let cm = current_metal as u32;
+        let player_id = join_id(mv[MI_PLAYER_ID], mv[MI_PLAYER_ID_HI]);
+        let ally_player_id = join_id(mv[MI_ALLY_PLAYER_ID], mv[MI_ALLY_PLAYER_ID_HI]);
+
+        // The claimed player_id must be the payer's: the on-chain payer key
+        // folded to 64 bits (see player_id_from_key)
+        let key_match: u32 = if player_id == player_id_from_key(
+            process_player_key_0,
+            process_player_key_1,
+            process_player_key_2,
+            process_player_key_3,
+        ) {
+            1
+        } else {
+            0
+        };
 
-        let owner_match: u32 = if state_data[PD_OWNER_EXISTS] == 1
-            && state_data[PD_OWNER_ID] == mv[MI_PLAYER_ID]
+        let owner_match: u32 = if key_match == 1
+            && state_data[PD_OWNER_EXISTS] == 1
+            && join_id(state_data[PD_OWNER_ID], state_data[PD_OWNER_ID_HI]) == player_id
         {
             1
         } else {
@@ -642,19 +1011,73 @@ mod circuits {
         };
 
         let has_ships: u32 = if cs >= mv[MI_SHIPS_TO_SEND] && mv[MI_SHIPS_TO_SEND] > 0 { 1 } else { 0 };
+        // Tiny sends only spam PendingMoveAccounts on the target
+        let meets_minimum: u32 =
+            if mv[MI_SHIPS_TO_SEND] as u64 >= min_ships_per_move { 1 } else { 0 };
         let has_metal: u32 = if cm >= mv[MI_METAL_TO_SEND] { 1 } else { 0 };
+        // Bound sends by the planet's caps so an oversized amount can't overflow
+        // the landing arithmetic in flush_planet
+        let ships_reasonable: u32 =
+            if mv[MI_SHIPS_TO_SEND] <= state_data[PS_MAX_SHIP_CAP] { 1 } else { 0 };
+        let metal_reasonable: u32 =
+            if mv[MI_METAL_TO_SEND] <= state_data[PS_MAX_METAL_CAP] { 1 } else { 0 };
 
         let distance = compute_distance(
             mv[MI_SOURCE_X] as u64, mv[MI_SOURCE_Y] as u64,
             mv[MI_TARGET_X] as u64, mv[MI_TARGET_Y] as u64,
+            distance_method,
         );
         let surviving = apply_distance_decay(mv[MI_SHIPS_TO_SEND], distance, state_data[PS_RANGE]);
+        let within_range: u32 = if distance <= state_data[PS_RANGE] as u64 { 1 } else { 0 };
+        // The program only checked the client's public bound against the map
+        let within_declared: u32 = if distance <= max_observable_distance { 1 } else { 0 };
+        let ships_survive: u32 = if surviving > 0 { 1 } else { 0 };
+
+        let half = map_diameter / 2;
+        let in_bounds: u32 = if in_map_bounds(mv[MI_SOURCE_X] as u64, half)
+            && in_map_bounds(mv[MI_SOURCE_Y] as u64, half)
+            && in_map_bounds(mv[MI_TARGET_X] as u64, half)
+            && in_map_bounds(mv[MI_TARGET_Y] as u64, half)
+        {
+            1
+        } else {
+            0
+        };
+
+        let landing_slot = compute_landing_slot(
+            current_slot as u32, distance, state_data[PS_VELOCITY], game_speed as u32,
+        ) as u64;
+        let landing_ok: u32 = if compute_abs_diff(client_landing_slot, landing_slot) <= LANDING_SLOT_TOLERANCE {
+            1
+        } else {
+            0
+        };
+
+        // A freshly captured planet can't launch until the cooldown has passed
+        let cooldown_over: u32 = if current_slot
+            >= state_data[PD_LAST_CAPTURE_SLOT] as u64 + capture_cooldown_slots
+        {
+            1
+        } else {
+            0
+        };
 
-        let valid = owner_match * has_ships * has_metal;
+        let valid = owner_match * has_ships * meets_minimum * has_metal * ships_reasonable
+            * metal_reasonable * ships_survive * within_range * within_declared * in_bounds
+            * landing_ok * cooldown_over;
 
         let new_ships = if valid == 1 { cs - mv[MI_SHIPS_TO_SEND] } else { cs };
         let new_metal = if valid == 1 { cm - mv[MI_METAL_TO_SEND] } else { cm };
 
+        // Allied fleets land under the ally's id, so flush treats them as friendly
+        // reinforcement of the ally's planet. Launching still requires owning the
+        // source: relaxing owner_match would let allies spend each other's ships.
+        let fleet_player_id = if alliance_valid == 1 && ally_player_id != 0 {
+            ally_player_id
+        } else {
+            player_id
+        };
+
         let updated_state: PlanetState = Pack::new([
             state_data[PS_BODY_TYPE],
             state_data[PS_SIZE],
@@ -671,33 +1094,65 @@ mod circuits {
             new_metal,
             state_data[PD_OWNER_EXISTS],
             state_data[PD_OWNER_ID],
+            state_data[PS_BASE_SHIP_CAP],
+            state_data[PS_BASE_METAL_CAP],
+            state_data[PD_LAST_CAPTURE_SLOT],
+            state_data[PS_BASE_SHIP_GEN],
+            state_data[PS_BASE_METAL_GEN],
+            state_data[PS_BASE_RANGE],
+            state_data[PS_BASE_VELOCITY],
+            state_data[PD_OWNER_ID_HI],
         ]);
 
         let move_data = PendingMoveData {
             ships_arriving: if valid == 1 { surviving } else { 0 },
-            metal_arriving: if valid == 1 { mv[MI_METAL_TO_SEND] } else { 0 },
+            // Metal decays over the same distance as ships so long hauls aren't free
+            metal_arriving: if valid == 1 {
+                apply_distance_decay(mv[MI_METAL_TO_SEND], distance, state_data[PS_RANGE])
+            } else {
+                0
+            },
             attacking_planet_id: mv[MI_SOURCE_PLANET_ID],
-            attacking_player_id: mv[MI_PLAYER_ID],
+            attacking_player_id: fleet_player_id,
+        };
+        let ships_sent: u64 = if valid == 1 && reveal_ships_sent == 1 {
+            mv[MI_SHIPS_TO_SEND] as u64
+        } else {
+            0
         };
 
         (
             planet_input.owner.from_arcis(updated_state),
             Mxe::get().from_arcis(move_data),
+            landing_slot.reveal(),
+            (valid as u8).reveal(),
+            (within_range as u8).reveal(),
+            ships_sent.reveal(),
         )
     }
 
     /// 4. flush_planet: Process a single landed move against planet state.
-    /// Input: (PlanetState, PendingMoveData, FlushTimingInput)
-    /// Output: PlanetState -- only dynamic fields change during flush
+    /// Input: (PlanetState, PendingMoveData) + plaintext timing (current_slot,
+    /// game_speed, last_updated_slot, all read on-chain)
+    /// Output: (PlanetState, captured, captured_metal, FlushRevealed) -- only dynamic
+    /// fields change during flush. captured is revealed for the combat log;
+    /// captured_metal is revealed only in PointsBurning games (points_burning == 1)
+    /// so the callback can award points, and is 0 otherwise so other modes don't
+    /// leak cargo sizes. FlushRevealed is encrypted to the observer for client
+    /// resource accounting.
     /// planet_input.owner re-encrypts updated state.
     #[instruction]
     pub fn flush_planet(
         planet_input: Enc<Shared, PlanetState>,
         m0: Enc<Mxe, PendingMoveData>,
-        flush_input: Enc<Shared, FlushTimingInput>,
-    ) -> Enc<Shared, PlanetState> {
-        let state_data: [u32; 15] = planet_input.to_arcis().unpack();
-        let fi = flush_input.to_arcis();
+        current_slot: u64,
+        game_speed: u64,
+        last_updated_slot: u64,
+        quasar_ship_penalty_bps: u64,
+        points_burning: u64,
+        observer: Shared,
+    ) -> (Enc<Shared, PlanetState>, u8, u64, Enc<Shared, FlushRevealed>) {
+        let state_data: [u32; 23] = planet_input.to_arcis().unpack();
 
         // Compute current resources via lazy generation
         let gen_ships = if state_data[PD_OWNER_EXISTS] == 1 {
@@ -705,9 +1160,9 @@ mod circuits {
                 state_data[PD_SHIPS],
                 state_data[PS_MAX_SHIP_CAP],
                 state_data[PS_SHIP_GEN],
-                fi.last_updated_slot,
-                fi.current_slot,
-                fi.game_speed,
+                last_updated_slot as u32,
+                current_slot as u32,
+                game_speed as u32,
             )
         } else {
             state_data[PD_SHIPS]
@@ -717,9 +1172,9 @@ mod circuits {
                 state_data[PD_METAL],
                 state_data[PS_MAX_METAL_CAP],
                 state_data[PS_METAL_GEN],
-                fi.last_updated_slot,
-                fi.current_slot,
-                fi.game_speed,
+                last_updated_slot as u32,
+                current_slot as u32,
+                game_speed as u32,
             )
         } else {
             state_data[PD_METAL]
@@ -728,17 +1183,47 @@ mod circuits {
         // Decrypt the move slot (MPC reads from on-chain account)
         let d0 = m0.to_arcis();
 
-        // Apply combat for the single move
-        let (ships, metal, o_exists, o_id) = apply_combat(
-            gen_ships,
-            gen_metal,
-            state_data[PS_MAX_SHIP_CAP],
-            state_data[PS_MAX_METAL_CAP],
-            state_data[PD_OWNER_EXISTS],
-            state_data[PD_OWNER_ID],
-            d0.ships_arriving, d0.metal_arriving, d0.attacking_player_id,
+        let arriving_ships = apply_quasar_penalty(
+            d0.ships_arriving,
+            state_data[PS_BODY_TYPE],
+            quasar_ship_penalty_bps,
         );
 
+        // Unclaimed asteroid belts are colonized, not fought over: the native
+        // ships leave, the arriving fleet settles and its metal counts as income
+        let colonize_belt = state_data[PS_BODY_TYPE] == 3
+            && state_data[PD_OWNER_EXISTS] == 0
+            && arriving_ships > 0;
+
+        // Apply combat for the single move
+        let (ships, metal, o_exists, o_id) = if colonize_belt {
+            (
+                cap_at(arriving_ships, state_data[PS_MAX_SHIP_CAP]),
+                add_capped(gen_metal, d0.metal_arriving, state_data[PS_MAX_METAL_CAP]),
+                1,
+                d0.attacking_player_id,
+            )
+        } else {
+            apply_combat(
+                gen_ships,
+                gen_metal,
+                state_data[PS_MAX_SHIP_CAP],
+                state_data[PS_MAX_METAL_CAP],
+                state_data[PD_OWNER_EXISTS],
+                join_id(state_data[PD_OWNER_ID], state_data[PD_OWNER_ID_HI]),
+                arriving_ships, d0.metal_arriving, d0.attacking_player_id,
+            )
+        };
+
+        let captured = o_exists == 1
+            && (state_data[PD_OWNER_EXISTS] == 0
+                || join_id(state_data[PD_OWNER_ID], state_data[PD_OWNER_ID_HI]) != o_id);
+        let last_capture_slot = if captured {
+            current_slot as u32
+        } else {
+            state_data[PD_LAST_CAPTURE_SLOT]
+        };
+
         let updated_state: PlanetState = Pack::new([
             state_data[PS_BODY_TYPE],
             state_data[PS_SIZE],
@@ -754,26 +1239,63 @@ mod circuits {
             ships,
             metal,
             o_exists,
-            o_id,
+            id_lo(o_id),
+            state_data[PS_BASE_SHIP_CAP],
+            state_data[PS_BASE_METAL_CAP],
+            last_capture_slot,
+            state_data[PS_BASE_SHIP_GEN],
+            state_data[PS_BASE_METAL_GEN],
+            state_data[PS_BASE_RANGE],
+            state_data[PS_BASE_VELOCITY],
+            id_hi(o_id),
         ]);
+        let captured_metal: u64 = if captured && points_burning == 1 {
+            d0.metal_arriving as u64
+        } else {
+            0
+        };
 
-        planet_input.owner.from_arcis(updated_state)
+        let revealed = FlushRevealed {
+            gen_ships_before_flush: gen_ships as u64,
+            gen_metal_before_flush: gen_metal as u64,
+        };
+
+        (
+            planet_input.owner.from_arcis(updated_state),
+            (captured as u8).reveal(),
+            captured_metal.reveal(),
+            observer.from_arcis(revealed),
+        )
     }
 
     /// 5. upgrade_planet: Upgrade a planet, spending metal.
-    /// Input: (PlanetState, UpgradePlanetInput)
-    /// Output: (PlanetState, UpgradeRevealed)
+    /// Input: (PlanetState, UpgradePlanetInput) + plaintext max_planet_level,
+    /// timing (current_slot, game_speed, last_updated_slot, all read on-chain)
+    /// and payer key (player_id must equal player_id_from_key of it)
+    /// Output: (PlanetState, UpgradeRevealed, focus: u8 plaintext)
     /// planet_input.owner re-encrypts updated state.
+    /// Planets already at max_planet_level can't be upgraded further.
     #[instruction]
     pub fn upgrade_planet(
         planet_input: Enc<Shared, PlanetState>,
         upgrade_input: Enc<Shared, UpgradePlanetInput>,
-    ) -> (Enc<Shared, PlanetState>, Enc<Shared, UpgradeRevealed>) {
-        let state_data: [u32; 15] = planet_input.to_arcis().unpack();
+        max_planet_level: u64,
+        current_slot: u64,
+        game_speed: u64,
+        last_updated_slot: u64,
+        player_key_0: u64,
+        player_key_1: u64,
+        player_key_2: u64,
+        player_key_3: u64,
+    ) -> (Enc<Shared, PlanetState>, Enc<Shared, UpgradeRevealed>, u8) {
+        let state_data: [u32; 23] = planet_input.to_arcis().unpack();
         let ui = upgrade_input.to_arcis();
 
+        let key_match = ui.player_id
+            == player_id_from_key(player_key_0, player_key_1, player_key_2, player_key_3);
         let owner_match: u32 = if state_data[PD_OWNER_EXISTS] == 1
-            && state_data[PD_OWNER_ID] == ui.player_id
+            && join_id(state_data[PD_OWNER_ID], state_data[PD_OWNER_ID_HI]) == ui.player_id
+            && key_match
         {
             1
         } else {
@@ -786,29 +1308,79 @@ mod circuits {
             state_data[PD_METAL],
             state_data[PS_MAX_METAL_CAP],
             state_data[PS_METAL_GEN],
-            ui.last_updated_slot,
-            ui.current_slot,
-            ui.game_speed,
+            last_updated_slot as u32,
+            current_slot as u32,
+            game_speed as u32,
         );
         let current_ships = compute_current_resource(
             state_data[PD_SHIPS],
             state_data[PS_MAX_SHIP_CAP],
             state_data[PS_SHIP_GEN],
-            ui.last_updated_slot,
-            ui.current_slot,
-            ui.game_speed,
+            last_updated_slot as u32,
+            current_slot as u32,
+            game_speed as u32,
         );
 
         let cost = upgrade_cost(state_data[PS_LEVEL]);
         let can_afford: u32 = if current_metal >= cost { 1 } else { 0 };
 
-        let valid = owner_match * is_planet * can_afford;
+        let valid_focus: u32 = if ui.focus <= 4 { 1 } else { 0 };
+
+        let at_max: u32 = if state_data[PS_LEVEL] as u64 >= max_planet_level { 1 } else { 0 };
+
+        let valid = owner_match * is_planet * can_afford * valid_focus * (1 - at_max);
 
         let new_level = if valid == 1 { state_data[PS_LEVEL] + 1 } else { state_data[PS_LEVEL] };
         let new_metal = if valid == 1 { current_metal - cost } else { current_metal };
-        let new_ship_cap = if valid == 1 { state_data[PS_MAX_SHIP_CAP] * 2 } else { state_data[PS_MAX_SHIP_CAP] };
-        let new_metal_cap = if valid == 1 { state_data[PS_MAX_METAL_CAP] * 2 } else { state_data[PS_MAX_METAL_CAP] };
-        let new_ship_gen = if valid == 1 { state_data[PS_SHIP_GEN] * 2 } else { state_data[PS_SHIP_GEN] };
+        // Balanced focus adds 50% to both capacities and ship gen instead of
+        // doubling a single stat
+        let balanced = valid == 1 && ui.focus == 4;
+        // Double the pre-comet capacities, then reapply comet boosts on top
+        let doubled_base_ship_cap = if valid == 1 {
+            state_data[PS_BASE_SHIP_CAP] * 2
+        } else {
+            state_data[PS_BASE_SHIP_CAP]
+        };
+        let doubled_base_metal_cap = if valid == 1 {
+            state_data[PS_BASE_METAL_CAP] * 2
+        } else {
+            state_data[PS_BASE_METAL_CAP]
+        };
+        let new_base_ship_cap = if balanced {
+            doubled_base_ship_cap * 3 / 2
+        } else {
+            doubled_base_ship_cap
+        };
+        let new_base_metal_cap = if balanced {
+            doubled_base_metal_cap * 3 / 2
+        } else {
+            doubled_base_metal_cap
+        };
+        let new_ship_cap = if valid == 1 {
+            new_base_ship_cap
+                * comet_cap_multiplier(state_data[PS_COMET_0], state_data[PS_COMET_1], 1)
+        } else {
+            state_data[PS_MAX_SHIP_CAP]
+        };
+        let new_metal_cap = if valid == 1 {
+            new_base_metal_cap
+                * comet_cap_multiplier(state_data[PS_COMET_0], state_data[PS_COMET_1], 2)
+        } else {
+            state_data[PS_MAX_METAL_CAP]
+        };
+        let base_ship_gen = if valid == 1 { state_data[PS_SHIP_GEN] * 2 } else { state_data[PS_SHIP_GEN] };
+        let new_ship_gen = if valid == 1 && ui.focus == 2 {
+            base_ship_gen * 2
+        } else if balanced {
+            base_ship_gen * 3 / 2
+        } else {
+            base_ship_gen
+        };
+        let new_metal_gen = if valid == 1 && ui.focus == 3 {
+            state_data[PS_METAL_GEN] * 2
+        } else {
+            state_data[PS_METAL_GEN]
+        };
 
         let new_range = if valid == 1 && ui.focus == 0 {
             state_data[PS_RANGE] * 2
@@ -820,6 +1392,34 @@ mod circuits {
         } else {
             state_data[PS_VELOCITY]
         };
+        // Same upgrade applied to the pre-comet values comet_drift rebuilds from
+        let doubled_base_ship_gen = if valid == 1 {
+            state_data[PS_BASE_SHIP_GEN] * 2
+        } else {
+            state_data[PS_BASE_SHIP_GEN]
+        };
+        let new_base_ship_gen = if valid == 1 && ui.focus == 2 {
+            doubled_base_ship_gen * 2
+        } else if balanced {
+            doubled_base_ship_gen * 3 / 2
+        } else {
+            doubled_base_ship_gen
+        };
+        let new_base_metal_gen = if valid == 1 && ui.focus == 3 {
+            state_data[PS_BASE_METAL_GEN] * 2
+        } else {
+            state_data[PS_BASE_METAL_GEN]
+        };
+        let new_base_range = if valid == 1 && ui.focus == 0 {
+            state_data[PS_BASE_RANGE] * 2
+        } else {
+            state_data[PS_BASE_RANGE]
+        };
+        let new_base_velocity = if valid == 1 && ui.focus == 1 {
+            state_data[PS_BASE_VELOCITY] * 2
+        } else {
+            state_data[PS_BASE_VELOCITY]
+        };
 
         let updated_state: PlanetState = Pack::new([
             state_data[PS_BODY_TYPE],
@@ -827,7 +1427,7 @@ mod circuits {
             new_ship_cap,
             new_ship_gen,
             new_metal_cap,
-            state_data[PS_METAL_GEN],
+            new_metal_gen,
             new_range,
             new_velocity,
             new_level,
@@ -837,16 +1437,858 @@ mod circuits {
             new_metal,
             state_data[PD_OWNER_EXISTS],
             state_data[PD_OWNER_ID],
+            new_base_ship_cap,
+            new_base_metal_cap,
+            state_data[PD_LAST_CAPTURE_SLOT],
+            new_base_ship_gen,
+            new_base_metal_gen,
+            new_base_range,
+            new_base_velocity,
+            state_data[PD_OWNER_ID_HI],
         ]);
 
         let revealed = UpgradeRevealed {
             success: valid,
             new_level,
+            at_max_level: at_max,
         };
 
+        // Out-of-range focus is revealed as 255 so the callback can reject it.
+        let focus: u8 = if valid_focus == 1 { ui.focus as u8 } else { 255 };
+
         (
             planet_input.owner.from_arcis(updated_state),
             upgrade_input.owner.from_arcis(revealed),
+            focus.reveal(),
+        )
+    }
+
+    /// 6. surrender_planet: Owner abandons a planet without launching ships.
+    /// Input: (PlanetState, SurrenderInput) + payer key (player_id must equal
+    /// player_id_from_key of it)
+    /// Output: (PlanetState, surrendered: u8 plaintext) -- owner cleared, ships
+    /// capped at native count. surrendered is revealed so the callback only
+    /// announces a surrender that happened.
+    /// planet_input.owner re-encrypts updated state.
+    #[instruction]
+    pub fn surrender_planet(
+        planet_input: Enc<Shared, PlanetState>,
+        surrender_input: Enc<Shared, SurrenderInput>,
+        player_key_0: u64,
+        player_key_1: u64,
+        player_key_2: u64,
+        player_key_3: u64,
+    ) -> (Enc<Shared, PlanetState>, u8) {
+        let state_data: [u32; 23] = planet_input.to_arcis().unpack();
+        let si = surrender_input.to_arcis();
+
+        let key_match = si.player_id
+            == player_id_from_key(player_key_0, player_key_1, player_key_2, player_key_3);
+        let owner_match: u32 = if state_data[PD_OWNER_EXISTS] == 1
+            && join_id(state_data[PD_OWNER_ID], state_data[PD_OWNER_ID_HI]) == si.player_id
+            && key_match
+        {
+            1
+        } else {
+            0
+        };
+
+        let (_sc, _sg, _mc, _mg, _r, _v, native_ships) =
+            base_stats(state_data[PS_BODY_TYPE], state_data[PS_SIZE]);
+
+        let new_ships = if owner_match == 1 {
+            cap_at(state_data[PD_SHIPS], native_ships)
+        } else {
+            state_data[PD_SHIPS]
+        };
+        let new_owner_exists = if owner_match == 1 { 0u32 } else { state_data[PD_OWNER_EXISTS] };
+        let new_owner_id = if owner_match == 1 {
+            0u64
+        } else {
+            join_id(state_data[PD_OWNER_ID], state_data[PD_OWNER_ID_HI])
+        };
+
+        let updated_state: PlanetState = Pack::new([
+            state_data[PS_BODY_TYPE],
+            state_data[PS_SIZE],
+            state_data[PS_MAX_SHIP_CAP],
+            state_data[PS_SHIP_GEN],
+            state_data[PS_MAX_METAL_CAP],
+            state_data[PS_METAL_GEN],
+            state_data[PS_RANGE],
+            state_data[PS_VELOCITY],
+            state_data[PS_LEVEL],
+            state_data[PS_COMET_0],
+            state_data[PS_COMET_1],
+            new_ships,
+            state_data[PD_METAL],
+            new_owner_exists,
+            id_lo(new_owner_id),
+            state_data[PS_BASE_SHIP_CAP],
+            state_data[PS_BASE_METAL_CAP],
+            state_data[PD_LAST_CAPTURE_SLOT],
+            state_data[PS_BASE_SHIP_GEN],
+            state_data[PS_BASE_METAL_GEN],
+            state_data[PS_BASE_RANGE],
+            state_data[PS_BASE_VELOCITY],
+            id_hi(new_owner_id),
+        ]);
+
+        (
+            planet_input.owner.from_arcis(updated_state),
+            (owner_match as u8).reveal(),
+        )
+    }
+
+    /// 7. transfer_resources: Move ships/metal between two planets without combat.
+    /// Source must be owned by player_id; destination must be owned by player_id,
+    /// or by ally_player_id when both the payer and the ally were verified as
+    /// alliance members on-chain. ally_player_id must match the id derived from the
+    /// ally's wallet key (ally_key_0..3), so any other player's planet is rejected.
+    /// player_id must equal player_id_from_key of the payer key (player_key_0..3).
+    /// Input: (PlanetState, PlanetState, TransferInput) + plaintext timing + ally
+    /// key + payer key
+    /// Output: (PlanetState, PlanetState) -- re-encrypted for each planet's owner
+    #[instruction]
+    pub fn transfer_resources(
+        source_input: Enc<Shared, PlanetState>,
+        dest_input: Enc<Shared, PlanetState>,
+        transfer_input: Enc<Shared, TransferInput>,
+        current_slot: u64,
+        game_speed: u64,
+        source_last_updated_slot: u64,
+        dest_last_updated_slot: u64,
+        allied: u64,
+        ally_key_0: u64,
+        ally_key_1: u64,
+        ally_key_2: u64,
+        ally_key_3: u64,
+        player_key_0: u64,
+        player_key_1: u64,
+        player_key_2: u64,
+        player_key_3: u64,
+    ) -> (Enc<Shared, PlanetState>, Enc<Shared, PlanetState>) {
+        let src: [u32; 23] = source_input.to_arcis().unpack();
+        let dst: [u32; 23] = dest_input.to_arcis().unpack();
+        let ti = transfer_input.to_arcis();
+
+        // Lazy generation up to current_slot for both planets
+        let src_ships = compute_current_resource(
+            src[PD_SHIPS], src[PS_MAX_SHIP_CAP], src[PS_SHIP_GEN],
+            source_last_updated_slot as u32, current_slot as u32, game_speed as u32,
+        );
+        let src_metal = compute_current_resource(
+            src[PD_METAL], src[PS_MAX_METAL_CAP], src[PS_METAL_GEN],
+            source_last_updated_slot as u32, current_slot as u32, game_speed as u32,
+        );
+        let dst_ships = if dst[PD_OWNER_EXISTS] == 1 {
+            compute_current_resource(
+                dst[PD_SHIPS], dst[PS_MAX_SHIP_CAP], dst[PS_SHIP_GEN],
+                dest_last_updated_slot as u32, current_slot as u32, game_speed as u32,
+            )
+        } else {
+            dst[PD_SHIPS]
+        };
+        let dst_metal = if dst[PD_OWNER_EXISTS] == 1 {
+            compute_current_resource(
+                dst[PD_METAL], dst[PS_MAX_METAL_CAP], dst[PS_METAL_GEN],
+                dest_last_updated_slot as u32, current_slot as u32, game_speed as u32,
+            )
+        } else {
+            dst[PD_METAL]
+        };
+
+        let src_owner_id = join_id(src[PD_OWNER_ID], src[PD_OWNER_ID_HI]);
+        let dst_owner_id = join_id(dst[PD_OWNER_ID], dst[PD_OWNER_ID_HI]);
+        let source_owned: u32 = if src[PD_OWNER_EXISTS] == 1 && src_owner_id == ti.player_id {
+            1
+        } else {
+            0
+        };
+        let dest_owned: u32 = if dst[PD_OWNER_EXISTS] == 1 && dst_owner_id == ti.player_id {
+            1
+        } else {
+            0
+        };
+        let ally_id = player_id_from_key(ally_key_0, ally_key_1, ally_key_2, ally_key_3);
+        let dest_allied: u32 = if allied == 1
+            && ti.ally_player_id == ally_id
+            && dst[PD_OWNER_EXISTS] == 1
+            && dst_owner_id == ti.ally_player_id
+        {
+            1
+        } else {
+            0
+        };
+        let dest_ok: u32 = if dest_owned == 1 || dest_allied == 1 { 1 } else { 0 };
+
+        let has_ships: u32 = if src_ships >= ti.ships_amount { 1 } else { 0 };
+        let has_metal: u32 = if src_metal >= ti.metal_amount { 1 } else { 0 };
+
+        let key_match: u32 = if ti.player_id
+            == player_id_from_key(player_key_0, player_key_1, player_key_2, player_key_3)
+        {
+            1
+        } else {
+            0
+        };
+
+        let valid = source_owned * dest_ok * has_ships * has_metal * key_match;
+
+        let new_src_ships = if valid == 1 { src_ships - ti.ships_amount } else { src_ships };
+        let new_src_metal = if valid == 1 { src_metal - ti.metal_amount } else { src_metal };
+        let new_dst_ships = if valid == 1 {
+            cap_at(dst_ships + ti.ships_amount, dst[PS_MAX_SHIP_CAP])
+        } else {
+            dst_ships
+        };
+        let new_dst_metal = if valid == 1 {
+            cap_at(dst_metal + ti.metal_amount, dst[PS_MAX_METAL_CAP])
+        } else {
+            dst_metal
+        };
+
+        let updated_source: PlanetState = Pack::new([
+            src[PS_BODY_TYPE],
+            src[PS_SIZE],
+            src[PS_MAX_SHIP_CAP],
+            src[PS_SHIP_GEN],
+            src[PS_MAX_METAL_CAP],
+            src[PS_METAL_GEN],
+            src[PS_RANGE],
+            src[PS_VELOCITY],
+            src[PS_LEVEL],
+            src[PS_COMET_0],
+            src[PS_COMET_1],
+            new_src_ships,
+            new_src_metal,
+            src[PD_OWNER_EXISTS],
+            src[PD_OWNER_ID],
+            src[PS_BASE_SHIP_CAP],
+            src[PS_BASE_METAL_CAP],
+            src[PD_LAST_CAPTURE_SLOT],
+            src[PS_BASE_SHIP_GEN],
+            src[PS_BASE_METAL_GEN],
+            src[PS_BASE_RANGE],
+            src[PS_BASE_VELOCITY],
+            src[PD_OWNER_ID_HI],
+        ]);
+
+        let updated_dest: PlanetState = Pack::new([
+            dst[PS_BODY_TYPE],
+            dst[PS_SIZE],
+            dst[PS_MAX_SHIP_CAP],
+            dst[PS_SHIP_GEN],
+            dst[PS_MAX_METAL_CAP],
+            dst[PS_METAL_GEN],
+            dst[PS_RANGE],
+            dst[PS_VELOCITY],
+            dst[PS_LEVEL],
+            dst[PS_COMET_0],
+            dst[PS_COMET_1],
+            new_dst_ships,
+            new_dst_metal,
+            dst[PD_OWNER_EXISTS],
+            dst[PD_OWNER_ID],
+            dst[PS_BASE_SHIP_CAP],
+            dst[PS_BASE_METAL_CAP],
+            dst[PD_LAST_CAPTURE_SLOT],
+            dst[PS_BASE_SHIP_GEN],
+            dst[PS_BASE_METAL_GEN],
+            dst[PS_BASE_RANGE],
+            dst[PS_BASE_VELOCITY],
+            dst[PD_OWNER_ID_HI],
+        ]);
+
+        (
+            source_input.owner.from_arcis(updated_source),
+            dest_input.owner.from_arcis(updated_dest),
+        )
+    }
+
+    /// 8. spy_move: Scout a target planet without sending ships.
+    /// Source must be owned by player_id and the target within the source's range
+    /// (measured with distance_method). player_id must equal player_id_from_key
+    /// of the payer key (player_key_0..3).
+    /// Input: (PlanetState source, PlanetState target, SpyInput) + distance_method
+    /// + payer key
+    /// Output: SpyRevealed -- type, size and whether the target is owned; never
+    /// ship or metal counts. Encrypted for spy_input.owner.
+    #[instruction]
+    pub fn spy_move(
+        source_input: Enc<Shared, PlanetState>,
+        target_input: Enc<Shared, PlanetState>,
+        spy_input: Enc<Shared, SpyInput>,
+        distance_method: u64,
+        player_key_0: u64,
+        player_key_1: u64,
+        player_key_2: u64,
+        player_key_3: u64,
+    ) -> Enc<Shared, SpyRevealed> {
+        let src: [u32; 23] = source_input.to_arcis().unpack();
+        let dst: [u32; 23] = target_input.to_arcis().unpack();
+        let si = spy_input.to_arcis();
+
+        let key_match = si.player_id
+            == player_id_from_key(player_key_0, player_key_1, player_key_2, player_key_3);
+        let owner_match: u32 = if src[PD_OWNER_EXISTS] == 1
+            && join_id(src[PD_OWNER_ID], src[PD_OWNER_ID_HI]) == si.player_id
+            && key_match
+        {
+            1
+        } else {
+            0
+        };
+
+        let distance = compute_distance(
+            si.source_x as u64, si.source_y as u64,
+            si.target_x as u64, si.target_y as u64,
+            distance_method,
+        );
+        let in_range: u32 = if distance <= src[PS_RANGE] as u64 { 1 } else { 0 };
+
+        let valid = owner_match * in_range;
+
+        let revealed = SpyRevealed {
+            valid: valid as u8,
+            body_type: if valid == 1 { dst[PS_BODY_TYPE] as u8 } else { 0 },
+            size: if valid == 1 { dst[PS_SIZE] as u8 } else { 0 },
+            owner_exists: if valid == 1 { dst[PD_OWNER_EXISTS] as u8 } else { 0 },
+        };
+
+        spy_input.owner.from_arcis(revealed)
+    }
+
+    /// 9. comet_collect: Absorb a drifting comet into a free comet slot.
+    /// boost is the on-chain CometBoost discriminant + 1 (1-6, same encoding as
+    /// comet_0/comet_1). Fails (state unchanged) if both slots are taken.
+    /// Input: PlanetState + plaintext boost
+    /// Output: (PlanetState, collected: u8 plaintext)
+    /// planet_input.owner re-encrypts updated state.
+    #[instruction]
+    pub fn comet_collect(
+        planet_input: Enc<Shared, PlanetState>,
+        boost: u64,
+    ) -> (Enc<Shared, PlanetState>, u8) {
+        let state_data: [u32; 23] = planet_input.to_arcis().unpack();
+        let comet_val = boost as u32;
+
+        let slot_0_free = state_data[PS_COMET_0] == 0;
+        let slot_1_free = state_data[PS_COMET_1] == 0;
+        let valid: u32 = if slot_0_free || slot_1_free { 1 } else { 0 };
+
+        let new_comet_0 = if slot_0_free { comet_val } else { state_data[PS_COMET_0] };
+        let new_comet_1 = if !slot_0_free && slot_1_free {
+            comet_val
+        } else {
+            state_data[PS_COMET_1]
+        };
+
+        let applied = if valid == 1 { comet_val } else { 0 };
+        let (ship_cap, ship_gen, metal_cap, metal_gen, range, velocity) = apply_one_comet(
+            applied,
+            state_data[PS_MAX_SHIP_CAP],
+            state_data[PS_SHIP_GEN],
+            state_data[PS_MAX_METAL_CAP],
+            state_data[PS_METAL_GEN],
+            state_data[PS_RANGE],
+            state_data[PS_VELOCITY],
+        );
+
+        let updated_state: PlanetState = Pack::new([
+            state_data[PS_BODY_TYPE],
+            state_data[PS_SIZE],
+            ship_cap,
+            ship_gen,
+            metal_cap,
+            metal_gen,
+            range,
+            velocity,
+            state_data[PS_LEVEL],
+            new_comet_0,
+            new_comet_1,
+            state_data[PD_SHIPS],
+            state_data[PD_METAL],
+            state_data[PD_OWNER_EXISTS],
+            state_data[PD_OWNER_ID],
+            state_data[PS_BASE_SHIP_CAP],
+            state_data[PS_BASE_METAL_CAP],
+            state_data[PD_LAST_CAPTURE_SLOT],
+            state_data[PS_BASE_SHIP_GEN],
+            state_data[PS_BASE_METAL_GEN],
+            state_data[PS_BASE_RANGE],
+            state_data[PS_BASE_VELOCITY],
+            state_data[PD_OWNER_ID_HI],
+        ]);
+
+        (
+            planet_input.owner.from_arcis(updated_state),
+            (valid as u8).reveal(),
+        )
+    }
+
+    /// 10. mine_asteroid: Owner of an AsteroidBelt extracts a one-time bonus of
+    /// size * 50 metal on top of lazy generation, capped at max_metal_capacity.
+    /// player_id must equal player_id_from_key of the payer key (player_key_0..3).
+    /// Input: (PlanetState, MineInput) + plaintext timing sourced on-chain + payer key
+    /// Output: (PlanetState, MineRevealed, success: u8 plaintext)
+    /// success is revealed so the callback only starts the cooldown on a real mine.
+    /// planet_input.owner re-encrypts updated state.
+    #[instruction]
+    pub fn mine_asteroid(
+        planet_input: Enc<Shared, PlanetState>,
+        mine_input: Enc<Shared, MineInput>,
+        current_slot: u64,
+        game_speed: u64,
+        last_updated_slot: u64,
+        player_key_0: u64,
+        player_key_1: u64,
+        player_key_2: u64,
+        player_key_3: u64,
+    ) -> (Enc<Shared, PlanetState>, Enc<Shared, MineRevealed>, u8) {
+        let state_data: [u32; 23] = planet_input.to_arcis().unpack();
+        let mi = mine_input.to_arcis();
+
+        let key_match = mi.player_id
+            == player_id_from_key(player_key_0, player_key_1, player_key_2, player_key_3);
+        let owner_match: u32 = if state_data[PD_OWNER_EXISTS] == 1
+            && join_id(state_data[PD_OWNER_ID], state_data[PD_OWNER_ID_HI]) == mi.player_id
+            && key_match
+        {
+            1
+        } else {
+            0
+        };
+        let is_asteroid: u32 = if state_data[PS_BODY_TYPE] == 3 { 1 } else { 0 };
+        let valid = owner_match * is_asteroid;
+
+        let current_metal = compute_current_resource(
+            state_data[PD_METAL],
+            state_data[PS_MAX_METAL_CAP],
+            state_data[PS_METAL_GEN],
+            last_updated_slot as u32,
+            current_slot as u32,
+            game_speed as u32,
+        );
+        let current_ships = compute_current_resource(
+            state_data[PD_SHIPS],
+            state_data[PS_MAX_SHIP_CAP],
+            state_data[PS_SHIP_GEN],
+            last_updated_slot as u32,
+            current_slot as u32,
+            game_speed as u32,
+        );
+
+        let bonus = state_data[PS_SIZE] * 50;
+        let new_metal = if valid == 1 {
+            cap_at(current_metal + bonus, state_data[PS_MAX_METAL_CAP])
+        } else {
+            current_metal
+        };
+        let metal_yield = if valid == 1 && new_metal >= state_data[PD_METAL] {
+            new_metal - state_data[PD_METAL]
+        } else {
+            0
+        };
+
+        let updated_state: PlanetState = Pack::new([
+            state_data[PS_BODY_TYPE],
+            state_data[PS_SIZE],
+            state_data[PS_MAX_SHIP_CAP],
+            state_data[PS_SHIP_GEN],
+            state_data[PS_MAX_METAL_CAP],
+            state_data[PS_METAL_GEN],
+            state_data[PS_RANGE],
+            state_data[PS_VELOCITY],
+            state_data[PS_LEVEL],
+            state_data[PS_COMET_0],
+            state_data[PS_COMET_1],
+            current_ships,
+            new_metal,
+            state_data[PD_OWNER_EXISTS],
+            state_data[PD_OWNER_ID],
+            state_data[PS_BASE_SHIP_CAP],
+            state_data[PS_BASE_METAL_CAP],
+            state_data[PD_LAST_CAPTURE_SLOT],
+            state_data[PS_BASE_SHIP_GEN],
+            state_data[PS_BASE_METAL_GEN],
+            state_data[PS_BASE_RANGE],
+            state_data[PS_BASE_VELOCITY],
+            state_data[PD_OWNER_ID_HI],
+        ]);
+
+        let revealed = MineRevealed {
+            success: valid,
+            metal_yield,
+        };
+
+        (
+            planet_input.owner.from_arcis(updated_state),
+            mine_input.owner.from_arcis(revealed),
+            (valid as u8).reveal(),
+        )
+    }
+
+    /// 11. check_ownership: Does player_id still own this planet?
+    /// player_id must equal player_id_from_key of the payer's pubkey
+    /// (player_key_0..3, supplied on-chain). With strict == 0 (check_spawn_lost)
+    /// a mismatched id reports the planet as still owned, so it can never clear
+    /// has_spawned; with strict == 1 (claim_race_to_center_victory) it reports
+    /// the planet as not owned, so it can never prove ownership.
+    /// Input: (PlanetState, OwnershipInput) + plaintext payer key + strict
+    /// Output: is_owner: u8 plaintext (the callback acts on it). State is untouched.
+    #[instruction]
+    pub fn check_ownership(
+        planet_input: Enc<Shared, PlanetState>,
+        ownership_input: Enc<Shared, OwnershipInput>,
+        player_key_0: u64,
+        player_key_1: u64,
+        player_key_2: u64,
+        player_key_3: u64,
+        strict: u64,
+    ) -> u8 {
+        let state_data: [u32; 23] = planet_input.to_arcis().unpack();
+        let oi = ownership_input.to_arcis();
+
+        let key_match = oi.player_id
+            == player_id_from_key(player_key_0, player_key_1, player_key_2, player_key_3);
+        let owns = state_data[PD_OWNER_EXISTS] == 1
+            && join_id(state_data[PD_OWNER_ID], state_data[PD_OWNER_ID_HI]) == oi.player_id;
+        let is_owner: u8 = if strict == 1 {
+            if owns && key_match { 1 } else { 0 }
+        } else if owns || !key_match {
+            1
+        } else {
+            0
+        };
+
+        is_owner.reveal()
+    }
+
+    /// 12. wormhole_jump: Send ships between two SpacetimeRips with no transit.
+    /// Source must be owned by player_id and both bodies must be SpacetimeRips.
+    /// Moves up to ships_to_jump ships with no distance decay; they land on the
+    /// destination immediately, resolved like any other arriving move.
+    /// player_id must equal player_id_from_key of the payer key (player_key_0..3).
+    /// Input: (PlanetState, PlanetState, WormholeInput) + plaintext timing + payer key
+    /// Output: (PlanetState, PlanetState) -- re-encrypted for each planet's owner
+    #[instruction]
+    pub fn wormhole_jump(
+        source_input: Enc<Shared, PlanetState>,
+        dest_input: Enc<Shared, PlanetState>,
+        wormhole_input: Enc<Shared, WormholeInput>,
+        current_slot: u64,
+        game_speed: u64,
+        source_last_updated_slot: u64,
+        dest_last_updated_slot: u64,
+        player_key_0: u64,
+        player_key_1: u64,
+        player_key_2: u64,
+        player_key_3: u64,
+    ) -> (Enc<Shared, PlanetState>, Enc<Shared, PlanetState>) {
+        let src: [u32; 23] = source_input.to_arcis().unpack();
+        let dst: [u32; 23] = dest_input.to_arcis().unpack();
+        let wi = wormhole_input.to_arcis();
+
+        // Lazy generation up to current_slot for both planets
+        let src_ships = compute_current_resource(
+            src[PD_SHIPS], src[PS_MAX_SHIP_CAP], src[PS_SHIP_GEN],
+            source_last_updated_slot as u32, current_slot as u32, game_speed as u32,
+        );
+        let src_metal = compute_current_resource(
+            src[PD_METAL], src[PS_MAX_METAL_CAP], src[PS_METAL_GEN],
+            source_last_updated_slot as u32, current_slot as u32, game_speed as u32,
+        );
+        let dst_ships = if dst[PD_OWNER_EXISTS] == 1 {
+            compute_current_resource(
+                dst[PD_SHIPS], dst[PS_MAX_SHIP_CAP], dst[PS_SHIP_GEN],
+                dest_last_updated_slot as u32, current_slot as u32, game_speed as u32,
+            )
+        } else {
+            dst[PD_SHIPS]
+        };
+        let dst_metal = if dst[PD_OWNER_EXISTS] == 1 {
+            compute_current_resource(
+                dst[PD_METAL], dst[PS_MAX_METAL_CAP], dst[PS_METAL_GEN],
+                dest_last_updated_slot as u32, current_slot as u32, game_speed as u32,
+            )
+        } else {
+            dst[PD_METAL]
+        };
+
+        let key_match = wi.player_id
+            == player_id_from_key(player_key_0, player_key_1, player_key_2, player_key_3);
+        let source_owned: u32 = if src[PD_OWNER_EXISTS] == 1
+            && join_id(src[PD_OWNER_ID], src[PD_OWNER_ID_HI]) == wi.player_id
+            && key_match
+        {
+            1
+        } else {
+            0
+        };
+        let both_rips: u32 = if src[PS_BODY_TYPE] == 2 && dst[PS_BODY_TYPE] == 2 { 1 } else { 0 };
+
+        let valid = source_owned * both_rips;
+
+        // Jump up to ships_to_jump; never more than the source holds
+        let jumped = if wi.ships_to_jump > src_ships { src_ships } else { wi.ships_to_jump };
+        let sent = if valid == 1 { jumped } else { 0 };
+
+        let dst_owner_id = join_id(dst[PD_OWNER_ID], dst[PD_OWNER_ID_HI]);
+        let (new_dst_ships, new_dst_metal, new_owner_exists, new_owner_id) = apply_combat(
+            dst_ships,
+            dst_metal,
+            dst[PS_MAX_SHIP_CAP],
+            dst[PS_MAX_METAL_CAP],
+            dst[PD_OWNER_EXISTS],
+            dst_owner_id,
+            sent,
+            0,
+            wi.player_id,
+        );
+
+        let final_dst_ships = if valid == 1 { new_dst_ships } else { dst_ships };
+        let final_dst_metal = if valid == 1 { new_dst_metal } else { dst_metal };
+        let final_owner_exists = if valid == 1 { new_owner_exists } else { dst[PD_OWNER_EXISTS] };
+        let final_owner_id = if valid == 1 { new_owner_id } else { dst_owner_id };
+        let dst_captured = final_owner_exists == 1
+            && (dst[PD_OWNER_EXISTS] == 0 || dst_owner_id != final_owner_id);
+        let final_last_capture = if dst_captured {
+            current_slot as u32
+        } else {
+            dst[PD_LAST_CAPTURE_SLOT]
+        };
+
+        let updated_source: PlanetState = Pack::new([
+            src[PS_BODY_TYPE],
+            src[PS_SIZE],
+            src[PS_MAX_SHIP_CAP],
+            src[PS_SHIP_GEN],
+            src[PS_MAX_METAL_CAP],
+            src[PS_METAL_GEN],
+            src[PS_RANGE],
+            src[PS_VELOCITY],
+            src[PS_LEVEL],
+            src[PS_COMET_0],
+            src[PS_COMET_1],
+            src_ships - sent,
+            src_metal,
+            src[PD_OWNER_EXISTS],
+            src[PD_OWNER_ID],
+            src[PS_BASE_SHIP_CAP],
+            src[PS_BASE_METAL_CAP],
+            src[PD_LAST_CAPTURE_SLOT],
+            src[PS_BASE_SHIP_GEN],
+            src[PS_BASE_METAL_GEN],
+            src[PS_BASE_RANGE],
+            src[PS_BASE_VELOCITY],
+            src[PD_OWNER_ID_HI],
+        ]);
+
+        let updated_dest: PlanetState = Pack::new([
+            dst[PS_BODY_TYPE],
+            dst[PS_SIZE],
+            dst[PS_MAX_SHIP_CAP],
+            dst[PS_SHIP_GEN],
+            dst[PS_MAX_METAL_CAP],
+            dst[PS_METAL_GEN],
+            dst[PS_RANGE],
+            dst[PS_VELOCITY],
+            dst[PS_LEVEL],
+            dst[PS_COMET_0],
+            dst[PS_COMET_1],
+            final_dst_ships,
+            final_dst_metal,
+            final_owner_exists,
+            id_lo(final_owner_id),
+            dst[PS_BASE_SHIP_CAP],
+            dst[PS_BASE_METAL_CAP],
+            final_last_capture,
+            dst[PS_BASE_SHIP_GEN],
+            dst[PS_BASE_METAL_GEN],
+            dst[PS_BASE_RANGE],
+            dst[PS_BASE_VELOCITY],
+            id_hi(final_owner_id),
+        ]);
+
+        (
+            source_input.owner.from_arcis(updated_source),
+            dest_input.owner.from_arcis(updated_dest),
+        )
+    }
+
+    /// 13. comet_drift: Re-roll a planet's comets from public per-epoch entropy
+    /// mix_hash(epoch_slot, game_id, planet_hash_prefix), where epoch_slot is
+    /// the first slot of the current drift epoch, so every call within an epoch
+    /// rolls the same comets. Capacities, gen speeds, range and velocity are
+    /// rebuilt from the pre-comet bases. Owned planets have resources brought
+    /// up to current_slot first so the new rates only apply from now on.
+    /// Input: PlanetState + plaintext entropy and timing
+    /// Output: PlanetState -- planet_input.owner re-encrypts updated state
+    #[instruction]
+    pub fn comet_drift(
+        planet_input: Enc<Shared, PlanetState>,
+        current_slot: u64,
+        epoch_slot: u64,
+        game_id: u64,
+        planet_hash_prefix: u64,
+        game_speed: u64,
+        last_updated_slot: u64,
+    ) -> Enc<Shared, PlanetState> {
+        let state_data: [u32; 23] = planet_input.to_arcis().unpack();
+
+        let owned = state_data[PD_OWNER_EXISTS] == 1;
+        let ships = if owned {
+            compute_current_resource(
+                state_data[PD_SHIPS], state_data[PS_MAX_SHIP_CAP], state_data[PS_SHIP_GEN],
+                last_updated_slot as u32, current_slot as u32, game_speed as u32,
+            )
+        } else {
+            state_data[PD_SHIPS]
+        };
+        let metal = if owned {
+            compute_current_resource(
+                state_data[PD_METAL], state_data[PS_MAX_METAL_CAP], state_data[PS_METAL_GEN],
+                last_updated_slot as u32, current_slot as u32, game_speed as u32,
+            )
+        } else {
+            state_data[PD_METAL]
+        };
+
+        let entropy = mix_hash(epoch_slot, game_id, planet_hash_prefix);
+        let (new_comet_0, new_comet_1) =
+            comets_from_bytes(entropy[3] as u64, entropy[4] as u64, entropy[5] as u64);
+
+        let ship_cap = state_data[PS_BASE_SHIP_CAP]
+            * comet_cap_multiplier(new_comet_0, new_comet_1, 1);
+        let metal_cap = state_data[PS_BASE_METAL_CAP]
+            * comet_cap_multiplier(new_comet_0, new_comet_1, 2);
+        let ship_gen = state_data[PS_BASE_SHIP_GEN]
+            * comet_cap_multiplier(new_comet_0, new_comet_1, 3);
+        let metal_gen = state_data[PS_BASE_METAL_GEN]
+            * comet_cap_multiplier(new_comet_0, new_comet_1, 4);
+        let range = state_data[PS_BASE_RANGE]
+            * comet_cap_multiplier(new_comet_0, new_comet_1, 5);
+        let velocity = state_data[PS_BASE_VELOCITY]
+            * comet_cap_multiplier(new_comet_0, new_comet_1, 6);
+
+        let updated_state: PlanetState = Pack::new([
+            state_data[PS_BODY_TYPE],
+            state_data[PS_SIZE],
+            ship_cap,
+            ship_gen,
+            metal_cap,
+            metal_gen,
+            range,
+            velocity,
+            state_data[PS_LEVEL],
+            new_comet_0,
+            new_comet_1,
+            cap_at(ships, ship_cap),
+            cap_at(metal, metal_cap),
+            state_data[PD_OWNER_EXISTS],
+            state_data[PD_OWNER_ID],
+            state_data[PS_BASE_SHIP_CAP],
+            state_data[PS_BASE_METAL_CAP],
+            state_data[PD_LAST_CAPTURE_SLOT],
+            state_data[PS_BASE_SHIP_GEN],
+            state_data[PS_BASE_METAL_GEN],
+            state_data[PS_BASE_RANGE],
+            state_data[PS_BASE_VELOCITY],
+            state_data[PD_OWNER_ID_HI],
+        ]);
+
+        planet_input.owner.from_arcis(updated_state)
+    }
+
+    /// 14. transfer_owner: Hand an owned planet to an ally without combat.
+    /// Resources are brought up to current_slot so the new owner inherits the
+    /// accrued ships/metal. new_owner_id must equal player_id_from_key of the
+    /// receiving player's pubkey (new_owner_key_0..3, supplied on-chain), which
+    /// also becomes the stored owner; player_id must likewise derive from the
+    /// payer key (player_key_0..3).
+    /// Input: (PlanetState, TransferOwnerInput) + plaintext timing + receiver key + payer key
+    /// Output: (PlanetState, u8 success) -- planet_input.owner re-encrypts updated state
+    #[instruction]
+    pub fn transfer_owner(
+        planet_input: Enc<Shared, PlanetState>,
+        transfer_owner_input: Enc<Shared, TransferOwnerInput>,
+        current_slot: u64,
+        game_speed: u64,
+        last_updated_slot: u64,
+        new_owner_key_0: u64,
+        new_owner_key_1: u64,
+        new_owner_key_2: u64,
+        new_owner_key_3: u64,
+        player_key_0: u64,
+        player_key_1: u64,
+        player_key_2: u64,
+        player_key_3: u64,
+    ) -> (Enc<Shared, PlanetState>, u8) {
+        let state_data: [u32; 23] = planet_input.to_arcis().unpack();
+        let ti = transfer_owner_input.to_arcis();
+        let receiver_id =
+            player_id_from_key(new_owner_key_0, new_owner_key_1, new_owner_key_2, new_owner_key_3);
+
+        let key_match = ti.player_id
+            == player_id_from_key(player_key_0, player_key_1, player_key_2, player_key_3);
+
+        let valid = state_data[PD_OWNER_EXISTS] == 1
+            && join_id(state_data[PD_OWNER_ID], state_data[PD_OWNER_ID_HI]) == ti.player_id
+            && key_match
+            && ti.new_owner_id == receiver_id;
+
+        let ships = if valid {
+            compute_current_resource(
+                state_data[PD_SHIPS], state_data[PS_MAX_SHIP_CAP], state_data[PS_SHIP_GEN],
+                last_updated_slot as u32, current_slot as u32, game_speed as u32,
+            )
+        } else {
+            state_data[PD_SHIPS]
+        };
+        let metal = if valid {
+            compute_current_resource(
+                state_data[PD_METAL], state_data[PS_MAX_METAL_CAP], state_data[PS_METAL_GEN],
+                last_updated_slot as u32, current_slot as u32, game_speed as u32,
+            )
+        } else {
+            state_data[PD_METAL]
+        };
+        let new_owner_id = if valid {
+            receiver_id
+        } else {
+            join_id(state_data[PD_OWNER_ID], state_data[PD_OWNER_ID_HI])
+        };
+
+        let updated_state: PlanetState = Pack::new([
+            state_data[PS_BODY_TYPE],
+            state_data[PS_SIZE],
+            state_data[PS_MAX_SHIP_CAP],
+            state_data[PS_SHIP_GEN],
+            state_data[PS_MAX_METAL_CAP],
+            state_data[PS_METAL_GEN],
+            state_data[PS_RANGE],
+            state_data[PS_VELOCITY],
+            state_data[PS_LEVEL],
+            state_data[PS_COMET_0],
+            state_data[PS_COMET_1],
+            ships,
+            metal,
+            state_data[PD_OWNER_EXISTS],
+            id_lo(new_owner_id),
+            state_data[PS_BASE_SHIP_CAP],
+            state_data[PS_BASE_METAL_CAP],
+            state_data[PD_LAST_CAPTURE_SLOT],
+            state_data[PS_BASE_SHIP_GEN],
+            state_data[PS_BASE_METAL_GEN],
+            state_data[PS_BASE_RANGE],
+            state_data[PS_BASE_VELOCITY],
+            id_hi(new_owner_id),
+        ]);
+
+        (
+            planet_input.owner.from_arcis(updated_state),
+            (valid as u8).reveal(),
         )
     }
 }
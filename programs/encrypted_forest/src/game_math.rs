@@ -0,0 +1,475 @@
+// ---------------------------------------------------------------------------
+// Plaintext mirrors of the game math inside the Arcis circuits
+// (encrypted-ixs/src/lib.rs). Keep these in lockstep with the circuit
+// versions so clients can predict planet stats and costs off-chain.
+// ---------------------------------------------------------------------------
+
+use crate::{DistanceMethod, NoiseThresholds};
+use sha3::{Digest, Sha3_256};
+
+/// Round cap of the circuit's compute_property_hash loop.
+pub const MAX_HASH_ROUNDS: u16 = 200;
+
+/// SHA3-256 property hash of (x || y || game_id || 8 zero bytes), iterated up
+/// to `hash_rounds` times (capped at MAX_HASH_ROUNDS). This is the hash the
+/// circuits derive noise from, not the on-chain planet hash.
+pub fn property_hash(x: i64, y: i64, game_id: u64, hash_rounds: u16) -> [u8; 32] {
+    let mut input = [0u8; 32];
+    input[0..8].copy_from_slice(&x.to_le_bytes());
+    input[8..16].copy_from_slice(&y.to_le_bytes());
+    input[16..24].copy_from_slice(&game_id.to_le_bytes());
+    let mut hash: [u8; 32] = Sha3_256::digest(input).into();
+    for _ in 1..hash_rounds.min(MAX_HASH_ROUNDS) {
+        hash = Sha3_256::digest(hash).into();
+    }
+    hash
+}
+
+/// 16-bit noise value: hash[i] is the high byte and hash[i + 6] the low byte.
+pub fn noise_u16(hash: &[u8; 32], i: usize) -> u16 {
+    (hash[i] as u16) << 8 | hash[i + 6] as u16
+}
+
+/// True when determine_body_type would place no body at this property hash:
+/// noise0 below dead_space_threshold, or noise1 past every type threshold.
+pub fn is_dead_space(hash: &[u8; 32], nt: &NoiseThresholds) -> bool {
+    noise_u16(hash, 0) < nt.dead_space_threshold
+        || noise_u16(hash, 1) >= nt.asteroid_belt_threshold
+}
+
+/// Body-type thresholds and size thresholds are each strictly increasing.
+/// dead_space_threshold gates a separate noise value and is not ordered
+/// against the body-type chain.
+pub fn noise_thresholds_ordered(nt: &NoiseThresholds) -> bool {
+    nt.planet_threshold < nt.quasar_threshold
+        && nt.quasar_threshold < nt.spacetime_rip_threshold
+        && nt.spacetime_rip_threshold < nt.asteroid_belt_threshold
+        && nt.size_threshold_1 < nt.size_threshold_2
+        && nt.size_threshold_2 < nt.size_threshold_3
+        && nt.size_threshold_3 < nt.size_threshold_4
+        && nt.size_threshold_4 < nt.size_threshold_5
+}
+
+/// Move id a newly queued move will take: next_move_id plus the moves still
+/// awaiting their process_move callback. None when it would pass u64::MAX.
+pub fn predicted_move_id(next_move_id: u64, queued_count: u8) -> Option<u64> {
+    next_move_id.checked_add(queued_count as u64)
+}
+
+/// Round up to the next power of two (minimum 1, capped at 256).
+pub fn pow2_ceil(value: u64) -> u64 {
+    if value <= 1 {
+        1
+    } else if value <= 2 {
+        2
+    } else if value <= 4 {
+        4
+    } else if value <= 8 {
+        8
+    } else if value <= 16 {
+        16
+    } else if value <= 32 {
+        32
+    } else if value <= 64 {
+        64
+    } else if value <= 128 {
+        128
+    } else {
+        256
+    }
+}
+
+/// Base stats for a celestial body.
+/// body_type: 0=Planet, 1=Quasar, 2=SpacetimeRip, 3=AsteroidBelt. size: 1-6.
+/// Returns (ship_cap, ship_gen, metal_cap, metal_gen, range, velocity, native_ships)
+pub fn base_stats(body_type: u8, size: u8) -> (u64, u64, u64, u64, u64, u64, u64) {
+    let s = size as u64;
+    let s_sq = s * s;
+
+    if body_type == 0 {
+        let native = if size == 1 { 0 } else { 10 * s };
+        (100 * s_sq, s, 0, 0, pow2_ceil(3 + s), pow2_ceil(1 + s), native)
+    } else if body_type == 1 {
+        (500 * s_sq, 0, 500 * s_sq, 0, pow2_ceil(2 + s), pow2_ceil(1 + s), 20 * s)
+    } else if body_type == 2 {
+        (50 * s_sq, s, 0, 0, pow2_ceil(2 + s), pow2_ceil(1 + s), 15 * s)
+    } else {
+        (80 * s_sq, 0, 200 * s_sq, 2 * s, pow2_ceil(2 + s), pow2_ceil(1 + s), 10 * s)
+    }
+}
+
+/// Apply one comet boost. comet_val 0 = inactive (no boost).
+/// comet_val 1=ShipCapacity, 2=MetalCapacity, 3=ShipGenSpeed,
+/// 4=MetalGenSpeed, 5=Range, 6=LaunchVelocity.
+/// Takes and returns (ship_cap, ship_gen, metal_cap, metal_gen, range, velocity).
+pub fn apply_one_comet(
+    comet_val: u8,
+    stats: (u64, u64, u64, u64, u64, u64),
+) -> (u64, u64, u64, u64, u64, u64) {
+    let (ship_cap, ship_gen, metal_cap, metal_gen, range, velocity) = stats;
+    (
+        if comet_val == 1 { ship_cap * 2 } else { ship_cap },
+        if comet_val == 3 { ship_gen * 2 } else { ship_gen },
+        if comet_val == 2 { metal_cap * 2 } else { metal_cap },
+        if comet_val == 4 { metal_gen * 2 } else { metal_gen },
+        if comet_val == 5 { range * 2 } else { range },
+        if comet_val == 6 { velocity * 2 } else { velocity },
+    )
+}
+
+/// Full stats for a freshly initialized body: base stats with both comets applied.
+/// Returns (ship_cap, ship_gen, metal_cap, metal_gen, range, velocity, native_ships)
+pub fn planet_stats(
+    body_type: u8,
+    size: u8,
+    comet_0: u8,
+    comet_1: u8,
+) -> (u64, u64, u64, u64, u64, u64, u64) {
+    let (sc, sg, mc, mg, r, v, native) = base_stats(body_type, size);
+    let boosted = apply_one_comet(comet_1, apply_one_comet(comet_0, (sc, sg, mc, mg, r, v)));
+    (boosted.0, boosted.1, boosted.2, boosted.3, boosted.4, boosted.5, native)
+}
+
+/// Upgrade cost in metal for a planet at `level`.
+/// Levels 1-9 cost 100 * 2^level; any other level costs 100 * 1024.
+pub fn upgrade_cost(level: u64) -> u64 {
+    let mult = if (1..=9).contains(&level) { 1u64 << level } else { 1024 };
+    100 * mult
+}
+
+/// Current resource count via lazy generation, capped at max_capacity.
+/// game_speed = 10000 is 1x; lower = faster. Saturates instead of
+/// overflowing, so it is safe on arbitrary account data. UIs call it to show
+/// ship and metal counts between computations; for values the circuit's u32
+/// state can hold it returns the circuit's result.
+pub fn compute_current_resource(
+    last_count: u64,
+    max_capacity: u64,
+    gen_speed: u64,
+    last_updated_slot: u64,
+    current_slot: u64,
+    game_speed: u64,
+) -> u64 {
+    if gen_speed == 0 || game_speed == 0 || current_slot <= last_updated_slot {
+        return last_count;
+    }
+    let elapsed = current_slot - last_updated_slot;
+    let generated = gen_speed.saturating_mul(elapsed).saturating_mul(10000) / game_speed;
+    last_count.saturating_add(generated).min(max_capacity)
+}
+
+/// Distance under the game's `DistanceMethod`: ChebyshevApprox is
+/// max(dx, dy) + min(dx, dy) / 2, Manhattan is dx + dy.
+/// Saturates at u64::MAX for coordinates at opposite extremes.
+pub fn compute_distance(sx: i64, sy: i64, tx: i64, ty: i64, method: DistanceMethod) -> u64 {
+    let dx = sx.abs_diff(tx);
+    let dy = sy.abs_diff(ty);
+    match method {
+        DistanceMethod::ChebyshevApprox => dx.max(dy).saturating_add(dx.min(dy) / 2),
+        DistanceMethod::Manhattan => dx.saturating_add(dy),
+    }
+}
+
+/// Largest distance between two points of a map_diameter-wide map under
+/// `method`: 1.5 * map_diameter for ChebyshevApprox (opposite corners),
+/// 2 * map_diameter for Manhattan. Saturates at u64::MAX.
+pub fn max_map_distance(map_diameter: u64, method: DistanceMethod) -> u64 {
+    match method {
+        DistanceMethod::ChebyshevApprox => map_diameter.saturating_mul(3) / 2,
+        DistanceMethod::Manhattan => map_diameter.saturating_mul(2),
+    }
+}
+
+/// Ships (or metal) surviving a trip: one unit lost per `range` distance travelled.
+pub fn apply_distance_decay(ships: u64, distance: u64, range: u64) -> u64 {
+    if range == 0 {
+        return 0;
+    }
+    ships.saturating_sub(distance / range)
+}
+
+/// Slots in flight: distance * game_speed / (velocity * 10000), computed in
+/// u128 and saturated at u64::MAX. Zero velocity or game_speed never lands.
+pub fn compute_travel_time(distance: u64, velocity: u64, game_speed: u64) -> u64 {
+    if velocity == 0 || game_speed == 0 {
+        return 999_999_999;
+    }
+    let slots = distance as u128 * game_speed as u128 / (velocity as u128 * 10000);
+    u64::try_from(slots).unwrap_or(u64::MAX)
+}
+
+/// Landing slot: current_slot + compute_travel_time(distance, velocity, game_speed),
+/// saturated at u64::MAX.
+pub fn compute_landing_slot(current_slot: u64, distance: u64, velocity: u64, game_speed: u64) -> u64 {
+    current_slot.saturating_add(compute_travel_time(distance, velocity, game_speed))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn default_thresholds() -> NoiseThresholds {
+        NoiseThresholds {
+            dead_space_threshold: 64768,
+            planet_threshold: 32768,
+            quasar_threshold: 49152,
+            spacetime_rip_threshold: 57344,
+            asteroid_belt_threshold: 65280,
+            size_threshold_1: 32768,
+            size_threshold_2: 49152,
+            size_threshold_3: 57344,
+            size_threshold_4: 61440,
+            size_threshold_5: 64000,
+        }
+    }
+
+    #[test]
+    fn default_thresholds_are_ordered() {
+        assert!(noise_thresholds_ordered(&default_thresholds()));
+    }
+
+    #[test]
+    fn equal_body_thresholds_are_rejected() {
+        let mut nt = default_thresholds();
+        nt.quasar_threshold = nt.planet_threshold;
+        assert!(!noise_thresholds_ordered(&nt));
+
+        let mut nt = default_thresholds();
+        nt.asteroid_belt_threshold = nt.spacetime_rip_threshold;
+        assert!(!noise_thresholds_ordered(&nt));
+    }
+
+    #[test]
+    fn off_by_one_body_thresholds_are_accepted() {
+        let mut nt = default_thresholds();
+        nt.planet_threshold = 0;
+        nt.quasar_threshold = 1;
+        nt.spacetime_rip_threshold = 2;
+        nt.asteroid_belt_threshold = u16::MAX;
+        assert!(noise_thresholds_ordered(&nt));
+    }
+
+    #[test]
+    fn decreasing_size_thresholds_are_rejected() {
+        let mut nt = default_thresholds();
+        nt.size_threshold_5 = nt.size_threshold_4 - 1;
+        assert!(!noise_thresholds_ordered(&nt));
+
+        let mut nt = default_thresholds();
+        nt.size_threshold_2 = nt.size_threshold_1;
+        assert!(!noise_thresholds_ordered(&nt));
+    }
+
+    #[test]
+    fn dead_space_threshold_is_unconstrained() {
+        let mut nt = default_thresholds();
+        nt.dead_space_threshold = 0;
+        assert!(noise_thresholds_ordered(&nt));
+        nt.dead_space_threshold = u16::MAX;
+        assert!(noise_thresholds_ordered(&nt));
+    }
+
+    #[test]
+    fn pow2_ceil_rounds_up_and_caps() {
+        assert_eq!(pow2_ceil(0), 1);
+        assert_eq!(pow2_ceil(1), 1);
+        assert_eq!(pow2_ceil(3), 4);
+        assert_eq!(pow2_ceil(64), 64);
+        assert_eq!(pow2_ceil(129), 256);
+        assert_eq!(pow2_ceil(1_000), 256);
+    }
+
+    #[test]
+    fn base_stats_per_body_type() {
+        // Miniscule planets start without native ships
+        assert_eq!(base_stats(0, 1), (100, 1, 0, 0, 4, 2, 0));
+        assert_eq!(base_stats(0, 3), (900, 3, 0, 0, 8, 4, 30));
+        assert_eq!(base_stats(1, 2), (2000, 0, 2000, 0, 4, 4, 40));
+        assert_eq!(base_stats(2, 1), (50, 1, 0, 0, 4, 2, 15));
+        assert_eq!(base_stats(3, 6), (2880, 0, 7200, 12, 8, 8, 60));
+    }
+
+    #[test]
+    fn apply_one_comet_doubles_one_stat() {
+        let stats = (10, 20, 30, 40, 50, 60);
+        assert_eq!(apply_one_comet(0, stats), stats);
+        assert_eq!(apply_one_comet(1, stats), (20, 20, 30, 40, 50, 60));
+        assert_eq!(apply_one_comet(2, stats), (10, 20, 60, 40, 50, 60));
+        assert_eq!(apply_one_comet(3, stats), (10, 40, 30, 40, 50, 60));
+        assert_eq!(apply_one_comet(4, stats), (10, 20, 30, 80, 50, 60));
+        assert_eq!(apply_one_comet(5, stats), (10, 20, 30, 40, 100, 60));
+        assert_eq!(apply_one_comet(6, stats), (10, 20, 30, 40, 50, 120));
+        assert_eq!(apply_one_comet(7, stats), stats);
+    }
+
+    #[test]
+    fn planet_stats_applies_both_comets() {
+        assert_eq!(planet_stats(0, 2, 0, 0), base_stats(0, 2));
+        // ShipCapacity + Range on a size-2 planet
+        assert_eq!(planet_stats(0, 2, 1, 5), (800, 2, 0, 0, 16, 4, 20));
+    }
+
+    #[test]
+    fn upgrade_cost_doubles_per_level() {
+        assert_eq!(upgrade_cost(1), 200);
+        assert_eq!(upgrade_cost(2), 400);
+        assert_eq!(upgrade_cost(9), 51_200);
+        // Out-of-range levels pay the capped price
+        assert_eq!(upgrade_cost(0), 102_400);
+        assert_eq!(upgrade_cost(10), 102_400);
+    }
+
+    #[test]
+    fn property_hash_is_deterministic_and_round_capped() {
+        let once = property_hash(-5, 7, 1, 1);
+        assert_eq!(once, property_hash(-5, 7, 1, 1));
+        assert_ne!(once, property_hash(-5, 7, 2, 1));
+        assert_ne!(once, property_hash(-5, 7, 1, 2));
+        assert_eq!(
+            property_hash(-5, 7, 1, MAX_HASH_ROUNDS),
+            property_hash(-5, 7, 1, u16::MAX)
+        );
+    }
+
+    #[test]
+    fn max_map_distance_reaches_opposite_corners() {
+        let half = 500;
+        for method in [DistanceMethod::ChebyshevApprox, DistanceMethod::Manhattan] {
+            assert_eq!(
+                max_map_distance(2 * half as u64, method),
+                compute_distance(-half, -half, half, half, method)
+            );
+        }
+        assert_eq!(max_map_distance(u64::MAX, DistanceMethod::ChebyshevApprox), u64::MAX / 2);
+        assert_eq!(max_map_distance(u64::MAX, DistanceMethod::Manhattan), u64::MAX);
+    }
+
+    #[test]
+    fn zero_game_speed_never_lands() {
+        assert_eq!(compute_travel_time(50, 2, 0), 999_999_999);
+        assert_eq!(compute_landing_slot(100, 50, 2, 0), 100 + 999_999_999);
+        // Zero velocity takes the same branch
+        assert_eq!(compute_landing_slot(100, 50, 0, 10_000), 100 + 999_999_999);
+        // 1x speed: 40 distance at velocity 4 takes 10 slots
+        assert_eq!(compute_landing_slot(100, 40, 4, 10_000), 110);
+    }
+
+    #[test]
+    fn zero_game_speed_generates_nothing() {
+        assert_eq!(compute_current_resource(7, 100, 3, 10, 50, 0), 7);
+        assert_eq!(compute_current_resource(7, 100, 3, 10, 20, 10_000), 37);
+    }
+
+    #[test]
+    fn predicted_move_id_near_u64_max() {
+        assert_eq!(predicted_move_id(u64::MAX - 1, 0), Some(u64::MAX - 1));
+        assert_eq!(predicted_move_id(u64::MAX - 1, 1), Some(u64::MAX));
+        assert_eq!(predicted_move_id(u64::MAX - 1, 2), None);
+        assert_eq!(predicted_move_id(u64::MAX, 1), None);
+        assert_eq!(predicted_move_id(5, 8), Some(13));
+    }
+
+    /// The circuit's u32 compute_current_resource, copied from encrypted-ixs.
+    fn circuit_current_resource(
+        last_count: u32,
+        max_capacity: u32,
+        gen_speed: u32,
+        last_updated_slot: u32,
+        current_slot: u32,
+        game_speed: u32,
+    ) -> u32 {
+        if gen_speed == 0 || game_speed == 0 || current_slot <= last_updated_slot {
+            return last_count;
+        }
+        let elapsed = current_slot - last_updated_slot;
+        let generated = (gen_speed as u64) * (elapsed as u64) * 10000 / (game_speed as u64);
+        let total = (last_count as u64) + generated;
+        if total > (max_capacity as u64) {
+            max_capacity
+        } else {
+            total as u32
+        }
+    }
+
+    /// The circuit's u32 compute_landing_slot, copied from encrypted-ixs.
+    fn circuit_landing_slot(current_slot: u32, distance: u64, velocity: u32, game_speed: u32) -> u32 {
+        if velocity == 0 || game_speed == 0 {
+            return current_slot + 999999999;
+        }
+        let travel_time = distance * (game_speed as u64) / ((velocity as u64) * 10000);
+        ((current_slot as u64) + travel_time) as u32
+    }
+
+    /// xorshift64, so the comparisons below are reproducible.
+    fn next_rand(state: &mut u64) -> u64 {
+        *state ^= *state << 13;
+        *state ^= *state >> 7;
+        *state ^= *state << 17;
+        *state
+    }
+
+    #[test]
+    fn current_resource_matches_circuit() {
+        let mut rng = 0x2096_u64;
+        for _ in 0..1000 {
+            let last_count = next_rand(&mut rng) as u32;
+            let max_capacity = next_rand(&mut rng) as u32;
+            let gen_speed = (next_rand(&mut rng) % 65_536) as u32;
+            let last_updated_slot = (next_rand(&mut rng) % (1 << 31)) as u32;
+            // Mostly forward in time, sometimes at or before the last update
+            let current_slot = if next_rand(&mut rng).is_multiple_of(8) {
+                last_updated_slot.saturating_sub((next_rand(&mut rng) % 100) as u32)
+            } else {
+                last_updated_slot + (next_rand(&mut rng) % (1 << 24)) as u32
+            };
+            let game_speed = (next_rand(&mut rng) % 20_001) as u32;
+
+            let expected = circuit_current_resource(
+                last_count, max_capacity, gen_speed, last_updated_slot, current_slot, game_speed,
+            );
+            let args = (
+                last_count as u64,
+                max_capacity as u64,
+                gen_speed as u64,
+                last_updated_slot as u64,
+                current_slot as u64,
+                game_speed as u64,
+            );
+            assert_eq!(
+                compute_current_resource(args.0, args.1, args.2, args.3, args.4, args.5),
+                expected as u64,
+                "inputs {args:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn landing_slot_matches_circuit() {
+        let mut rng = 0x1a2d_u64;
+        for _ in 0..1000 {
+            let current_slot = (next_rand(&mut rng) % (1 << 31)) as u32;
+            let distance = next_rand(&mut rng) % (1 << 24);
+            let velocity = (next_rand(&mut rng) % 1_000) as u32;
+            let game_speed = (next_rand(&mut rng) % 20_001) as u32;
+
+            let expected = circuit_landing_slot(current_slot, distance, velocity, game_speed);
+            assert_eq!(
+                compute_landing_slot(current_slot as u64, distance, velocity as u64, game_speed as u64),
+                expected as u64,
+                "inputs {:?}",
+                (current_slot, distance, velocity, game_speed)
+            );
+        }
+    }
+
+    #[test]
+    fn resource_and_travel_math_saturate() {
+        assert_eq!(compute_current_resource(0, u64::MAX, u64::MAX, 0, u64::MAX, 1), u64::MAX);
+        assert_eq!(compute_current_resource(u64::MAX - 1, u64::MAX, 1, 0, 10, 1), u64::MAX);
+        assert_eq!(compute_current_resource(0, 500, u64::MAX, 0, u64::MAX, 1), 500);
+        assert_eq!(compute_travel_time(u64::MAX, 1, u64::MAX), u64::MAX);
+        assert_eq!(compute_travel_time(u64::MAX, u64::MAX, u64::MAX), u64::MAX / 10_000);
+        assert_eq!(compute_landing_slot(u64::MAX, 10, 1, 10_000), u64::MAX);
+        assert_eq!(compute_landing_slot(u64::MAX - 5, 50, 2, 0), u64::MAX);
+    }
+}
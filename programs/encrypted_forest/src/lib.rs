@@ -2,6 +2,9 @@ use anchor_lang::prelude::*;
 use arcium_anchor::prelude::*;
 use arcium_client::idl::arcium::types::{CallbackAccount, CircuitSource, OffChainCircuitSource};
 use arcium_macros::circuit_hash;
+use sha2::{Digest, Sha256};
+
+pub mod game_math;
 
 // ---------------------------------------------------------------------------
 // Computation definition offsets for each encrypted instruction
@@ -11,23 +14,81 @@ const COMP_DEF_OFFSET_INIT_SPAWN_PLANET: u32 = comp_def_offset("init_spawn_plane
 const COMP_DEF_OFFSET_PROCESS_MOVE: u32 = comp_def_offset("process_move");
 const COMP_DEF_OFFSET_FLUSH_PLANET: u32 = comp_def_offset("flush_planet");
 const COMP_DEF_OFFSET_UPGRADE_PLANET: u32 = comp_def_offset("upgrade_planet");
+const COMP_DEF_OFFSET_SURRENDER_PLANET: u32 = comp_def_offset("surrender_planet");
+const COMP_DEF_OFFSET_TRANSFER_RESOURCES: u32 = comp_def_offset("transfer_resources");
+const COMP_DEF_OFFSET_SPY_MOVE: u32 = comp_def_offset("spy_move");
+const COMP_DEF_OFFSET_COMET_COLLECT: u32 = comp_def_offset("comet_collect");
+const COMP_DEF_OFFSET_MINE_ASTEROID: u32 = comp_def_offset("mine_asteroid");
+const COMP_DEF_OFFSET_CHECK_OWNERSHIP: u32 = comp_def_offset("check_ownership");
+const COMP_DEF_OFFSET_WORMHOLE_JUMP: u32 = comp_def_offset("wormhole_jump");
+const COMP_DEF_OFFSET_COMET_DRIFT: u32 = comp_def_offset("comet_drift");
+const COMP_DEF_OFFSET_TRANSFER_OWNER: u32 = comp_def_offset("transfer_owner");
 
 declare_id!("8BscA3fCxbBTkNCNHSopiQ84Q4A58YYzvQkqwbUM7wqA");
 
 // ---------------------------------------------------------------------------
 // Constants
 // ---------------------------------------------------------------------------
-const PLANET_STATE_FIELDS: usize = 3;   // Pack<[u32;15]> = 60 bytes => ceil(60/26) = 3 FEs
+const PLANET_STATE_FIELDS: usize = 4;   // Pack<[u32;23]> = 92 bytes => ceil(92/26) = 4 FEs
+const LEGACY_PLANET_STATE_FIELDS: usize = 3; // Pack<[u32;18]> before the base gen stats
 const PENDING_MOVE_DATA_FIELDS: usize = 4;
+const SPAWN_COORDS_FIELDS: usize = 2;    // Enc<Mxe, SpawnCoords>: x, y
 
 // Base size for PendingMovesMetadata:
 // discriminator(8) + game_id(8) + planet_hash(32) + next_move_id(8) + move_count(2) +
-// queued_count(1) + queued_landing_slots(8 * 8 = 64) + vec_prefix(4)
-const PENDING_MOVES_META_BASE_SIZE: usize = 8 + 8 + 32 + 8 + 2 + 1 + 64 + 4;
+// capacity(2) + queued_count(1) + queued_landing_slots(8 * 8 = 64) +
+// last_move_queued_slot(8) + vec_prefix(4)
+const PENDING_MOVES_META_BASE_SIZE: usize = 8 + 8 + 32 + 8 + 2 + 2 + 1 + 64 + 8 + 4;
 // Each PendingMoveEntry: landing_slot(8) + move_id(8)
 const PENDING_MOVE_ENTRY_SIZE: usize = 16;
 // Max queued moves per planet (requires one flush call per move)
 const MAX_QUEUED_CALLBACKS: usize = 8;
+// extend_game must leave at least this many slots of play from now
+const MIN_EXTENSION_SLOTS: u64 = 100;
+// Shortest game create_game accepts (end_slot - start_slot)
+const MIN_GAME_DURATION_SLOTS: u64 = 1_000;
+// Slots after end_slot during which cleanup_planet waits for pending moves to be flushed
+const GRACE_PERIOD_SLOTS: u64 = 10_000;
+// Planet accounts cleanup_player checks via remaining_accounts
+const MAX_CLEANUP_PLANET_CHECKS: usize = 8;
+// Slots a comet_collect computation may stay in flight before the admin can reclaim the comet
+const COMET_COLLECT_TIMEOUT_SLOTS: u64 = 1_000;
+// Slots a flush_planet computation may stay in flight before the move can be re-queued
+const FLUSH_RETRY_SLOTS: u64 = 1_000;
+// broadcast_bulk entries per call: each costs 48 bytes of data plus a 32-byte
+// planet account, which keeps the transaction under the 1232-byte limit
+const MAX_BROADCAST_ENTRIES: usize = 10;
+// Upper bound on AdminMultisig.signers (keeps the account fixed-size)
+const MAX_MULTISIG_SIGNERS: usize = 10;
+// Moves abandon_move_batch withdraws per call
+const MAX_ABANDON_MOVES: usize = 8;
+// Existing spawns init_spawn_planet can check separation against (circuit arity)
+const MAX_SEPARATION_CHECKS: usize = 8;
+// update_hash_rounds calls a game allows (bounds the prior planets an init must check)
+const MAX_HASH_ROUNDS_UPDATES: usize = 4;
+// Offset the circuits add to signed coordinates; (COORD_BIAS, COORD_BIAS) is the map center
+const COORD_BIAS: u64 = 1 << 31;
+/// Smallest non-zero priority_fee_lamports the priority-capable queue_*
+/// instructions accept; 0 opts out of paying a priority fee. The fee is a
+/// donation to the Arcium fee pool: Arcium does not use it to order the
+/// computation queue, so paying it does not make a computation run sooner.
+pub const MIN_PRIORITY_FEE: u64 = 5_000;
+// Each LeaderboardEntry: owner(32) + points(8)
+const LEADERBOARD_ENTRY_SIZE: usize = 32 + 8;
+// EncryptedCelestialBody layout version written at creation (byte offset 8)
+const CURRENT_VERSION: u8 = 2;
+// Version of bodies rewritten by migrate_celestial_body: their state predates
+// the pre-comet gen stats, so queue_comet_drift refuses them
+const MIGRATED_VERSION: u8 = 1;
+/// Protocol version of this deployment, reported by get_program_version and
+/// stamped on each Game at creation.
+pub const PROGRAM_VERSION: u32 = 1;
+/// Oldest client protocol version this deployment still accepts.
+pub const MIN_COMPATIBLE_CLIENT_VERSION: u32 = 1;
+// Version-0 bodies (see LegacyCelestialBody)
+const LEGACY_CELESTIAL_BODY_SIZE: usize = 8 + LegacyCelestialBody::INIT_SPACE;
+// Games in the original layout (see LegacyGame)
+const LEGACY_GAME_SIZE: usize = 8 + LegacyGame::INIT_SPACE;
 
 // ---------------------------------------------------------------------------
 // Account byte offsets for reading encrypted data from on-chain accounts
@@ -35,36 +96,440 @@ const MAX_QUEUED_CALLBACKS: usize = 8;
 
 // PendingMoveAccount layout (after 8-byte discriminator):
 //   game_id(8) + planet_hash(32) + move_id(8) + landing_slot(8) + payer(32) + populated(1) = 89 bytes
+//   payer(32) starts at offset 64
 //   enc_nonce(16) starts at offset 97
 //   enc_ciphertexts[4*32] at offset 113
+const MOVE_ACCOUNT_PAYER_OFFSET: usize = 64;
 const MOVE_ACCOUNT_ENC_NONCE_OFFSET: usize = 97;
 const MOVE_CT_OFFSET: u32 = 113;
+const MOVE_ACCOUNT_PLANET_HASH_OFFSET: usize = 16;
+const MOVE_ACCOUNT_CANONICAL_HASH_OFFSET: usize = 241;
+const MOVE_ACCOUNT_FLUSH_QUEUED_SLOT_OFFSET: usize = 305;
+
+// ---------------------------------------------------------------------------
+// Helper: EncryptedCelestialBody layout version
+// Read from raw bytes so version-0 accounts (no version byte) can still be
+// recognized before Anchor tries to deserialize them.
+// ---------------------------------------------------------------------------
+fn celestial_body_version(info: &AccountInfo) -> u8 {
+    match info.try_borrow_data() {
+        Ok(data) if data.len() >= EncryptedCelestialBody::MAX_SIZE => data[8],
+        _ => 0,
+    }
+}
+
+// ---------------------------------------------------------------------------
+// PDA helpers -- same seeds as the #[account(seeds = ...)] constraints below,
+// so off-chain Rust clients and the program derive addresses identically.
+// ---------------------------------------------------------------------------
+pub fn game_pda(program_id: &Pubkey, game_id: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"game", game_id.to_le_bytes().as_ref()], program_id)
+}
+
+pub fn player_pda(program_id: &Pubkey, game_id: u64, owner: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"player", game_id.to_le_bytes().as_ref(), owner.as_ref()],
+        program_id,
+    )
+}
+
+pub fn planet_pda(program_id: &Pubkey, game_id: u64, planet_hash: &[u8; 32]) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"planet", game_id.to_le_bytes().as_ref(), planet_hash.as_ref()],
+        program_id,
+    )
+}
+
+pub fn pending_moves_pda(
+    program_id: &Pubkey,
+    game_id: u64,
+    planet_hash: &[u8; 32],
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"moves", game_id.to_le_bytes().as_ref(), planet_hash.as_ref()],
+        program_id,
+    )
+}
+
+pub fn move_pda(
+    program_id: &Pubkey,
+    game_id: u64,
+    planet_hash: &[u8; 32],
+    move_id: u64,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            b"move",
+            game_id.to_le_bytes().as_ref(),
+            planet_hash.as_ref(),
+            move_id.to_le_bytes().as_ref(),
+        ],
+        program_id,
+    )
+}
+
+pub fn offset_tracker_pda(program_id: &Pubkey, game_id: u64, owner: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"offset", game_id.to_le_bytes().as_ref(), owner.as_ref()],
+        program_id,
+    )
+}
+
+/// Computation offset for a player's `counter`-th queue_* call. Computation
+/// accounts are shared by every player of the MXE, so the counter is hashed
+/// with the game and owner rather than used directly.
+pub fn derive_computation_offset(game_id: u64, owner: &Pubkey, counter: u64) -> u64 {
+    let mut data = Vec::with_capacity(6 + 8 + 32 + 8);
+    data.extend_from_slice(b"offset");
+    data.extend_from_slice(&game_id.to_le_bytes());
+    data.extend_from_slice(owner.as_ref());
+    data.extend_from_slice(&counter.to_le_bytes());
+    let mut prefix = [0u8; 8];
+    prefix.copy_from_slice(&blake3::hash(&data).as_bytes()[..8]);
+    u64::from_le_bytes(prefix)
+}
+
+pub fn leaderboard_pda(program_id: &Pubkey, game_id: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"leaderboard", game_id.to_le_bytes().as_ref()], program_id)
+}
+
+// ---------------------------------------------------------------------------
+// Helper: slots since the planet last resolved a landed move. Unlike
+// last_updated_slot (any state write), this is how stale the planet is
+// relative to its pending moves.
+// ---------------------------------------------------------------------------
+pub fn slots_since_flush(planet: &EncryptedCelestialBody, current_slot: u64) -> u64 {
+    current_slot.saturating_sub(planet.last_flushed_slot)
+}
 
 // ---------------------------------------------------------------------------
 // Hash helper
 // ---------------------------------------------------------------------------
-pub fn compute_planet_hash(x: i64, y: i64, game_id: u64, hash_rounds: u16) -> [u8; 32] {
+fn planet_hash_fn(data: &[u8], hash_algorithm: HashAlgorithm) -> [u8; 32] {
+    match hash_algorithm {
+        HashAlgorithm::Blake3 => *blake3::hash(data).as_bytes(),
+        HashAlgorithm::Sha256 => Sha256::digest(data).into(),
+    }
+}
+
+pub fn compute_planet_hash(
+    x: i64,
+    y: i64,
+    game_id: u64,
+    hash_rounds: u16,
+    hash_algorithm: HashAlgorithm,
+) -> [u8; 32] {
     let mut input = [0u8; 24];
     input[0..8].copy_from_slice(&x.to_le_bytes());
     input[8..16].copy_from_slice(&y.to_le_bytes());
     input[16..24].copy_from_slice(&game_id.to_le_bytes());
-    let mut hash = *blake3::hash(&input).as_bytes();
-    for _ in 1..hash_rounds {
-        hash = *blake3::hash(&hash).as_bytes();
+    let hash = planet_hash_fn(&input, hash_algorithm);
+    extend_planet_hash(hash, hash_rounds.saturating_sub(1), hash_algorithm)
+}
+
+/// Applies `extra_rounds` more rounds to a planet hash. Each round only
+/// rehashes the previous output, so a location's hash under more rounds
+/// follows from its hash under fewer.
+pub fn extend_planet_hash(
+    mut hash: [u8; 32],
+    extra_rounds: u16,
+    hash_algorithm: HashAlgorithm,
+) -> [u8; 32] {
+    for _ in 0..extra_rounds {
+        hash = planet_hash_fn(&hash, hash_algorithm);
     }
     hash
 }
 
+// ---------------------------------------------------------------------------
+// Helper: once update_hash_rounds has run, the same (x, y) hashes to a new
+// planet PDA. An init must then prove the location has no planet under any
+// earlier hash_rounds value: base_planet_hash is its hash under the game's
+// first value, and prior_planets holds its planet PDA under each earlier value
+// (game.prior_hash_rounds order), none of which may hold an account.
+// ---------------------------------------------------------------------------
+fn check_no_prior_rounds_planet(
+    program_id: &Pubkey,
+    game: &Game,
+    planet_hash: &[u8; 32],
+    base_planet_hash: Option<[u8; 32]>,
+    prior_planets: &[AccountInfo],
+) -> Result<()> {
+    let updates = game.hash_rounds_updates as usize;
+    require!(prior_planets.len() == updates, ErrorCode::PriorRoundsPlanetsRequired);
+    if updates == 0 {
+        return Ok(());
+    }
+    let mut hash = base_planet_hash.ok_or(ErrorCode::PriorRoundsPlanetsRequired)?;
+    let mut rounds = game.prior_hash_rounds[0];
+    for (i, acc) in prior_planets.iter().enumerate() {
+        let level = game.prior_hash_rounds[i];
+        hash = extend_planet_hash(hash, level - rounds, game.hash_algorithm);
+        rounds = level;
+        let (expected_pda, _) = planet_pda(program_id, game.game_id, &hash);
+        require!(acc.key() == expected_pda, ErrorCode::PriorRoundsPlanetsRequired);
+        require!(acc.data_is_empty(), ErrorCode::PlanetExistsUnderPriorRounds);
+    }
+    hash = extend_planet_hash(hash, game.hash_rounds - rounds, game.hash_algorithm);
+    require!(hash == *planet_hash, ErrorCode::InvalidPlanetHash);
+    Ok(())
+}
+
+// ---------------------------------------------------------------------------
+// Helper: admin gate for the sensitive game instructions. The game admin
+// passes directly; otherwise the signer must belong to the AdminMultisig and
+// its live proposal must match `action` with at least `threshold` approvals.
+// The proposal is consumed on success so it cannot be replayed.
+// ---------------------------------------------------------------------------
+fn authorize_admin(
+    game: &Game,
+    signer: Pubkey,
+    multisig: Option<&mut AdminMultisig>,
+    action: AdminActionType,
+    current_slot: u64,
+) -> Result<()> {
+    if game.admin == signer {
+        return Ok(());
+    }
+    let multisig = multisig.ok_or(ErrorCode::Unauthorized)?;
+    require!(multisig.signers.contains(&signer), ErrorCode::Unauthorized);
+
+    let pending = multisig
+        .pending_action
+        .as_ref()
+        .ok_or(ErrorCode::NoPendingAdminAction)?;
+    require!(pending.action_type == action, ErrorCode::AdminActionMismatch);
+    require!(
+        !multisig.is_expired(pending, current_slot),
+        ErrorCode::ActionExpired
+    );
+    require!(
+        pending.approvals.len() >= multisig.threshold as usize,
+        ErrorCode::InsufficientApprovals
+    );
+
+    multisig.pending_action = None;
+    Ok(())
+}
+
+// ---------------------------------------------------------------------------
+// Helper: callback verify_output failure. The transaction still fails, but
+// the event lands in its logs so indexers can spot and re-queue stuck
+// computations.
+// ---------------------------------------------------------------------------
+fn computation_failed(circuit_name: &str, computation_account: Pubkey, code: ErrorCode) -> Error {
+    emit!(ComputationFailedEvent {
+        circuit_name: circuit_name.to_string(),
+        computation_account,
+        error_code: code as u32 + anchor_lang::error::ERROR_CODE_OFFSET,
+    });
+    code.into()
+}
+
+/// Classifies a verify_output error so clients can pick a recovery path:
+/// a bad cluster signature (re-queue on a healthy cluster), an output for a
+/// computation that is no longer current (drop it), or an output from the
+/// wrong circuit (re-check the comp def). Anything else, including an MPC
+/// abort, stays AbortedComputation. Matches on the arcium_anchor variant
+/// name, which is stable across releases where the error type is not.
+fn map_arcium_error(e: &impl core::fmt::Debug) -> ErrorCode {
+    let name = format!("{:?}", e);
+    if name.contains("Signature") {
+        ErrorCode::ComputationSignatureInvalid
+    } else if name.contains("Stale") || name.contains("Expired") || name.contains("Status") {
+        ErrorCode::ComputationOutputStale
+    } else if name.contains("Circuit") || name.contains("CompDef") || name.contains("Mismatch") {
+        ErrorCode::ComputationCircuitMismatch
+    } else {
+        ErrorCode::AbortedComputation
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Helper: cleanup_player ownership check. Ownership is encrypted, so the
+// player's planets (found off-chain, e.g. with check_ownership) are passed as
+// up to MAX_CLEANUP_PLANET_CHECKS remaining accounts and must already be
+// closed by cleanup_planet. The spawn planet is the one we can derive, so it
+// must be among them while has_spawned is set.
+// ---------------------------------------------------------------------------
+fn require_planets_closed(
+    program_id: &Pubkey,
+    game_id: u64,
+    player: &Player,
+    planets: &[AccountInfo],
+) -> Result<()> {
+    require!(
+        planets.len() <= MAX_CLEANUP_PLANET_CHECKS,
+        ErrorCode::PlayerStillOwnsPlanets
+    );
+    for planet in planets {
+        require!(
+            planet.data_is_empty() && planet.lamports() == 0,
+            ErrorCode::PlayerStillOwnsPlanets
+        );
+    }
+    if player.has_spawned {
+        let (spawn_pda, _) = planet_pda(program_id, game_id, &player.spawn_planet_hash);
+        require!(
+            planets.iter().any(|p| p.key() == spawn_pda),
+            ErrorCode::PlayerStillOwnsPlanets
+        );
+    }
+    Ok(())
+}
+
+// ---------------------------------------------------------------------------
+// Helper: spawn distance from the map center. Only RaceToCenter restricts it;
+// spawning next to the center there would win on the spot.
+// ---------------------------------------------------------------------------
+fn min_spawn_distance_from_center(win_condition: &WinCondition) -> u64 {
+    match win_condition {
+        WinCondition::RaceToCenter { min_spawn_distance } => *min_spawn_distance,
+        _ => 0,
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Helper: priority fee. queue_process_move, queue_flush_planet and
+// queue_upgrade_planet may donate to the Arcium fee pool on top of the fixed
+// computation fee. The pool pays the Arx nodes that execute the cluster's
+// queue, but Arcium does not order the queue by it, so the transfer buys no
+// earlier execution (see MIN_PRIORITY_FEE). 0 skips the transfer.
+// ---------------------------------------------------------------------------
+fn require_priority_fee(priority_fee_lamports: u64) -> Result<()> {
+    require!(
+        priority_fee_lamports == 0 || priority_fee_lamports >= MIN_PRIORITY_FEE,
+        ErrorCode::InsufficientPriorityFee
+    );
+    Ok(())
+}
+
+fn pay_priority_fee<'info>(
+    payer: &Signer<'info>,
+    pool_account: &Account<'info, FeePool>,
+    system_program: &Program<'info, System>,
+    priority_fee_lamports: u64,
+) -> Result<()> {
+    if priority_fee_lamports == 0 {
+        return Ok(());
+    }
+    anchor_lang::system_program::transfer(
+        CpiContext::new(
+            system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: payer.to_account_info(),
+                to: pool_account.to_account_info(),
+            },
+        ),
+        priority_fee_lamports,
+    )
+}
+
+// ---------------------------------------------------------------------------
+// Helper: broadcast gate. The Player account is only present when its PDA
+// (seeded by the broadcaster) exists, so its presence proves membership.
+// ---------------------------------------------------------------------------
+fn require_player_or_spectator(
+    broadcaster: Pubkey,
+    is_player: bool,
+    registry: Option<&Account<SpectatorRegistry>>,
+) -> Result<()> {
+    let is_spectator = registry.is_some_and(|r| r.spectators.contains(&broadcaster));
+    require!(is_player || is_spectator, ErrorCode::BroadcasterNotRegistered);
+    Ok(())
+}
+
+// ---------------------------------------------------------------------------
+// Helper: noise thresholds must partition the 16-bit noise ranges in order.
+// dead_space_threshold gates a separate hash byte, so it is not part of the
+// body-type chain.
+// ---------------------------------------------------------------------------
+pub fn validate_noise_thresholds(nt: &NoiseThresholds) -> Result<()> {
+    require!(
+        game_math::noise_thresholds_ordered(nt),
+        ErrorCode::InvalidNoiseThresholds
+    );
+    Ok(())
+}
+
+// ---------------------------------------------------------------------------
+// Helper: pick the circuit hash for init_comp_def_* (compiled or override)
+// ---------------------------------------------------------------------------
+// An override replaces the audited compiled hash, so it is restricted to the
+// program upgrade authority; the compiled hash needs no extra signer.
+fn resolve_circuit_hash(
+    name: &str,
+    compiled: [u8; 32],
+    hash_override: Option<[u8; 32]>,
+    payer: &Pubkey,
+    program_data: Option<&ProgramData>,
+) -> Result<[u8; 32]> {
+    if hash_override.is_some() {
+        let authority = program_data.and_then(|pd| pd.upgrade_authority_address);
+        require!(authority == Some(*payer), ErrorCode::Unauthorized);
+    }
+    let hash = hash_override.unwrap_or(compiled);
+    emit!(CompDefUpdatedEvent {
+        name: name.to_string(),
+        new_hash: hash,
+    });
+    Ok(hash)
+}
+
+// ---------------------------------------------------------------------------
+// Helper: award PointsBurning points to a player, returns the points added
+// ---------------------------------------------------------------------------
+fn award_points(player: &mut Player, win_condition: &WinCondition, metal: u64) -> u64 {
+    let delta = match win_condition {
+        WinCondition::PointsBurning { points_per_metal } => metal.saturating_mul(*points_per_metal),
+        _ => 0,
+    };
+    player.points = player.points.saturating_add(delta);
+    delta
+}
+
+// ---------------------------------------------------------------------------
+// Helper: a wallet key as four little-endian u64 words, the plaintext form
+// circuits fold into player_id_from_key
+// ---------------------------------------------------------------------------
+fn pubkey_words(key: &Pubkey) -> [u64; 4] {
+    let bytes = key.to_bytes();
+    let mut words = [0u64; 4];
+    for (i, word) in words.iter_mut().enumerate() {
+        *word = u64::from_le_bytes(bytes[i * 8..i * 8 + 8].try_into().unwrap());
+    }
+    words
+}
+
 // ---------------------------------------------------------------------------
 // Helper: extract [u8; 32] from a Vec<u8> at index i
 // ---------------------------------------------------------------------------
-fn extract_ct(data: &[u8], index: usize) -> [u8; 32] {
+fn extract_ct(data: &[u8], index: usize) -> Result<[u8; 32]> {
+    let start = index * 32;
+    require!(start + 32 <= data.len(), ErrorCode::InvalidMoveInput);
+    Ok(extract_ct_unchecked(data, index))
+}
+
+/// Panics if `data` is shorter than `(index + 1) * 32`. Only use after the
+/// caller has already checked the ciphertext length.
+fn extract_ct_unchecked(data: &[u8], index: usize) -> [u8; 32] {
     let start = index * 32;
     let mut out = [0u8; 32];
     out.copy_from_slice(&data[start..start + 32]);
     out
 }
 
+// ---------------------------------------------------------------------------
+// Helper: callback account for an optional account. Anchor reads the program
+// id in an optional slot as None.
+// ---------------------------------------------------------------------------
+fn optional_callback_account(key: Option<Pubkey>) -> CallbackAccount {
+    CallbackAccount {
+        pubkey: key.unwrap_or(crate::ID),
+        is_writable: key.is_some(),
+    }
+}
+
 // ===========================================================================
 // Program
 // ===========================================================================
@@ -75,18 +540,29 @@ pub mod encrypted_forest {
 
     // -----------------------------------------------------------------------
     // Computation Definition Initializers
+    // circuit_hash_override = Some(hash) registers a rebuilt circuit without
+    // redeploying the program. Comp defs are program-wide (no Game exists
+    // yet), so the signing payer is the admin here.
     // -----------------------------------------------------------------------
 
     pub fn init_comp_def_init_planet(
         ctx: Context<InitInitPlanetCompDef>,
         circuit_base_url: String,
+        circuit_hash_override: Option<[u8; 32]>,
     ) -> Result<()> {
         let source_url = format!("{}/init_planet.arcis", circuit_base_url);
+        let hash = resolve_circuit_hash(
+            "init_planet",
+            circuit_hash!("init_planet"),
+            circuit_hash_override,
+            &ctx.accounts.payer.key(),
+            ctx.accounts.program_data.as_deref(),
+        )?;
         init_comp_def(
             ctx.accounts,
             Some(CircuitSource::OffChain(OffChainCircuitSource {
                 source: source_url,
-                hash: circuit_hash!("init_planet"),
+                hash,
             })),
             None,
         )?;
@@ -96,13 +572,21 @@ pub mod encrypted_forest {
     pub fn init_comp_def_init_spawn_planet(
         ctx: Context<InitInitSpawnPlanetCompDef>,
         circuit_base_url: String,
+        circuit_hash_override: Option<[u8; 32]>,
     ) -> Result<()> {
         let source_url = format!("{}/init_spawn_planet.arcis", circuit_base_url);
+        let hash = resolve_circuit_hash(
+            "init_spawn_planet",
+            circuit_hash!("init_spawn_planet"),
+            circuit_hash_override,
+            &ctx.accounts.payer.key(),
+            ctx.accounts.program_data.as_deref(),
+        )?;
         init_comp_def(
             ctx.accounts,
             Some(CircuitSource::OffChain(OffChainCircuitSource {
                 source: source_url,
-                hash: circuit_hash!("init_spawn_planet"),
+                hash,
             })),
             None,
         )?;
@@ -112,13 +596,21 @@ pub mod encrypted_forest {
     pub fn init_comp_def_process_move(
         ctx: Context<InitProcessMoveCompDef>,
         circuit_base_url: String,
+        circuit_hash_override: Option<[u8; 32]>,
     ) -> Result<()> {
         let source_url = format!("{}/process_move.arcis", circuit_base_url);
+        let hash = resolve_circuit_hash(
+            "process_move",
+            circuit_hash!("process_move"),
+            circuit_hash_override,
+            &ctx.accounts.payer.key(),
+            ctx.accounts.program_data.as_deref(),
+        )?;
         init_comp_def(
             ctx.accounts,
             Some(CircuitSource::OffChain(OffChainCircuitSource {
                 source: source_url,
-                hash: circuit_hash!("process_move"),
+                hash,
             })),
             None,
         )?;
@@ -128,13 +620,21 @@ pub mod encrypted_forest {
     pub fn init_comp_def_flush_planet(
         ctx: Context<InitFlushPlanetCompDef>,
         circuit_base_url: String,
+        circuit_hash_override: Option<[u8; 32]>,
     ) -> Result<()> {
         let source_url = format!("{}/flush_planet.arcis", circuit_base_url);
+        let hash = resolve_circuit_hash(
+            "flush_planet",
+            circuit_hash!("flush_planet"),
+            circuit_hash_override,
+            &ctx.accounts.payer.key(),
+            ctx.accounts.program_data.as_deref(),
+        )?;
         init_comp_def(
             ctx.accounts,
             Some(CircuitSource::OffChain(OffChainCircuitSource {
                 source: source_url,
-                hash: circuit_hash!("flush_planet"),
+                hash,
             })),
             None,
         )?;
@@ -144,13 +644,237 @@ pub mod encrypted_forest {
     pub fn init_comp_def_upgrade_planet(
         ctx: Context<InitUpgradePlanetCompDef>,
         circuit_base_url: String,
+        circuit_hash_override: Option<[u8; 32]>,
     ) -> Result<()> {
         let source_url = format!("{}/upgrade_planet.arcis", circuit_base_url);
+        let hash = resolve_circuit_hash(
+            "upgrade_planet",
+            circuit_hash!("upgrade_planet"),
+            circuit_hash_override,
+            &ctx.accounts.payer.key(),
+            ctx.accounts.program_data.as_deref(),
+        )?;
+        init_comp_def(
+            ctx.accounts,
+            Some(CircuitSource::OffChain(OffChainCircuitSource {
+                source: source_url,
+                hash,
+            })),
+            None,
+        )?;
+        Ok(())
+    }
+
+    pub fn init_comp_def_surrender_planet(
+        ctx: Context<InitSurrenderPlanetCompDef>,
+        circuit_base_url: String,
+        circuit_hash_override: Option<[u8; 32]>,
+    ) -> Result<()> {
+        let source_url = format!("{}/surrender_planet.arcis", circuit_base_url);
+        let hash = resolve_circuit_hash(
+            "surrender_planet",
+            circuit_hash!("surrender_planet"),
+            circuit_hash_override,
+            &ctx.accounts.payer.key(),
+            ctx.accounts.program_data.as_deref(),
+        )?;
+        init_comp_def(
+            ctx.accounts,
+            Some(CircuitSource::OffChain(OffChainCircuitSource {
+                source: source_url,
+                hash,
+            })),
+            None,
+        )?;
+        Ok(())
+    }
+
+    pub fn init_comp_def_transfer_resources(
+        ctx: Context<InitTransferResourcesCompDef>,
+        circuit_base_url: String,
+        circuit_hash_override: Option<[u8; 32]>,
+    ) -> Result<()> {
+        let source_url = format!("{}/transfer_resources.arcis", circuit_base_url);
+        let hash = resolve_circuit_hash(
+            "transfer_resources",
+            circuit_hash!("transfer_resources"),
+            circuit_hash_override,
+            &ctx.accounts.payer.key(),
+            ctx.accounts.program_data.as_deref(),
+        )?;
+        init_comp_def(
+            ctx.accounts,
+            Some(CircuitSource::OffChain(OffChainCircuitSource {
+                source: source_url,
+                hash,
+            })),
+            None,
+        )?;
+        Ok(())
+    }
+
+    pub fn init_comp_def_spy_move(
+        ctx: Context<InitSpyMoveCompDef>,
+        circuit_base_url: String,
+        circuit_hash_override: Option<[u8; 32]>,
+    ) -> Result<()> {
+        let source_url = format!("{}/spy_move.arcis", circuit_base_url);
+        let hash = resolve_circuit_hash(
+            "spy_move",
+            circuit_hash!("spy_move"),
+            circuit_hash_override,
+            &ctx.accounts.payer.key(),
+            ctx.accounts.program_data.as_deref(),
+        )?;
+        init_comp_def(
+            ctx.accounts,
+            Some(CircuitSource::OffChain(OffChainCircuitSource {
+                source: source_url,
+                hash,
+            })),
+            None,
+        )?;
+        Ok(())
+    }
+
+    pub fn init_comp_def_comet_collect(
+        ctx: Context<InitCometCollectCompDef>,
+        circuit_base_url: String,
+        circuit_hash_override: Option<[u8; 32]>,
+    ) -> Result<()> {
+        let source_url = format!("{}/comet_collect.arcis", circuit_base_url);
+        let hash = resolve_circuit_hash(
+            "comet_collect",
+            circuit_hash!("comet_collect"),
+            circuit_hash_override,
+            &ctx.accounts.payer.key(),
+            ctx.accounts.program_data.as_deref(),
+        )?;
+        init_comp_def(
+            ctx.accounts,
+            Some(CircuitSource::OffChain(OffChainCircuitSource {
+                source: source_url,
+                hash,
+            })),
+            None,
+        )?;
+        Ok(())
+    }
+
+    pub fn init_comp_def_mine_asteroid(
+        ctx: Context<InitMineAsteroidCompDef>,
+        circuit_base_url: String,
+        circuit_hash_override: Option<[u8; 32]>,
+    ) -> Result<()> {
+        let source_url = format!("{}/mine_asteroid.arcis", circuit_base_url);
+        let hash = resolve_circuit_hash(
+            "mine_asteroid",
+            circuit_hash!("mine_asteroid"),
+            circuit_hash_override,
+            &ctx.accounts.payer.key(),
+            ctx.accounts.program_data.as_deref(),
+        )?;
+        init_comp_def(
+            ctx.accounts,
+            Some(CircuitSource::OffChain(OffChainCircuitSource {
+                source: source_url,
+                hash,
+            })),
+            None,
+        )?;
+        Ok(())
+    }
+
+    pub fn init_comp_def_check_ownership(
+        ctx: Context<InitCheckOwnershipCompDef>,
+        circuit_base_url: String,
+        circuit_hash_override: Option<[u8; 32]>,
+    ) -> Result<()> {
+        let source_url = format!("{}/check_ownership.arcis", circuit_base_url);
+        let hash = resolve_circuit_hash(
+            "check_ownership",
+            circuit_hash!("check_ownership"),
+            circuit_hash_override,
+            &ctx.accounts.payer.key(),
+            ctx.accounts.program_data.as_deref(),
+        )?;
+        init_comp_def(
+            ctx.accounts,
+            Some(CircuitSource::OffChain(OffChainCircuitSource {
+                source: source_url,
+                hash,
+            })),
+            None,
+        )?;
+        Ok(())
+    }
+
+    pub fn init_comp_def_wormhole_jump(
+        ctx: Context<InitWormholeJumpCompDef>,
+        circuit_base_url: String,
+        circuit_hash_override: Option<[u8; 32]>,
+    ) -> Result<()> {
+        let source_url = format!("{}/wormhole_jump.arcis", circuit_base_url);
+        let hash = resolve_circuit_hash(
+            "wormhole_jump",
+            circuit_hash!("wormhole_jump"),
+            circuit_hash_override,
+            &ctx.accounts.payer.key(),
+            ctx.accounts.program_data.as_deref(),
+        )?;
+        init_comp_def(
+            ctx.accounts,
+            Some(CircuitSource::OffChain(OffChainCircuitSource {
+                source: source_url,
+                hash,
+            })),
+            None,
+        )?;
+        Ok(())
+    }
+
+    pub fn init_comp_def_comet_drift(
+        ctx: Context<InitCometDriftCompDef>,
+        circuit_base_url: String,
+        circuit_hash_override: Option<[u8; 32]>,
+    ) -> Result<()> {
+        let source_url = format!("{}/comet_drift.arcis", circuit_base_url);
+        let hash = resolve_circuit_hash(
+            "comet_drift",
+            circuit_hash!("comet_drift"),
+            circuit_hash_override,
+            &ctx.accounts.payer.key(),
+            ctx.accounts.program_data.as_deref(),
+        )?;
+        init_comp_def(
+            ctx.accounts,
+            Some(CircuitSource::OffChain(OffChainCircuitSource {
+                source: source_url,
+                hash,
+            })),
+            None,
+        )?;
+        Ok(())
+    }
+
+    pub fn init_comp_def_transfer_owner(
+        ctx: Context<InitTransferOwnerCompDef>,
+        circuit_base_url: String,
+        circuit_hash_override: Option<[u8; 32]>,
+    ) -> Result<()> {
+        let source_url = format!("{}/transfer_owner.arcis", circuit_base_url);
+        let hash = resolve_circuit_hash(
+            "transfer_owner",
+            circuit_hash!("transfer_owner"),
+            circuit_hash_override,
+            &ctx.accounts.payer.key(),
+            ctx.accounts.program_data.as_deref(),
+        )?;
         init_comp_def(
             ctx.accounts,
             Some(CircuitSource::OffChain(OffChainCircuitSource {
                 source: source_url,
-                hash: circuit_hash!("upgrade_planet"),
+                hash,
             })),
             None,
         )?;
@@ -173,11 +897,56 @@ pub mod encrypted_forest {
         server_pubkey: Option<Pubkey>,
         noise_thresholds: NoiseThresholds,
         hash_rounds: u16,
+        max_moves_per_slot: u8,
+        max_pending_moves: u8,
+        quasar_ship_penalty_bps: u64,
+        move_cooldown_slots: u64,
+        max_players: u32,
+        min_spawn_separation: u64,
+        comet_drift_interval_slots: u64,
+        hash_algorithm: HashAlgorithm,
+        security_deposit_lamports: u64,
+        deposit_recipient: Option<Pubkey>,
+        capture_cooldown_slots: u64,
+        distance_method: DistanceMethod,
+        valid_spawn_body_types: u8,
+        max_planet_level: u8,
+        min_ships_per_move: u64,
+        points_to_win: u64,
     ) -> Result<()> {
+        validate_noise_thresholds(&noise_thresholds)?;
         require!(map_diameter > 0, ErrorCode::InvalidMapDiameter);
         require!(game_speed > 0, ErrorCode::InvalidGameSpeed);
         require!(end_slot > start_slot, ErrorCode::InvalidTimeRange);
+        require!(start_slot >= Clock::get()?.slot, ErrorCode::StartSlotInPast);
+        require!(
+            end_slot - start_slot >= MIN_GAME_DURATION_SLOTS,
+            ErrorCode::GameTooShort
+        );
         require!(hash_rounds >= 1, ErrorCode::InvalidHashRounds);
+        require!(max_moves_per_slot >= 1, ErrorCode::InvalidMaxMovesPerSlot);
+        require!(
+            max_pending_moves >= 1 && max_pending_moves as usize <= MAX_QUEUED_CALLBACKS,
+            ErrorCode::InvalidMaxPendingMoves
+        );
+        require!(
+            quasar_ship_penalty_bps <= 10_000,
+            ErrorCode::InvalidQuasarPenalty
+        );
+        // Bits 0-3 are the four body types; at least one must be spawnable
+        require!(
+            valid_spawn_body_types != 0 && valid_spawn_body_types <= 0x0F,
+            ErrorCode::InvalidSpawnBodyTypes
+        );
+        require!(max_planet_level >= 2, ErrorCode::InvalidMaxPlanetLevel);
+        require!(min_ships_per_move >= 1, ErrorCode::InvalidMinShipsPerMove);
+        // Each spawn is checked against every earlier one, MAX_SEPARATION_CHECKS at most
+        if min_spawn_separation > 0 {
+            require!(
+                max_players >= 1 && max_players as usize <= MAX_SEPARATION_CHECKS + 1,
+                ErrorCode::SpawnSeparationFull
+            );
+        }
         if whitelist {
             require!(server_pubkey.is_some(), ErrorCode::WhitelistRequiresServer);
         }
@@ -194,32 +963,516 @@ pub mod encrypted_forest {
         game.server_pubkey = server_pubkey;
         game.noise_thresholds = noise_thresholds;
         game.hash_rounds = hash_rounds;
+        game.prior_hash_rounds = [0u16; MAX_HASH_ROUNDS_UPDATES];
+        game.hash_rounds_updates = 0;
+        game.max_moves_per_slot = max_moves_per_slot;
+        game.max_pending_moves = max_pending_moves;
+        game.quasar_ship_penalty_bps = quasar_ship_penalty_bps;
+        game.move_cooldown_slots = move_cooldown_slots;
+        game.max_players = if max_players == 0 { u32::MAX } else { max_players };
+        game.current_player_count = 0;
+        game.planet_count = 0;
+        game.min_spawn_separation = min_spawn_separation;
+        game.spawn_count = 0;
+        game.spawn_planet_hashes = [[0u8; 32]; MAX_SEPARATION_CHECKS];
+        game.min_spawn_distance_from_center = min_spawn_distance_from_center(&win_condition);
+        game.comet_drift_interval_slots = comet_drift_interval_slots;
+        game.hash_algorithm = hash_algorithm;
+        game.security_deposit_lamports = security_deposit_lamports;
+        game.deposit_recipient = deposit_recipient.unwrap_or(game.admin);
+        game.capture_cooldown_slots = capture_cooldown_slots;
+        game.distance_method = distance_method;
+        game.valid_spawn_body_types = valid_spawn_body_types;
+        game.max_planet_level = max_planet_level;
+        game.min_ships_per_move = min_ships_per_move;
+        game.points_to_win = points_to_win;
+        game.winner = None;
+        game.ended_early = false;
+        game.paused = false;
+        game.allow_force_cleanup = false;
+        game.pending_admin = None;
+        game.account_version = PROGRAM_VERSION;
+
+        let program_state = &mut ctx.accounts.program_state;
+        program_state.active_game_count = program_state.active_game_count.saturating_add(1);
 
         Ok(())
     }
 
-    pub fn init_player(ctx: Context<InitPlayer>, _game_id: u64) -> Result<()> {
-        let game = &ctx.accounts.game;
+    /// Freeze or unfreeze a game. Only blocks new queue_* instructions;
+    /// callbacks for computations already in flight still land.
+    pub fn set_game_paused(ctx: Context<SetGamePaused>, _game_id: u64, paused: bool) -> Result<()> {
+        let clock = Clock::get()?;
+        authorize_admin(
+            &ctx.accounts.game,
+            ctx.accounts.admin.key(),
+            ctx.accounts.admin_multisig.as_deref_mut(),
+            AdminActionType::SetPaused { paused },
+            clock.slot,
+        )?;
 
-        if game.whitelist {
-            require!(
-                ctx.accounts.server.is_some(),
-                ErrorCode::WhitelistServerRequired
-            );
-            if let Some(ref server) = ctx.accounts.server {
-                require!(
-                    Some(server.key()) == game.server_pubkey,
-                    ErrorCode::InvalidServerKey
-                );
-            }
-        }
+        let game = &mut ctx.accounts.game;
+        game.paused = paused;
 
-        let player = &mut ctx.accounts.player;
+        emit!(GamePausedEvent {
+            game_id: game.game_id,
+            paused,
+            slot: clock.slot,
+        });
+
+        Ok(())
+    }
+
+    /// Push back end_slot of a running game (e.g. after an outage). Never shortens.
+    pub fn extend_game(ctx: Context<ExtendGame>, _game_id: u64, new_end_slot: u64) -> Result<()> {
+        let clock = Clock::get()?;
+        authorize_admin(
+            &ctx.accounts.game,
+            ctx.accounts.admin.key(),
+            ctx.accounts.admin_multisig.as_deref_mut(),
+            AdminActionType::ExtendGame { new_end_slot },
+            clock.slot,
+        )?;
+
+        let game = &mut ctx.accounts.game;
+        require!(clock.slot <= game.end_slot, ErrorCode::GameEnded);
+        require!(new_end_slot > game.end_slot, ErrorCode::InvalidExtension);
+        require!(
+            new_end_slot > clock.slot.saturating_add(MIN_EXTENSION_SLOTS),
+            ErrorCode::InvalidExtension
+        );
+
+        let old_end_slot = game.end_slot;
+        game.end_slot = new_end_slot;
+
+        emit!(GameExtendedEvent {
+            game_id: game.game_id,
+            old_end_slot,
+            new_end_slot,
+        });
+
+        Ok(())
+    }
+
+    /// Let players be cleaned up without proving their planets are closed.
+    /// Ownership is encrypted, so this is the escape hatch once the grace
+    /// period has passed.
+    pub fn set_allow_force_cleanup(
+        ctx: Context<SetAllowForceCleanup>,
+        _game_id: u64,
+        allow: bool,
+    ) -> Result<()> {
+        let game = &mut ctx.accounts.game;
+        let clock = Clock::get()?;
+        require!(
+            clock.slot > game.end_slot.saturating_add(GRACE_PERIOD_SLOTS),
+            ErrorCode::ForceCleanupTooEarly
+        );
+        game.allow_force_cleanup = allow;
+        Ok(())
+    }
+
+    /// Raise planet-hash difficulty mid-game. Only affects planets initialized
+    /// afterwards: each planet keeps the hash_rounds it was created under, and
+    /// inits must prove their location has no planet under an earlier value.
+    pub fn update_hash_rounds(
+        ctx: Context<UpdateHashRounds>,
+        _game_id: u64,
+        new_rounds: u16,
+    ) -> Result<()> {
+        let game = &mut ctx.accounts.game;
+        require!(new_rounds > game.hash_rounds, ErrorCode::InvalidHashRounds);
+        let updates = game.hash_rounds_updates as usize;
+        require!(updates < MAX_HASH_ROUNDS_UPDATES, ErrorCode::HashRoundsUpdatesExhausted);
+
+        let old_rounds = game.hash_rounds;
+        game.prior_hash_rounds[updates] = old_rounds;
+        game.hash_rounds_updates += 1;
+        game.hash_rounds = new_rounds;
+
+        emit!(HashRoundsUpdatedEvent {
+            game_id: game.game_id,
+            old_rounds,
+            new_rounds,
+        });
+
+        Ok(())
+    }
+
+    /// Step 1 of an admin handover: the current admin nominates a successor.
+    pub fn propose_admin_transfer(
+        ctx: Context<ProposeAdminTransfer>,
+        _game_id: u64,
+        new_admin: Pubkey,
+    ) -> Result<()> {
+        authorize_admin(
+            &ctx.accounts.game,
+            ctx.accounts.admin.key(),
+            ctx.accounts.admin_multisig.as_deref_mut(),
+            AdminActionType::ProposeAdminTransfer { new_admin },
+            Clock::get()?.slot,
+        )?;
+
+        let game = &mut ctx.accounts.game;
+        game.pending_admin = Some(new_admin);
+
+        emit!(AdminTransferProposedEvent {
+            game_id: game.game_id,
+            current_admin: game.admin,
+            pending_admin: new_admin,
+        });
+
+        Ok(())
+    }
+
+    /// Step 2: the nominee signs to take over. Clears pending_admin.
+    pub fn accept_admin_transfer(ctx: Context<AcceptAdminTransfer>, _game_id: u64) -> Result<()> {
+        let game = &mut ctx.accounts.game;
+        let previous_admin = game.admin;
+        game.admin = ctx.accounts.new_admin.key();
+        game.pending_admin = None;
+
+        emit!(AdminTransferCompletedEvent {
+            game_id: game.game_id,
+            previous_admin,
+            new_admin: game.admin,
+        });
+
+        Ok(())
+    }
+
+    /// Set up an M-of-N signer set that can stand in for the admin on
+    /// set_game_paused, extend_game and propose_admin_transfer.
+    pub fn create_admin_multisig(
+        ctx: Context<CreateAdminMultisig>,
+        _game_id: u64,
+        signers: Vec<Pubkey>,
+        threshold: u8,
+        proposal_ttl_slots: u64,
+    ) -> Result<()> {
+        require!(
+            !signers.is_empty() && signers.len() <= MAX_MULTISIG_SIGNERS,
+            ErrorCode::InvalidMultisigConfig
+        );
+        require!(
+            threshold >= 1 && threshold as usize <= signers.len(),
+            ErrorCode::InvalidMultisigConfig
+        );
+        require!(proposal_ttl_slots > 0, ErrorCode::InvalidMultisigConfig);
+        for (i, signer) in signers.iter().enumerate() {
+            require!(!signers[..i].contains(signer), ErrorCode::InvalidMultisigConfig);
+        }
+
+        let multisig = &mut ctx.accounts.admin_multisig;
+        multisig.game_id = ctx.accounts.game.game_id;
+        multisig.signers = signers;
+        multisig.threshold = threshold;
+        multisig.proposal_ttl_slots = proposal_ttl_slots;
+        multisig.pending_action = None;
+
+        Ok(())
+    }
+
+    /// Open a proposal; the proposer counts as the first approval.
+    /// Only one live proposal at a time; an expired one can be replaced.
+    pub fn propose_admin_action(
+        ctx: Context<ProposeAdminAction>,
+        _game_id: u64,
+        action_type: AdminActionType,
+    ) -> Result<()> {
+        let clock = Clock::get()?;
+        let proposer = ctx.accounts.signer.key();
+        let multisig = &mut ctx.accounts.admin_multisig;
+        require!(multisig.signers.contains(&proposer), ErrorCode::Unauthorized);
+        if let Some(pending) = &multisig.pending_action {
+            require!(
+                multisig.is_expired(pending, clock.slot),
+                ErrorCode::AdminActionPending
+            );
+        }
+
+        multisig.pending_action = Some(PendingAdminAction {
+            action_type,
+            proposed_at_slot: clock.slot,
+            approvals: vec![proposer],
+        });
+
+        emit!(AdminActionProposedEvent {
+            game_id: multisig.game_id,
+            action_type,
+            proposer,
+        });
+
+        Ok(())
+    }
+
+    pub fn approve_admin_action(ctx: Context<ApproveAdminAction>, _game_id: u64) -> Result<()> {
+        let clock = Clock::get()?;
+        let approver = ctx.accounts.signer.key();
+        let multisig = &mut ctx.accounts.admin_multisig;
+        require!(multisig.signers.contains(&approver), ErrorCode::Unauthorized);
+
+        let (game_id, threshold, ttl) =
+            (multisig.game_id, multisig.threshold, multisig.proposal_ttl_slots);
+        let pending = multisig
+            .pending_action
+            .as_mut()
+            .ok_or(ErrorCode::NoPendingAdminAction)?;
+        require!(
+            clock.slot <= pending.proposed_at_slot.saturating_add(ttl),
+            ErrorCode::ActionExpired
+        );
+        require!(
+            !pending.approvals.contains(&approver),
+            ErrorCode::AlreadyApproved
+        );
+        pending.approvals.push(approver);
+
+        emit!(AdminActionApprovedEvent {
+            game_id,
+            approver,
+            approvals: pending.approvals.len() as u8,
+            threshold,
+        });
+
+        Ok(())
+    }
+
+    pub fn init_player(ctx: Context<InitPlayer>, _game_id: u64) -> Result<()> {
+        let game = &mut ctx.accounts.game;
+
+        if game.whitelist {
+            require!(
+                ctx.accounts.server.is_some(),
+                ErrorCode::WhitelistServerRequired
+            );
+            if let Some(ref server) = ctx.accounts.server {
+                require!(
+                    Some(server.key()) == game.server_pubkey,
+                    ErrorCode::InvalidServerKey
+                );
+            }
+        }
+
+        game.current_player_count = game
+            .current_player_count
+            .checked_add(1)
+            .ok_or(ErrorCode::GameFull)?;
+        require!(game.current_player_count <= game.max_players, ErrorCode::GameFull);
+
+        let player = &mut ctx.accounts.player;
         player.owner = ctx.accounts.owner.key();
         player.game_id = game.game_id;
         player.points = 0;
         player.has_spawned = false;
+        player.last_move_slot = 0;
+        player.moves_this_slot = 0;
+
+        let tracker = &mut ctx.accounts.offset_tracker;
+        tracker.game_id = game.game_id;
+        tracker.owner = player.owner;
+
+        let deposit = &mut ctx.accounts.deposit;
+        deposit.game_id = game.game_id;
+        deposit.owner = player.owner;
+        deposit.amount = game.security_deposit_lamports;
+        if deposit.amount > 0 {
+            anchor_lang::system_program::transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    anchor_lang::system_program::Transfer {
+                        from: ctx.accounts.owner.to_account_info(),
+                        to: deposit.to_account_info(),
+                    },
+                ),
+                deposit.amount,
+            )?;
+        }
+
+        if let Some(profile) = ctx.accounts.player_profile.as_mut() {
+            profile.total_games = profile.total_games.saturating_add(1);
+        }
+
+        emit!(PlayerJoinedEvent {
+            game_id: game.game_id,
+            player: player.owner,
+            player_count: game.current_player_count,
+        });
+
+        Ok(())
+    }
+
+    /// One-time setup of the cross-game PlayerProfile for the signer.
+    pub fn init_player_profile(ctx: Context<InitPlayerProfile>) -> Result<()> {
+        let profile = &mut ctx.accounts.player_profile;
+        profile.owner = ctx.accounts.owner.key();
+        profile.total_games = 0;
+        profile.total_wins = 0;
+        profile.lifetime_points = 0;
+        profile.planets_captured = 0;
+        profile.ships_sent = 0;
+        Ok(())
+    }
 
+    // -----------------------------------------------------------------------
+    // Alliances
+    // -----------------------------------------------------------------------
+
+    pub fn create_alliance(
+        ctx: Context<CreateAlliance>,
+        _game_id: u64,
+        alliance_id: u64,
+        member_cap: u8,
+    ) -> Result<()> {
+        require!(member_cap >= 1, ErrorCode::InvalidMemberCap);
+
+        let alliance = &mut ctx.accounts.alliance;
+        alliance.game_id = ctx.accounts.game.game_id;
+        alliance.id = alliance_id;
+        alliance.founder = ctx.accounts.founder.key();
+        alliance.member_cap = member_cap;
+        alliance.members = vec![ctx.accounts.founder.key()];
+
+        Ok(())
+    }
+
+    pub fn add_alliance_member(
+        ctx: Context<UpdateAllianceMembers>,
+        _game_id: u64,
+        _alliance_id: u64,
+        member: Pubkey,
+    ) -> Result<()> {
+        let alliance = &mut ctx.accounts.alliance;
+        require!(
+            !alliance.members.contains(&member),
+            ErrorCode::AlreadyAllianceMember
+        );
+        require!(
+            alliance.members.len() < alliance.member_cap as usize,
+            ErrorCode::AllianceFull
+        );
+        alliance.members.push(member);
+        Ok(())
+    }
+
+    pub fn remove_alliance_member(
+        ctx: Context<UpdateAllianceMembers>,
+        _game_id: u64,
+        _alliance_id: u64,
+        member: Pubkey,
+    ) -> Result<()> {
+        let alliance = &mut ctx.accounts.alliance;
+        require!(member != alliance.founder, ErrorCode::InvalidAllianceMember);
+        let pos = alliance
+            .members
+            .iter()
+            .position(|m| *m == member)
+            .ok_or(ErrorCode::NotAllianceMember)?;
+        alliance.members.remove(pos);
+        Ok(())
+    }
+
+    // -----------------------------------------------------------------------
+    // Leaderboard
+    // -----------------------------------------------------------------------
+
+    pub fn create_leaderboard(
+        ctx: Context<CreateLeaderboard>,
+        _game_id: u64,
+        capacity: u8,
+    ) -> Result<()> {
+        require!(capacity >= 1, ErrorCode::InvalidLeaderboardCapacity);
+
+        let leaderboard = &mut ctx.accounts.leaderboard;
+        leaderboard.game_id = ctx.accounts.game.game_id;
+        leaderboard.capacity = capacity;
+        leaderboard.entries = Vec::new();
+
+        Ok(())
+    }
+
+    /// Re-rank the caller using their current Player.points. Entries are kept
+    /// sorted by points (descending); the lowest entry drops off when full.
+    pub fn update_leaderboard(ctx: Context<UpdateLeaderboard>, _game_id: u64) -> Result<()> {
+        let owner = ctx.accounts.owner.key();
+        let points = ctx.accounts.player.points;
+        let leaderboard = &mut ctx.accounts.leaderboard;
+
+        leaderboard.entries.retain(|e| e.owner != owner);
+        let rank = leaderboard.entries.partition_point(|e| e.points >= points);
+        if rank < leaderboard.capacity as usize {
+            leaderboard.entries.insert(rank, LeaderboardEntry { owner, points });
+            leaderboard.entries.truncate(leaderboard.capacity as usize);
+
+            emit!(LeaderboardUpdatedEvent {
+                game_id: leaderboard.game_id,
+                rank: rank as u8,
+                owner,
+                points,
+            });
+        }
+
+        Ok(())
+    }
+
+    // -----------------------------------------------------------------------
+    // Spectators
+    // -----------------------------------------------------------------------
+
+    pub fn create_spectator_registry(
+        ctx: Context<CreateSpectatorRegistry>,
+        _game_id: u64,
+        max_spectators: u16,
+    ) -> Result<()> {
+        require!(max_spectators >= 1, ErrorCode::InvalidSpectatorCapacity);
+
+        let registry = &mut ctx.accounts.spectator_registry;
+        registry.game_id = ctx.accounts.game.game_id;
+        registry.max_spectators = max_spectators;
+        registry.spectators = Vec::new();
+
+        Ok(())
+    }
+
+    /// Register the signer as a spectator. Whitelisted games require the
+    /// server co-signature, as in init_player.
+    pub fn register_spectator(ctx: Context<RegisterSpectator>, _game_id: u64) -> Result<()> {
+        let game = &ctx.accounts.game;
+        if game.whitelist {
+            let server = ctx
+                .accounts
+                .server
+                .as_ref()
+                .ok_or(ErrorCode::WhitelistServerRequired)?;
+            require!(
+                Some(server.key()) == game.server_pubkey,
+                ErrorCode::InvalidServerKey
+            );
+        }
+
+        let spectator = ctx.accounts.spectator.key();
+        let registry = &mut ctx.accounts.spectator_registry;
+        require!(
+            !registry.spectators.contains(&spectator),
+            ErrorCode::AlreadySpectator
+        );
+        require!(
+            registry.spectators.len() < registry.max_spectators as usize,
+            ErrorCode::SpectatorRegistryFull
+        );
+        registry.spectators.push(spectator);
+        Ok(())
+    }
+
+    pub fn deregister_spectator(ctx: Context<DeregisterSpectator>, _game_id: u64) -> Result<()> {
+        let spectator = ctx.accounts.spectator.key();
+        let registry = &mut ctx.accounts.spectator_registry;
+        let idx = registry
+            .spectators
+            .iter()
+            .position(|s| *s == spectator)
+            .ok_or(ErrorCode::NotSpectator)?;
+        registry.spectators.remove(idx);
         Ok(())
     }
 
@@ -232,34 +1485,59 @@ pub mod encrypted_forest {
 
     pub fn queue_init_planet(
         ctx: Context<QueueInitPlanet>,
-        computation_offset: u64,
         planet_hash: [u8; 32],
         // 2 ciphertexts: x, y (the fog-of-war secret)
         ciphertexts: Vec<u8>,
         pubkey: [u8; 32],
         nonce: u128,
         observer_pubkey: [u8; 32],
+        // Required once hash_rounds was raised; see check_no_prior_rounds_planet
+        base_planet_hash: Option<[u8; 32]>,
     ) -> Result<()> {
         require!(ciphertexts.len() == 2 * 32, ErrorCode::InvalidInitPlanet);
 
         let game = &ctx.accounts.game;
         let clock = Clock::get()?;
+        require!(!game.paused, ErrorCode::GamePaused);
         require!(clock.slot < game.end_slot, ErrorCode::GameEnded);
+        require!(!game.ended_early, ErrorCode::GameEnded);
+        // remaining_accounts: the location's planet PDA under each earlier hash_rounds
+        check_no_prior_rounds_planet(
+            ctx.program_id,
+            game,
+            &planet_hash,
+            base_planet_hash,
+            ctx.remaining_accounts,
+        )?;
 
         let body = &mut ctx.accounts.celestial_body;
+        body.version = CURRENT_VERSION;
         body.planet_hash = planet_hash;
         body.last_updated_slot = clock.slot;
         body.last_flushed_slot = clock.slot;
+        body.initializer = ctx.accounts.payer.key();
+        body.hash_rounds = game.hash_rounds;
 
         let pending = &mut ctx.accounts.pending_moves;
         pending.game_id = game.game_id;
         pending.planet_hash = planet_hash;
         pending.next_move_id = 0;
         pending.move_count = 0;
+        pending.capacity = 0;
         pending.queued_count = 0;
         pending.queued_landing_slots = [0u64; 8];
+        pending.last_move_queued_slot = 0;
         pending.moves = Vec::new();
 
+        let combat_log = &mut ctx.accounts.combat_log;
+        combat_log.game_id = game.game_id;
+        combat_log.planet_hash = planet_hash;
+
+        let computation_offset = ctx
+            .accounts
+            .offset_tracker
+            .advance(ctx.accounts.game.game_id, ctx.accounts.payer.key())?;
+
         ctx.accounts.sign_pda_account.bump = ctx.bumps.sign_pda_account;
 
         let nt = &game.noise_thresholds;
@@ -267,14 +1545,15 @@ pub mod encrypted_forest {
             // Enc<Shared, CoordInput>: pubkey + nonce + 2 encrypted fields
             .x25519_pubkey(pubkey)
             .plaintext_u128(nonce)
-            .encrypted_u64(extract_ct(&ciphertexts, 0))  // x
-            .encrypted_u64(extract_ct(&ciphertexts, 1))  // y
+            .encrypted_u64(extract_ct(&ciphertexts, 0)?)  // x
+            .encrypted_u64(extract_ct(&ciphertexts, 1)?)  // y
             // Plaintext params from Game account
             .plaintext_u64(game.game_id)
             .plaintext_u64(nt.dead_space_threshold as u64)
             .plaintext_u64(nt.planet_threshold as u64)
             .plaintext_u64(nt.quasar_threshold as u64)
             .plaintext_u64(nt.spacetime_rip_threshold as u64)
+            .plaintext_u64(nt.asteroid_belt_threshold as u64)
             .plaintext_u64(nt.size_threshold_1 as u64)
             .plaintext_u64(nt.size_threshold_2 as u64)
             .plaintext_u64(nt.size_threshold_3 as u64)
@@ -289,7 +1568,9 @@ pub mod encrypted_forest {
             .plaintext_u128(0u128)
             .build();
 
+        let game_pda = ctx.accounts.game.key();
         let body_pda = ctx.accounts.celestial_body.key();
+        let payer_key = ctx.accounts.payer.key();
 
         queue_computation(
             ctx.accounts,
@@ -298,10 +1579,20 @@ pub mod encrypted_forest {
             vec![InitPlanetCallback::callback_ix(
                 computation_offset,
                 &ctx.accounts.mxe_account,
-                &[CallbackAccount {
-                    pubkey: body_pda,
-                    is_writable: true,
-                }],
+                &[
+                    CallbackAccount {
+                        pubkey: game_pda,
+                        is_writable: true,
+                    },
+                    CallbackAccount {
+                        pubkey: body_pda,
+                        is_writable: true,
+                    },
+                    CallbackAccount {
+                        pubkey: payer_key,
+                        is_writable: false,
+                    },
+                ],
             )?],
             1,
             0,
@@ -322,7 +1613,11 @@ pub mod encrypted_forest {
             Ok(o) => o,
             Err(e) => {
                 msg!("init_planet verify_output FAILED: {:?}", e);
-                return Err(ErrorCode::AbortedComputation.into());
+                return Err(computation_failed(
+                    "init_planet",
+                    ctx.accounts.computation_account.key(),
+                    map_arcium_error(&e),
+                ));
             }
         };
 
@@ -343,7 +1638,12 @@ pub mod encrypted_forest {
 
         planet.last_updated_slot = Clock::get()?.slot;
 
+        let game = &mut ctx.accounts.game;
+        game.planet_count = game.planet_count.saturating_add(1);
+
         emit!(InitPlanetEvent {
+            game_id: ctx.accounts.game.game_id,
+            initializer: ctx.accounts.initializer.key(),
             encrypted_planet_hash: revealed.ciphertexts[0],
             encrypted_valid: revealed.ciphertexts[1],
             encryption_key: revealed.encryption_key,
@@ -356,19 +1656,22 @@ pub mod encrypted_forest {
     // -----------------------------------------------------------------------
     // Queue init_spawn_planet
     // Encrypted: SpawnInput (x, y, player_id, source_planet_id) = 4 * 32 bytes
-    // Plaintext: game_id + 9 thresholds sourced from Game account
-    // Output: (PlanetState, SpawnPlanetRevealed)
+    // Plaintext: game_id + 10 thresholds + map center + min spawn distance/separation
+    // Enc<Mxe, SpawnCoords> x 8: all earlier spawns from remaining_accounts, padded
+    // Output: (PlanetState, SpawnPlanetRevealed, is_placement_valid, reached_center,
+    //          SpawnCoords)
     // -----------------------------------------------------------------------
 
     pub fn queue_init_spawn_planet(
         ctx: Context<QueueInitSpawnPlanet>,
-        computation_offset: u64,
         planet_hash: [u8; 32],
         // 4 ciphertexts: x, y, player_id, source_planet_id
         ciphertexts: Vec<u8>,
         pubkey: [u8; 32],
         nonce: u128,
         observer_pubkey: [u8; 32],
+        // Required once hash_rounds was raised; see check_no_prior_rounds_planet
+        base_planet_hash: Option<[u8; 32]>,
     ) -> Result<()> {
         require!(ciphertexts.len() == 4 * 32, ErrorCode::InvalidSpawnValidation);
 
@@ -377,44 +1680,121 @@ pub mod encrypted_forest {
 
         let game = &ctx.accounts.game;
         let clock = Clock::get()?;
+        require!(!game.paused, ErrorCode::GamePaused);
         require!(clock.slot >= game.start_slot, ErrorCode::GameNotStarted);
         require!(clock.slot < game.end_slot, ErrorCode::GameEnded);
+        require!(!game.ended_early, ErrorCode::GameEnded);
 
         let body = &mut ctx.accounts.celestial_body;
+        body.version = CURRENT_VERSION;
         body.planet_hash = planet_hash;
         body.last_updated_slot = clock.slot;
         body.last_flushed_slot = clock.slot;
+        body.initializer = ctx.accounts.payer.key();
+        body.hash_rounds = game.hash_rounds;
 
         let pending = &mut ctx.accounts.pending_moves;
         pending.game_id = game.game_id;
         pending.planet_hash = planet_hash;
         pending.next_move_id = 0;
         pending.move_count = 0;
+        pending.capacity = 0;
         pending.moves = Vec::new();
 
+        let combat_log = &mut ctx.accounts.combat_log;
+        combat_log.game_id = game.game_id;
+        combat_log.planet_hash = planet_hash;
+
+        let computation_offset = ctx
+            .accounts
+            .offset_tracker
+            .advance(ctx.accounts.game.game_id, ctx.accounts.payer.key())?;
+
         ctx.accounts.sign_pda_account.bump = ctx.bumps.sign_pda_account;
 
+        // Only RaceToCenter games keep spawns away from the map center, which is
+        // the origin in the circuit's biased coordinate space
+        let map_center = COORD_BIAS;
+        let min_spawn_distance = game.min_spawn_distance_from_center;
+
+        // remaining_accounts: every spawn placed so far (game.spawn_planet_hashes, in
+        // order) so the new spawn keeps min_spawn_separation from all of them, then
+        // the location's planet PDA under each earlier hash_rounds
+        let spawn_count = if game.min_spawn_separation > 0 {
+            game.spawn_count as usize
+        } else {
+            0
+        };
+        require!(spawn_count <= MAX_SEPARATION_CHECKS, ErrorCode::SpawnSeparationFull);
+        require!(
+            ctx.remaining_accounts.len() >= spawn_count,
+            ErrorCode::InvalidSpawnValidation
+        );
+        let (spawn_accounts, prior_planets) = ctx.remaining_accounts.split_at(spawn_count);
+        check_no_prior_rounds_planet(
+            ctx.program_id,
+            game,
+            &planet_hash,
+            base_planet_hash,
+            prior_planets,
+        )?;
+        let mut others = [([0u8; 16], [[0u8; 32]; SPAWN_COORDS_FIELDS]); MAX_SEPARATION_CHECKS];
+        for (i, acc) in spawn_accounts.iter().enumerate() {
+            let (expected_pda, _) =
+                planet_pda(ctx.program_id, game.game_id, &game.spawn_planet_hashes[i]);
+            require!(acc.key() == expected_pda, ErrorCode::InvalidSpawnValidation);
+            require!(acc.owner == &crate::ID, ErrorCode::InvalidSpawnValidation);
+            let data = acc.try_borrow_data()?;
+            let other = EncryptedCelestialBody::try_deserialize(&mut &data[..])?;
+            require!(other.has_spawn_coords, ErrorCode::InvalidSpawnValidation);
+            others[i] = (other.spawn_coords_nonce, other.spawn_coords_ciphertexts);
+        }
+
         let nt = &game.noise_thresholds;
-        let args = ArgBuilder::new()
+        let mut builder = ArgBuilder::new()
             // Enc<Shared, SpawnInput>: pubkey + nonce + 4 encrypted fields
             .x25519_pubkey(pubkey)
             .plaintext_u128(nonce)
-            .encrypted_u64(extract_ct(&ciphertexts, 0))   // x
-            .encrypted_u64(extract_ct(&ciphertexts, 1))   // y
-            .encrypted_u32(extract_ct(&ciphertexts, 2))   // player_id
-            .encrypted_u32(extract_ct(&ciphertexts, 3))   // source_planet_id
+            .encrypted_u64(extract_ct(&ciphertexts, 0)?)   // x
+            .encrypted_u64(extract_ct(&ciphertexts, 1)?)   // y
+            .encrypted_u64(extract_ct(&ciphertexts, 2)?)   // player_id
+            .encrypted_u32(extract_ct(&ciphertexts, 3)?)   // source_planet_id
             // Plaintext params from Game account
             .plaintext_u64(game.game_id)
             .plaintext_u64(nt.dead_space_threshold as u64)
             .plaintext_u64(nt.planet_threshold as u64)
             .plaintext_u64(nt.quasar_threshold as u64)
             .plaintext_u64(nt.spacetime_rip_threshold as u64)
+            .plaintext_u64(nt.asteroid_belt_threshold as u64)
             .plaintext_u64(nt.size_threshold_1 as u64)
             .plaintext_u64(nt.size_threshold_2 as u64)
             .plaintext_u64(nt.size_threshold_3 as u64)
             .plaintext_u64(nt.size_threshold_4 as u64)
             .plaintext_u64(nt.size_threshold_5 as u64)
             .plaintext_u64(game.hash_rounds as u64)
+            // RaceToCenter spawn distance check
+            .plaintext_u64(map_center)
+            .plaintext_u64(map_center)
+            .plaintext_u64(min_spawn_distance)
+            // Spawn separation check
+            .plaintext_u64(game.min_spawn_separation)
+            .plaintext_u64(game.distance_method as u64)
+            .plaintext_u64(game.valid_spawn_body_types as u64)
+            .plaintext_u64(spawn_count as u64);
+        // Payer's key words: the circuit binds the new owner id to them
+        for word in pubkey_words(&ctx.accounts.payer.key()) {
+            builder = builder.plaintext_u64(word);
+        }
+
+        // Enc<Mxe, SpawnCoords> x 8 -- unused slots are zero padding the circuit skips
+        for (nonce_bytes, cts) in others.iter() {
+            builder = builder
+                .plaintext_u128(u128::from_le_bytes(*nonce_bytes))
+                .encrypted_u64(cts[0])   // x
+                .encrypted_u64(cts[1]);  // y
+        }
+
+        let args = builder
             // Planet key (Shared handle for PlanetState output encryption)
             .x25519_pubkey(pubkey)
             .plaintext_u128(0u128)
@@ -423,8 +1803,10 @@ pub mod encrypted_forest {
             .plaintext_u128(0u128)
             .build();
 
+        let game_pda = ctx.accounts.game.key();
         let player_pda = ctx.accounts.player.key();
         let body_pda = ctx.accounts.celestial_body.key();
+        let profile_pda = ctx.accounts.player_profile.as_ref().map(|p| p.key());
 
         queue_computation(
             ctx.accounts,
@@ -434,6 +1816,10 @@ pub mod encrypted_forest {
                 computation_offset,
                 &ctx.accounts.mxe_account,
                 &[
+                    CallbackAccount {
+                        pubkey: game_pda,
+                        is_writable: true,
+                    },
                     CallbackAccount {
                         pubkey: player_pda,
                         is_writable: true,
@@ -442,6 +1828,7 @@ pub mod encrypted_forest {
                         pubkey: body_pda,
                         is_writable: true,
                     },
+                    optional_callback_account(profile_pda),
                 ],
             )?],
             1,
@@ -463,13 +1850,21 @@ pub mod encrypted_forest {
             Ok(o) => o,
             Err(e) => {
                 msg!("init_spawn_planet verify_output FAILED: {:?}", e);
-                return Err(ErrorCode::AbortedComputation.into());
+                return Err(computation_failed(
+                    "init_spawn_planet",
+                    ctx.accounts.computation_account.key(),
+                    map_arcium_error(&e),
+                ));
             }
         };
 
-        // Output tuple: (Enc<Shared, PlanetState>, Enc<Shared, SpawnPlanetRevealed>)
+        // Output tuple: (Enc<Shared, PlanetState>, Enc<Shared, SpawnPlanetRevealed>,
+        //                is_placement_valid, reached_center, Enc<Mxe, SpawnCoords>)
         let enc_state = &o.field_0.field_0;
         let revealed = &o.field_0.field_1;
+        let is_placement_valid = o.field_0.field_2;
+        let reached_center = o.field_0.field_3;
+        let enc_coords = &o.field_0.field_4;
 
         let planet = &mut ctx.accounts.celestial_body;
 
@@ -482,11 +1877,66 @@ pub mod encrypted_forest {
             i += 1;
         }
 
-        planet.last_updated_slot = Clock::get()?.slot;
+        let slot = Clock::get()?.slot;
+        planet.last_updated_slot = slot;
+
+        // Set only on a respawn (check_spawn_lost keeps the old hash)
+        let previous_spawn = ctx.accounts.player.spawn_planet_hash;
+
+        // A spawn too close to the center or to another spawn leaves the planet
+        // unowned; let the player retry
+        if is_placement_valid == 1 {
+            ctx.accounts.player.has_spawned = true;
+            ctx.accounts.player.spawn_planet_hash = planet.planet_hash;
+
+            // Keep the MXE-encrypted location so later spawns can check separation
+            planet.has_spawn_coords = true;
+            planet.spawn_coords_nonce = enc_coords.nonce.to_le_bytes();
+            let mut c = 0;
+            while c < SPAWN_COORDS_FIELDS {
+                planet.spawn_coords_ciphertexts[c] = enc_coords.ciphertexts[c];
+                c += 1;
+            }
+        }
 
-        ctx.accounts.player.has_spawned = true;
+        let game = &mut ctx.accounts.game;
+        game.planet_count = game.planet_count.saturating_add(1);
+        if is_placement_valid == 1 && game.min_spawn_separation > 0 {
+            // A respawn takes over the player's old entry, so respawns don't
+            // use up the separation list
+            let listed = (game.spawn_count as usize).min(MAX_SEPARATION_CHECKS);
+            match game.spawn_planet_hashes[..listed]
+                .iter()
+                .position(|hash| *hash == previous_spawn)
+            {
+                Some(i) => game.spawn_planet_hashes[i] = planet.planet_hash,
+                None => {
+                    if listed < MAX_SEPARATION_CHECKS {
+                        game.spawn_planet_hashes[listed] = planet.planet_hash;
+                    }
+                    game.spawn_count = game.spawn_count.saturating_add(1);
+                }
+            }
+        }
+        if matches!(game.win_condition, WinCondition::RaceToCenter { .. })
+            && reached_center == 1
+            && game.winner.is_none()
+        {
+            let winner = ctx.accounts.player.owner;
+            game.winner = Some(winner);
+            if let Some(profile) = ctx.accounts.player_profile.as_mut() {
+                profile.total_wins = profile.total_wins.saturating_add(1);
+            }
+            emit!(VictoryClaimedEvent {
+                game_id: game.game_id,
+                winner,
+                slot,
+            });
+        }
 
         emit!(InitSpawnPlanetEvent {
+            game_id: ctx.accounts.game.game_id,
+            actor: ctx.accounts.player.owner,
             encrypted_planet_hash: revealed.ciphertexts[0],
             encrypted_valid: revealed.ciphertexts[1],
             encrypted_spawn_valid: revealed.ciphertexts[2],
@@ -500,32 +1950,56 @@ pub mod encrypted_forest {
     // -----------------------------------------------------------------------
     // Queue process_move
     // Planet state + move input passed inline as ciphertexts.
-    // move_cts = 2 * 32 bytes (Pack<[u32; 8]> = 2 FEs).
+    // move_cts = 2 * 32 bytes (Pack<[u32; 11]> = 2 FEs).
     // Output: (PlanetState, PendingMoveData, MoveRevealed)
     // -----------------------------------------------------------------------
 
     pub fn queue_process_move(
         ctx: Context<QueueProcessMove>,
-        computation_offset: u64,
         landing_slot: u64,        // public: client-computed, MPC-validated
-        current_ships: u64,       // plaintext: client-computed lazy resource generation
-        current_metal: u64,       // plaintext: client-computed lazy resource generation
-        move_cts: Vec<u8>,        // 2 * 32 = 64 bytes (Pack<[u32;8]>)
+        max_observable_distance: u64, // public upper bound on the move's distance, MPC-validated
+        move_cts: Vec<u8>,        // 2 * 32 = 64 bytes (Pack<[u32;11]>)
         move_pubkey: [u8; 32],
         move_nonce: u128,
+        priority_fee_lamports: u64, // optional tip to the Arcium fee pool (0 = none)
     ) -> Result<()> {
         require!(move_cts.len() == 2 * 32, ErrorCode::InvalidMoveInput);
+        require_priority_fee(priority_fee_lamports)?;
 
         let game = &ctx.accounts.game;
         let clock = Clock::get()?;
+        require!(!game.paused, ErrorCode::GamePaused);
         require!(clock.slot >= game.start_slot, ErrorCode::GameNotStarted);
         require!(clock.slot < game.end_slot, ErrorCode::GameEnded);
+        require!(!game.ended_early, ErrorCode::GameEnded);
 
         // landing_slot must be in the future
         require!(landing_slot > clock.slot, ErrorCode::InvalidMoveInput);
 
+        // Cheap pre-MPC range check: a declared bound past the distance
+        // between opposite corners is rejected before any Arcium fee is spent.
+        // The circuit checks the real distance against the bound.
+        require!(
+            max_observable_distance
+                <= game_math::max_map_distance(game.map_diameter, game.distance_method),
+            ErrorCode::InvalidObservableDistance
+        );
+
+        // Per-player rate limit: at most max_moves_per_slot moves in one slot
+        let player = &mut ctx.accounts.player;
+        if clock.slot == player.last_move_slot {
+            require!(
+                player.moves_this_slot < game.max_moves_per_slot,
+                ErrorCode::RateLimitExceeded
+            );
+        } else {
+            player.last_move_slot = clock.slot;
+            player.moves_this_slot = 0;
+        }
+        player.moves_this_slot += 1;
+
         // Enforce: source planet must have all landed moves flushed
-        let source_pending = &ctx.accounts.source_pending;
+        let source_pending = &mut ctx.accounts.source_pending;
         if !source_pending.moves.is_empty() {
             require!(
                 source_pending.moves[0].landing_slot > clock.slot,
@@ -533,19 +2007,48 @@ pub mod encrypted_forest {
             );
         }
 
-        // Directly insert PendingMoveEntry into target's moves Vec (sorted by landing_slot).
-        // This is done here (before MPC) so the callback only needs source_body + move_account.
-        // The move_account.populated flag ensures flush skips moves with incomplete MPC.
-        let target_pending = &mut ctx.accounts.target_pending;
-        let move_id = target_pending.next_move_id;
-        target_pending.next_move_id = move_id + 1;
-
+        // Per-planet cooldown between launches from the same source
+        require!(
+            source_pending.last_move_queued_slot == 0
+                || clock.slot.saturating_sub(source_pending.last_move_queued_slot)
+                    >= game.move_cooldown_slots,
+            ErrorCode::MoveCooldown
+        );
+        source_pending.last_move_queued_slot = clock.slot;
+
+        // Per-game cap on moves targeting one planet. Entries are registered at
+        // queue time, so this counts moves still awaiting their callback too
+        let target_pending = &ctx.accounts.target_pending;
+        require!(
+            target_pending.moves.len() < game.max_pending_moves as usize,
+            ErrorCode::TooManyPendingMoves
+        );
+
+        // Directly insert PendingMoveEntry into target's moves Vec (sorted by landing_slot)
+        // at the client's landing slot; the callback re-keys it to the MPC-computed one.
+        // The move_account.populated flag ensures flush skips moves with incomplete MPC.
+        let target_pending = &mut ctx.accounts.target_pending;
+        let move_id = target_pending.next_move_id;
+        // Seeds use wrapping_add so account validation can't panic; reject here instead
+        game_math::predicted_move_id(move_id, target_pending.queued_count)
+            .ok_or(ErrorCode::MoveIdOverflow)?;
+        target_pending.next_move_id = move_id
+            .checked_add(1)
+            .ok_or(ErrorCode::MoveIdOverflow)?;
+
+        require!(
+            !target_pending.moves.iter().any(|e| e.move_id == move_id),
+            ErrorCode::DuplicateMoveId
+        );
+
+        // Ties on landing_slot are ordered by move_id so flush order is deterministic
         let entry = PendingMoveEntry { landing_slot, move_id };
         let pos = target_pending.moves
-            .binary_search_by_key(&landing_slot, |e| e.landing_slot)
+            .binary_search_by_key(&(landing_slot, move_id), |e| (e.landing_slot, e.move_id))
             .unwrap_or_else(|e| e);
         target_pending.moves.insert(pos, entry);
         target_pending.move_count = target_pending.moves.len() as u16;
+        target_pending.capacity = target_pending.capacity_for(target_pending.moves.len());
 
         // Initialize PendingMoveAccount (enc data written by MPC callback)
         let move_acc = &mut ctx.accounts.move_account;
@@ -555,6 +2058,27 @@ pub mod encrypted_forest {
         move_acc.landing_slot = landing_slot;
         move_acc.payer = ctx.accounts.payer.key();
         move_acc.populated = false; // set to true by callback after MPC completes
+        move_acc.canonical_planet_hash = target_pending.planet_hash;
+        move_acc.source_planet_hash = ctx.accounts.source_body.planet_hash;
+        move_acc.flush_queued_slot = 0;
+
+        // Allied reinforcement is only unlocked for verified alliance members
+        let alliance_valid = match &ctx.accounts.alliance {
+            Some(alliance) => {
+                require!(alliance.game_id == game.game_id, ErrorCode::NotAllianceMember);
+                require!(
+                    alliance.members.contains(&ctx.accounts.payer.key()),
+                    ErrorCode::NotAllianceMember
+                );
+                1u64
+            }
+            None => 0u64,
+        };
+
+        let computation_offset = ctx
+            .accounts
+            .offset_tracker
+            .advance(ctx.accounts.game.game_id, ctx.accounts.payer.key())?;
 
         ctx.accounts.sign_pda_account.bump = ctx.bumps.sign_pda_account;
 
@@ -566,29 +2090,57 @@ pub mod encrypted_forest {
             .plaintext_u128(u128::from_le_bytes(source.state_enc_nonce))
             .encrypted_u32(source.state_enc_ciphertexts[0])  // Pack FE 0
             .encrypted_u32(source.state_enc_ciphertexts[1])  // Pack FE 1
-            .encrypted_u32(source.state_enc_ciphertexts[2]); // Pack FE 2
+            .encrypted_u32(source.state_enc_ciphertexts[2])  // Pack FE 2
+            .encrypted_u32(source.state_enc_ciphertexts[3]); // Pack FE 3
 
         // Enc<Shared, ProcessMoveInputPacked> — all inline (2 packed FEs)
         // move_input.owner encrypts revealed output (no separate observer needed)
         builder = builder
             .x25519_pubkey(move_pubkey)
             .plaintext_u128(move_nonce)
-            .encrypted_u32(extract_ct(&move_cts, 0))  // Pack FE 0
-            .encrypted_u32(extract_ct(&move_cts, 1)); // Pack FE 1
+            .encrypted_u32(extract_ct(&move_cts, 0)?)  // Pack FE 0
+            .encrypted_u32(extract_ct(&move_cts, 1)?); // Pack FE 1
 
-        // Plaintext params: lazy-generation computed client-side
+        // Plaintext params: timing for lazy generation, which runs inside MPC
         // NOTE: Use plaintext_u64 (not u32) because Arcium allocates comp account space
         // based on the comp_def parameter types. u32 reserves less space than actual storage.
         builder = builder
-            .plaintext_u64(current_ships)
-            .plaintext_u64(current_metal)
             .plaintext_u64(clock.slot)
-            .plaintext_u64(game.game_speed);
+            .plaintext_u64(game.game_speed)
+            .plaintext_u64(source.last_updated_slot)
+            .plaintext_u64(game.map_diameter) // bounds check stays inside MPC
+            .plaintext_u64(landing_slot)      // checked against the MPC-computed slot
+            .plaintext_u64(alliance_valid)    // gates ally_player_id reinforcement
+            .plaintext_u64(game.capture_cooldown_slots)
+            .plaintext_u64(game.distance_method as u64);
+
+        // Payer's pubkey as four LE u64s; the circuit binds the move's player_id to it
+        for word in pubkey_words(&ctx.accounts.payer.key()) {
+            builder = builder.plaintext_u64(word);
+        }
+        builder = builder
+            .plaintext_u64(ctx.accounts.game.min_ships_per_move)
+            .plaintext_u64(max_observable_distance)
+            // The ship count is only made public for payers who track a profile
+            .plaintext_u64(ctx.accounts.player_profile.is_some() as u64);
 
         let args = builder.build();
 
+        let started = ProcessMoveStartedEvent {
+            game_id: ctx.accounts.game.game_id,
+            actor: ctx.accounts.payer.key(),
+            source_planet_hash: ctx.accounts.source_body.planet_hash,
+            target_planet_hash: ctx.accounts.target_pending.planet_hash,
+            move_id,
+            queued_slot: clock.slot,
+            landing_slot,
+            computation_offset,
+        };
+
         let source_body_pda = ctx.accounts.source_body.key();
         let move_account_pda = ctx.accounts.move_account.key();
+        let target_pending_pda = ctx.accounts.target_pending.key();
+        let profile_pda = ctx.accounts.player_profile.as_ref().map(|p| p.key());
 
         let callbacks = vec![ProcessMoveCallback::callback_ix(
             computation_offset,
@@ -602,9 +2154,21 @@ pub mod encrypted_forest {
                     pubkey: move_account_pda,
                     is_writable: true,
                 },
+                CallbackAccount {
+                    pubkey: target_pending_pda,
+                    is_writable: true,
+                },
+                optional_callback_account(profile_pda),
             ],
         )?];
 
+        pay_priority_fee(
+            &ctx.accounts.payer,
+            &ctx.accounts.pool_account,
+            &ctx.accounts.system_program,
+            priority_fee_lamports,
+        )?;
+
         queue_computation(
             ctx.accounts,
             computation_offset,
@@ -614,6 +2178,8 @@ pub mod encrypted_forest {
             0,
         )?;
 
+        emit!(started);
+
         Ok(())
     }
 
@@ -629,13 +2195,42 @@ pub mod encrypted_forest {
             Ok(o) => o,
             Err(e) => {
                 msg!("process_move verify_output FAILED: {:?}", e);
-                return Err(ErrorCode::AbortedComputation.into());
+                return Err(computation_failed(
+                    "process_move",
+                    ctx.accounts.computation_account.key(),
+                    map_arcium_error(&e),
+                ));
             }
         };
 
-        // Output tuple: (Enc<Shared, PlanetState>, Enc<Mxe, PendingMoveData>)
+        // Output tuple: (Enc<Shared, PlanetState>, Enc<Mxe, PendingMoveData>, landing_slot,
+        //                valid, within_range, ships_sent)
         let enc_state = &o.field_0.field_0;
         let enc_move_data = &o.field_0.field_1;
+        let landing_slot = o.field_0.field_2;
+        let valid = o.field_0.field_3;
+        let within_range = o.field_0.field_4;
+        let ships_sent = o.field_0.field_5;
+        if within_range == 0 {
+            msg!("process_move: target is beyond the source planet's range");
+        }
+
+        // Replay guard: the move must belong to this target, still be awaiting its
+        // callback, and still be listed on the target.
+        let target_pending = &ctx.accounts.target_pending;
+        let move_acc = &ctx.accounts.move_account;
+        require!(
+            move_acc.game_id == target_pending.game_id
+                && move_acc.planet_hash == target_pending.planet_hash,
+            ErrorCode::CallbackReplay
+        );
+        require!(!move_acc.populated, ErrorCode::CallbackReplay);
+        // A move abandoned while in flight has no entry left to re-key
+        let entry_idx = target_pending
+            .moves
+            .iter()
+            .position(|e| e.move_id == move_acc.move_id)
+            .ok_or(ErrorCode::CallbackReplay)?;
 
         // Update source planet
         let source = &mut ctx.accounts.source_body;
@@ -656,35 +2251,66 @@ pub mod encrypted_forest {
             move_acc.enc_ciphertexts[ci] = enc_move_data.ciphertexts[ci];
             ci += 1;
         }
+        // Record the MPC-computed arrival rather than the client's estimate, on
+        // both the move account and the target's entry, which is re-sorted so
+        // flushes and abandon_move_batch can rely on (landing_slot, move_id)
+        move_acc.landing_slot = landing_slot;
         move_acc.populated = true;
 
+        let target_pending = &mut ctx.accounts.target_pending;
+        let mut entry = target_pending.moves.remove(entry_idx);
+        entry.landing_slot = landing_slot;
+        let pos = target_pending
+            .moves
+            .binary_search_by_key(&(landing_slot, entry.move_id), |e| (e.landing_slot, e.move_id))
+            .unwrap_or_else(|e| e);
+        target_pending.moves.insert(pos, entry);
+
+        if valid == 1 {
+            if let Some(profile) = ctx.accounts.player_profile.as_mut() {
+                profile.ships_sent = profile.ships_sent.saturating_add(ships_sent);
+            }
+        }
+
+        emit!(ProcessMoveEvent {
+            game_id: move_acc.game_id,
+            actor: move_acc.payer,
+            source_planet_hash: ctx.accounts.source_body.planet_hash,
+            target_planet_hash: move_acc.planet_hash,
+            move_id: move_acc.move_id,
+            landing_slot,
+            valid: valid == 1,
+        });
+
         Ok(())
     }
 
     // -----------------------------------------------------------------------
     // Queue flush_planet (single move)
-    // Planet state + move data passed inline as ciphertexts.
-    // flush_timing_cts = 4 * 32 (FlushTimingInput: current_slot, game_speed, last_updated_slot, flush_count)
+    // Planet state + move data passed inline as ciphertexts; timing is read
+    // on-chain.
     // Output: Enc<Shared, PlanetState>
     // -----------------------------------------------------------------------
 
     pub fn queue_flush_planet(
         ctx: Context<QueueFlushPlanet>,
-        computation_offset: u64,
         flush_count: u8,
-        flush_cts: Vec<u8>,      // 4 * 32 (FlushTimingInput)
-        flush_pubkey: [u8; 32],
-        flush_nonce: u128,
+        observer_pubkey: [u8; 32],
+        observer_nonce: u128,
+        priority_fee_lamports: u64, // optional tip to the Arcium fee pool (0 = none)
     ) -> Result<()> {
-        require!(flush_cts.len() == 4 * 32, ErrorCode::FlushFailed);
         require!(flush_count == 1, ErrorCode::FlushFailed);
+        require_priority_fee(priority_fee_lamports)?;
         require!(
             ctx.remaining_accounts.len() >= 1,
             ErrorCode::FlushFailed
         );
 
+        require!(!ctx.accounts.game.paused, ErrorCode::GamePaused);
+
         let clock = Clock::get()?;
         let pending = &ctx.accounts.pending_moves;
+        let game_id = ctx.accounts.game.game_id;
 
         // Verify that the first move has landed
         require!(!pending.moves.is_empty(), ErrorCode::FlushFailed);
@@ -693,28 +2319,80 @@ pub mod encrypted_forest {
             ErrorCode::FlushFailed
         );
 
-        // Validate remaining_accounts[0] is the correct PendingMoveAccount PDA
+        // Validate remaining_accounts[0] is the correct PendingMoveAccount PDA.
+        // Merged moves keep the PDA of the planet they were queued against, so
+        // the seed hash is read from the account and the canonical hash must
+        // name this planet.
         let entry = &pending.moves[0];
-        let (expected_pda, _) = Pubkey::find_program_address(
-            &[
-                b"move",
-                pending.game_id.to_le_bytes().as_ref(),
-                pending.planet_hash.as_ref(),
-                entry.move_id.to_le_bytes().as_ref(),
-            ],
-            ctx.program_id,
-        );
-        require!(
-            ctx.remaining_accounts[0].key() == expected_pda,
-            ErrorCode::FlushFailed
-        );
-
-        // Ensure move_account has been populated by the MPC callback
         {
+            let acc_data = ctx.remaining_accounts[0].try_borrow_data()?;
+            require!(
+                acc_data.len() >= PendingMoveAccount::MAX_SIZE,
+                ErrorCode::FlushFailed
+            );
+            let seed_hash: [u8; 32] = acc_data
+                [MOVE_ACCOUNT_PLANET_HASH_OFFSET..MOVE_ACCOUNT_PLANET_HASH_OFFSET + 32]
+                .try_into()
+                .map_err(|_| ErrorCode::FlushFailed)?;
+            // Derived from the Game account, not the (cross-checked) metadata copy
+            let (expected_pda, _) = move_pda(ctx.program_id, game_id, &seed_hash, entry.move_id);
+            require!(
+                ctx.remaining_accounts[0].key() == expected_pda,
+                ErrorCode::FlushFailed
+            );
+            require!(
+                acc_data[MOVE_ACCOUNT_CANONICAL_HASH_OFFSET..MOVE_ACCOUNT_CANONICAL_HASH_OFFSET + 32]
+                    == pending.planet_hash[..],
+                ErrorCode::FlushFailed
+            );
+        }
+
+        // Ensure move_account has been populated by the MPC callback, and that
+        // attacker_player is the Player PDA of whoever queued the move
+        let move_payer = {
             let acc_data = ctx.remaining_accounts[0].try_borrow_data()?;
             let populated = acc_data[96]; // offset of `populated` field
             require!(populated == 1, ErrorCode::FlushFailed);
+
+            // One flush per move in flight; a computation that never called
+            // back frees the move after FLUSH_RETRY_SLOTS
+            let queued_slot = u64::from_le_bytes(
+                acc_data[MOVE_ACCOUNT_FLUSH_QUEUED_SLOT_OFFSET..MOVE_ACCOUNT_FLUSH_QUEUED_SLOT_OFFSET + 8]
+                    .try_into()
+                    .map_err(|_| ErrorCode::FlushFailed)?,
+            );
+            require!(
+                queued_slot == 0 || clock.slot > queued_slot.saturating_add(FLUSH_RETRY_SLOTS),
+                ErrorCode::FlushInFlight
+            );
+
+            let move_payer = Pubkey::try_from(
+                &acc_data[MOVE_ACCOUNT_PAYER_OFFSET..MOVE_ACCOUNT_PAYER_OFFSET + 32],
+            )
+            .map_err(|_| ErrorCode::FlushFailed)?;
+            let (expected_player, _) = player_pda(ctx.program_id, game_id, &move_payer);
+            require!(
+                ctx.accounts.attacker_player.key() == expected_player,
+                ErrorCode::FlushFailed
+            );
+            move_payer
+        };
+        if let Some(profile) = ctx.accounts.attacker_profile.as_ref() {
+            require!(profile.owner == move_payer, ErrorCode::FlushFailed);
         }
+        let attacker_profile_pda = ctx.accounts.attacker_profile.as_ref().map(|p| p.key());
+
+        {
+            require!(ctx.remaining_accounts[0].is_writable, ErrorCode::FlushFailed);
+            let mut acc_data = ctx.remaining_accounts[0].try_borrow_mut_data()?;
+            acc_data[MOVE_ACCOUNT_FLUSH_QUEUED_SLOT_OFFSET..MOVE_ACCOUNT_FLUSH_QUEUED_SLOT_OFFSET + 8]
+                .copy_from_slice(&clock.slot.to_le_bytes());
+        }
+
+        let computation_offset = ctx
+            .accounts
+            .offset_tracker
+            .advance(ctx.accounts.game.game_id, ctx.accounts.payer.key())?;
 
         ctx.accounts.sign_pda_account.bump = ctx.bumps.sign_pda_account;
 
@@ -725,7 +2403,8 @@ pub mod encrypted_forest {
             .plaintext_u128(u128::from_le_bytes(body.state_enc_nonce))
             .encrypted_u32(body.state_enc_ciphertexts[0])  // Pack FE 0
             .encrypted_u32(body.state_enc_ciphertexts[1])  // Pack FE 1
-            .encrypted_u32(body.state_enc_ciphertexts[2]); // Pack FE 2
+            .encrypted_u32(body.state_enc_ciphertexts[2])  // Pack FE 2
+            .encrypted_u32(body.state_enc_ciphertexts[3]); // Pack FE 3
 
         // Enc<Mxe, PendingMoveData> — nonce + 4 ciphertexts read from PendingMoveAccount
         {
@@ -744,23 +2423,54 @@ pub mod encrypted_forest {
                 .encrypted_u32(move_cts[0])   // ships_arriving
                 .encrypted_u32(move_cts[1])   // metal_arriving
                 .encrypted_u32(move_cts[2])   // attacking_planet_id
-                .encrypted_u32(move_cts[3]);  // attacking_player_id
+                .encrypted_u64(move_cts[3]);  // attacking_player_id
         }
 
-        // FlushTimingInput (4 fields: current_slot, game_speed, last_updated_slot, flush_count)
+        // Plaintext params: lazy generation timing sourced on-chain
         // planet_input.owner re-encrypts output (no separate planet_key needed)
         builder = builder
-            .x25519_pubkey(flush_pubkey)
-            .plaintext_u128(flush_nonce)
-            .encrypted_u32(extract_ct(&flush_cts, 0))  // current_slot
-            .encrypted_u32(extract_ct(&flush_cts, 1))  // game_speed
-            .encrypted_u32(extract_ct(&flush_cts, 2))  // last_updated_slot
-            .encrypted_u32(extract_ct(&flush_cts, 3));  // flush_count
+            .plaintext_u64(clock.slot)
+            .plaintext_u64(ctx.accounts.game.game_speed)
+            .plaintext_u64(body.last_updated_slot);
+
+        // Plaintext params: Quasar arrival penalty, and whether captured metal
+        // may be revealed (PointsBurning only)
+        let points_burning = matches!(
+            ctx.accounts.game.win_condition,
+            WinCondition::PointsBurning { .. }
+        );
+        builder = builder
+            .plaintext_u64(ctx.accounts.game.quasar_ship_penalty_bps)
+            .plaintext_u64(points_burning as u64);
+
+        // Observer (Shared handle for FlushRevealed)
+        builder = builder
+            .x25519_pubkey(observer_pubkey)
+            .plaintext_u128(observer_nonce);
 
         let args = builder.build();
 
+        let started = FlushStartedEvent {
+            planet_hash: ctx.accounts.pending_moves.planet_hash,
+            game_id,
+            move_id: ctx.accounts.pending_moves.moves[0].move_id,
+            queued_slot: clock.slot,
+            computation_offset,
+        };
+
         let body_pda = ctx.accounts.celestial_body.key();
         let pending_pda = ctx.accounts.pending_moves.key();
+        let game_pda = ctx.accounts.game.key();
+        let attacker_player_pda = ctx.accounts.attacker_player.key();
+        let move_account_pda = ctx.remaining_accounts[0].key();
+        let combat_log_pda = ctx.accounts.combat_log.key();
+
+        pay_priority_fee(
+            &ctx.accounts.payer,
+            &ctx.accounts.pool_account,
+            &ctx.accounts.system_program,
+            priority_fee_lamports,
+        )?;
 
         queue_computation(
             ctx.accounts,
@@ -778,12 +2488,35 @@ pub mod encrypted_forest {
                         pubkey: pending_pda,
                         is_writable: true,
                     },
+                    CallbackAccount {
+                        pubkey: game_pda,
+                        is_writable: false,
+                    },
+                    CallbackAccount {
+                        pubkey: attacker_player_pda,
+                        is_writable: true,
+                    },
+                    CallbackAccount {
+                        pubkey: move_account_pda,
+                        is_writable: false,
+                    },
+                    CallbackAccount {
+                        pubkey: combat_log_pda,
+                        is_writable: true,
+                    },
+                    CallbackAccount {
+                        pubkey: move_payer,
+                        is_writable: true,
+                    },
+                    optional_callback_account(attacker_profile_pda),
                 ],
             )?],
             1,
             0,
         )?;
 
+        emit!(started);
+
         Ok(())
     }
 
@@ -799,12 +2532,37 @@ pub mod encrypted_forest {
             Ok(o) => o,
             Err(e) => {
                 msg!("flush_planet verify_output FAILED: {:?}", e);
-                return Err(ErrorCode::AbortedComputation.into());
+                return Err(computation_failed(
+                    "flush_planet",
+                    ctx.accounts.computation_account.key(),
+                    map_arcium_error(&e),
+                ));
             }
         };
 
-        // Output: Enc<Shared, PlanetState> (single value, not tuple)
-        let enc_state = &o.field_0;
+        // queue_flush_planet leaves pending_moves untouched and the flushed move is
+        // only removed below, so a failed verification above leaves the FIFO
+        // intact and the flush can simply be re-queued.
+
+        // Match exactly the flushed move on move_id (the Vec is sorted by
+        // landing_slot, not id), which stays correct even if the callback lands
+        // several slots after queueing or a later move has since reached the
+        // front. A move that is gone was already flushed by an earlier copy of
+        // this computation; applying it again would credit it twice.
+        let flushed_move_id = ctx.accounts.move_account.move_id;
+        let pos = ctx
+            .accounts
+            .pending_moves
+            .moves
+            .iter()
+            .position(|e| e.move_id == flushed_move_id)
+            .ok_or(ErrorCode::CallbackReplay)?;
+
+        // Output tuple: (Enc<Shared, PlanetState>, captured, captured_metal, Enc<Shared, FlushRevealed>)
+        let enc_state = &o.field_0.field_0;
+        let captured = o.field_0.field_1 == 1;
+        let captured_metal = o.field_0.field_2;
+        let revealed = &o.field_0.field_3;
 
         // Update planet
         let planet = &mut ctx.accounts.celestial_body;
@@ -819,41 +2577,112 @@ pub mod encrypted_forest {
         planet.last_updated_slot = slot;
         planet.last_flushed_slot = slot;
 
-        // Remove the flushed move from front of sorted array
+        // Remove the flushed move
         let pending = &mut ctx.accounts.pending_moves;
-        if !pending.moves.is_empty() && pending.moves[0].landing_slot <= slot {
-            pending.moves.remove(0);
-        }
+        pending.moves.remove(pos);
         pending.move_count = pending.moves.len() as u16;
 
+        // Shrink once under a quarter full and hand the freed rent to the
+        // flushed move's payer, who funded the growth when queueing
+        let new_capacity = pending.capacity_for(pending.moves.len());
+        if new_capacity != pending.capacity {
+            pending.capacity = new_capacity;
+            let info = pending.to_account_info();
+            info.resize(PendingMovesMetadata::space(new_capacity))?;
+            let excess = info
+                .lamports()
+                .saturating_sub(Rent::get()?.minimum_balance(info.data_len()));
+            if excess > 0 {
+                **info.try_borrow_mut_lamports()? -= excess;
+                **ctx.accounts.move_payer.try_borrow_mut_lamports()? += excess;
+            }
+        }
+
+        emit!(CombatEvent {
+            source_planet_hash: ctx.accounts.move_account.source_planet_hash,
+            target_planet_hash: planet.planet_hash,
+            flushed_move_id,
+            game_id: ctx.accounts.move_account.game_id,
+        });
+
         emit!(FlushPlanetEvent {
             planet_hash: planet.planet_hash,
             flushed_count: 1,
+            last_updated_slot: planet.last_updated_slot,
+            last_flushed_slot: planet.last_flushed_slot,
+            encrypted_gen_ships: revealed.ciphertexts[0],
+            encrypted_gen_metal: revealed.ciphertexts[1],
+            encryption_key: revealed.encryption_key,
+            nonce: revealed.nonce.to_le_bytes(),
+        });
+
+        if captured {
+            if let Some(profile) = ctx.accounts.attacker_profile.as_mut() {
+                profile.planets_captured = profile.planets_captured.saturating_add(1);
+            }
+        }
+
+        let combat_log = &mut ctx.accounts.combat_log;
+        combat_log.total_attacks = combat_log.total_attacks.saturating_add(1);
+        if captured {
+            combat_log.total_captures = combat_log.total_captures.saturating_add(1);
+        } else {
+            combat_log.total_defenses = combat_log.total_defenses.saturating_add(1);
+        }
+        emit!(CombatLogUpdatedEvent {
+            planet_hash: combat_log.planet_hash,
+            total_captures: combat_log.total_captures,
         });
 
+        // Conquest: award PointsBurning points for the metal carried in
+        if captured_metal > 0 {
+            let player = &mut ctx.accounts.attacker_player;
+            let points_delta = award_points(player, &ctx.accounts.game.win_condition, captured_metal);
+            if points_delta > 0 {
+                emit!(PointsAwardedEvent {
+                    player: player.owner,
+                    points_delta,
+                    new_total: player.points,
+                });
+            }
+        }
+
         Ok(())
     }
 
     // -----------------------------------------------------------------------
     // Queue upgrade_planet
     // Planet state + upgrade input passed inline as ciphertexts.
-    // upgrade_cts = 6 * 32.
+    // upgrade_cts = 3 * 32; timing is read on-chain.
     // Output: (PlanetState, UpgradeRevealed)
     // -----------------------------------------------------------------------
 
     pub fn queue_upgrade_planet(
         ctx: Context<QueueUpgradePlanet>,
-        computation_offset: u64,
-        upgrade_cts: Vec<u8>,     // 6 * 32
+        upgrade_cts: Vec<u8>,     // 3 * 32
         upgrade_pubkey: [u8; 32],
         upgrade_nonce: u128,
+        priority_fee_lamports: u64, // optional tip to the Arcium fee pool (0 = none)
     ) -> Result<()> {
-        require!(upgrade_cts.len() == 6 * 32, ErrorCode::UpgradeFailed);
+        require!(upgrade_cts.len() == 3 * 32, ErrorCode::UpgradeFailed);
+        require_priority_fee(priority_fee_lamports)?;
 
         let game = &ctx.accounts.game;
         let clock = Clock::get()?;
+        require!(!game.paused, ErrorCode::GamePaused);
         require!(clock.slot >= game.start_slot, ErrorCode::GameNotStarted);
         require!(clock.slot < game.end_slot, ErrorCode::GameEnded);
+        require!(!game.ended_early, ErrorCode::GameEnded);
+
+        // In-flight moves were sized against the current caps; the flush would
+        // re-cap them with the upgraded stats, so they must all land first
+        let pending = &ctx.accounts.pending_moves;
+        require!(pending.moves.is_empty(), ErrorCode::MustFlushFirst);
+
+        let computation_offset = ctx
+            .accounts
+            .offset_tracker
+            .advance(ctx.accounts.game.game_id, ctx.accounts.payer.key())?;
 
         ctx.accounts.sign_pda_account.bump = ctx.bumps.sign_pda_account;
 
@@ -864,23 +2693,49 @@ pub mod encrypted_forest {
             .plaintext_u128(u128::from_le_bytes(body.state_enc_nonce))
             .encrypted_u32(body.state_enc_ciphertexts[0])  // Pack FE 0
             .encrypted_u32(body.state_enc_ciphertexts[1])  // Pack FE 1
-            .encrypted_u32(body.state_enc_ciphertexts[2]); // Pack FE 2
+            .encrypted_u32(body.state_enc_ciphertexts[2])  // Pack FE 2
+            .encrypted_u32(body.state_enc_ciphertexts[3]); // Pack FE 3
 
-        // UpgradePlanetInput: 6 fields (player_id, focus, current_slot, game_speed, last_updated_slot, metal_upgrade_cost)
+        // UpgradePlanetInput: 3 fields (player_id, focus, metal_upgrade_cost)
         builder = builder
             .x25519_pubkey(upgrade_pubkey)
             .plaintext_u128(upgrade_nonce)
-            .encrypted_u32(extract_ct(&upgrade_cts, 0))  // player_id
-            .encrypted_u32(extract_ct(&upgrade_cts, 1))  // focus
-            .encrypted_u32(extract_ct(&upgrade_cts, 2))  // current_slot
-            .encrypted_u32(extract_ct(&upgrade_cts, 3))  // game_speed
-            .encrypted_u32(extract_ct(&upgrade_cts, 4))  // last_updated_slot
-            .encrypted_u32(extract_ct(&upgrade_cts, 5)); // metal_upgrade_cost
+            .encrypted_u64(extract_ct(&upgrade_cts, 0)?)  // player_id
+            .encrypted_u32(extract_ct(&upgrade_cts, 1)?)  // focus
+            .encrypted_u32(extract_ct(&upgrade_cts, 2)?) // metal_upgrade_cost
+            .plaintext_u64(game.max_planet_level as u64);
+
+        // Plaintext params: lazy generation timing sourced on-chain
+        builder = builder
+            .plaintext_u64(clock.slot)
+            .plaintext_u64(game.game_speed)
+            .plaintext_u64(body.last_updated_slot);
+        // Payer's key words: the circuit checks the claimed player_id against them
+        for word in pubkey_words(&ctx.accounts.payer.key()) {
+            builder = builder.plaintext_u64(word);
+        }
         // planet_input.owner re-encrypts output (no separate planet_key needed)
 
         let args = builder.build();
 
+        let started = UpgradeStartedEvent {
+            game_id: ctx.accounts.game.game_id,
+            actor: ctx.accounts.payer.key(),
+            planet_hash: ctx.accounts.celestial_body.planet_hash,
+            queued_slot: clock.slot,
+            computation_offset,
+        };
+
         let body_pda = ctx.accounts.celestial_body.key();
+        let game_pda = ctx.accounts.game.key();
+        let payer_key = ctx.accounts.payer.key();
+
+        pay_priority_fee(
+            &ctx.accounts.payer,
+            &ctx.accounts.pool_account,
+            &ctx.accounts.system_program,
+            priority_fee_lamports,
+        )?;
 
         queue_computation(
             ctx.accounts,
@@ -889,15 +2744,27 @@ pub mod encrypted_forest {
             vec![UpgradePlanetCallback::callback_ix(
                 computation_offset,
                 &ctx.accounts.mxe_account,
-                &[CallbackAccount {
-                    pubkey: body_pda,
-                    is_writable: true,
-                }],
+                &[
+                    CallbackAccount {
+                        pubkey: body_pda,
+                        is_writable: true,
+                    },
+                    CallbackAccount {
+                        pubkey: game_pda,
+                        is_writable: false,
+                    },
+                    CallbackAccount {
+                        pubkey: payer_key,
+                        is_writable: false,
+                    },
+                ],
             )?],
             1,
             0,
         )?;
 
+        emit!(started);
+
         Ok(())
     }
 
@@ -913,13 +2780,22 @@ pub mod encrypted_forest {
             Ok(o) => o,
             Err(e) => {
                 msg!("upgrade_planet verify_output FAILED: {:?}", e);
-                return Err(ErrorCode::AbortedComputation.into());
+                return Err(computation_failed(
+                    "upgrade_planet",
+                    ctx.accounts.computation_account.key(),
+                    map_arcium_error(&e),
+                ));
             }
         };
 
-        // Output tuple: (Enc<Shared, PlanetState>, Enc<Shared, UpgradeRevealed>)
+        // Output tuple: (Enc<Shared, PlanetState>, Enc<Shared, UpgradeRevealed>, u8)
         let enc_state = &o.field_0.field_0;
         let revealed = &o.field_0.field_1;
+        let focus = o.field_0.field_2;
+        require!(
+            focus <= UpgradeFocus::Balanced as u8,
+            ErrorCode::InvalidUpgradeFocus
+        );
 
         let planet = &mut ctx.accounts.celestial_body;
 
@@ -935,478 +2811,5191 @@ pub mod encrypted_forest {
         planet.last_updated_slot = Clock::get()?.slot;
 
         emit!(UpgradePlanetEvent {
+            game_id: ctx.accounts.game.game_id,
+            actor: ctx.accounts.actor.key(),
             planet_hash: planet.planet_hash,
             encrypted_success: revealed.ciphertexts[0],
             encrypted_new_level: revealed.ciphertexts[1],
+            encrypted_at_max_level: revealed.ciphertexts[2],
             encryption_key: revealed.encryption_key,
             nonce: revealed.nonce.to_le_bytes(),
+            focus,
         });
 
         Ok(())
     }
 
     // -----------------------------------------------------------------------
-    // Broadcast
+    // Queue surrender_planet
+    // Planet state + surrender input passed inline as ciphertexts.
+    // surrender_cts = 1 * 32 (SurrenderInput: player_id).
+    // Output: (PlanetState, surrendered)
     // -----------------------------------------------------------------------
 
-    pub fn broadcast(
-        ctx: Context<Broadcast>,
-        _game_id: u64,
-        x: i64,
-        y: i64,
-        planet_hash: [u8; 32],
+    pub fn queue_surrender_planet(
+        ctx: Context<QueueSurrenderPlanet>,
+        surrender_cts: Vec<u8>,   // 1 * 32
+        surrender_pubkey: [u8; 32],
+        surrender_nonce: u128,
     ) -> Result<()> {
+        require!(surrender_cts.len() == 32, ErrorCode::SurrenderFailed);
+
         let game = &ctx.accounts.game;
-        let computed = compute_planet_hash(x, y, game.game_id, game.hash_rounds);
-        require!(computed == planet_hash, ErrorCode::InvalidPlanetHash);
+        let clock = Clock::get()?;
+        require!(!game.paused, ErrorCode::GamePaused);
+        require!(clock.slot >= game.start_slot, ErrorCode::GameNotStarted);
+        require!(clock.slot < game.end_slot, ErrorCode::GameEnded);
+        require!(!game.ended_early, ErrorCode::GameEnded);
 
-        emit!(BroadcastEvent {
-            x,
-            y,
-            game_id: game.game_id,
-            planet_hash,
-            broadcaster: ctx.accounts.broadcaster.key(),
-        });
+        // Landed moves could change ownership, so they must be resolved first
+        let pending = &ctx.accounts.pending_moves;
+        if !pending.moves.is_empty() {
+            require!(
+                pending.moves[0].landing_slot > clock.slot,
+                ErrorCode::MustFlushFirst
+            );
+        }
 
-        Ok(())
-    }
+        let computation_offset = ctx
+            .accounts
+            .offset_tracker
+            .advance(ctx.accounts.game.game_id, ctx.accounts.payer.key())?;
 
-    // -----------------------------------------------------------------------
-    // Cleanup
-    // -----------------------------------------------------------------------
+        ctx.accounts.sign_pda_account.bump = ctx.bumps.sign_pda_account;
+
+        // Enc<Shared, PlanetState> — all inline
+        let body = &ctx.accounts.celestial_body;
+        let mut builder = ArgBuilder::new()
+            .x25519_pubkey(body.state_enc_pubkey)
+            .plaintext_u128(u128::from_le_bytes(body.state_enc_nonce))
+            .encrypted_u32(body.state_enc_ciphertexts[0])  // Pack FE 0
+            .encrypted_u32(body.state_enc_ciphertexts[1])  // Pack FE 1
+            .encrypted_u32(body.state_enc_ciphertexts[2])  // Pack FE 2
+            .encrypted_u32(body.state_enc_ciphertexts[3]); // Pack FE 3
+
+        // SurrenderInput: 1 field (player_id)
+        // planet_input.owner re-encrypts output (no separate planet_key needed)
+        builder = builder
+            .x25519_pubkey(surrender_pubkey)
+            .plaintext_u128(surrender_nonce)
+            .encrypted_u64(extract_ct_unchecked(&surrender_cts, 0)); // player_id
+        // Payer's key words: the circuit checks the claimed player_id against them
+        for word in pubkey_words(&ctx.accounts.payer.key()) {
+            builder = builder.plaintext_u64(word);
+        }
+
+        let args = builder.build();
+
+        let body_pda = ctx.accounts.celestial_body.key();
+        let player_pda = ctx.accounts.player.key();
+
+        queue_computation(
+            ctx.accounts,
+            computation_offset,
+            args,
+            vec![SurrenderPlanetCallback::callback_ix(
+                computation_offset,
+                &ctx.accounts.mxe_account,
+                &[
+                    CallbackAccount {
+                        pubkey: body_pda,
+                        is_writable: true,
+                    },
+                    CallbackAccount {
+                        pubkey: player_pda,
+                        is_writable: false,
+                    },
+                ],
+            )?],
+            1,
+            0,
+        )?;
 
-    pub fn cleanup_game(ctx: Context<CleanupGame>, _game_id: u64) -> Result<()> {
-        let game = &ctx.accounts.game;
-        let clock = Clock::get()?;
-        require!(clock.slot > game.end_slot, ErrorCode::GameNotEnded);
         Ok(())
     }
 
-    pub fn cleanup_player(ctx: Context<CleanupPlayer>, _game_id: u64) -> Result<()> {
-        let game = &ctx.accounts.game;
-        let clock = Clock::get()?;
-        require!(clock.slot > game.end_slot, ErrorCode::GameNotEnded);
+    #[arcium_callback(encrypted_ix = "surrender_planet")]
+    pub fn surrender_planet_callback(
+        ctx: Context<SurrenderPlanetCallback>,
+        output: SignedComputationOutputs<SurrenderPlanetOutput>,
+    ) -> Result<()> {
+        let o = match output.verify_output(
+            &ctx.accounts.cluster_account,
+            &ctx.accounts.computation_account,
+        ) {
+            Ok(o) => o,
+            Err(e) => {
+                msg!("surrender_planet verify_output FAILED: {:?}", e);
+                return Err(computation_failed(
+                    "surrender_planet",
+                    ctx.accounts.computation_account.key(),
+                    map_arcium_error(&e),
+                ));
+            }
+        };
+
+        // Output tuple: (Enc<Shared, PlanetState>, u8 surrendered)
+        let enc_state = &o.field_0.field_0;
+        let surrendered = o.field_0.field_1;
+
+        let planet = &mut ctx.accounts.celestial_body;
+        planet.state_enc_pubkey = enc_state.encryption_key;
+        planet.state_enc_nonce = enc_state.nonce.to_le_bytes();
+        let mut i = 0;
+        while i < PLANET_STATE_FIELDS {
+            planet.state_enc_ciphertexts[i] = enc_state.ciphertexts[i];
+            i += 1;
+        }
+        planet.last_updated_slot = Clock::get()?.slot;
+
+        // A failed ownership check leaves the planet as it was
+        if surrendered == 1 {
+            emit!(SurrenderEvent {
+                planet_hash: planet.planet_hash,
+                former_owner: ctx.accounts.player.owner,
+            });
+        }
+
         Ok(())
     }
 
-    pub fn cleanup_planet(
-        ctx: Context<CleanupPlanet>,
-        _game_id: u64,
-        _planet_hash: [u8; 32],
+    // -----------------------------------------------------------------------
+    // Queue transfer_resources
+    // Source + destination planet state passed inline as ciphertexts.
+    // transfer_cts = 4 * 32 (TransferInput: player_id, ally_player_id, ships, metal).
+    // Plaintext: current_slot, game_speed, both last_updated_slots, allied flag,
+    // ally key words.
+    // Output: (PlanetState, PlanetState)
+    // -----------------------------------------------------------------------
+
+    pub fn queue_transfer_resources(
+        ctx: Context<QueueTransferResources>,
+        transfer_cts: Vec<u8>,    // 4 * 32
+        transfer_pubkey: [u8; 32],
+        transfer_nonce: u128,
+        ally: Pubkey, // wallet owning the destination in an allied transfer (ignored otherwise)
     ) -> Result<()> {
+        require!(transfer_cts.len() == 4 * 32, ErrorCode::TransferFailed);
+        require!(
+            ctx.accounts.source_body.key() != ctx.accounts.dest_body.key(),
+            ErrorCode::TransferFailed
+        );
+
         let game = &ctx.accounts.game;
         let clock = Clock::get()?;
-        require!(clock.slot > game.end_slot, ErrorCode::GameNotEnded);
-        Ok(())
-    }
-}
+        require!(!game.paused, ErrorCode::GamePaused);
+        require!(clock.slot >= game.start_slot, ErrorCode::GameNotStarted);
+        require!(clock.slot < game.end_slot, ErrorCode::GameEnded);
+        require!(!game.ended_early, ErrorCode::GameEnded);
 
-// ===========================================================================
-// Account Structures
-// ===========================================================================
+        // Both planets must have all landed moves flushed
+        for pending in [&ctx.accounts.source_pending, &ctx.accounts.dest_pending] {
+            if !pending.moves.is_empty() {
+                require!(
+                    pending.moves[0].landing_slot > clock.slot,
+                    ErrorCode::MustFlushFirst
+                );
+            }
+        }
 
-#[account]
-#[derive(InitSpace)]
-pub struct Game {
-    pub admin: Pubkey,
-    pub game_id: u64,
-    pub map_diameter: u64,
-    pub game_speed: u64,
-    pub start_slot: u64,
-    pub end_slot: u64,
-    pub win_condition: WinCondition,
-    pub whitelist: bool,
-    pub server_pubkey: Option<Pubkey>,
-    pub noise_thresholds: NoiseThresholds,
-    /// Number of iterated BLAKE3 rounds for planet hash difficulty.
-    pub hash_rounds: u16,
-}
+        // Allied transfers are only unlocked when both the payer and the
+        // receiving ally are members; the circuit binds ally_player_id to `ally`
+        let (allied, ally_key) = match &ctx.accounts.alliance {
+            Some(alliance) => {
+                require!(alliance.game_id == game.game_id, ErrorCode::NotAllianceMember);
+                require!(
+                    alliance.members.contains(&ctx.accounts.payer.key()),
+                    ErrorCode::NotAllianceMember
+                );
+                require!(
+                    ally != ctx.accounts.payer.key() && alliance.members.contains(&ally),
+                    ErrorCode::NotAllianceMember
+                );
+                (1u64, ally)
+            }
+            None => (0u64, Pubkey::default()),
+        };
 
-#[account]
-#[derive(InitSpace)]
-pub struct Player {
-    pub owner: Pubkey,
-    pub game_id: u64,
-    pub points: u64,
-    pub has_spawned: bool,
-}
+        let computation_offset = ctx
+            .accounts
+            .offset_tracker
+            .advance(ctx.accounts.game.game_id, ctx.accounts.payer.key())?;
 
-#[account]
-pub struct EncryptedCelestialBody {
-    pub planet_hash: [u8; 32],
-    pub last_updated_slot: u64,
-    pub last_flushed_slot: u64,
-    // State section (144 bytes) -- Pack<[u32;15]> = 3 FEs
-    pub state_enc_pubkey: [u8; 32],
-    pub state_enc_nonce: [u8; 16],
-    pub state_enc_ciphertexts: [[u8; 32]; PLANET_STATE_FIELDS],
-}
+        ctx.accounts.sign_pda_account.bump = ctx.bumps.sign_pda_account;
 
-impl EncryptedCelestialBody {
-    pub const MAX_SIZE: usize = 8
-        + 32   // planet_hash
-        + 8    // last_updated_slot
-        + 8    // last_flushed_slot
-        // State section
-        + 32   // state_enc_pubkey
-        + 16   // state_enc_nonce
-        + (PLANET_STATE_FIELDS * 32); // state_enc_ciphertexts (3 packed FEs)
-}
+        // Enc<Shared, PlanetState> (source) — all inline
+        let source = &ctx.accounts.source_body;
+        let mut builder = ArgBuilder::new()
+            .x25519_pubkey(source.state_enc_pubkey)
+            .plaintext_u128(u128::from_le_bytes(source.state_enc_nonce))
+            .encrypted_u32(source.state_enc_ciphertexts[0])  // Pack FE 0
+            .encrypted_u32(source.state_enc_ciphertexts[1])  // Pack FE 1
+            .encrypted_u32(source.state_enc_ciphertexts[2])  // Pack FE 2
+            .encrypted_u32(source.state_enc_ciphertexts[3]); // Pack FE 3
 
-/// Dynamic-size account tracking pending moves for a planet.
-/// Sorted by landing_slot so front always has earliest-landing move.
-/// Includes a fixed FIFO buffer for landing_slots awaiting callback.
-#[account]
-pub struct PendingMovesMetadata {
-    pub game_id: u64,
-    pub planet_hash: [u8; 32],
-    pub next_move_id: u64,
-    pub move_count: u16,
-    /// FIFO buffer: queue_process_move pushes, process_move_callback pops.
-    pub queued_count: u8,
-    pub queued_landing_slots: [u64; 8],
-    pub moves: Vec<PendingMoveEntry>,
-}
+        // Enc<Shared, PlanetState> (destination) — all inline
+        let dest = &ctx.accounts.dest_body;
+        builder = builder
+            .x25519_pubkey(dest.state_enc_pubkey)
+            .plaintext_u128(u128::from_le_bytes(dest.state_enc_nonce))
+            .encrypted_u32(dest.state_enc_ciphertexts[0])  // Pack FE 0
+            .encrypted_u32(dest.state_enc_ciphertexts[1])  // Pack FE 1
+            .encrypted_u32(dest.state_enc_ciphertexts[2])  // Pack FE 2
+            .encrypted_u32(dest.state_enc_ciphertexts[3]); // Pack FE 3
+
+        // TransferInput: 4 fields
+        builder = builder
+            .x25519_pubkey(transfer_pubkey)
+            .plaintext_u128(transfer_nonce)
+            .encrypted_u64(extract_ct_unchecked(&transfer_cts, 0))  // player_id
+            .encrypted_u64(extract_ct_unchecked(&transfer_cts, 1))  // ally_player_id
+            .encrypted_u32(extract_ct_unchecked(&transfer_cts, 2))  // ships_amount
+            .encrypted_u32(extract_ct_unchecked(&transfer_cts, 3)); // metal_amount
+
+        // Plaintext params: lazy generation timing sourced on-chain
+        builder = builder
+            .plaintext_u64(clock.slot)
+            .plaintext_u64(game.game_speed)
+            .plaintext_u64(source.last_updated_slot)
+            .plaintext_u64(dest.last_updated_slot)
+            .plaintext_u64(allied);
+        // Ally's key words: the circuit derives the ally's player id from them
+        for word in pubkey_words(&ally_key) {
+            builder = builder.plaintext_u64(word);
+        }
+        // Payer's key words: the circuit checks the claimed player_id against them
+        for word in pubkey_words(&ctx.accounts.payer.key()) {
+            builder = builder.plaintext_u64(word);
+        }
 
-impl PendingMovesMetadata {
-    /// Base size (no entries). Grows by PENDING_MOVE_ENTRY_SIZE per move.
-    pub const BASE_SIZE: usize = PENDING_MOVES_META_BASE_SIZE;
-}
+        let args = builder.build();
 
-/// Entry in the sorted moves array.
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
-pub struct PendingMoveEntry {
-    pub landing_slot: u64,
-    pub move_id: u64,
-}
+        let source_pda = ctx.accounts.source_body.key();
+        let dest_pda = ctx.accounts.dest_body.key();
 
-/// Individual move account (one per in-flight move).
-/// PDA: ["move", game_id, planet_hash, move_id]
-#[account]
-pub struct PendingMoveAccount {
-    pub game_id: u64,
-    pub planet_hash: [u8; 32],
-    pub move_id: u64,
-    pub landing_slot: u64,
-    pub payer: Pubkey,
-    /// Set to true by the MPC callback once encrypted data is written.
-    /// Flush skips moves where populated == false (MPC not yet complete).
-    pub populated: bool,
-    pub enc_nonce: u128,
-    pub enc_ciphertexts: [[u8; 32]; 4],  // ships, metal, attacking_planet_id, attacking_player_id
-}
+        queue_computation(
+            ctx.accounts,
+            computation_offset,
+            args,
+            vec![TransferResourcesCallback::callback_ix(
+                computation_offset,
+                &ctx.accounts.mxe_account,
+                &[
+                    CallbackAccount {
+                        pubkey: source_pda,
+                        is_writable: true,
+                    },
+                    CallbackAccount {
+                        pubkey: dest_pda,
+                        is_writable: true,
+                    },
+                ],
+            )?],
+            1,
+            0,
+        )?;
 
-impl PendingMoveAccount {
-    pub const MAX_SIZE: usize = 8
-        + 8    // game_id
-        + 32   // planet_hash
-        + 8    // move_id
-        + 8    // landing_slot
-        + 32   // payer
-        + 1    // populated
-        + 16   // enc_nonce
-        + (4 * 32); // enc_ciphertexts
-}
+        Ok(())
+    }
 
-// ===========================================================================
-// Enums
-// ===========================================================================
+    #[arcium_callback(encrypted_ix = "transfer_resources")]
+    pub fn transfer_resources_callback(
+        ctx: Context<TransferResourcesCallback>,
+        output: SignedComputationOutputs<TransferResourcesOutput>,
+    ) -> Result<()> {
+        let o = match output.verify_output(
+            &ctx.accounts.cluster_account,
+            &ctx.accounts.computation_account,
+        ) {
+            Ok(o) => o,
+            Err(e) => {
+                msg!("transfer_resources verify_output FAILED: {:?}", e);
+                return Err(computation_failed(
+                    "transfer_resources",
+                    ctx.accounts.computation_account.key(),
+                    map_arcium_error(&e),
+                ));
+            }
+        };
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
-pub enum CelestialBodyType {
-    Planet,
-    Quasar,
-    SpacetimeRip,
-    AsteroidBelt,
-}
+        // Output tuple: (Enc<Shared, PlanetState>, Enc<Shared, PlanetState>)
+        let enc_source = &o.field_0.field_0;
+        let enc_dest = &o.field_0.field_1;
+        let slot = Clock::get()?.slot;
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
-pub enum WinCondition {
-    PointsBurning { points_per_metal: u64 },
-    RaceToCenter { min_spawn_distance: u64 },
-}
+        let source = &mut ctx.accounts.source_body;
+        source.state_enc_pubkey = enc_source.encryption_key;
+        source.state_enc_nonce = enc_source.nonce.to_le_bytes();
+        let mut i = 0;
+        while i < PLANET_STATE_FIELDS {
+            source.state_enc_ciphertexts[i] = enc_source.ciphertexts[i];
+            i += 1;
+        }
+        source.last_updated_slot = slot;
+
+        let dest = &mut ctx.accounts.dest_body;
+        dest.state_enc_pubkey = enc_dest.encryption_key;
+        dest.state_enc_nonce = enc_dest.nonce.to_le_bytes();
+        let mut j = 0;
+        while j < PLANET_STATE_FIELDS {
+            dest.state_enc_ciphertexts[j] = enc_dest.ciphertexts[j];
+            j += 1;
+        }
+        dest.last_updated_slot = slot;
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
-pub enum UpgradeFocus {
-    Range,
-    LaunchVelocity,
-}
+        Ok(())
+    }
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
-pub enum CometBoost {
-    ShipCapacity,
-    MetalCapacity,
-    ShipGenSpeed,
-    MetalGenSpeed,
-    Range,
-    LaunchVelocity,
-}
+    // -----------------------------------------------------------------------
+    // Queue spy_move
+    // Source + target planet state passed inline as ciphertexts.
+    // spy_cts = 5 * 32 (SpyInput: player_id, source_x, source_y, target_x, target_y).
+    // Output: SpyRevealed (encrypted for the spy). No planet state changes.
+    // -----------------------------------------------------------------------
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, InitSpace)]
-pub struct NoiseThresholds {
-    pub dead_space_threshold: u8,
-    pub planet_threshold: u8,
-    pub quasar_threshold: u8,
-    pub spacetime_rip_threshold: u8,
-    pub asteroid_belt_threshold: u8,
-    pub size_threshold_1: u8,
-    pub size_threshold_2: u8,
-    pub size_threshold_3: u8,
-    pub size_threshold_4: u8,
-    pub size_threshold_5: u8,
-}
+    pub fn queue_spy_move(
+        ctx: Context<QueueSpyMove>,
+        spy_cts: Vec<u8>,         // 5 * 32
+        spy_pubkey: [u8; 32],
+        spy_nonce: u128,
+    ) -> Result<()> {
+        require!(spy_cts.len() == 5 * 32, ErrorCode::SpyFailed);
 
-// ===========================================================================
-// Events
-// ===========================================================================
+        let game = &ctx.accounts.game;
+        let clock = Clock::get()?;
+        require!(!game.paused, ErrorCode::GamePaused);
+        require!(clock.slot >= game.start_slot, ErrorCode::GameNotStarted);
+        require!(clock.slot < game.end_slot, ErrorCode::GameEnded);
+        require!(!game.ended_early, ErrorCode::GameEnded);
 
-#[event]
-pub struct InitPlanetEvent {
-    pub encrypted_planet_hash: [u8; 32],
-    pub encrypted_valid: [u8; 32],
-    pub encryption_key: [u8; 32],
-    pub nonce: [u8; 16],
-}
+        // Ownership of the source is checked in MPC, so landed moves must be resolved
+        let source_pending = &ctx.accounts.source_pending;
+        if !source_pending.moves.is_empty() {
+            require!(
+                source_pending.moves[0].landing_slot > clock.slot,
+                ErrorCode::MustFlushFirst
+            );
+        }
 
-#[event]
-pub struct InitSpawnPlanetEvent {
-    pub encrypted_planet_hash: [u8; 32],
-    pub encrypted_valid: [u8; 32],
-    pub encrypted_spawn_valid: [u8; 32],
-    pub encryption_key: [u8; 32],
-    pub nonce: [u8; 16],
-}
+        let computation_offset = ctx
+            .accounts
+            .offset_tracker
+            .advance(ctx.accounts.game.game_id, ctx.accounts.payer.key())?;
 
-#[event]
-pub struct FlushPlanetEvent {
-    pub planet_hash: [u8; 32],
-    pub flushed_count: u8,
-}
+        ctx.accounts.sign_pda_account.bump = ctx.bumps.sign_pda_account;
 
-#[event]
-pub struct UpgradePlanetEvent {
-    pub planet_hash: [u8; 32],
-    pub encrypted_success: [u8; 32],
-    pub encrypted_new_level: [u8; 32],
-    pub encryption_key: [u8; 32],
-    pub nonce: [u8; 16],
-}
+        // Enc<Shared, PlanetState> (source) — all inline
+        let source = &ctx.accounts.source_body;
+        let mut builder = ArgBuilder::new()
+            .x25519_pubkey(source.state_enc_pubkey)
+            .plaintext_u128(u128::from_le_bytes(source.state_enc_nonce))
+            .encrypted_u32(source.state_enc_ciphertexts[0])  // Pack FE 0
+            .encrypted_u32(source.state_enc_ciphertexts[1])  // Pack FE 1
+            .encrypted_u32(source.state_enc_ciphertexts[2])  // Pack FE 2
+            .encrypted_u32(source.state_enc_ciphertexts[3]); // Pack FE 3
 
-#[event]
-pub struct BroadcastEvent {
-    pub x: i64,
-    pub y: i64,
-    pub game_id: u64,
-    pub planet_hash: [u8; 32],
+        // Enc<Shared, PlanetState> (target) — all inline
+        let target = &ctx.accounts.target_body;
+        builder = builder
+            .x25519_pubkey(target.state_enc_pubkey)
+            .plaintext_u128(u128::from_le_bytes(target.state_enc_nonce))
+            .encrypted_u32(target.state_enc_ciphertexts[0])  // Pack FE 0
+            .encrypted_u32(target.state_enc_ciphertexts[1])  // Pack FE 1
+            .encrypted_u32(target.state_enc_ciphertexts[2])  // Pack FE 2
+            .encrypted_u32(target.state_enc_ciphertexts[3]); // Pack FE 3
+
+        // SpyInput: 5 fields
+        // spy_input.owner encrypts revealed output
+        builder = builder
+            .x25519_pubkey(spy_pubkey)
+            .plaintext_u128(spy_nonce)
+            .encrypted_u64(extract_ct_unchecked(&spy_cts, 0))  // player_id
+            .encrypted_u32(extract_ct_unchecked(&spy_cts, 1))  // source_x
+            .encrypted_u32(extract_ct_unchecked(&spy_cts, 2))  // source_y
+            .encrypted_u32(extract_ct_unchecked(&spy_cts, 3))  // target_x
+            .encrypted_u32(extract_ct_unchecked(&spy_cts, 4)); // target_y
+
+        // Plaintext param: distance formula for the range check
+        builder = builder.plaintext_u64(ctx.accounts.game.distance_method as u64);
+        // Payer's key words: the circuit checks the claimed player_id against them
+        for word in pubkey_words(&ctx.accounts.payer.key()) {
+            builder = builder.plaintext_u64(word);
+        }
+
+        let args = builder.build();
+
+        let target_pda = ctx.accounts.target_body.key();
+
+        queue_computation(
+            ctx.accounts,
+            computation_offset,
+            args,
+            vec![SpyMoveCallback::callback_ix(
+                computation_offset,
+                &ctx.accounts.mxe_account,
+                &[CallbackAccount {
+                    pubkey: target_pda,
+                    is_writable: false,
+                }],
+            )?],
+            1,
+            0,
+        )?;
+
+        Ok(())
+    }
+
+    #[arcium_callback(encrypted_ix = "spy_move")]
+    pub fn spy_move_callback(
+        ctx: Context<SpyMoveCallback>,
+        output: SignedComputationOutputs<SpyMoveOutput>,
+    ) -> Result<()> {
+        let o = match output.verify_output(
+            &ctx.accounts.cluster_account,
+            &ctx.accounts.computation_account,
+        ) {
+            Ok(o) => o,
+            Err(e) => {
+                msg!("spy_move verify_output FAILED: {:?}", e);
+                return Err(computation_failed(
+                    "spy_move",
+                    ctx.accounts.computation_account.key(),
+                    map_arcium_error(&e),
+                ));
+            }
+        };
+
+        // Output: Enc<Shared, SpyRevealed> (single value, not tuple)
+        let revealed = &o.field_0;
+
+        emit!(SpyEvent {
+            planet_hash: ctx.accounts.target_body.planet_hash,
+            encrypted_valid: revealed.ciphertexts[0],
+            encrypted_body_type: revealed.ciphertexts[1],
+            encrypted_size: revealed.ciphertexts[2],
+            encrypted_owner_exists: revealed.ciphertexts[3],
+            encryption_key: revealed.encryption_key,
+            nonce: revealed.nonce.to_le_bytes(),
+        });
+
+        Ok(())
+    }
+
+    // -----------------------------------------------------------------------
+    // Comets
+    // spawn_comet (admin) places a comet on a planet hash; queue_comet_collect
+    // lets that planet absorb it into a free comet slot via MPC. The admin
+    // funds the comet and gets its rent back when it closes.
+    // Plaintext: boost (CometBoost discriminant + 1).
+    // Output: (PlanetState, collected u8)
+    // -----------------------------------------------------------------------
+
+    pub fn spawn_comet(
+        ctx: Context<SpawnComet>,
+        _game_id: u64,
+        comet_id: u64,
+        boost: CometBoost,
+        location_hash: [u8; 32],
+    ) -> Result<()> {
+        let comet = &mut ctx.accounts.comet;
+        comet.comet_id = comet_id;
+        comet.game_id = ctx.accounts.game.game_id;
+        comet.boost = boost;
+        comet.location_hash = location_hash;
+        comet.discoverer = Pubkey::default();
+        comet.creator = ctx.accounts.admin.key();
+        comet.claimed_slot = 0;
+
+        Ok(())
+    }
+
+    /// Close a comet whose comet_collect computation never called back, once
+    /// COMET_COLLECT_TIMEOUT_SLOTS have passed since it was claimed. The rent
+    /// returns to the admin who spawned it.
+    pub fn reclaim_comet(ctx: Context<ReclaimComet>, _game_id: u64, _comet_id: u64) -> Result<()> {
+        let comet = &ctx.accounts.comet;
+        require!(comet.discoverer != Pubkey::default(), ErrorCode::CometNotStale);
+        let slot = Clock::get()?.slot;
+        require!(
+            slot >= comet.claimed_slot.saturating_add(COMET_COLLECT_TIMEOUT_SLOTS),
+            ErrorCode::CometNotStale
+        );
+        Ok(())
+    }
+
+    pub fn queue_comet_collect(
+        ctx: Context<QueueCometCollect>,
+    ) -> Result<()> {
+        let game = &ctx.accounts.game;
+        let clock = Clock::get()?;
+        require!(!game.paused, ErrorCode::GamePaused);
+        require!(clock.slot >= game.start_slot, ErrorCode::GameNotStarted);
+        require!(clock.slot < game.end_slot, ErrorCode::GameEnded);
+        require!(!game.ended_early, ErrorCode::GameEnded);
+
+        let comet = &mut ctx.accounts.comet;
+        require!(
+            comet.location_hash == ctx.accounts.celestial_body.planet_hash,
+            ErrorCode::CometLocationMismatch
+        );
+        // One collection attempt in flight at a time
+        require!(comet.discoverer == Pubkey::default(), ErrorCode::CometAlreadyClaimed);
+        comet.discoverer = ctx.accounts.payer.key();
+        comet.claimed_slot = clock.slot;
+        let boost = comet.boost as u64 + 1;
+
+        let computation_offset = ctx
+            .accounts
+            .offset_tracker
+            .advance(ctx.accounts.game.game_id, ctx.accounts.payer.key())?;
+
+        ctx.accounts.sign_pda_account.bump = ctx.bumps.sign_pda_account;
+
+        // Enc<Shared, PlanetState> — all inline
+        // planet_input.owner re-encrypts output (no separate planet_key needed)
+        let body = &ctx.accounts.celestial_body;
+        let args = ArgBuilder::new()
+            .x25519_pubkey(body.state_enc_pubkey)
+            .plaintext_u128(u128::from_le_bytes(body.state_enc_nonce))
+            .encrypted_u32(body.state_enc_ciphertexts[0])  // Pack FE 0
+            .encrypted_u32(body.state_enc_ciphertexts[1])  // Pack FE 1
+            .encrypted_u32(body.state_enc_ciphertexts[2])  // Pack FE 2
+            .encrypted_u32(body.state_enc_ciphertexts[3])  // Pack FE 3
+            .plaintext_u64(boost)
+            .build();
+
+        let body_pda = ctx.accounts.celestial_body.key();
+        let comet_pda = ctx.accounts.comet.key();
+        let creator_key = ctx.accounts.comet.creator;
+
+        queue_computation(
+            ctx.accounts,
+            computation_offset,
+            args,
+            vec![CometCollectCallback::callback_ix(
+                computation_offset,
+                &ctx.accounts.mxe_account,
+                &[
+                    CallbackAccount {
+                        pubkey: body_pda,
+                        is_writable: true,
+                    },
+                    CallbackAccount {
+                        pubkey: comet_pda,
+                        is_writable: true,
+                    },
+                    CallbackAccount {
+                        pubkey: creator_key,
+                        is_writable: true,
+                    },
+                ],
+            )?],
+            1,
+            0,
+        )?;
+
+        Ok(())
+    }
+
+    #[arcium_callback(encrypted_ix = "comet_collect")]
+    pub fn comet_collect_callback(
+        ctx: Context<CometCollectCallback>,
+        output: SignedComputationOutputs<CometCollectOutput>,
+    ) -> Result<()> {
+        let o = match output.verify_output(
+            &ctx.accounts.cluster_account,
+            &ctx.accounts.computation_account,
+        ) {
+            Ok(o) => o,
+            Err(e) => {
+                msg!("comet_collect verify_output FAILED: {:?}", e);
+                return Err(computation_failed(
+                    "comet_collect",
+                    ctx.accounts.computation_account.key(),
+                    map_arcium_error(&e),
+                ));
+            }
+        };
+
+        // Output tuple: (Enc<Shared, PlanetState>, u8 collected)
+        let enc_state = &o.field_0.field_0;
+        let collected = o.field_0.field_1;
+
+        let planet = &mut ctx.accounts.celestial_body;
+        planet.state_enc_pubkey = enc_state.encryption_key;
+        planet.state_enc_nonce = enc_state.nonce.to_le_bytes();
+        let mut i = 0;
+        while i < PLANET_STATE_FIELDS {
+            planet.state_enc_ciphertexts[i] = enc_state.ciphertexts[i];
+            i += 1;
+        }
+        planet.last_updated_slot = Clock::get()?.slot;
+
+        if collected == 1 {
+            emit!(CometCollectedEvent {
+                game_id: ctx.accounts.comet.game_id,
+                comet_id: ctx.accounts.comet.comet_id,
+                planet_hash: planet.planet_hash,
+                collector: ctx.accounts.comet.discoverer,
+            });
+            ctx.accounts.comet.close(ctx.accounts.creator.to_account_info())?;
+        } else {
+            // Both comet slots were taken; free the comet for another attempt
+            ctx.accounts.comet.discoverer = Pubkey::default();
+        }
+
+        Ok(())
+    }
+
+    // -----------------------------------------------------------------------
+    // Queue mine_asteroid
+    // Planet state + mine input passed inline as ciphertexts.
+    // mine_cts = 1 * 32 (MineInput: player_id).
+    // Plaintext: current_slot, game_speed, last_updated_slot.
+    // Output: (PlanetState, MineRevealed, success)
+    // -----------------------------------------------------------------------
+
+    pub fn queue_mine_asteroid(
+        ctx: Context<QueueMineAsteroid>,
+        mine_cts: Vec<u8>,        // 1 * 32
+        mine_pubkey: [u8; 32],
+        mine_nonce: u128,
+    ) -> Result<()> {
+        require!(mine_cts.len() == 32, ErrorCode::MineFailed);
+
+        let game = &ctx.accounts.game;
+        let clock = Clock::get()?;
+        require!(!game.paused, ErrorCode::GamePaused);
+        require!(clock.slot >= game.start_slot, ErrorCode::GameNotStarted);
+        require!(clock.slot < game.end_slot, ErrorCode::GameEnded);
+        require!(!game.ended_early, ErrorCode::GameEnded);
+
+        // At most one successful mine per game_speed slots
+        let body = &ctx.accounts.celestial_body;
+        require!(
+            body.last_mined_slot == 0
+                || clock.slot >= body.last_mined_slot.saturating_add(game.game_speed),
+            ErrorCode::MiningCooldown
+        );
+
+        // Landed moves would be overwritten by the mined state, so they must
+        // be flushed first
+        let pending = &ctx.accounts.pending_moves;
+        if !pending.moves.is_empty() {
+            require!(
+                pending.moves[0].landing_slot > clock.slot,
+                ErrorCode::MustFlushFirst
+            );
+        }
+
+        let computation_offset = ctx
+            .accounts
+            .offset_tracker
+            .advance(ctx.accounts.game.game_id, ctx.accounts.payer.key())?;
+
+        ctx.accounts.sign_pda_account.bump = ctx.bumps.sign_pda_account;
+
+        // Enc<Shared, PlanetState> — all inline
+        let mut builder = ArgBuilder::new()
+            .x25519_pubkey(body.state_enc_pubkey)
+            .plaintext_u128(u128::from_le_bytes(body.state_enc_nonce))
+            .encrypted_u32(body.state_enc_ciphertexts[0])  // Pack FE 0
+            .encrypted_u32(body.state_enc_ciphertexts[1])  // Pack FE 1
+            .encrypted_u32(body.state_enc_ciphertexts[2])  // Pack FE 2
+            .encrypted_u32(body.state_enc_ciphertexts[3]); // Pack FE 3
+
+        // MineInput: 1 field (player_id)
+        builder = builder
+            .x25519_pubkey(mine_pubkey)
+            .plaintext_u128(mine_nonce)
+            .encrypted_u64(extract_ct_unchecked(&mine_cts, 0)); // player_id
+        // planet_input.owner re-encrypts output (no separate planet_key needed)
+
+        // Plaintext params: lazy generation timing sourced on-chain
+        builder = builder
+            .plaintext_u64(clock.slot)
+            .plaintext_u64(game.game_speed)
+            .plaintext_u64(body.last_updated_slot);
+        // Payer's key words: the circuit checks the claimed player_id against them
+        for word in pubkey_words(&ctx.accounts.payer.key()) {
+            builder = builder.plaintext_u64(word);
+        }
+
+        let args = builder.build();
+
+        let body_pda = ctx.accounts.celestial_body.key();
+
+        queue_computation(
+            ctx.accounts,
+            computation_offset,
+            args,
+            vec![MineAsteroidCallback::callback_ix(
+                computation_offset,
+                &ctx.accounts.mxe_account,
+                &[CallbackAccount {
+                    pubkey: body_pda,
+                    is_writable: true,
+                }],
+            )?],
+            1,
+            0,
+        )?;
+
+        Ok(())
+    }
+
+    #[arcium_callback(encrypted_ix = "mine_asteroid")]
+    pub fn mine_asteroid_callback(
+        ctx: Context<MineAsteroidCallback>,
+        output: SignedComputationOutputs<MineAsteroidOutput>,
+    ) -> Result<()> {
+        let o = match output.verify_output(
+            &ctx.accounts.cluster_account,
+            &ctx.accounts.computation_account,
+        ) {
+            Ok(o) => o,
+            Err(e) => {
+                msg!("mine_asteroid verify_output FAILED: {:?}", e);
+                return Err(computation_failed(
+                    "mine_asteroid",
+                    ctx.accounts.computation_account.key(),
+                    map_arcium_error(&e),
+                ));
+            }
+        };
+
+        // Output tuple: (Enc<Shared, PlanetState>, Enc<Shared, MineRevealed>, u8 success)
+        let enc_state = &o.field_0.field_0;
+        let revealed = &o.field_0.field_1;
+        let success = o.field_0.field_2;
+
+        let planet = &mut ctx.accounts.celestial_body;
+        planet.state_enc_pubkey = enc_state.encryption_key;
+        planet.state_enc_nonce = enc_state.nonce.to_le_bytes();
+        let mut i = 0;
+        while i < PLANET_STATE_FIELDS {
+            planet.state_enc_ciphertexts[i] = enc_state.ciphertexts[i];
+            i += 1;
+        }
+        let slot = Clock::get()?.slot;
+        planet.last_updated_slot = slot;
+        // Failed attempts (wrong owner / not an asteroid belt) don't start the cooldown
+        if success == 1 {
+            planet.last_mined_slot = slot;
+        }
+
+        emit!(MiningCompleteEvent {
+            planet_hash: planet.planet_hash,
+            encrypted_success: revealed.ciphertexts[0],
+            encrypted_yield: revealed.ciphertexts[1],
+            encryption_key: revealed.encryption_key,
+            nonce: revealed.nonce.to_le_bytes(),
+        });
+
+        Ok(())
+    }
+
+    // -----------------------------------------------------------------------
+    // Check spawn lost
+    // If the player's spawn planet has been captured, clear has_spawned so
+    // they can call queue_init_spawn_planet again.
+    // ownership_cts = 1 * 32 (OwnershipInput: player_id).
+    // Plaintext: payer key words (player_id must derive from the payer).
+    // Output: is_owner (plaintext)
+    // -----------------------------------------------------------------------
+
+    pub fn check_spawn_lost(
+        ctx: Context<CheckSpawnLost>,
+        ownership_cts: Vec<u8>,   // 1 * 32
+        ownership_pubkey: [u8; 32],
+        ownership_nonce: u128,
+    ) -> Result<()> {
+        require!(ownership_cts.len() == 32, ErrorCode::InvalidOwnershipInput);
+
+        let game = &ctx.accounts.game;
+        let clock = Clock::get()?;
+        require!(!game.paused, ErrorCode::GamePaused);
+        require!(clock.slot >= game.start_slot, ErrorCode::GameNotStarted);
+        require!(clock.slot < game.end_slot, ErrorCode::GameEnded);
+        require!(ctx.accounts.player.has_spawned, ErrorCode::NotSpawned);
+
+        let computation_offset = ctx
+            .accounts
+            .offset_tracker
+            .advance(ctx.accounts.game.game_id, ctx.accounts.payer.key())?;
+
+        ctx.accounts.sign_pda_account.bump = ctx.bumps.sign_pda_account;
+
+        // Enc<Shared, PlanetState> — all inline
+        let body = &ctx.accounts.celestial_body;
+        let mut builder = ArgBuilder::new()
+            .x25519_pubkey(body.state_enc_pubkey)
+            .plaintext_u128(u128::from_le_bytes(body.state_enc_nonce))
+            .encrypted_u32(body.state_enc_ciphertexts[0])  // Pack FE 0
+            .encrypted_u32(body.state_enc_ciphertexts[1])  // Pack FE 1
+            .encrypted_u32(body.state_enc_ciphertexts[2])  // Pack FE 2
+            .encrypted_u32(body.state_enc_ciphertexts[3]); // Pack FE 3
+
+        // OwnershipInput: 1 field (player_id)
+        builder = builder
+            .x25519_pubkey(ownership_pubkey)
+            .plaintext_u128(ownership_nonce)
+            .encrypted_u64(extract_ct_unchecked(&ownership_cts, 0)); // player_id
+
+        // Plaintext param: payer key, so only the spawn owner's own id can
+        // report the spawn as lost
+        for word in pubkey_words(&ctx.accounts.payer.key()) {
+            builder = builder.plaintext_u64(word);
+        }
+
+        let args = builder
+            .plaintext_u64(0) // strict: a mismatched id reads as owner
+            .build();
+
+        let player_pda = ctx.accounts.player.key();
+
+        queue_computation(
+            ctx.accounts,
+            computation_offset,
+            args,
+            vec![CheckOwnershipCallback::callback_ix(
+                computation_offset,
+                &ctx.accounts.mxe_account,
+                &[
+                    CallbackAccount {
+                        pubkey: player_pda,
+                        is_writable: true,
+                    },
+                    // No RaceToCenter claim
+                    optional_callback_account(None),
+                    optional_callback_account(None),
+                ],
+            )?],
+            1,
+            0,
+        )?;
+
+        Ok(())
+    }
+
+    #[arcium_callback(encrypted_ix = "check_ownership")]
+    pub fn check_ownership_callback(
+        ctx: Context<CheckOwnershipCallback>,
+        output: SignedComputationOutputs<CheckOwnershipOutput>,
+    ) -> Result<()> {
+        let o = match output.verify_output(
+            &ctx.accounts.cluster_account,
+            &ctx.accounts.computation_account,
+        ) {
+            Ok(o) => o,
+            Err(e) => {
+                msg!("check_ownership verify_output FAILED: {:?}", e);
+                return Err(computation_failed(
+                    "check_ownership",
+                    ctx.accounts.computation_account.key(),
+                    map_arcium_error(&e),
+                ));
+            }
+        };
+
+        // Output: is_owner (single plaintext u8)
+        let is_owner = o.field_0;
+
+        // Queued by claim_race_to_center_victory: is_owner is the strict check
+        // that the payer owns the revealed planet
+        if let Some(game) = ctx.accounts.game.as_mut() {
+            let slot = Clock::get()?.slot;
+            if is_owner == 1 && game.winner.is_none() && slot < game.end_slot {
+                let winner = ctx.accounts.player.owner;
+                game.winner = Some(winner);
+                if let Some(profile) = ctx.accounts.player_profile.as_mut() {
+                    profile.total_wins = profile.total_wins.saturating_add(1);
+                }
+                emit!(VictoryClaimedEvent {
+                    game_id: game.game_id,
+                    winner,
+                    slot,
+                });
+            } else {
+                msg!("claim_race_to_center_victory: ownership not proven or game over");
+            }
+            return Ok(());
+        }
+
+        let player = &mut ctx.accounts.player;
+        if is_owner == 0 {
+            player.has_spawned = false;
+            emit!(SpawnLostEvent {
+                game_id: player.game_id,
+                player: player.owner,
+                spawn_planet_hash: player.spawn_planet_hash,
+            });
+        }
+
+        Ok(())
+    }
+
+    // -----------------------------------------------------------------------
+    // Queue wormhole_jump
+    // Source + destination SpacetimeRip state passed inline as ciphertexts.
+    // wormhole_cts = 2 * 32 (WormholeInput: player_id, ships_to_jump).
+    // Plaintext: current_slot, game_speed, both last_updated_slots.
+    // Output: (PlanetState, PlanetState)
+    // -----------------------------------------------------------------------
+
+    pub fn queue_wormhole_jump(
+        ctx: Context<QueueWormholeJump>,
+        wormhole_cts: Vec<u8>,    // 2 * 32
+        wormhole_pubkey: [u8; 32],
+        wormhole_nonce: u128,
+    ) -> Result<()> {
+        require!(wormhole_cts.len() == 2 * 32, ErrorCode::WormholeJumpFailed);
+        require!(
+            ctx.accounts.source_body.key() != ctx.accounts.dest_body.key(),
+            ErrorCode::WormholeJumpFailed
+        );
+
+        let game = &ctx.accounts.game;
+        let clock = Clock::get()?;
+        require!(!game.paused, ErrorCode::GamePaused);
+        require!(clock.slot >= game.start_slot, ErrorCode::GameNotStarted);
+        require!(clock.slot < game.end_slot, ErrorCode::GameEnded);
+        require!(!game.ended_early, ErrorCode::GameEnded);
+
+        // Ships land instantly, so both planets must have all landed moves flushed
+        for pending in [&ctx.accounts.source_pending, &ctx.accounts.dest_pending] {
+            if !pending.moves.is_empty() {
+                require!(
+                    pending.moves[0].landing_slot > clock.slot,
+                    ErrorCode::MustFlushFirst
+                );
+            }
+        }
+
+        let computation_offset = ctx
+            .accounts
+            .offset_tracker
+            .advance(ctx.accounts.game.game_id, ctx.accounts.payer.key())?;
+
+        ctx.accounts.sign_pda_account.bump = ctx.bumps.sign_pda_account;
+
+        // Enc<Shared, PlanetState> (source) — all inline
+        let source = &ctx.accounts.source_body;
+        let mut builder = ArgBuilder::new()
+            .x25519_pubkey(source.state_enc_pubkey)
+            .plaintext_u128(u128::from_le_bytes(source.state_enc_nonce))
+            .encrypted_u32(source.state_enc_ciphertexts[0])  // Pack FE 0
+            .encrypted_u32(source.state_enc_ciphertexts[1])  // Pack FE 1
+            .encrypted_u32(source.state_enc_ciphertexts[2])  // Pack FE 2
+            .encrypted_u32(source.state_enc_ciphertexts[3]); // Pack FE 3
+
+        // Enc<Shared, PlanetState> (destination) — all inline
+        let dest = &ctx.accounts.dest_body;
+        builder = builder
+            .x25519_pubkey(dest.state_enc_pubkey)
+            .plaintext_u128(u128::from_le_bytes(dest.state_enc_nonce))
+            .encrypted_u32(dest.state_enc_ciphertexts[0])  // Pack FE 0
+            .encrypted_u32(dest.state_enc_ciphertexts[1])  // Pack FE 1
+            .encrypted_u32(dest.state_enc_ciphertexts[2])  // Pack FE 2
+            .encrypted_u32(dest.state_enc_ciphertexts[3]); // Pack FE 3
+
+        // WormholeInput: 2 fields
+        builder = builder
+            .x25519_pubkey(wormhole_pubkey)
+            .plaintext_u128(wormhole_nonce)
+            .encrypted_u64(extract_ct_unchecked(&wormhole_cts, 0))  // player_id
+            .encrypted_u32(extract_ct_unchecked(&wormhole_cts, 1)); // ships_to_jump
+
+        // Plaintext params: lazy generation timing sourced on-chain
+        builder = builder
+            .plaintext_u64(clock.slot)
+            .plaintext_u64(game.game_speed)
+            .plaintext_u64(source.last_updated_slot)
+            .plaintext_u64(dest.last_updated_slot);
+        // Payer's key words: the circuit checks the claimed player_id against them
+        for word in pubkey_words(&ctx.accounts.payer.key()) {
+            builder = builder.plaintext_u64(word);
+        }
+
+        let args = builder.build();
+
+        let source_pda = ctx.accounts.source_body.key();
+        let dest_pda = ctx.accounts.dest_body.key();
+
+        queue_computation(
+            ctx.accounts,
+            computation_offset,
+            args,
+            vec![WormholeJumpCallback::callback_ix(
+                computation_offset,
+                &ctx.accounts.mxe_account,
+                &[
+                    CallbackAccount {
+                        pubkey: source_pda,
+                        is_writable: true,
+                    },
+                    CallbackAccount {
+                        pubkey: dest_pda,
+                        is_writable: true,
+                    },
+                ],
+            )?],
+            1,
+            0,
+        )?;
+
+        Ok(())
+    }
+
+    #[arcium_callback(encrypted_ix = "wormhole_jump")]
+    pub fn wormhole_jump_callback(
+        ctx: Context<WormholeJumpCallback>,
+        output: SignedComputationOutputs<WormholeJumpOutput>,
+    ) -> Result<()> {
+        let o = match output.verify_output(
+            &ctx.accounts.cluster_account,
+            &ctx.accounts.computation_account,
+        ) {
+            Ok(o) => o,
+            Err(e) => {
+                msg!("wormhole_jump verify_output FAILED: {:?}", e);
+                return Err(computation_failed(
+                    "wormhole_jump",
+                    ctx.accounts.computation_account.key(),
+                    map_arcium_error(&e),
+                ));
+            }
+        };
+
+        // Output tuple: (Enc<Shared, PlanetState>, Enc<Shared, PlanetState>)
+        let enc_source = &o.field_0.field_0;
+        let enc_dest = &o.field_0.field_1;
+        let slot = Clock::get()?.slot;
+
+        let source = &mut ctx.accounts.source_body;
+        source.state_enc_pubkey = enc_source.encryption_key;
+        source.state_enc_nonce = enc_source.nonce.to_le_bytes();
+        let mut i = 0;
+        while i < PLANET_STATE_FIELDS {
+            source.state_enc_ciphertexts[i] = enc_source.ciphertexts[i];
+            i += 1;
+        }
+        source.last_updated_slot = slot;
+
+        let dest = &mut ctx.accounts.dest_body;
+        dest.state_enc_pubkey = enc_dest.encryption_key;
+        dest.state_enc_nonce = enc_dest.nonce.to_le_bytes();
+        let mut j = 0;
+        while j < PLANET_STATE_FIELDS {
+            dest.state_enc_ciphertexts[j] = enc_dest.ciphertexts[j];
+            j += 1;
+        }
+        dest.last_updated_slot = slot;
+
+        Ok(())
+    }
+
+    // -----------------------------------------------------------------------
+    // Queue comet_drift
+    // Planet state passed inline as ciphertexts. Permissionless, at most once
+    // per comet_drift_interval_slots since the planet's last update. The roll
+    // is seeded from the start of the current drift epoch
+    // (slot / comet_drift_interval_slots), so a caller can choose whether an
+    // epoch's drift happens but not its outcome.
+    // Plaintext: current_slot, epoch_slot, game_id, planet_hash prefix
+    // (entropy) plus game_speed and last_updated_slot (lazy generation).
+    // Output: PlanetState
+    // -----------------------------------------------------------------------
+
+    pub fn queue_comet_drift(
+        ctx: Context<QueueCometDrift>,
+    ) -> Result<()> {
+        let game = &ctx.accounts.game;
+        let clock = Clock::get()?;
+        require!(!game.paused, ErrorCode::GamePaused);
+        require!(clock.slot >= game.start_slot, ErrorCode::GameNotStarted);
+        require!(clock.slot < game.end_slot, ErrorCode::GameEnded);
+        require!(!game.ended_early, ErrorCode::GameEnded);
+        require!(game.comet_drift_interval_slots > 0, ErrorCode::CometDriftDisabled);
+
+        let body = &ctx.accounts.celestial_body;
+        // Migrated bodies have no pre-comet gen stats to rebuild from
+        require!(body.version > MIGRATED_VERSION, ErrorCode::InvalidAccountVersion);
+        require!(
+            clock.slot >= body.last_updated_slot.saturating_add(game.comet_drift_interval_slots),
+            ErrorCode::CometDriftNotReady
+        );
+        let epoch_slot = clock.slot - clock.slot % game.comet_drift_interval_slots;
+
+        // Resources are brought up to now, so landed moves must be flushed first
+        let pending = &ctx.accounts.pending_moves;
+        if !pending.moves.is_empty() {
+            require!(
+                pending.moves[0].landing_slot > clock.slot,
+                ErrorCode::MustFlushFirst
+            );
+        }
+
+        let computation_offset = ctx
+            .accounts
+            .offset_tracker
+            .advance(ctx.accounts.game.game_id, ctx.accounts.payer.key())?;
+
+        ctx.accounts.sign_pda_account.bump = ctx.bumps.sign_pda_account;
+
+        let planet_hash_prefix = u64::from_le_bytes(
+            body.planet_hash[..8]
+                .try_into()
+                .map_err(|_| ErrorCode::InvalidPlanetHash)?,
+        );
+
+        // Enc<Shared, PlanetState> — all inline
+        // planet_input.owner re-encrypts output (no separate planet_key needed)
+        let args = ArgBuilder::new()
+            .x25519_pubkey(body.state_enc_pubkey)
+            .plaintext_u128(u128::from_le_bytes(body.state_enc_nonce))
+            .encrypted_u32(body.state_enc_ciphertexts[0])  // Pack FE 0
+            .encrypted_u32(body.state_enc_ciphertexts[1])  // Pack FE 1
+            .encrypted_u32(body.state_enc_ciphertexts[2])  // Pack FE 2
+            .encrypted_u32(body.state_enc_ciphertexts[3])  // Pack FE 3
+            // Plaintext params: drift entropy and lazy generation, sourced on-chain
+            .plaintext_u64(clock.slot)
+            .plaintext_u64(epoch_slot)
+            .plaintext_u64(game.game_id)
+            .plaintext_u64(planet_hash_prefix)
+            .plaintext_u64(game.game_speed)
+            .plaintext_u64(body.last_updated_slot)
+            .build();
+
+        let body_pda = ctx.accounts.celestial_body.key();
+
+        queue_computation(
+            ctx.accounts,
+            computation_offset,
+            args,
+            vec![CometDriftCallback::callback_ix(
+                computation_offset,
+                &ctx.accounts.mxe_account,
+                &[CallbackAccount {
+                    pubkey: body_pda,
+                    is_writable: true,
+                }],
+            )?],
+            1,
+            0,
+        )?;
+
+        Ok(())
+    }
+
+    #[arcium_callback(encrypted_ix = "comet_drift")]
+    pub fn comet_drift_callback(
+        ctx: Context<CometDriftCallback>,
+        output: SignedComputationOutputs<CometDriftOutput>,
+    ) -> Result<()> {
+        let o = match output.verify_output(
+            &ctx.accounts.cluster_account,
+            &ctx.accounts.computation_account,
+        ) {
+            Ok(o) => o,
+            Err(e) => {
+                msg!("comet_drift verify_output FAILED: {:?}", e);
+                return Err(computation_failed(
+                    "comet_drift",
+                    ctx.accounts.computation_account.key(),
+                    map_arcium_error(&e),
+                ));
+            }
+        };
+
+        // Output: Enc<Shared, PlanetState>
+        let enc_state = &o.field_0;
+
+        let planet = &mut ctx.accounts.celestial_body;
+        planet.state_enc_pubkey = enc_state.encryption_key;
+        planet.state_enc_nonce = enc_state.nonce.to_le_bytes();
+        let mut i = 0;
+        while i < PLANET_STATE_FIELDS {
+            planet.state_enc_ciphertexts[i] = enc_state.ciphertexts[i];
+            i += 1;
+        }
+        planet.last_updated_slot = Clock::get()?.slot;
+
+        Ok(())
+    }
+
+    // -----------------------------------------------------------------------
+    // Queue transfer_owner
+    // Hands a planet to an ally without combat. Both players must be members
+    // of the passed alliance; the new owner doesn't need to have spawned.
+    // transfer_cts = 2 * 32 (TransferOwnerInput: player_id, new_owner_id).
+    // Plaintext: current_slot, game_speed, last_updated_slot.
+    // Output: (PlanetState, u8 success)
+    // -----------------------------------------------------------------------
+
+    pub fn queue_transfer_owner(
+        ctx: Context<QueueTransferOwner>,
+        transfer_cts: Vec<u8>,    // 2 * 32
+        transfer_pubkey: [u8; 32],
+        transfer_nonce: u128,
+    ) -> Result<()> {
+        require!(transfer_cts.len() == 2 * 32, ErrorCode::TransferOwnerFailed);
+
+        let game = &ctx.accounts.game;
+        let clock = Clock::get()?;
+        require!(!game.paused, ErrorCode::GamePaused);
+        require!(clock.slot >= game.start_slot, ErrorCode::GameNotStarted);
+        require!(clock.slot < game.end_slot, ErrorCode::GameEnded);
+        require!(!game.ended_early, ErrorCode::GameEnded);
+
+        let alliance = &ctx.accounts.alliance;
+        require!(alliance.game_id == game.game_id, ErrorCode::NotAllianceMember);
+        require!(
+            alliance.members.contains(&ctx.accounts.payer.key())
+                && alliance.members.contains(&ctx.accounts.new_owner_player.owner),
+            ErrorCode::NotAllianceMember
+        );
+
+        // Landed moves could change ownership, so they must be resolved first
+        let pending = &ctx.accounts.pending_moves;
+        if !pending.moves.is_empty() {
+            require!(
+                pending.moves[0].landing_slot > clock.slot,
+                ErrorCode::MustFlushFirst
+            );
+        }
+
+        let computation_offset = ctx
+            .accounts
+            .offset_tracker
+            .advance(ctx.accounts.game.game_id, ctx.accounts.payer.key())?;
+
+        ctx.accounts.sign_pda_account.bump = ctx.bumps.sign_pda_account;
+
+        // Enc<Shared, PlanetState> — all inline
+        let body = &ctx.accounts.celestial_body;
+        let mut builder = ArgBuilder::new()
+            .x25519_pubkey(body.state_enc_pubkey)
+            .plaintext_u128(u128::from_le_bytes(body.state_enc_nonce))
+            .encrypted_u32(body.state_enc_ciphertexts[0])  // Pack FE 0
+            .encrypted_u32(body.state_enc_ciphertexts[1])  // Pack FE 1
+            .encrypted_u32(body.state_enc_ciphertexts[2])  // Pack FE 2
+            .encrypted_u32(body.state_enc_ciphertexts[3])  // Pack FE 3
+            // TransferOwnerInput: 2 fields
+            // planet_input.owner re-encrypts output (no separate planet_key needed)
+            .x25519_pubkey(transfer_pubkey)
+            .plaintext_u128(transfer_nonce)
+            .encrypted_u64(extract_ct_unchecked(&transfer_cts, 0))  // player_id
+            .encrypted_u64(extract_ct_unchecked(&transfer_cts, 1))  // new_owner_id
+            // Plaintext params: lazy generation, sourced on-chain
+            .plaintext_u64(clock.slot)
+            .plaintext_u64(game.game_speed)
+            .plaintext_u64(body.last_updated_slot);
+        // Receiver's key words: the circuit derives the new owner id from them
+        for word in pubkey_words(&ctx.accounts.new_owner_player.owner) {
+            builder = builder.plaintext_u64(word);
+        }
+        // Payer's key words: the circuit checks the claimed player_id against them
+        for word in pubkey_words(&ctx.accounts.payer.key()) {
+            builder = builder.plaintext_u64(word);
+        }
+        let args = builder.build();
+
+        let body_pda = ctx.accounts.celestial_body.key();
+        let player_pda = ctx.accounts.player.key();
+        let new_owner_pda = ctx.accounts.new_owner_player.key();
+
+        queue_computation(
+            ctx.accounts,
+            computation_offset,
+            args,
+            vec![TransferOwnerCallback::callback_ix(
+                computation_offset,
+                &ctx.accounts.mxe_account,
+                &[
+                    CallbackAccount {
+                        pubkey: body_pda,
+                        is_writable: true,
+                    },
+                    CallbackAccount {
+                        pubkey: player_pda,
+                        is_writable: false,
+                    },
+                    CallbackAccount {
+                        pubkey: new_owner_pda,
+                        is_writable: false,
+                    },
+                ],
+            )?],
+            1,
+            0,
+        )?;
+
+        Ok(())
+    }
+
+    #[arcium_callback(encrypted_ix = "transfer_owner")]
+    pub fn transfer_owner_callback(
+        ctx: Context<TransferOwnerCallback>,
+        output: SignedComputationOutputs<TransferOwnerOutput>,
+    ) -> Result<()> {
+        let o = match output.verify_output(
+            &ctx.accounts.cluster_account,
+            &ctx.accounts.computation_account,
+        ) {
+            Ok(o) => o,
+            Err(e) => {
+                msg!("transfer_owner verify_output FAILED: {:?}", e);
+                return Err(computation_failed(
+                    "transfer_owner",
+                    ctx.accounts.computation_account.key(),
+                    map_arcium_error(&e),
+                ));
+            }
+        };
+
+        // Output tuple: (Enc<Shared, PlanetState>, u8 success)
+        let enc_state = &o.field_0.field_0;
+        let success = o.field_0.field_1;
+
+        let planet = &mut ctx.accounts.celestial_body;
+        planet.state_enc_pubkey = enc_state.encryption_key;
+        planet.state_enc_nonce = enc_state.nonce.to_le_bytes();
+        let mut i = 0;
+        while i < PLANET_STATE_FIELDS {
+            planet.state_enc_ciphertexts[i] = enc_state.ciphertexts[i];
+            i += 1;
+        }
+        planet.last_updated_slot = Clock::get()?.slot;
+
+        emit!(OwnershipTransferredEvent {
+            planet_hash: planet.planet_hash,
+            from: ctx.accounts.player.owner,
+            to: ctx.accounts.new_owner_player.owner,
+            success: success == 1,
+        });
+
+        Ok(())
+    }
+
+    // -----------------------------------------------------------------------
+    // Broadcast
+    // -----------------------------------------------------------------------
+
+    pub fn broadcast(
+        ctx: Context<Broadcast>,
+        _game_id: u64,
+        x: i64,
+        y: i64,
+        planet_hash: [u8; 32],
+    ) -> Result<()> {
+        require_player_or_spectator(
+            ctx.accounts.broadcaster.key(),
+            ctx.accounts.player.is_some(),
+            ctx.accounts.spectator_registry.as_deref(),
+        )?;
+
+        let game = &ctx.accounts.game;
+        // Only planets that went through queue_init_planet can be broadcast
+        let body = &ctx.accounts.celestial_body;
+        require!(body.planet_hash == planet_hash, ErrorCode::InvalidPlanetHash);
+        let computed = compute_planet_hash(x, y, game.game_id, body.hash_rounds, game.hash_algorithm);
+        require!(computed == planet_hash, ErrorCode::InvalidPlanetHash);
+
+        emit!(BroadcastEvent {
+            x,
+            y,
+            game_id: game.game_id,
+            planet_hash,
+            broadcaster: ctx.accounts.broadcaster.key(),
+            win_condition: game.win_condition,
+            game_speed: game.game_speed,
+            last_updated_slot: body.last_updated_slot,
+        });
+
+        Ok(())
+    }
+
+    /// Broadcast up to MAX_BROADCAST_ENTRIES planets in one transaction.
+    /// remaining_accounts: each entry's planet PDA, in entry order. As with
+    /// broadcast, only initialized planets can be broadcast.
+    /// All-or-nothing: the first invalid entry fails the whole call.
+    pub fn broadcast_bulk(
+        ctx: Context<BroadcastBulk>,
+        _game_id: u64,
+        entries: Vec<BroadcastEntry>,
+    ) -> Result<()> {
+        require!(
+            !entries.is_empty() && entries.len() <= MAX_BROADCAST_ENTRIES,
+            ErrorCode::InvalidBroadcastEntries
+        );
+        require_player_or_spectator(
+            ctx.accounts.broadcaster.key(),
+            ctx.accounts.player.is_some(),
+            ctx.accounts.spectator_registry.as_deref(),
+        )?;
+
+        require!(
+            ctx.remaining_accounts.len() == entries.len(),
+            ErrorCode::InvalidBroadcastEntries
+        );
+
+        let game = &ctx.accounts.game;
+        for (i, (entry, acc)) in entries.iter().zip(ctx.remaining_accounts.iter()).enumerate() {
+            let (expected_pda, _) = planet_pda(ctx.program_id, game.game_id, &entry.planet_hash);
+            let initialized = acc.key() == expected_pda
+                && acc.owner == &crate::ID
+                && EncryptedCelestialBody::try_deserialize(&mut &acc.try_borrow_data()?[..])
+                    .is_ok_and(|body| {
+                        body.planet_hash == entry.planet_hash
+                            && compute_planet_hash(
+                                entry.x,
+                                entry.y,
+                                game.game_id,
+                                body.hash_rounds,
+                                game.hash_algorithm,
+                            ) == entry.planet_hash
+                    });
+            if !initialized {
+                msg!("broadcast_bulk: entry {} has an invalid or uninitialized planet", i);
+                return Err(ErrorCode::InvalidPlanetHash.into());
+            }
+        }
+
+        emit!(BroadcastBulkEvent {
+            game_id: game.game_id,
+            entries,
+            broadcaster: ctx.accounts.broadcaster.key(),
+        });
+
+        Ok(())
+    }
+
+    /// Read-only sanity check: does (x, y) hash to planet_hash and lie on the map?
+    /// Never fails on a mismatch; the result is reported in the event.
+    pub fn verify_coordinates(
+        ctx: Context<VerifyCoordinates>,
+        _game_id: u64,
+        x: i64,
+        y: i64,
+        planet_hash: [u8; 32],
+    ) -> Result<()> {
+        let game = &ctx.accounts.game;
+        let computed = compute_planet_hash(x, y, game.game_id, game.hash_rounds, game.hash_algorithm);
+        let half = game.map_diameter / 2;
+        let in_bounds = x.unsigned_abs() <= half && y.unsigned_abs() <= half;
+
+        emit!(CoordinatesVerifiedEvent {
+            x,
+            y,
+            game_id: game.game_id,
+            planet_hash,
+            valid: computed == planet_hash && in_bounds,
+        });
+
+        Ok(())
+    }
+
+    /// Publicly reveal a planet's coordinates, e.g. to back a RaceToCenter
+    /// claim. The first player to reveal a planet owns its CoordinateProof.
+    pub fn create_coordinate_proof(
+        ctx: Context<CreateCoordinateProof>,
+        _game_id: u64,
+        planet_hash: [u8; 32],
+        x: i64,
+        y: i64,
+    ) -> Result<()> {
+        let game = &ctx.accounts.game;
+        let hash_rounds = ctx
+            .accounts
+            .celestial_body
+            .as_ref()
+            .map_or(game.hash_rounds, |body| body.hash_rounds);
+        let computed = compute_planet_hash(x, y, game.game_id, hash_rounds, game.hash_algorithm);
+        require!(computed == planet_hash, ErrorCode::InvalidPlanetHash);
+
+        let slot = Clock::get()?.slot;
+        let proof = &mut ctx.accounts.coordinate_proof;
+        proof.planet_hash = planet_hash;
+        proof.x = x;
+        proof.y = y;
+        proof.owner = ctx.accounts.player.owner;
+        proof.revealed_slot = slot;
+
+        emit!(CoordinateRevealedEvent {
+            game_id: game.game_id,
+            planet_hash,
+            x,
+            y,
+            owner: proof.owner,
+            revealed_slot: slot,
+        });
+
+        Ok(())
+    }
+
+    /// Win a RaceToCenter game with a CoordinateProof that lies closer than
+    /// min_spawn_distance to the map center (0, 0), measured with the game's
+    /// distance_method. Queues a strict check_ownership of the proof's planet,
+    /// bound to the payer key; its callback declares the winner.
+    /// ownership_cts = 1 * 32 (OwnershipInput: player_id).
+    pub fn claim_race_to_center_victory(
+        ctx: Context<ClaimRaceToCenterVictory>,
+        _game_id: u64,
+        _planet_hash: [u8; 32],
+        ownership_cts: Vec<u8>,   // 1 * 32
+        ownership_pubkey: [u8; 32],
+        ownership_nonce: u128,
+    ) -> Result<()> {
+        require!(ownership_cts.len() == 32, ErrorCode::InvalidOwnershipInput);
+
+        let slot = Clock::get()?.slot;
+        let game = &ctx.accounts.game;
+        let min_spawn_distance = match game.win_condition {
+            WinCondition::RaceToCenter { min_spawn_distance } => min_spawn_distance,
+            _ => return Err(ErrorCode::NotRaceToCenter.into()),
+        };
+        require!(!game.paused, ErrorCode::GamePaused);
+        require!(slot >= game.start_slot, ErrorCode::GameNotStarted);
+        require!(slot < game.end_slot, ErrorCode::GameEnded);
+        require!(game.winner.is_none(), ErrorCode::WinnerAlreadyDeclared);
+
+        let proof = &ctx.accounts.coordinate_proof;
+        let distance = game_math::compute_distance(proof.x, proof.y, 0, 0, game.distance_method);
+        require!(distance < min_spawn_distance, ErrorCode::NotNearCenter);
+
+        let computation_offset = ctx
+            .accounts
+            .offset_tracker
+            .advance(ctx.accounts.game.game_id, ctx.accounts.payer.key())?;
+
+        ctx.accounts.sign_pda_account.bump = ctx.bumps.sign_pda_account;
+
+        // Enc<Shared, PlanetState> — all inline
+        let body = &ctx.accounts.celestial_body;
+        let mut builder = ArgBuilder::new()
+            .x25519_pubkey(body.state_enc_pubkey)
+            .plaintext_u128(u128::from_le_bytes(body.state_enc_nonce))
+            .encrypted_u32(body.state_enc_ciphertexts[0])  // Pack FE 0
+            .encrypted_u32(body.state_enc_ciphertexts[1])  // Pack FE 1
+            .encrypted_u32(body.state_enc_ciphertexts[2])  // Pack FE 2
+            .encrypted_u32(body.state_enc_ciphertexts[3])  // Pack FE 3
+            // OwnershipInput: 1 field (player_id)
+            .x25519_pubkey(ownership_pubkey)
+            .plaintext_u128(ownership_nonce)
+            .encrypted_u64(extract_ct_unchecked(&ownership_cts, 0)); // player_id
+
+        // Plaintext param: payer key, so only the payer's own id proves ownership
+        for word in pubkey_words(&ctx.accounts.payer.key()) {
+            builder = builder.plaintext_u64(word);
+        }
+
+        let args = builder
+            .plaintext_u64(1) // strict: a mismatched id reads as not owner
+            .build();
+
+        let player_pda = ctx.accounts.player.key();
+        let game_pda = ctx.accounts.game.key();
+        let profile_pda = ctx.accounts.player_profile.as_ref().map(|p| p.key());
+
+        queue_computation(
+            ctx.accounts,
+            computation_offset,
+            args,
+            vec![CheckOwnershipCallback::callback_ix(
+                computation_offset,
+                &ctx.accounts.mxe_account,
+                &[
+                    CallbackAccount {
+                        pubkey: player_pda,
+                        is_writable: true,
+                    },
+                    CallbackAccount {
+                        pubkey: game_pda,
+                        is_writable: true,
+                    },
+                    optional_callback_account(profile_pda),
+                ],
+            )?],
+            1,
+            0,
+        )?;
+
+        Ok(())
+    }
+
+    /// Win a PointsBurning game early once the player's points reach
+    /// points_to_win. Ends the game: later queue_* instructions fail.
+    pub fn claim_points_victory(ctx: Context<ClaimPointsVictory>, _game_id: u64) -> Result<()> {
+        let slot = Clock::get()?.slot;
+        let game = &mut ctx.accounts.game;
+        require!(
+            matches!(game.win_condition, WinCondition::PointsBurning { .. }),
+            ErrorCode::NotPointsBurning
+        );
+        require!(slot >= game.start_slot, ErrorCode::GameNotStarted);
+        require!(slot < game.end_slot, ErrorCode::GameEnded);
+        require!(game.winner.is_none(), ErrorCode::WinnerAlreadyDeclared);
+        require!(
+            game.points_to_win > 0 && ctx.accounts.player.points >= game.points_to_win,
+            ErrorCode::NotEnoughPoints
+        );
+
+        let winner = ctx.accounts.player.owner;
+        game.winner = Some(winner);
+        game.ended_early = true;
+        if let Some(profile) = ctx.accounts.player_profile.as_mut() {
+            profile.total_wins = profile.total_wins.saturating_add(1);
+        }
+        emit!(VictoryClaimedEvent {
+            game_id: game.game_id,
+            winner,
+            slot,
+        });
+
+        Ok(())
+    }
+
+    /// Close a planet that init_planet found to be dead space. The MPC result
+    /// is only visible to the observer, so the closer reveals (x, y) and the
+    /// program re-derives the noise from them. Rent of the planet, its
+    /// pending-moves list and combat log goes back to the body's initializer,
+    /// who paid for them.
+    pub fn close_dead_planet(
+        ctx: Context<CloseDeadPlanet>,
+        _game_id: u64,
+        planet_hash: [u8; 32],
+        proof_x: i64,
+        proof_y: i64,
+    ) -> Result<()> {
+        let game = &ctx.accounts.game;
+        let hash_rounds = ctx.accounts.celestial_body.hash_rounds;
+        let computed = compute_planet_hash(proof_x, proof_y, game.game_id, hash_rounds, game.hash_algorithm);
+        require!(computed == planet_hash, ErrorCode::InvalidPlanetHash);
+
+        let property_hash = game_math::property_hash(proof_x, proof_y, game.game_id, hash_rounds);
+        require!(
+            game_math::is_dead_space(&property_hash, &game.noise_thresholds),
+            ErrorCode::NotDeadSpace
+        );
+
+        let pending = &ctx.accounts.pending_moves;
+        require!(pending.moves.is_empty(), ErrorCode::PendingMovesRemaining);
+
+        emit!(DeadPlanetClosedEvent {
+            game_id: game.game_id,
+            planet_hash,
+            x: proof_x,
+            y: proof_y,
+            closer: ctx.accounts.closer.key(),
+        });
+
+        Ok(())
+    }
+
+    /// Re-emit the game's public configuration for indexers that missed
+    /// create_game. Changes no state.
+    pub fn get_game_config(ctx: Context<GetGameConfig>, _game_id: u64) -> Result<()> {
+        let game = &ctx.accounts.game;
+        emit!(GameConfigEvent {
+            game_id: game.game_id,
+            map_diameter: game.map_diameter,
+            game_speed: game.game_speed,
+            start_slot: game.start_slot,
+            end_slot: game.end_slot,
+            win_condition: game.win_condition,
+            noise_thresholds: game.noise_thresholds,
+            hash_rounds: game.hash_rounds,
+            hash_algorithm: game.hash_algorithm,
+            distance_method: game.distance_method,
+        });
+
+        Ok(())
+    }
+
+    /// Report the protocol version so clients can detect a mismatched
+    /// deployment before submitting transactions. Changes no state.
+    pub fn get_program_version(_ctx: Context<GetProgramVersion>) -> Result<()> {
+        emit!(ProgramVersionEvent {
+            version: PROGRAM_VERSION,
+            min_compatible_client_version: MIN_COMPATIBLE_CLIENT_VERSION,
+        });
+
+        Ok(())
+    }
+
+    /// Admin-only: move a planet's last_updated_slot forward after a
+    /// validator outage or slot skip, so the resumed game doesn't pay out
+    /// the whole gap as lazy generation. Never rewinds and never passes the
+    /// current slot.
+    pub fn admin_reset_planet_timestamp(
+        ctx: Context<AdminResetPlanetTimestamp>,
+        _game_id: u64,
+        planet_hash: [u8; 32],
+        new_slot: u64,
+    ) -> Result<()> {
+        let clock = Clock::get()?;
+        let body = &mut ctx.accounts.celestial_body;
+        require!(
+            new_slot <= clock.slot && new_slot >= body.last_updated_slot,
+            ErrorCode::InvalidPlanetTimestamp
+        );
+
+        let old_slot = body.last_updated_slot;
+        body.last_updated_slot = new_slot;
+
+        emit!(PlanetTimestampResetEvent {
+            game_id: ctx.accounts.game.game_id,
+            planet_hash,
+            old_slot,
+            new_slot,
+        });
+
+        Ok(())
+    }
+
+    // -----------------------------------------------------------------------
+    // Migration
+    // -----------------------------------------------------------------------
+
+    /// Rewrite a version-0 EncryptedCelestialBody (LegacyCelestialBody) in
+    /// the current layout. Fields added since then get their defaults.
+    pub fn migrate_celestial_body(
+        ctx: Context<MigrateCelestialBody>,
+        _game_id: u64,
+        _planet_hash: [u8; 32],
+    ) -> Result<()> {
+        let body = ctx.accounts.celestial_body.to_account_info();
+        let legacy = {
+            let data = body.try_borrow_data()?;
+            require!(
+                data.len() == LEGACY_CELESTIAL_BODY_SIZE
+                    && data[..8] == *EncryptedCelestialBody::DISCRIMINATOR,
+                ErrorCode::InvalidAccountVersion
+            );
+            LegacyCelestialBody::deserialize(&mut &data[8..])?
+        };
+
+        // Admin tops up rent for the added fields
+        let new_size = EncryptedCelestialBody::MAX_SIZE;
+        let rent_due = Rent::get()?
+            .minimum_balance(new_size)
+            .saturating_sub(body.lamports());
+        if rent_due > 0 {
+            anchor_lang::system_program::transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    anchor_lang::system_program::Transfer {
+                        from: ctx.accounts.admin.to_account_info(),
+                        to: body.clone(),
+                    },
+                ),
+                rent_due,
+            )?;
+        }
+        body.resize(new_size)?;
+
+        let mut migrated: EncryptedCelestialBody = legacy.into();
+        // The original layout kept no initializer; dead-space rent goes to the
+        // admin who paid for the migration
+        migrated.initializer = ctx.accounts.admin.key();
+        // The original layout predates update_hash_rounds, so the body was
+        // created under the game's first hash_rounds value
+        let game = &ctx.accounts.game;
+        migrated.hash_rounds = if game.hash_rounds_updates > 0 {
+            game.prior_hash_rounds[0]
+        } else {
+            game.hash_rounds
+        };
+        let mut data = body.try_borrow_mut_data()?;
+        data.fill(0);
+        migrated.try_serialize(&mut &mut data[..])?;
+        Ok(())
+    }
+
+    /// Rewrite a Game stored in the original layout (LegacyGame) in the
+    /// current one. Each 8-bit threshold t becomes t << 8, which keeps the
+    /// game's body distribution unchanged under the 16-bit noise values.
+    pub fn migrate_game(ctx: Context<MigrateGame>, _game_id: u64) -> Result<()> {
+        let game_info = ctx.accounts.game.to_account_info();
+        let legacy = {
+            let data = game_info.try_borrow_data()?;
+            require!(
+                data.len() == LEGACY_GAME_SIZE && data[..8] == *Game::DISCRIMINATOR,
+                ErrorCode::InvalidAccountVersion
+            );
+            LegacyGame::deserialize(&mut &data[8..])?
+        };
+        require!(legacy.admin == ctx.accounts.admin.key(), ErrorCode::Unauthorized);
+
+        // Admin tops up rent for the wider thresholds
+        let new_size = 8 + Game::INIT_SPACE;
+        let rent_due = Rent::get()?
+            .minimum_balance(new_size)
+            .saturating_sub(game_info.lamports());
+        if rent_due > 0 {
+            anchor_lang::system_program::transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    anchor_lang::system_program::Transfer {
+                        from: ctx.accounts.admin.to_account_info(),
+                        to: game_info.clone(),
+                    },
+                ),
+                rent_due,
+            )?;
+        }
+        game_info.resize(new_size)?;
+
+        let game: Game = legacy.into();
+        validate_noise_thresholds(&game.noise_thresholds)?;
+        let mut data = game_info.try_borrow_mut_data()?;
+        data.fill(0);
+        game.try_serialize(&mut &mut data[..])?;
+        Ok(())
+    }
+
+    // -----------------------------------------------------------------------
+    // Abandon moves
+    // -----------------------------------------------------------------------
+
+    /// Withdraw up to MAX_ABANDON_MOVES of the caller's own moves that have
+    /// not landed yet. remaining_accounts: one writable PendingMoveAccount
+    /// per entry of `move_ids`, in the same order. Each entry is dropped from
+    /// the target's pending list and its account closed to the payer.
+    pub fn abandon_move_batch(
+        ctx: Context<AbandonMoveBatch>,
+        game_id: u64,
+        target_planet_hash: [u8; 32],
+        move_ids: Vec<u64>,
+    ) -> Result<()> {
+        require!(
+            !move_ids.is_empty() && move_ids.len() <= MAX_ABANDON_MOVES,
+            ErrorCode::InvalidMoveBatch
+        );
+        require!(
+            ctx.remaining_accounts.len() == move_ids.len(),
+            ErrorCode::InvalidMoveBatch
+        );
+
+        let clock = Clock::get()?;
+        let payer = ctx.accounts.payer.to_account_info();
+        let pending = &mut ctx.accounts.pending_moves;
+
+        for (acc, &move_id) in ctx.remaining_accounts.iter().zip(move_ids.iter()) {
+            let (expected_pda, _) = move_pda(ctx.program_id, game_id, &target_planet_hash, move_id);
+            require!(acc.key() == expected_pda, ErrorCode::InvalidMoveBatch);
+            require!(acc.owner == ctx.program_id && acc.is_writable, ErrorCode::InvalidMoveBatch);
+
+            let move_acc = {
+                let data = acc.try_borrow_data()?;
+                PendingMoveAccount::try_deserialize(&mut &data[..])?
+            };
+            require!(move_acc.payer == payer.key(), ErrorCode::Unauthorized);
+            require!(move_acc.landing_slot > clock.slot, ErrorCode::MoveAlreadyLanded);
+
+            let idx = pending
+                .moves
+                .binary_search_by_key(&(move_acc.landing_slot, move_id), |e| {
+                    (e.landing_slot, e.move_id)
+                })
+                .map_err(|_| ErrorCode::InvalidMoveBatch)?;
+            pending.moves.remove(idx);
+
+            // Close the move account, refunding its rent to the payer
+            **payer.try_borrow_mut_lamports()? += acc.lamports();
+            **acc.try_borrow_mut_lamports()? = 0;
+            acc.assign(&System::id());
+            acc.resize(0)?;
+        }
+
+        pending.move_count = pending.moves.len() as u16;
+        pending.capacity = pending.capacity_for(pending.moves.len());
+        Ok(())
+    }
+
+    // -----------------------------------------------------------------------
+    // Cleanup
+    // -----------------------------------------------------------------------
+
+    /// remaining_accounts (optional): the game's Leaderboard PDA (writable),
+    /// closed to the closer in the same transaction.
+    pub fn cleanup_game(ctx: Context<CleanupGame>, game_id: u64) -> Result<()> {
+        let game = &ctx.accounts.game;
+        let clock = Clock::get()?;
+        require!(clock.slot > game.end_slot, ErrorCode::GameNotEnded);
+
+        if let Some(acc) = ctx.remaining_accounts.first() {
+            let (expected_pda, _) = leaderboard_pda(ctx.program_id, game_id);
+            require!(acc.key() == expected_pda, ErrorCode::InvalidLeaderboard);
+            require!(acc.owner == ctx.program_id && acc.is_writable, ErrorCode::InvalidLeaderboard);
+
+            let leaderboard = {
+                let data = acc.try_borrow_data()?;
+                Leaderboard::try_deserialize(&mut &data[..])?
+            };
+            require!(leaderboard.game_id == game_id, ErrorCode::InvalidLeaderboard);
+
+            emit!(LeaderboardClosedEvent {
+                game_id,
+                final_entries: leaderboard.entries,
+            });
+
+            // Close the leaderboard, refunding its rent to the closer
+            let closer = ctx.accounts.closer.to_account_info();
+            **closer.try_borrow_mut_lamports()? += acc.lamports();
+            **acc.try_borrow_mut_lamports()? = 0;
+            acc.assign(&System::id());
+            acc.resize(0)?;
+        }
+
+        // Last record of the game before its account is closed
+        emit!(GameFinalisedEvent {
+            game_id,
+            winner: game.winner,
+            total_slots: game.end_slot.saturating_sub(game.start_slot),
+            final_player_count: game.current_player_count,
+            final_planet_count: game.planet_count,
+            admin: game.admin,
+        });
+
+        let program_state = &mut ctx.accounts.program_state;
+        program_state.active_game_count = program_state.active_game_count.saturating_sub(1);
+        Ok(())
+    }
+
+    /// Close the shared Arcium signer PDA once every game has been cleaned up.
+    /// The next queue_* instruction recreates it via init_if_needed, and
+    /// callbacks of computations already in flight do not use it.
+    pub fn close_sign_pda(ctx: Context<CloseSignPda>) -> Result<()> {
+        require!(
+            ctx.accounts.program_state.active_game_count == 0,
+            ErrorCode::GamesStillActive
+        );
+        Ok(())
+    }
+
+    /// Self-cleanup refunds the PlayerDeposit to the owner. The admin may
+    /// force-clean once GRACE_PERIOD_SLOTS have passed since end_slot, in
+    /// which case the deposit goes to Game.deposit_recipient. Players of games
+    /// without a deposit (including migrated ones) have no PlayerDeposit.
+    pub fn cleanup_player(ctx: Context<CleanupPlayer>, _game_id: u64) -> Result<()> {
+        let game = &mut ctx.accounts.game;
+        let clock = Clock::get()?;
+        require!(clock.slot > game.end_slot, ErrorCode::GameNotEnded);
+
+        if !game.allow_force_cleanup {
+            require_planets_closed(
+                ctx.program_id,
+                game.game_id,
+                &ctx.accounts.player,
+                ctx.remaining_accounts,
+            )?;
+        }
+
+        let closer = ctx.accounts.closer.key();
+        let refund_to = if closer == ctx.accounts.player.owner {
+            ctx.accounts.owner.to_account_info()
+        } else {
+            require!(closer == game.admin, ErrorCode::Unauthorized);
+            require!(
+                clock.slot > game.end_slot.saturating_add(GRACE_PERIOD_SLOTS),
+                ErrorCode::ForceCleanupTooEarly
+            );
+            ctx.accounts.deposit_recipient.to_account_info()
+        };
+        if let Some(deposit) = ctx.accounts.deposit.as_ref() {
+            deposit.close(refund_to)?;
+        } else {
+            require!(game.security_deposit_lamports == 0, ErrorCode::DepositRequired);
+        }
+
+        game.current_player_count = game.current_player_count.saturating_sub(1);
+
+        if let Some(profile) = ctx.accounts.player_profile.as_mut() {
+            profile.lifetime_points = profile
+                .lifetime_points
+                .saturating_add(ctx.accounts.player.points);
+        }
+
+        emit!(PlayerFinalisedEvent {
+            game_id: game.game_id,
+            player: ctx.accounts.player.owner,
+            final_points: ctx.accounts.player.points,
+            has_spawned: ctx.accounts.player.has_spawned,
+        });
+        emit!(PlayerLeftEvent {
+            game_id: game.game_id,
+            player: ctx.accounts.player.owner,
+            player_count: game.current_player_count,
+        });
+        Ok(())
+    }
+
+    pub fn cleanup_planet(
+        ctx: Context<CleanupPlanet>,
+        _game_id: u64,
+        _planet_hash: [u8; 32],
+    ) -> Result<()> {
+        let game = &ctx.accounts.game;
+        let clock = Clock::get()?;
+        require!(clock.slot > game.end_slot, ErrorCode::GameNotEnded);
+        // Give players time to flush unresolved moves before force-closing
+        require!(
+            ctx.accounts.pending_moves.moves.is_empty()
+                || clock.slot > game.end_slot.saturating_add(GRACE_PERIOD_SLOTS),
+            ErrorCode::PendingMovesRemaining
+        );
+        Ok(())
+    }
+
+    /// Close an orphaned PendingMoveAccount that was never flushed and drop
+    /// its entry from the target planet's PendingMovesMetadata.
+    pub fn cleanup_move_account(
+        ctx: Context<CleanupMoveAccount>,
+        _game_id: u64,
+        _planet_hash: [u8; 32],
+        move_id: u64,
+    ) -> Result<()> {
+        let game = &ctx.accounts.game;
+        let clock = Clock::get()?;
+        require!(clock.slot > game.end_slot, ErrorCode::GameNotEnded);
+
+        let pending = &mut ctx.accounts.pending_moves;
+        pending.moves.retain(|e| e.move_id != move_id);
+        pending.move_count = pending.moves.len() as u16;
+        pending.capacity = pending.capacity_for(pending.moves.len());
+
+        Ok(())
+    }
+
+    /// Admin-only: fold a planet's pending moves into another planet's list
+    /// after an annexation and close the source list to the admin.
+    /// remaining_accounts: one writable PendingMoveAccount per source entry,
+    /// in the order of `source_pending.moves`. Every move must already be
+    /// populated by its process_move callback. Move accounts keep their PDA;
+    /// only `canonical_planet_hash` is rewritten to the destination.
+    pub fn merge_pending_moves(
+        ctx: Context<MergePendingMoves>,
+        game_id: u64,
+        source_planet_hash: [u8; 32],
+        dest_planet_hash: [u8; 32],
+    ) -> Result<()> {
+        require!(
+            source_planet_hash != dest_planet_hash,
+            ErrorCode::InvalidMergeTarget
+        );
+
+        let source = &ctx.accounts.source_pending;
+        require!(
+            ctx.remaining_accounts.len() == source.moves.len(),
+            ErrorCode::InvalidMoveBatch
+        );
+
+        let dest = &mut ctx.accounts.dest_pending;
+        for (acc, entry) in ctx.remaining_accounts.iter().zip(source.moves.iter()) {
+            require!(acc.owner == ctx.program_id && acc.is_writable, ErrorCode::InvalidMoveBatch);
+
+            let mut move_acc = {
+                let data = acc.try_borrow_data()?;
+                PendingMoveAccount::try_deserialize(&mut &data[..])?
+            };
+            // Seed hash comes from the account so moves merged earlier can move again
+            let (expected_pda, _) =
+                move_pda(ctx.program_id, game_id, &move_acc.planet_hash, entry.move_id);
+            require!(
+                acc.key() == expected_pda
+                    && move_acc.game_id == game_id
+                    && move_acc.canonical_planet_hash == source_planet_hash,
+                ErrorCode::InvalidMoveBatch
+            );
+            require!(move_acc.populated, ErrorCode::MergeMovesNotReady);
+
+            // Flush and abandon remove entries by move_id, so ids must stay unique
+            require!(
+                !dest.moves.iter().any(|e| e.move_id == entry.move_id),
+                ErrorCode::DuplicateMoveId
+            );
+            let pos = dest
+                .moves
+                .binary_search_by_key(&(entry.landing_slot, entry.move_id), |e| {
+                    (e.landing_slot, e.move_id)
+                })
+                .unwrap_or_else(|e| e);
+            dest.moves.insert(pos, *entry);
+
+            move_acc.canonical_planet_hash = dest_planet_hash;
+            let mut data = acc.try_borrow_mut_data()?;
+            move_acc.try_serialize(&mut &mut data[..])?;
+        }
+
+        // Keep future ids on the destination clear of the merged ones
+        dest.next_move_id = dest.next_move_id.max(source.next_move_id);
+        dest.move_count = dest.moves.len() as u16;
+        dest.capacity = dest.capacity_for(dest.moves.len());
+
+        emit!(PendingMovesMergedEvent {
+            game_id,
+            source_planet_hash,
+            dest_planet_hash,
+            merged_count: source.moves.len() as u16,
+        });
+
+        Ok(())
+    }
+}
+
+// ===========================================================================
+// Account Structures
+// ===========================================================================
+
+#[account]
+#[derive(InitSpace)]
+pub struct Game {
+    pub admin: Pubkey,
+    pub game_id: u64,
+    pub map_diameter: u64,
+    pub game_speed: u64,
+    pub start_slot: u64,
+    pub end_slot: u64,
+    pub win_condition: WinCondition,
+    pub whitelist: bool,
+    pub server_pubkey: Option<Pubkey>,
+    pub noise_thresholds: NoiseThresholds,
+    /// Number of iterated hash_algorithm rounds for planet hash difficulty.
+    pub hash_rounds: u16,
+    /// hash_rounds values in effect before each update_hash_rounds, oldest first.
+    pub prior_hash_rounds: [u16; MAX_HASH_ROUNDS_UPDATES],
+    /// Entries of prior_hash_rounds in use.
+    pub hash_rounds_updates: u8,
+    /// Cap on queue_process_move calls per player per slot.
+    pub max_moves_per_slot: u8,
+    /// Cap on in-flight moves per target planet (<= MAX_QUEUED_CALLBACKS).
+    pub max_pending_moves: u8,
+    /// Share of arriving ships lost at a Quasar, in basis points (<= 10_000).
+    pub quasar_ship_penalty_bps: u64,
+    /// Min slots between queue_process_move calls from one source planet.
+    pub move_cooldown_slots: u64,
+    /// Maximum players allowed to join; 0 at creation means u32::MAX.
+    pub max_players: u32,
+    /// Players currently registered via init_player.
+    pub current_player_count: u32,
+    /// Planets created so far, counted by init_planet_callback and
+    /// init_spawn_planet_callback; never decremented.
+    pub planet_count: u64,
+    /// Min distance a new spawn must keep from every earlier spawn (0 = off).
+    pub min_spawn_separation: u64,
+    /// Spawns placed while min_spawn_separation is set, not counting respawns
+    /// (which replace the player's entry); at most MAX_SEPARATION_CHECKS + 1,
+    /// since each new one is checked against all earlier ones.
+    pub spawn_count: u8,
+    /// Planet hashes of the first MAX_SEPARATION_CHECKS spawns counted in spawn_count;
+    /// queue_init_spawn_planet must pass every one of them.
+    pub spawn_planet_hashes: [[u8; 32]; MAX_SEPARATION_CHECKS],
+    /// Min distance a spawn must keep from the map center; RaceToCenter's
+    /// min_spawn_distance, 0 (no restriction) for every other win condition.
+    pub min_spawn_distance_from_center: u64,
+    /// Min slots since the planet's last update before queue_comet_drift may run (0 = drift disabled).
+    pub comet_drift_interval_slots: u64,
+    /// Hash function behind compute_planet_hash for this game.
+    pub hash_algorithm: HashAlgorithm,
+    /// Lamports each player locks in a PlayerDeposit at init_player.
+    pub security_deposit_lamports: u64,
+    /// Receives the deposit of players the admin force-cleans (defaults to admin).
+    pub deposit_recipient: Pubkey,
+    /// Slots after a capture during which the captured planet can't launch moves (0 = off)
+    pub capture_cooldown_slots: u64,
+    /// Distance formula used by the process_move, spy_move and init_spawn_planet circuits.
+    pub distance_method: DistanceMethod,
+    /// Bitmask of body types init_spawn_planet accepts: bit 0 = Planet, 1 = Quasar, 2 = SpacetimeRip, 3 = AsteroidBelt.
+    pub valid_spawn_body_types: u8,
+    /// Highest level a planet can be upgraded to (>= 2); upgrades at this level fail in the circuit.
+    pub max_planet_level: u8,
+    /// Minimum ships a process_move must send (>= 1); smaller moves fail in the circuit.
+    pub min_ships_per_move: u64,
+    /// PointsBurning score that lets a player claim victory early via claim_points_victory (0 = disabled, only end_slot matters).
+    pub points_to_win: u64,
+    /// Set once a player satisfies the win condition.
+    pub winner: Option<Pubkey>,
+    /// Set by claim_points_victory: the game is over before end_slot, so
+    /// queue_* instructions are rejected (flushes still land).
+    pub ended_early: bool,
+    /// Admin kill-switch: blocks all queue_* instructions while set.
+    pub paused: bool,
+    /// Set by the admin after the grace period: cleanup_player skips the
+    /// closed-planet check.
+    pub allow_force_cleanup: bool,
+    /// Nominee from propose_admin_transfer, awaiting accept_admin_transfer.
+    pub pending_admin: Option<Pubkey>,
+    /// PROGRAM_VERSION of the program that created (or last migrated) the game.
+    pub account_version: u32,
+}
+
+/// The original Game layout (8-bit noise thresholds, ending at hash_rounds).
+/// Only read by migrate_game; fields added to Game since then are filled with
+/// their defaults on migration.
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace)]
+pub struct LegacyGame {
+    pub admin: Pubkey,
+    pub game_id: u64,
+    pub map_diameter: u64,
+    pub game_speed: u64,
+    pub start_slot: u64,
+    pub end_slot: u64,
+    pub win_condition: WinCondition,
+    pub whitelist: bool,
+    pub server_pubkey: Option<Pubkey>,
+    pub noise_thresholds: LegacyNoiseThresholds,
+    pub hash_rounds: u16,
+}
+
+impl From<LegacyGame> for Game {
+    fn from(g: LegacyGame) -> Self {
+        Game {
+            admin: g.admin,
+            game_id: g.game_id,
+            map_diameter: g.map_diameter,
+            game_speed: g.game_speed,
+            start_slot: g.start_slot,
+            end_slot: g.end_slot,
+            win_condition: g.win_condition,
+            whitelist: g.whitelist,
+            server_pubkey: g.server_pubkey,
+            noise_thresholds: g.noise_thresholds.into(),
+            hash_rounds: g.hash_rounds,
+            prior_hash_rounds: [0u16; MAX_HASH_ROUNDS_UPDATES],
+            hash_rounds_updates: 0,
+            // Legacy games had no move rate limits or player cap
+            max_moves_per_slot: u8::MAX,
+            max_pending_moves: MAX_QUEUED_CALLBACKS as u8,
+            quasar_ship_penalty_bps: 0,
+            move_cooldown_slots: 0,
+            max_players: u32::MAX,
+            // Legacy games didn't count players or planets
+            current_player_count: 0,
+            planet_count: 0,
+            min_spawn_separation: 0,
+            // Legacy games didn't record spawn locations
+            spawn_count: 0,
+            spawn_planet_hashes: [[0u8; 32]; MAX_SEPARATION_CHECKS],
+            min_spawn_distance_from_center: min_spawn_distance_from_center(&g.win_condition),
+            comet_drift_interval_slots: 0,
+            hash_algorithm: HashAlgorithm::Blake3,
+            security_deposit_lamports: 0,
+            deposit_recipient: g.admin,
+            capture_cooldown_slots: 0,
+            distance_method: DistanceMethod::ChebyshevApprox,
+            valid_spawn_body_types: 1,
+            // Legacy games had no level cap
+            max_planet_level: u8::MAX,
+            min_ships_per_move: 1,
+            points_to_win: 0,
+            winner: None,
+            ended_early: false,
+            paused: false,
+            allow_force_cleanup: false,
+            pending_admin: None,
+            account_version: PROGRAM_VERSION,
+        }
+    }
+}
+
+/// Program-wide bookkeeping shared by all games. PDA: ["program_state"].
+#[account]
+#[derive(InitSpace)]
+pub struct ProgramState {
+    /// Games created and not yet closed by cleanup_game.
+    pub active_game_count: u32,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Player {
+    pub owner: Pubkey,
+    pub game_id: u64,
+    pub points: u64,
+    pub has_spawned: bool,
+    /// Slot of the player's most recent queue_process_move.
+    pub last_move_slot: u64,
+    /// Moves queued during last_move_slot (rate limiter).
+    pub moves_this_slot: u8,
+    /// Planet claimed by init_spawn_planet; checked by check_spawn_lost.
+    pub spawn_planet_hash: [u8; 32],
+}
+
+/// Hands out computation offsets for a player's queue_* calls so clients
+/// don't pick their own. Created by init_player, or by the first queue_*
+/// call of a payer who never joined. PDA: ["offset", game_id, owner]
+#[account]
+#[derive(InitSpace)]
+pub struct ComputationOffsetTracker {
+    pub game_id: u64,
+    pub owner: Pubkey,
+    /// Number of offsets handed out so far.
+    pub next_offset: u64,
+}
+
+impl ComputationOffsetTracker {
+    /// Hand out the offset for the current counter value and move on to the
+    /// next one. Also stamps game_id/owner, since queue_* may have just
+    /// created the tracker.
+    pub fn advance(&mut self, game_id: u64, owner: Pubkey) -> Result<u64> {
+        self.game_id = game_id;
+        self.owner = owner;
+        let offset = derive_computation_offset(game_id, &owner, self.next_offset);
+        self.next_offset = self
+            .next_offset
+            .checked_add(1)
+            .ok_or(ErrorCode::ComputationOffsetOverflow)?;
+        Ok(offset)
+    }
+}
+
+/// Security deposit locked by init_player and paid out by cleanup_player.
+/// PDA: ["deposit", game_id, owner]
+#[account]
+#[derive(InitSpace)]
+pub struct PlayerDeposit {
+    pub game_id: u64,
+    pub owner: Pubkey,
+    /// Lamports locked on top of the account's rent.
+    pub amount: u64,
+}
+
+/// Cross-game player stats. PDA: ["profile", owner]. Survives cleanup_player.
+#[account]
+#[derive(InitSpace)]
+pub struct PlayerProfile {
+    pub owner: Pubkey,
+    /// Games joined via init_player with this profile attached.
+    pub total_games: u32,
+    pub total_wins: u32,
+    /// Player.points banked by cleanup_player at the end of each game.
+    pub lifetime_points: u64,
+    /// Planets taken by this player's landed moves (credited at flush).
+    pub planets_captured: u32,
+    /// Ships launched in valid moves.
+    pub ships_sent: u64,
+}
+
+#[account]
+pub struct EncryptedCelestialBody {
+    /// Layout version (CURRENT_VERSION at creation); bump on any layout change
+    /// and extend migrate_celestial_body to upgrade older accounts.
+    pub version: u8,
+    pub planet_hash: [u8; 32],
+    pub last_updated_slot: u64,
+    pub last_flushed_slot: u64,
+    /// Slot of the last successful mine_asteroid (0 = never mined).
+    pub last_mined_slot: u64,
+    // State section (176 bytes) -- Pack<[u32;23]> = 4 FEs
+    pub state_enc_pubkey: [u8; 32],
+    pub state_enc_nonce: [u8; 16],
+    pub state_enc_ciphertexts: [[u8; 32]; PLANET_STATE_FIELDS],
+    // Spawn section -- Enc<Mxe, SpawnCoords>, set only on placed spawn planets
+    pub has_spawn_coords: bool,
+    pub spawn_coords_nonce: [u8; 16],
+    pub spawn_coords_ciphertexts: [[u8; 32]; SPAWN_COORDS_FIELDS],
+    /// Payer of the init that created the body; close_dead_planet refunds
+    /// its rent here.
+    pub initializer: Pubkey,
+    /// game.hash_rounds when the body was created; planet_hash and the
+    /// body's properties were derived with it.
+    pub hash_rounds: u16,
+}
+
+impl EncryptedCelestialBody {
+    pub const MAX_SIZE: usize = 8
+        + 1    // version
+        + 32   // planet_hash
+        + 8    // last_updated_slot
+        + 8    // last_flushed_slot
+        + 8    // last_mined_slot
+        // State section
+        + 32   // state_enc_pubkey
+        + 16   // state_enc_nonce
+        + (PLANET_STATE_FIELDS * 32) // state_enc_ciphertexts (4 packed FEs)
+        // Spawn section
+        + 1    // has_spawn_coords
+        + 16   // spawn_coords_nonce
+        + (SPAWN_COORDS_FIELDS * 32) // spawn_coords_ciphertexts
+        + 32   // initializer
+        + 2; // hash_rounds
+}
+
+/// EncryptedCelestialBody layout before the version byte. Only read by
+/// migrate_celestial_body.
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace)]
+pub struct LegacyCelestialBody {
+    pub planet_hash: [u8; 32],
+    pub last_updated_slot: u64,
+    pub last_flushed_slot: u64,
+    pub state_enc_pubkey: [u8; 32],
+    pub state_enc_nonce: [u8; 16],
+    pub state_enc_ciphertexts: [[u8; 32]; LEGACY_PLANET_STATE_FIELDS],
+}
+
+impl From<LegacyCelestialBody> for EncryptedCelestialBody {
+    fn from(b: LegacyCelestialBody) -> Self {
+        EncryptedCelestialBody {
+            version: MIGRATED_VERSION,
+            planet_hash: b.planet_hash,
+            last_updated_slot: b.last_updated_slot,
+            last_flushed_slot: b.last_flushed_slot,
+            last_mined_slot: 0,
+            // The state words added since then sit in the zero padding of the
+            // last packed field, so they decrypt as 0
+            state_enc_pubkey: b.state_enc_pubkey,
+            state_enc_nonce: b.state_enc_nonce,
+            // FE 3 was never encrypted, so the pre-comet gen stats and the
+            // owner id's high word it holds decrypt to noise; MIGRATED_VERSION
+            // keeps comet_drift off them, and the planet's next capture
+            // rewrites the owner id
+            state_enc_ciphertexts: [
+                b.state_enc_ciphertexts[0],
+                b.state_enc_ciphertexts[1],
+                b.state_enc_ciphertexts[2],
+                [0u8; 32],
+            ],
+            // Legacy spawns kept no coordinates
+            has_spawn_coords: false,
+            spawn_coords_nonce: [0u8; 16],
+            spawn_coords_ciphertexts: [[0u8; 32]; SPAWN_COORDS_FIELDS],
+            // Not recorded; migrate_celestial_body sets it to the game admin
+            initializer: Pubkey::default(),
+            // Set by migrate_celestial_body from the game
+            hash_rounds: 0,
+        }
+    }
+}
+
+/// Public combat counters for a planet. PDA: ["combat_log", game_id, planet_hash].
+/// Updated by flush_planet_callback from what the flush reveals.
+#[account]
+#[derive(InitSpace)]
+pub struct CombatLog {
+    pub game_id: u64,
+    pub planet_hash: [u8; 32],
+    /// Every flushed arrival, attacks and reinforcements alike.
+    pub total_attacks: u32,
+    /// Arrivals that did not publicly capture the planet.
+    pub total_defenses: u32,
+    /// Captures by a fleet carrying metal (captured_metal > 0).
+    pub total_captures: u32,
+    /// Not yet tracked: flush does not reveal combat losses on-chain.
+    pub ships_lost: u64,
+    /// Not yet tracked: flush does not reveal combat losses on-chain.
+    pub metal_lost: u64,
+}
+
+/// Publicly revealed planet coordinates, checked against planet_hash at creation.
+/// PDA: ["coordinate_proof", game_id, planet_hash]
+#[account]
+#[derive(InitSpace)]
+pub struct CoordinateProof {
+    pub planet_hash: [u8; 32],
+    pub x: i64,
+    pub y: i64,
+    /// Player who revealed the coordinates. Informational: any current owner
+    /// of the planet can claim with the proof, since the claim's strict
+    /// check_ownership proves ownership itself.
+    pub owner: Pubkey,
+    pub revealed_slot: u64,
+}
+
+/// Dynamic-size account tracking pending moves for a planet.
+/// Sorted by landing_slot so front always has earliest-landing move.
+/// Moves are listed from queue time, including those awaiting their callback.
+#[account]
+pub struct PendingMovesMetadata {
+    pub game_id: u64,
+    pub planet_hash: [u8; 32],
+    pub next_move_id: u64,
+    pub move_count: u16,
+    /// Entries the account is currently sized for (>= move_count).
+    pub capacity: u16,
+    /// Unused and always 0: moves are listed in `moves` from queue time.
+    /// Kept for the account layout and the move PDA seed.
+    pub queued_count: u8,
+    /// Unused and always 0; kept for the account layout.
+    pub queued_landing_slots: [u64; 8],
+    /// Slot of the last queue_process_move launched from this planet.
+    pub last_move_queued_slot: u64,
+    pub moves: Vec<PendingMoveEntry>,
+}
+
+impl PendingMovesMetadata {
+    /// Base size (no entries). Grows by PENDING_MOVE_ENTRY_SIZE per unit of capacity.
+    pub const BASE_SIZE: usize = PENDING_MOVES_META_BASE_SIZE;
+
+    /// Account size for room to hold `capacity` entries.
+    pub fn space(capacity: u16) -> usize {
+        Self::BASE_SIZE + capacity as usize * PENDING_MOVE_ENTRY_SIZE
+    }
+
+    /// Capacity to hold `needed` entries. Doubles when full and shrinks to fit
+    /// once under a quarter is used, so reallocs are O(log N) rather than one
+    /// per move.
+    pub fn capacity_for(&self, needed: usize) -> u16 {
+        let capacity = self.capacity as usize;
+        if needed > capacity || needed < capacity / 4 {
+            needed.next_power_of_two() as u16
+        } else {
+            self.capacity
+        }
+    }
+}
+
+/// Entry in the sorted moves array.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct PendingMoveEntry {
+    pub landing_slot: u64,
+    pub move_id: u64,
+}
+
+/// Individual move account (one per in-flight move).
+/// PDA: ["move", game_id, planet_hash, move_id]
+#[account]
+pub struct PendingMoveAccount {
+    pub game_id: u64,
+    pub planet_hash: [u8; 32],
+    pub move_id: u64,
+    pub landing_slot: u64,
+    pub payer: Pubkey,
+    /// Set to true by the MPC callback once encrypted data is written.
+    /// Flush skips moves where populated == false (MPC not yet complete).
+    pub populated: bool,
+    pub enc_nonce: u128,
+    pub enc_ciphertexts: [[u8; 32]; 4],  // ships, metal, attacking_planet_id, attacking_player_id
+    /// Planet whose pending list holds this move. Equals `planet_hash` (the
+    /// PDA seed) unless merge_pending_moves moved it to another planet.
+    pub canonical_planet_hash: [u8; 32],
+    /// Planet the fleet was launched from, reported in CombatEvent.
+    pub source_planet_hash: [u8; 32],
+    /// Slot queue_flush_planet queued this move's flush (0 = none). A second
+    /// flush is rejected until FLUSH_RETRY_SLOTS have passed.
+    pub flush_queued_slot: u64,
+}
+
+impl PendingMoveAccount {
+    pub const MAX_SIZE: usize = 8
+        + 8    // game_id
+        + 32   // planet_hash
+        + 8    // move_id
+        + 8    // landing_slot
+        + 32   // payer
+        + 1    // populated
+        + 16   // enc_nonce
+        + (4 * 32) // enc_ciphertexts
+        + 32   // canonical_planet_hash
+        + 32   // source_planet_hash
+        + 8;   // flush_queued_slot
+}
+
+/// Alliance of players within a game. Members can transfer resources to
+/// each other's planets via queue_transfer_resources.
+/// PDA: ["alliance", game_id, alliance_id]
+#[account]
+pub struct Alliance {
+    pub game_id: u64,
+    pub id: u64,
+    pub founder: Pubkey,
+    pub member_cap: u8,
+    pub members: Vec<Pubkey>,
+}
+
+impl Alliance {
+    pub fn space(member_cap: u8) -> usize {
+        8
+            + 8    // game_id
+            + 8    // id
+            + 32   // founder
+            + 1    // member_cap
+            + 4 + (member_cap as usize * 32) // members
+    }
+}
+
+/// Comet drifting at a planet hash, waiting to be absorbed.
+/// discoverer is set while a comet_collect computation is in flight, from
+/// claimed_slot on. creator is the admin who paid its rent.
+/// PDA: ["comet", game_id, comet_id]
+#[account]
+#[derive(InitSpace)]
+pub struct Comet {
+    pub comet_id: u64,
+    pub game_id: u64,
+    pub boost: CometBoost,
+    pub location_hash: [u8; 32],
+    pub discoverer: Pubkey,
+    pub creator: Pubkey,
+    pub claimed_slot: u64,
+}
+
+/// M-of-N signer set that can authorize admin actions in place of game.admin.
+/// PDA: ["admin_multisig", game_id]
+#[account]
+pub struct AdminMultisig {
+    pub game_id: u64,
+    pub signers: Vec<Pubkey>,
+    pub threshold: u8,
+    pub proposal_ttl_slots: u64,
+    pub pending_action: Option<PendingAdminAction>,
+}
+
+impl AdminMultisig {
+    pub fn space(signer_count: usize) -> usize {
+        8
+            + 8    // game_id
+            + 4 + (signer_count * 32) // signers
+            + 1    // threshold
+            + 8    // proposal_ttl_slots
+            + 1 + PendingAdminAction::space(signer_count) // pending_action
+    }
+
+    pub fn is_expired(&self, pending: &PendingAdminAction, current_slot: u64) -> bool {
+        current_slot > pending.proposed_at_slot.saturating_add(self.proposal_ttl_slots)
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct PendingAdminAction {
+    pub action_type: AdminActionType,
+    pub proposed_at_slot: u64,
+    pub approvals: Vec<Pubkey>,
+}
+
+impl PendingAdminAction {
+    pub fn space(signer_count: usize) -> usize {
+        AdminActionType::INIT_SPACE
+            + 8    // proposed_at_slot
+            + 4 + (signer_count * 32) // approvals
+    }
+}
+
+/// Admin instructions a multisig can authorize, with their arguments.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
+pub enum AdminActionType {
+    SetPaused { paused: bool },
+    ExtendGame { new_end_slot: u64 },
+    ProposeAdminTransfer { new_admin: Pubkey },
+}
+
+/// Wallets allowed to follow a game without a Player account.
+/// PDA: ["spectators", game_id]
+#[account]
+pub struct SpectatorRegistry {
+    pub game_id: u64,
+    pub max_spectators: u16,
+    pub spectators: Vec<Pubkey>,
+}
+
+impl SpectatorRegistry {
+    pub fn space(spectator_count: usize) -> usize {
+        8
+            + 8    // game_id
+            + 2    // max_spectators
+            + 4 + (spectator_count * 32) // spectators
+    }
+}
+
+/// Top-N players by points.
+/// PDA: ["leaderboard", game_id]
+#[account]
+pub struct Leaderboard {
+    pub game_id: u64,
+    pub capacity: u8,
+    pub entries: Vec<LeaderboardEntry>,
+}
+
+impl Leaderboard {
+    pub fn space(entry_count: usize) -> usize {
+        8
+            + 8    // game_id
+            + 1    // capacity
+            + 4 + (entry_count * LEADERBOARD_ENTRY_SIZE) // entries
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct LeaderboardEntry {
+    pub owner: Pubkey,
+    pub points: u64,
+}
+
+// ===========================================================================
+// Enums
+// ===========================================================================
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
+pub enum CelestialBodyType {
+    Planet,
+    Quasar,
+    SpacetimeRip,
+    AsteroidBelt,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
+pub enum HashAlgorithm {
+    Blake3,
+    Sha256,
+}
+
+/// Passed to circuits as a plaintext u64 discriminant (0 = ChebyshevApprox).
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
+pub enum DistanceMethod {
+    /// max(dx, dy) + min(dx, dy) / 2, approximating Euclidean distance.
+    ChebyshevApprox,
+    /// dx + dy.
+    Manhattan,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
+pub enum WinCondition {
+    PointsBurning { points_per_metal: u64 },
+    RaceToCenter { min_spawn_distance: u64 },
+}
+
+/// One (x, y, planet_hash) tuple in a broadcast_bulk call.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct BroadcastEntry {
+    pub x: i64,
+    pub y: i64,
+    pub planet_hash: [u8; 32],
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
+pub enum UpgradeFocus {
+    Range,
+    LaunchVelocity,
+    ShipGenSpeed,
+    MetalGenSpeed,
+    /// +50% ship capacity, metal capacity and ship gen speed.
+    Balanced,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
+pub enum CometBoost {
+    ShipCapacity,
+    MetalCapacity,
+    ShipGenSpeed,
+    MetalGenSpeed,
+    Range,
+    LaunchVelocity,
+}
+
+/// Thresholds on the 16-bit noise values (0-65535) derived from the planet hash.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, InitSpace)]
+pub struct NoiseThresholds {
+    pub dead_space_threshold: u16,
+    pub planet_threshold: u16,
+    pub quasar_threshold: u16,
+    pub spacetime_rip_threshold: u16,
+    pub asteroid_belt_threshold: u16,
+    pub size_threshold_1: u16,
+    pub size_threshold_2: u16,
+    pub size_threshold_3: u16,
+    pub size_threshold_4: u16,
+    pub size_threshold_5: u16,
+}
+
+/// 8-bit thresholds stored by Game accounts created before NoiseThresholds
+/// widened to u16. Only read by migrate_game.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, InitSpace)]
+pub struct LegacyNoiseThresholds {
+    pub dead_space_threshold: u8,
+    pub planet_threshold: u8,
+    pub quasar_threshold: u8,
+    pub spacetime_rip_threshold: u8,
+    pub asteroid_belt_threshold: u8,
+    pub size_threshold_1: u8,
+    pub size_threshold_2: u8,
+    pub size_threshold_3: u8,
+    pub size_threshold_4: u8,
+    pub size_threshold_5: u8,
+}
+
+impl From<LegacyNoiseThresholds> for NoiseThresholds {
+    /// The 8-bit threshold becomes the high byte, which keeps the generated
+    /// distribution of an existing game unchanged.
+    fn from(t: LegacyNoiseThresholds) -> Self {
+        NoiseThresholds {
+            dead_space_threshold: (t.dead_space_threshold as u16) << 8,
+            planet_threshold: (t.planet_threshold as u16) << 8,
+            quasar_threshold: (t.quasar_threshold as u16) << 8,
+            spacetime_rip_threshold: (t.spacetime_rip_threshold as u16) << 8,
+            asteroid_belt_threshold: (t.asteroid_belt_threshold as u16) << 8,
+            size_threshold_1: (t.size_threshold_1 as u16) << 8,
+            size_threshold_2: (t.size_threshold_2 as u16) << 8,
+            size_threshold_3: (t.size_threshold_3 as u16) << 8,
+            size_threshold_4: (t.size_threshold_4 as u16) << 8,
+            size_threshold_5: (t.size_threshold_5 as u16) << 8,
+        }
+    }
+}
+
+// ===========================================================================
+// Events
+// ===========================================================================
+
+#[event]
+pub struct InitPlanetEvent {
+    pub game_id: u64,
+    pub initializer: Pubkey,
+    pub encrypted_planet_hash: [u8; 32],
+    pub encrypted_valid: [u8; 32],
+    pub encryption_key: [u8; 32],
+    pub nonce: [u8; 16],
+}
+
+#[event]
+pub struct InitSpawnPlanetEvent {
+    pub game_id: u64,
+    pub actor: Pubkey,
+    pub encrypted_planet_hash: [u8; 32],
+    pub encrypted_valid: [u8; 32],
+    pub encrypted_spawn_valid: [u8; 32],
+    pub encryption_key: [u8; 32],
+    pub nonce: [u8; 16],
+}
+
+/// Emitted when process_move is queued, before the MPC callback lands.
+#[event]
+pub struct ProcessMoveStartedEvent {
+    pub game_id: u64,
+    pub actor: Pubkey,
+    pub source_planet_hash: [u8; 32],
+    pub target_planet_hash: [u8; 32],
+    pub move_id: u64,
+    pub queued_slot: u64,
+    /// Client estimate; ProcessMoveEvent carries the MPC-computed value.
+    pub landing_slot: u64,
+    pub computation_offset: u64,
+}
+
+#[event]
+pub struct ProcessMoveEvent {
+    pub game_id: u64,
+    pub actor: Pubkey,
+    pub source_planet_hash: [u8; 32],
+    pub target_planet_hash: [u8; 32],
+    pub move_id: u64,
+    /// MPC-computed arrival (already public on the move account).
+    pub landing_slot: u64,
+    pub valid: bool,
+}
+
+#[event]
+pub struct CompDefUpdatedEvent {
+    pub name: String,
+    pub new_hash: [u8; 32],
+}
+
+#[event]
+pub struct CoordinatesVerifiedEvent {
+    pub x: i64,
+    pub y: i64,
+    pub game_id: u64,
+    pub planet_hash: [u8; 32],
+    pub valid: bool,
+}
+
+#[event]
+pub struct CoordinateRevealedEvent {
+    pub game_id: u64,
+    pub planet_hash: [u8; 32],
+    pub x: i64,
+    pub y: i64,
+    pub owner: Pubkey,
+    pub revealed_slot: u64,
+}
+
+#[event]
+pub struct GameConfigEvent {
+    pub game_id: u64,
+    pub map_diameter: u64,
+    pub game_speed: u64,
+    pub start_slot: u64,
+    pub end_slot: u64,
+    pub win_condition: WinCondition,
+    pub noise_thresholds: NoiseThresholds,
+    pub hash_rounds: u16,
+    pub hash_algorithm: HashAlgorithm,
+    pub distance_method: DistanceMethod,
+}
+
+#[event]
+pub struct ProgramVersionEvent {
+    pub version: u32,
+    pub min_compatible_client_version: u32,
+}
+
+#[event]
+pub struct AdminTransferProposedEvent {
+    pub game_id: u64,
+    pub current_admin: Pubkey,
+    pub pending_admin: Pubkey,
+}
+
+#[event]
+pub struct AdminTransferCompletedEvent {
+    pub game_id: u64,
+    pub previous_admin: Pubkey,
+    pub new_admin: Pubkey,
+}
+
+#[event]
+pub struct AdminActionProposedEvent {
+    pub game_id: u64,
+    pub action_type: AdminActionType,
+    pub proposer: Pubkey,
+}
+
+#[event]
+pub struct AdminActionApprovedEvent {
+    pub game_id: u64,
+    pub approver: Pubkey,
+    pub approvals: u8,
+    pub threshold: u8,
+}
+
+/// Emitted from a callback whose MPC output failed verification.
+/// computation_account is the Arcium PDA derived from the queued computation_offset.
+#[event]
+pub struct ComputationFailedEvent {
+    pub circuit_name: String,
+    pub computation_account: Pubkey,
+    pub error_code: u32,
+}
+
+#[event]
+pub struct GameExtendedEvent {
+    pub game_id: u64,
+    pub old_end_slot: u64,
+    pub new_end_slot: u64,
+}
+
+#[event]
+pub struct PlanetTimestampResetEvent {
+    pub game_id: u64,
+    pub planet_hash: [u8; 32],
+    pub old_slot: u64,
+    pub new_slot: u64,
+}
+
+#[event]
+pub struct HashRoundsUpdatedEvent {
+    pub game_id: u64,
+    pub old_rounds: u16,
+    pub new_rounds: u16,
+}
+
+#[event]
+pub struct GamePausedEvent {
+    pub game_id: u64,
+    pub paused: bool,
+    pub slot: u64,
+}
+
+#[event]
+pub struct LeaderboardUpdatedEvent {
+    pub game_id: u64,
+    pub rank: u8,
+    pub owner: Pubkey,
+    pub points: u64,
+}
+
+#[event]
+pub struct VictoryClaimedEvent {
+    pub game_id: u64,
+    pub winner: Pubkey,
+    pub slot: u64,
+}
+
+#[event]
+pub struct DeadPlanetClosedEvent {
+    pub game_id: u64,
+    pub planet_hash: [u8; 32],
+    pub x: i64,
+    pub y: i64,
+    pub closer: Pubkey,
+}
+
+/// Emitted when flush_planet is queued, before the MPC callback lands.
+#[event]
+pub struct FlushStartedEvent {
+    pub planet_hash: [u8; 32],
+    pub game_id: u64,
+    pub move_id: u64,
+    pub queued_slot: u64,
+    pub computation_offset: u64,
+}
+
+#[event]
+pub struct FlushPlanetEvent {
+    pub planet_hash: [u8; 32],
+    pub flushed_count: u8,
+    pub last_updated_slot: u64,
+    pub last_flushed_slot: u64,
+    /// Enc<Shared, FlushRevealed> for the observer: resources before the move landed
+    pub encrypted_gen_ships: [u8; 32],
+    pub encrypted_gen_metal: [u8; 32],
+    /// Observer pubkey passed to queue_flush_planet
+    pub encryption_key: [u8; 32],
+    pub nonce: [u8; 16],
+}
+
+/// Emitted by every flush so indexers can build a directed combat graph.
+#[event]
+pub struct CombatEvent {
+    pub source_planet_hash: [u8; 32],
+    pub target_planet_hash: [u8; 32],
+    pub flushed_move_id: u64,
+    pub game_id: u64,
+}
+
+#[event]
+pub struct CombatLogUpdatedEvent {
+    pub planet_hash: [u8; 32],
+    pub total_captures: u32,
+}
+
+#[event]
+pub struct PointsAwardedEvent {
+    pub player: Pubkey,
+    pub points_delta: u64,
+    pub new_total: u64,
+}
+
+/// Emitted when upgrade_planet is queued, before the MPC callback lands.
+#[event]
+pub struct UpgradeStartedEvent {
+    pub game_id: u64,
+    pub actor: Pubkey,
+    pub planet_hash: [u8; 32],
+    pub queued_slot: u64,
+    pub computation_offset: u64,
+}
+
+#[event]
+pub struct UpgradePlanetEvent {
+    pub game_id: u64,
+    pub actor: Pubkey,
+    pub planet_hash: [u8; 32],
+    pub encrypted_success: [u8; 32],
+    pub encrypted_new_level: [u8; 32],
+    /// 1 when the planet was already at `Game.max_planet_level`.
+    pub encrypted_at_max_level: [u8; 32],
+    pub encryption_key: [u8; 32],
+    pub nonce: [u8; 16],
+    /// Which stat was upgraded (UpgradeFocus discriminant).
+    pub focus: u8,
+}
+
+#[event]
+pub struct SpyEvent {
+    pub planet_hash: [u8; 32],
+    pub encrypted_valid: [u8; 32],
+    pub encrypted_body_type: [u8; 32],
+    pub encrypted_size: [u8; 32],
+    pub encrypted_owner_exists: [u8; 32],
+    pub encryption_key: [u8; 32],
+    pub nonce: [u8; 16],
+}
+
+#[event]
+pub struct SpawnLostEvent {
+    pub game_id: u64,
+    pub player: Pubkey,
+    pub spawn_planet_hash: [u8; 32],
+}
+
+#[event]
+pub struct PlayerJoinedEvent {
+    pub game_id: u64,
+    pub player: Pubkey,
+    pub player_count: u32,
+}
+
+#[event]
+pub struct PlayerLeftEvent {
+    pub game_id: u64,
+    pub player: Pubkey,
+    pub player_count: u32,
+}
+
+/// Encrypted with the mine input key (Enc<Shared, MineRevealed>: 2 fields).
+#[event]
+pub struct MiningCompleteEvent {
+    pub planet_hash: [u8; 32],
+    pub encrypted_success: [u8; 32],
+    pub encrypted_yield: [u8; 32],
+    pub encryption_key: [u8; 32],
+    pub nonce: [u8; 16],
+}
+
+#[event]
+pub struct CometCollectedEvent {
+    pub game_id: u64,
+    pub comet_id: u64,
+    pub planet_hash: [u8; 32],
+    pub collector: Pubkey,
+}
+
+#[event]
+pub struct SurrenderEvent {
+    pub planet_hash: [u8; 32],
+    pub former_owner: Pubkey,
+}
+
+#[event]
+pub struct OwnershipTransferredEvent {
+    pub planet_hash: [u8; 32],
+    pub from: Pubkey,
+    pub to: Pubkey,
+    /// False when the circuit rejected the transfer (payer didn't own the planet)
+    pub success: bool,
+}
+
+#[event]
+pub struct BroadcastEvent {
+    pub x: i64,
+    pub y: i64,
+    pub game_id: u64,
+    pub planet_hash: [u8; 32],
+    pub broadcaster: Pubkey,
+    pub win_condition: WinCondition,
+    pub game_speed: u64,
+    /// Planet's last state write, so listeners know how fresh it is.
+    pub last_updated_slot: u64,
+}
+
+#[event]
+pub struct BroadcastBulkEvent {
+    pub game_id: u64,
+    pub entries: Vec<BroadcastEntry>,
     pub broadcaster: Pubkey,
 }
 
-// ===========================================================================
-// Error Codes
-// ===========================================================================
+#[event]
+pub struct LeaderboardClosedEvent {
+    pub game_id: u64,
+    pub final_entries: Vec<LeaderboardEntry>,
+}
+
+/// Emitted by cleanup_game before the Game account is closed.
+#[event]
+pub struct GameFinalisedEvent {
+    pub game_id: u64,
+    pub winner: Option<Pubkey>,
+    /// end_slot - start_slot
+    pub total_slots: u64,
+    pub final_player_count: u32,
+    pub final_planet_count: u64,
+    pub admin: Pubkey,
+}
+
+/// Emitted by cleanup_player before the Player account is closed.
+#[event]
+pub struct PlayerFinalisedEvent {
+    pub game_id: u64,
+    pub player: Pubkey,
+    pub final_points: u64,
+    pub has_spawned: bool,
+}
+
+#[event]
+pub struct PendingMovesMergedEvent {
+    pub game_id: u64,
+    pub source_planet_hash: [u8; 32],
+    pub dest_planet_hash: [u8; 32],
+    pub merged_count: u16,
+}
+
+// ===========================================================================
+// Error Codes
+// ===========================================================================
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("The computation was aborted")]
+    AbortedComputation,
+    #[msg("Cluster not set")]
+    ClusterNotSet,
+    #[msg("Invalid map diameter")]
+    InvalidMapDiameter,
+    #[msg("Invalid game speed")]
+    InvalidGameSpeed,
+    #[msg("Hash rounds must be >= 1 and can never decrease")]
+    InvalidHashRounds,
+    #[msg("End slot must be after start slot")]
+    InvalidTimeRange,
+    #[msg("Whitelist games require a server pubkey")]
+    WhitelistRequiresServer,
+    #[msg("Whitelist server signature required")]
+    WhitelistServerRequired,
+    #[msg("Invalid server key")]
+    InvalidServerKey,
+    #[msg("Game has reached its player cap")]
+    GameFull,
+    #[msg("Player has already spawned")]
+    AlreadySpawned,
+    #[msg("Player has not spawned yet")]
+    NotSpawned,
+    #[msg("Game has not started")]
+    GameNotStarted,
+    #[msg("Game has ended")]
+    GameEnded,
+    #[msg("Game has not ended yet")]
+    GameNotEnded,
+    #[msg("Game is paused")]
+    GamePaused,
+    #[msg("Upgrade focus must be 0-4")]
+    InvalidUpgradeFocus,
+    #[msg("Too many moves queued this slot")]
+    RateLimitExceeded,
+    #[msg("Source planet is still on move cooldown")]
+    MoveCooldown,
+    #[msg("Noise thresholds must be strictly increasing")]
+    InvalidNoiseThresholds,
+    #[msg("max_moves_per_slot must be at least 1")]
+    InvalidMaxMovesPerSlot,
+    #[msg("max_pending_moves must be between 1 and MAX_QUEUED_CALLBACKS")]
+    InvalidMaxPendingMoves,
+    #[msg("quasar_ship_penalty_bps must be <= 10000")]
+    InvalidQuasarPenalty,
+    #[msg("Move id overflowed u64")]
+    MoveIdOverflow,
+    #[msg("Move id is already pending on this planet")]
+    DuplicateMoveId,
+    #[msg("Only the game admin can do this")]
+    Unauthorized,
+    #[msg("Signer is not the pending admin")]
+    NotPendingAdmin,
+    #[msg("New end slot must be later than the current one and at least MIN_EXTENSION_SLOTS away")]
+    InvalidExtension,
+    #[msg("Invalid planet hash")]
+    InvalidPlanetHash,
+    #[msg("broadcast_bulk takes 1..=MAX_BROADCAST_ENTRIES entries")]
+    InvalidBroadcastEntries,
+    #[msg("Coordinates are outside map bounds")]
+    CoordinatesOutOfBounds,
+    #[msg("Too many pending moves on target planet")]
+    TooManyPendingMoves,
+    #[msg("Invalid init planet result")]
+    InvalidInitPlanet,
+    #[msg("Invalid spawn validation")]
+    InvalidSpawnValidation,
+    #[msg("Invalid move input")]
+    InvalidMoveInput,
+    #[msg("Flush failed")]
+    FlushFailed,
+    #[msg("Upgrade failed")]
+    UpgradeFailed,
+    #[msg("Must flush landed moves before processing new moves")]
+    MustFlushFirst,
+    #[msg("Surrender failed")]
+    SurrenderFailed,
+    #[msg("Transfer failed")]
+    TransferFailed,
+    #[msg("Spy failed")]
+    SpyFailed,
+    #[msg("Comet is not at this planet")]
+    CometLocationMismatch,
+    #[msg("Comet collection already in progress")]
+    CometAlreadyClaimed,
+    #[msg("Mine failed")]
+    MineFailed,
+    #[msg("Invalid ownership check input")]
+    InvalidOwnershipInput,
+    #[msg("Asteroid belt was mined too recently")]
+    MiningCooldown,
+    #[msg("Wormhole jump failed")]
+    WormholeJumpFailed,
+    #[msg("Alliance member cap must be >= 1")]
+    InvalidMemberCap,
+    #[msg("Alliance is full")]
+    AllianceFull,
+    #[msg("Already an alliance member")]
+    AlreadyAllianceMember,
+    #[msg("Not an alliance member")]
+    NotAllianceMember,
+    #[msg("Only the alliance founder can do this")]
+    NotAllianceFounder,
+    #[msg("Invalid alliance member")]
+    InvalidAllianceMember,
+    #[msg("Leaderboard capacity must be >= 1")]
+    InvalidLeaderboardCapacity,
+    #[msg("Multisig needs 1..=MAX_MULTISIG_SIGNERS unique signers, 1 <= threshold <= signers and a non-zero TTL")]
+    InvalidMultisigConfig,
+    #[msg("Another admin action is still awaiting approval")]
+    AdminActionPending,
+    #[msg("No admin action has been proposed")]
+    NoPendingAdminAction,
+    #[msg("Signer already approved this action")]
+    AlreadyApproved,
+    #[msg("Proposed admin action does not match this instruction")]
+    AdminActionMismatch,
+    #[msg("Admin action has not reached the approval threshold")]
+    InsufficientApprovals,
+    #[msg("Admin action proposal has expired")]
+    ActionExpired,
+    #[msg("Celestial body is not an older layout version that can be migrated")]
+    InvalidAccountVersion,
+    #[msg("Games are still active; clean them up before closing the signer PDA")]
+    GamesStillActive,
+    #[msg("Planet still has pending moves; flush them or wait out the grace period")]
+    PendingMovesRemaining,
+    #[msg("Move has already landed and can no longer be abandoned")]
+    MoveAlreadyLanded,
+    #[msg("Move batch is empty, too large, or does not match the supplied move accounts")]
+    InvalidMoveBatch,
+    #[msg("Callback does not match an outstanding queued move")]
+    CallbackReplay,
+    #[msg("Comet drift is disabled for this game")]
+    CometDriftDisabled,
+    #[msg("Comet drift interval has not elapsed since the planet's last update")]
+    CometDriftNotReady,
+    #[msg("Cannot merge a planet's pending moves into itself")]
+    InvalidMergeTarget,
+    #[msg("Source planet has moves still awaiting their process_move callback")]
+    MergeMovesNotReady,
+    #[msg("Account is not this game's leaderboard")]
+    InvalidLeaderboard,
+    #[msg("Only the player can clean up before the grace period ends")]
+    ForceCleanupTooEarly,
+    #[msg("Computation offset counter overflow")]
+    ComputationOffsetOverflow,
+    #[msg("Spectator registry capacity must be at least 1")]
+    InvalidSpectatorCapacity,
+    #[msg("Spectator registry is full")]
+    SpectatorRegistryFull,
+    #[msg("Already registered as a spectator")]
+    AlreadySpectator,
+    #[msg("Not a registered spectator")]
+    NotSpectator,
+    #[msg("Broadcaster must be a player or registered spectator of this game")]
+    BroadcasterNotRegistered,
+    #[msg("Start slot is in the past")]
+    StartSlotInPast,
+    #[msg("Game is shorter than MIN_GAME_DURATION_SLOTS")]
+    GameTooShort,
+    #[msg("Ownership transfer failed")]
+    TransferOwnerFailed,
+    #[msg("Player may still own planets; close them first or wait for force cleanup")]
+    PlayerStillOwnsPlanets,
+    #[msg("Game is not a RaceToCenter game")]
+    NotRaceToCenter,
+    #[msg("Game already has a winner")]
+    WinnerAlreadyDeclared,
+    #[msg("Revealed coordinates are too far from the map center")]
+    NotNearCenter,
+    #[msg("Priority fee is below MIN_PRIORITY_FEE")]
+    InsufficientPriorityFee,
+    #[msg("valid_spawn_body_types must set at least one of bits 0-3")]
+    InvalidSpawnBodyTypes,
+    #[msg("max_planet_level must be at least 2")]
+    InvalidMaxPlanetLevel,
+    #[msg("The revealed coordinates are not dead space")]
+    NotDeadSpace,
+    #[msg("This planet has already been initialized")]
+    PlanetAlreadyInitialized,
+    #[msg("min_ships_per_move must be at least 1")]
+    InvalidMinShipsPerMove,
+    #[msg("Game is not a PointsBurning game")]
+    NotPointsBurning,
+    #[msg("Player has not reached points_to_win")]
+    NotEnoughPoints,
+    #[msg("New planet timestamp must be between its last_updated_slot and the current slot")]
+    InvalidPlanetTimestamp,
+    #[msg("max_observable_distance exceeds the largest distance on the map")]
+    InvalidObservableDistance,
+    #[msg("Comet collection is not in flight or has not timed out")]
+    CometNotStale,
+    #[msg("Spawn separation can't be checked against more existing spawns")]
+    SpawnSeparationFull,
+    #[msg("This game holds a security deposit; pass the player's PlayerDeposit")]
+    DepositRequired,
+    #[msg("A flush of this move is already in flight")]
+    FlushInFlight,
+    #[msg("The MPC output's cluster signature is invalid")]
+    ComputationSignatureInvalid,
+    #[msg("The MPC output is for a computation that is no longer current")]
+    ComputationOutputStale,
+    #[msg("The MPC output does not match the expected circuit")]
+    ComputationCircuitMismatch,
+    #[msg("update_hash_rounds has been called MAX_HASH_ROUNDS_UPDATES times")]
+    HashRoundsUpdatesExhausted,
+    #[msg("Pass the location's base hash and its planet PDA under each earlier hash_rounds")]
+    PriorRoundsPlanetsRequired,
+    #[msg("This location already has a planet from before a hash_rounds change")]
+    PlanetExistsUnderPriorRounds,
+}
+
+// ===========================================================================
+// Account Contexts
+// ===========================================================================
+
+// --- Computation Definition Initializers ---
+
+#[init_computation_definition_accounts("init_planet", payer)]
+#[derive(Accounts)]
+pub struct InitInitPlanetCompDef<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(mut, address = derive_mxe_pda!())]
+    pub mxe_account: Box<Account<'info, MXEAccount>>,
+    #[account(mut)]
+    /// CHECK: comp_def_account, checked by arcium program.
+    pub comp_def_account: UncheckedAccount<'info>,
+    #[account(mut, address = derive_mxe_lut_pda!())]
+    /// CHECK: address_lookup_table, checked by arcium program.
+    pub address_lookup_table: UncheckedAccount<'info>,
+    /// CHECK: lut_program
+    #[account(address = LUT_PROGRAM_ID)]
+    pub lut_program: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+    pub arcium_program: Program<'info, Arcium>,
+    /// Required only with circuit_hash_override: the payer must be its upgrade authority
+    #[account(
+        seeds = [crate::ID.as_ref()],
+        bump,
+        seeds::program = anchor_lang::solana_program::bpf_loader_upgradeable::ID,
+    )]
+    pub program_data: Option<Account<'info, ProgramData>>,
+}
+
+#[init_computation_definition_accounts("init_spawn_planet", payer)]
+#[derive(Accounts)]
+pub struct InitInitSpawnPlanetCompDef<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(mut, address = derive_mxe_pda!())]
+    pub mxe_account: Box<Account<'info, MXEAccount>>,
+    #[account(mut)]
+    /// CHECK: comp_def_account, checked by arcium program.
+    pub comp_def_account: UncheckedAccount<'info>,
+    #[account(mut, address = derive_mxe_lut_pda!())]
+    /// CHECK: address_lookup_table, checked by arcium program.
+    pub address_lookup_table: UncheckedAccount<'info>,
+    /// CHECK: lut_program
+    #[account(address = LUT_PROGRAM_ID)]
+    pub lut_program: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+    pub arcium_program: Program<'info, Arcium>,
+    /// Required only with circuit_hash_override: the payer must be its upgrade authority
+    #[account(
+        seeds = [crate::ID.as_ref()],
+        bump,
+        seeds::program = anchor_lang::solana_program::bpf_loader_upgradeable::ID,
+    )]
+    pub program_data: Option<Account<'info, ProgramData>>,
+}
+
+#[init_computation_definition_accounts("process_move", payer)]
+#[derive(Accounts)]
+pub struct InitProcessMoveCompDef<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(mut, address = derive_mxe_pda!())]
+    pub mxe_account: Box<Account<'info, MXEAccount>>,
+    #[account(mut)]
+    /// CHECK: comp_def_account, checked by arcium program.
+    pub comp_def_account: UncheckedAccount<'info>,
+    #[account(mut, address = derive_mxe_lut_pda!())]
+    /// CHECK: address_lookup_table, checked by arcium program.
+    pub address_lookup_table: UncheckedAccount<'info>,
+    /// CHECK: lut_program
+    #[account(address = LUT_PROGRAM_ID)]
+    pub lut_program: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+    pub arcium_program: Program<'info, Arcium>,
+    /// Required only with circuit_hash_override: the payer must be its upgrade authority
+    #[account(
+        seeds = [crate::ID.as_ref()],
+        bump,
+        seeds::program = anchor_lang::solana_program::bpf_loader_upgradeable::ID,
+    )]
+    pub program_data: Option<Account<'info, ProgramData>>,
+}
+
+#[init_computation_definition_accounts("flush_planet", payer)]
+#[derive(Accounts)]
+pub struct InitFlushPlanetCompDef<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(mut, address = derive_mxe_pda!())]
+    pub mxe_account: Box<Account<'info, MXEAccount>>,
+    #[account(mut)]
+    /// CHECK: comp_def_account, checked by arcium program.
+    pub comp_def_account: UncheckedAccount<'info>,
+    #[account(mut, address = derive_mxe_lut_pda!())]
+    /// CHECK: address_lookup_table, checked by arcium program.
+    pub address_lookup_table: UncheckedAccount<'info>,
+    /// CHECK: lut_program
+    #[account(address = LUT_PROGRAM_ID)]
+    pub lut_program: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+    pub arcium_program: Program<'info, Arcium>,
+    /// Required only with circuit_hash_override: the payer must be its upgrade authority
+    #[account(
+        seeds = [crate::ID.as_ref()],
+        bump,
+        seeds::program = anchor_lang::solana_program::bpf_loader_upgradeable::ID,
+    )]
+    pub program_data: Option<Account<'info, ProgramData>>,
+}
+
+#[init_computation_definition_accounts("upgrade_planet", payer)]
+#[derive(Accounts)]
+pub struct InitUpgradePlanetCompDef<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(mut, address = derive_mxe_pda!())]
+    pub mxe_account: Box<Account<'info, MXEAccount>>,
+    #[account(mut)]
+    /// CHECK: comp_def_account, checked by arcium program.
+    pub comp_def_account: UncheckedAccount<'info>,
+    #[account(mut, address = derive_mxe_lut_pda!())]
+    /// CHECK: address_lookup_table, checked by arcium program.
+    pub address_lookup_table: UncheckedAccount<'info>,
+    /// CHECK: lut_program
+    #[account(address = LUT_PROGRAM_ID)]
+    pub lut_program: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+    pub arcium_program: Program<'info, Arcium>,
+    /// Required only with circuit_hash_override: the payer must be its upgrade authority
+    #[account(
+        seeds = [crate::ID.as_ref()],
+        bump,
+        seeds::program = anchor_lang::solana_program::bpf_loader_upgradeable::ID,
+    )]
+    pub program_data: Option<Account<'info, ProgramData>>,
+}
+
+#[init_computation_definition_accounts("surrender_planet", payer)]
+#[derive(Accounts)]
+pub struct InitSurrenderPlanetCompDef<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(mut, address = derive_mxe_pda!())]
+    pub mxe_account: Box<Account<'info, MXEAccount>>,
+    #[account(mut)]
+    /// CHECK: comp_def_account, checked by arcium program.
+    pub comp_def_account: UncheckedAccount<'info>,
+    #[account(mut, address = derive_mxe_lut_pda!())]
+    /// CHECK: address_lookup_table, checked by arcium program.
+    pub address_lookup_table: UncheckedAccount<'info>,
+    /// CHECK: lut_program
+    #[account(address = LUT_PROGRAM_ID)]
+    pub lut_program: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+    pub arcium_program: Program<'info, Arcium>,
+    /// Required only with circuit_hash_override: the payer must be its upgrade authority
+    #[account(
+        seeds = [crate::ID.as_ref()],
+        bump,
+        seeds::program = anchor_lang::solana_program::bpf_loader_upgradeable::ID,
+    )]
+    pub program_data: Option<Account<'info, ProgramData>>,
+}
+
+#[init_computation_definition_accounts("transfer_resources", payer)]
+#[derive(Accounts)]
+pub struct InitTransferResourcesCompDef<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(mut, address = derive_mxe_pda!())]
+    pub mxe_account: Box<Account<'info, MXEAccount>>,
+    #[account(mut)]
+    /// CHECK: comp_def_account, checked by arcium program.
+    pub comp_def_account: UncheckedAccount<'info>,
+    #[account(mut, address = derive_mxe_lut_pda!())]
+    /// CHECK: address_lookup_table, checked by arcium program.
+    pub address_lookup_table: UncheckedAccount<'info>,
+    /// CHECK: lut_program
+    #[account(address = LUT_PROGRAM_ID)]
+    pub lut_program: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+    pub arcium_program: Program<'info, Arcium>,
+    /// Required only with circuit_hash_override: the payer must be its upgrade authority
+    #[account(
+        seeds = [crate::ID.as_ref()],
+        bump,
+        seeds::program = anchor_lang::solana_program::bpf_loader_upgradeable::ID,
+    )]
+    pub program_data: Option<Account<'info, ProgramData>>,
+}
+
+#[init_computation_definition_accounts("spy_move", payer)]
+#[derive(Accounts)]
+pub struct InitSpyMoveCompDef<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(mut, address = derive_mxe_pda!())]
+    pub mxe_account: Box<Account<'info, MXEAccount>>,
+    #[account(mut)]
+    /// CHECK: comp_def_account, checked by arcium program.
+    pub comp_def_account: UncheckedAccount<'info>,
+    #[account(mut, address = derive_mxe_lut_pda!())]
+    /// CHECK: address_lookup_table, checked by arcium program.
+    pub address_lookup_table: UncheckedAccount<'info>,
+    /// CHECK: lut_program
+    #[account(address = LUT_PROGRAM_ID)]
+    pub lut_program: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+    pub arcium_program: Program<'info, Arcium>,
+    /// Required only with circuit_hash_override: the payer must be its upgrade authority
+    #[account(
+        seeds = [crate::ID.as_ref()],
+        bump,
+        seeds::program = anchor_lang::solana_program::bpf_loader_upgradeable::ID,
+    )]
+    pub program_data: Option<Account<'info, ProgramData>>,
+}
+
+#[init_computation_definition_accounts("comet_collect", payer)]
+#[derive(Accounts)]
+pub struct InitCometCollectCompDef<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(mut, address = derive_mxe_pda!())]
+    pub mxe_account: Box<Account<'info, MXEAccount>>,
+    #[account(mut)]
+    /// CHECK: comp_def_account, checked by arcium program.
+    pub comp_def_account: UncheckedAccount<'info>,
+    #[account(mut, address = derive_mxe_lut_pda!())]
+    /// CHECK: address_lookup_table, checked by arcium program.
+    pub address_lookup_table: UncheckedAccount<'info>,
+    /// CHECK: lut_program
+    #[account(address = LUT_PROGRAM_ID)]
+    pub lut_program: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+    pub arcium_program: Program<'info, Arcium>,
+    /// Required only with circuit_hash_override: the payer must be its upgrade authority
+    #[account(
+        seeds = [crate::ID.as_ref()],
+        bump,
+        seeds::program = anchor_lang::solana_program::bpf_loader_upgradeable::ID,
+    )]
+    pub program_data: Option<Account<'info, ProgramData>>,
+}
+
+#[init_computation_definition_accounts("mine_asteroid", payer)]
+#[derive(Accounts)]
+pub struct InitMineAsteroidCompDef<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(mut, address = derive_mxe_pda!())]
+    pub mxe_account: Box<Account<'info, MXEAccount>>,
+    #[account(mut)]
+    /// CHECK: comp_def_account, checked by arcium program.
+    pub comp_def_account: UncheckedAccount<'info>,
+    #[account(mut, address = derive_mxe_lut_pda!())]
+    /// CHECK: address_lookup_table, checked by arcium program.
+    pub address_lookup_table: UncheckedAccount<'info>,
+    /// CHECK: lut_program
+    #[account(address = LUT_PROGRAM_ID)]
+    pub lut_program: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+    pub arcium_program: Program<'info, Arcium>,
+    /// Required only with circuit_hash_override: the payer must be its upgrade authority
+    #[account(
+        seeds = [crate::ID.as_ref()],
+        bump,
+        seeds::program = anchor_lang::solana_program::bpf_loader_upgradeable::ID,
+    )]
+    pub program_data: Option<Account<'info, ProgramData>>,
+}
+
+#[init_computation_definition_accounts("check_ownership", payer)]
+#[derive(Accounts)]
+pub struct InitCheckOwnershipCompDef<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(mut, address = derive_mxe_pda!())]
+    pub mxe_account: Box<Account<'info, MXEAccount>>,
+    #[account(mut)]
+    /// CHECK: comp_def_account, checked by arcium program.
+    pub comp_def_account: UncheckedAccount<'info>,
+    #[account(mut, address = derive_mxe_lut_pda!())]
+    /// CHECK: address_lookup_table, checked by arcium program.
+    pub address_lookup_table: UncheckedAccount<'info>,
+    /// CHECK: lut_program
+    #[account(address = LUT_PROGRAM_ID)]
+    pub lut_program: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+    pub arcium_program: Program<'info, Arcium>,
+    /// Required only with circuit_hash_override: the payer must be its upgrade authority
+    #[account(
+        seeds = [crate::ID.as_ref()],
+        bump,
+        seeds::program = anchor_lang::solana_program::bpf_loader_upgradeable::ID,
+    )]
+    pub program_data: Option<Account<'info, ProgramData>>,
+}
+
+#[init_computation_definition_accounts("wormhole_jump", payer)]
+#[derive(Accounts)]
+pub struct InitWormholeJumpCompDef<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(mut, address = derive_mxe_pda!())]
+    pub mxe_account: Box<Account<'info, MXEAccount>>,
+    #[account(mut)]
+    /// CHECK: comp_def_account, checked by arcium program.
+    pub comp_def_account: UncheckedAccount<'info>,
+    #[account(mut, address = derive_mxe_lut_pda!())]
+    /// CHECK: address_lookup_table, checked by arcium program.
+    pub address_lookup_table: UncheckedAccount<'info>,
+    /// CHECK: lut_program
+    #[account(address = LUT_PROGRAM_ID)]
+    pub lut_program: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+    pub arcium_program: Program<'info, Arcium>,
+    /// Required only with circuit_hash_override: the payer must be its upgrade authority
+    #[account(
+        seeds = [crate::ID.as_ref()],
+        bump,
+        seeds::program = anchor_lang::solana_program::bpf_loader_upgradeable::ID,
+    )]
+    pub program_data: Option<Account<'info, ProgramData>>,
+}
+
+#[init_computation_definition_accounts("comet_drift", payer)]
+#[derive(Accounts)]
+pub struct InitCometDriftCompDef<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(mut, address = derive_mxe_pda!())]
+    pub mxe_account: Box<Account<'info, MXEAccount>>,
+    #[account(mut)]
+    /// CHECK: comp_def_account, checked by arcium program.
+    pub comp_def_account: UncheckedAccount<'info>,
+    #[account(mut, address = derive_mxe_lut_pda!())]
+    /// CHECK: address_lookup_table, checked by arcium program.
+    pub address_lookup_table: UncheckedAccount<'info>,
+    /// CHECK: lut_program
+    #[account(address = LUT_PROGRAM_ID)]
+    pub lut_program: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+    pub arcium_program: Program<'info, Arcium>,
+    /// Required only with circuit_hash_override: the payer must be its upgrade authority
+    #[account(
+        seeds = [crate::ID.as_ref()],
+        bump,
+        seeds::program = anchor_lang::solana_program::bpf_loader_upgradeable::ID,
+    )]
+    pub program_data: Option<Account<'info, ProgramData>>,
+}
+
+#[init_computation_definition_accounts("transfer_owner", payer)]
+#[derive(Accounts)]
+pub struct InitTransferOwnerCompDef<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(mut, address = derive_mxe_pda!())]
+    pub mxe_account: Box<Account<'info, MXEAccount>>,
+    #[account(mut)]
+    /// CHECK: comp_def_account, checked by arcium program.
+    pub comp_def_account: UncheckedAccount<'info>,
+    #[account(mut, address = derive_mxe_lut_pda!())]
+    /// CHECK: address_lookup_table, checked by arcium program.
+    pub address_lookup_table: UncheckedAccount<'info>,
+    /// CHECK: lut_program
+    #[account(address = LUT_PROGRAM_ID)]
+    pub lut_program: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+    pub arcium_program: Program<'info, Arcium>,
+    /// Required only with circuit_hash_override: the payer must be its upgrade authority
+    #[account(
+        seeds = [crate::ID.as_ref()],
+        bump,
+        seeds::program = anchor_lang::solana_program::bpf_loader_upgradeable::ID,
+    )]
+    pub program_data: Option<Account<'info, ProgramData>>,
+}
+
+// --- Game Management ---
+
+#[derive(Accounts)]
+#[instruction(game_id: u64)]
+pub struct CreateGame<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + Game::INIT_SPACE,
+        seeds = [b"game", game_id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub game: Account<'info, Game>,
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = 8 + ProgramState::INIT_SPACE,
+        seeds = [b"program_state"],
+        bump,
+    )]
+    pub program_state: Account<'info, ProgramState>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(game_id: u64)]
+pub struct InitPlayer<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"game", game_id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub game: Account<'info, Game>,
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + Player::INIT_SPACE,
+        seeds = [b"player", game_id.to_le_bytes().as_ref(), owner.key().as_ref()],
+        bump,
+    )]
+    pub player: Account<'info, Player>,
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + PlayerDeposit::INIT_SPACE,
+        seeds = [b"deposit", game_id.to_le_bytes().as_ref(), owner.key().as_ref()],
+        bump,
+    )]
+    pub deposit: Account<'info, PlayerDeposit>,
+    /// May already exist if the owner queued a computation before joining.
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = 8 + ComputationOffsetTracker::INIT_SPACE,
+        seeds = [b"offset", game_id.to_le_bytes().as_ref(), owner.key().as_ref()],
+        bump,
+    )]
+    pub offset_tracker: Account<'info, ComputationOffsetTracker>,
+    pub server: Option<Signer<'info>>,
+    /// Optional: cross-game profile, credited with one more game
+    #[account(
+        mut,
+        seeds = [b"profile", owner.key().as_ref()],
+        bump,
+    )]
+    pub player_profile: Option<Account<'info, PlayerProfile>>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct InitPlayerProfile<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + PlayerProfile::INIT_SPACE,
+        seeds = [b"profile", owner.key().as_ref()],
+        bump,
+    )]
+    pub player_profile: Account<'info, PlayerProfile>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(game_id: u64)]
+pub struct SetGamePaused<'info> {
+    pub admin: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"game", game_id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub game: Account<'info, Game>,
+    #[account(
+        mut,
+        seeds = [b"admin_multisig", game_id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub admin_multisig: Option<Account<'info, AdminMultisig>>,
+}
+
+#[derive(Accounts)]
+#[instruction(game_id: u64)]
+pub struct ExtendGame<'info> {
+    pub admin: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"game", game_id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub game: Account<'info, Game>,
+    #[account(
+        mut,
+        seeds = [b"admin_multisig", game_id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub admin_multisig: Option<Account<'info, AdminMultisig>>,
+}
+
+#[derive(Accounts)]
+#[instruction(game_id: u64)]
+pub struct SetAllowForceCleanup<'info> {
+    pub admin: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"game", game_id.to_le_bytes().as_ref()],
+        bump,
+        constraint = game.admin == admin.key() @ ErrorCode::Unauthorized,
+    )]
+    pub game: Account<'info, Game>,
+}
+
+#[derive(Accounts)]
+#[instruction(game_id: u64)]
+pub struct UpdateHashRounds<'info> {
+    pub admin: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"game", game_id.to_le_bytes().as_ref()],
+        bump,
+        constraint = game.admin == admin.key() @ ErrorCode::Unauthorized,
+    )]
+    pub game: Account<'info, Game>,
+}
+
+#[derive(Accounts)]
+#[instruction(game_id: u64)]
+pub struct ProposeAdminTransfer<'info> {
+    pub admin: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"game", game_id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub game: Account<'info, Game>,
+    #[account(
+        mut,
+        seeds = [b"admin_multisig", game_id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub admin_multisig: Option<Account<'info, AdminMultisig>>,
+}
+
+#[derive(Accounts)]
+#[instruction(game_id: u64, signers: Vec<Pubkey>)]
+pub struct CreateAdminMultisig<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(
+        seeds = [b"game", game_id.to_le_bytes().as_ref()],
+        bump,
+        constraint = game.admin == admin.key() @ ErrorCode::Unauthorized,
+    )]
+    pub game: Account<'info, Game>,
+    #[account(
+        init,
+        payer = admin,
+        space = AdminMultisig::space(signers.len()),
+        seeds = [b"admin_multisig", game_id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub admin_multisig: Account<'info, AdminMultisig>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(game_id: u64)]
+pub struct ProposeAdminAction<'info> {
+    pub signer: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"admin_multisig", game_id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub admin_multisig: Account<'info, AdminMultisig>,
+}
+
+#[derive(Accounts)]
+#[instruction(game_id: u64)]
+pub struct ApproveAdminAction<'info> {
+    pub signer: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"admin_multisig", game_id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub admin_multisig: Account<'info, AdminMultisig>,
+}
+
+#[derive(Accounts)]
+#[instruction(game_id: u64)]
+pub struct AcceptAdminTransfer<'info> {
+    pub new_admin: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"game", game_id.to_le_bytes().as_ref()],
+        bump,
+        constraint = game.pending_admin == Some(new_admin.key()) @ ErrorCode::NotPendingAdmin,
+    )]
+    pub game: Account<'info, Game>,
+}
+
+// --- Alliances ---
+
+#[derive(Accounts)]
+#[instruction(game_id: u64, alliance_id: u64, member_cap: u8)]
+pub struct CreateAlliance<'info> {
+    #[account(mut)]
+    pub founder: Signer<'info>,
+    #[account(
+        seeds = [b"game", game_id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub game: Account<'info, Game>,
+    #[account(
+        init,
+        payer = founder,
+        space = Alliance::space(member_cap),
+        seeds = [b"alliance", game_id.to_le_bytes().as_ref(), alliance_id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub alliance: Account<'info, Alliance>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(game_id: u64, alliance_id: u64)]
+pub struct UpdateAllianceMembers<'info> {
+    pub founder: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"alliance", game_id.to_le_bytes().as_ref(), alliance_id.to_le_bytes().as_ref()],
+        bump,
+        constraint = alliance.founder == founder.key() @ ErrorCode::NotAllianceFounder,
+    )]
+    pub alliance: Account<'info, Alliance>,
+}
+
+// --- Leaderboard ---
+
+#[derive(Accounts)]
+#[instruction(game_id: u64)]
+pub struct CreateLeaderboard<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(
+        seeds = [b"game", game_id.to_le_bytes().as_ref()],
+        bump,
+        constraint = game.admin == admin.key() @ ErrorCode::Unauthorized,
+    )]
+    pub game: Account<'info, Game>,
+    #[account(
+        init,
+        payer = admin,
+        space = Leaderboard::space(0),
+        seeds = [b"leaderboard", game_id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub leaderboard: Account<'info, Leaderboard>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(game_id: u64)]
+pub struct UpdateLeaderboard<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    #[account(
+        seeds = [b"player", game_id.to_le_bytes().as_ref(), owner.key().as_ref()],
+        bump,
+    )]
+    pub player: Account<'info, Player>,
+    /// Grows by one entry per new ranked player, up to capacity.
+    #[account(
+        mut,
+        seeds = [b"leaderboard", game_id.to_le_bytes().as_ref()],
+        bump,
+        realloc = Leaderboard::space((leaderboard.entries.len() + 1).min(leaderboard.capacity as usize)),
+        realloc::payer = owner,
+        realloc::zero = false,
+    )]
+    pub leaderboard: Account<'info, Leaderboard>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(game_id: u64)]
+pub struct CreateSpectatorRegistry<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(
+        seeds = [b"game", game_id.to_le_bytes().as_ref()],
+        bump,
+        constraint = game.admin == admin.key() @ ErrorCode::Unauthorized,
+    )]
+    pub game: Account<'info, Game>,
+    #[account(
+        init,
+        payer = admin,
+        space = SpectatorRegistry::space(0),
+        seeds = [b"spectators", game_id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub spectator_registry: Account<'info, SpectatorRegistry>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(game_id: u64)]
+pub struct RegisterSpectator<'info> {
+    #[account(mut)]
+    pub spectator: Signer<'info>,
+    #[account(
+        seeds = [b"game", game_id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub game: Account<'info, Game>,
+    /// Grows by one entry per spectator, up to max_spectators.
+    #[account(
+        mut,
+        seeds = [b"spectators", game_id.to_le_bytes().as_ref()],
+        bump,
+        realloc = SpectatorRegistry::space(
+            (spectator_registry.spectators.len() + 1).min(spectator_registry.max_spectators as usize),
+        ),
+        realloc::payer = spectator,
+        realloc::zero = false,
+    )]
+    pub spectator_registry: Account<'info, SpectatorRegistry>,
+    pub server: Option<Signer<'info>>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(game_id: u64)]
+pub struct DeregisterSpectator<'info> {
+    #[account(mut)]
+    pub spectator: Signer<'info>,
+    /// Shrinks by one entry; the freed rent goes back to the spectator.
+    #[account(
+        mut,
+        seeds = [b"spectators", game_id.to_le_bytes().as_ref()],
+        bump,
+        realloc = SpectatorRegistry::space(spectator_registry.spectators.len().saturating_sub(1)),
+        realloc::payer = spectator,
+        realloc::zero = false,
+    )]
+    pub spectator_registry: Account<'info, SpectatorRegistry>,
+    pub system_program: Program<'info, System>,
+}
+
+// --- Queue Init Planet ---
+
+#[queue_computation_accounts("init_planet", payer)]
+#[derive(Accounts)]
+#[instruction(planet_hash: [u8; 32])]
+pub struct QueueInitPlanet<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        seeds = [b"game", game.game_id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub game: Box<Account<'info, Game>>,
+    /// init_if_needed so an existing planet fails with PlanetAlreadyInitialized
+    /// (a fresh account still has version 0) instead of a raw Anchor error.
+    /// Checked before the accounts below, which exist whenever this one does.
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = EncryptedCelestialBody::MAX_SIZE,
+        seeds = [b"planet", game.game_id.to_le_bytes().as_ref(), planet_hash.as_ref()],
+        bump,
+        constraint = celestial_body.version == 0 @ ErrorCode::PlanetAlreadyInitialized,
+    )]
+    pub celestial_body: Box<Account<'info, EncryptedCelestialBody>>,
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = PendingMovesMetadata::BASE_SIZE,
+        seeds = [b"moves", game.game_id.to_le_bytes().as_ref(), planet_hash.as_ref()],
+        bump,
+    )]
+    pub pending_moves: Box<Account<'info, PendingMovesMetadata>>,
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + CombatLog::INIT_SPACE,
+        seeds = [b"combat_log", game.game_id.to_le_bytes().as_ref(), planet_hash.as_ref()],
+        bump,
+    )]
+    pub combat_log: Box<Account<'info, CombatLog>>,
+    #[account(
+        init_if_needed,
+        space = 9,
+        payer = payer,
+        seeds = [&SIGN_PDA_SEED],
+        bump,
+        address = derive_sign_pda!(),
+    )]
+    pub sign_pda_account: Account<'info, ArciumSignerAccount>,
+    #[account(address = derive_mxe_pda!())]
+    pub mxe_account: Box<Account<'info, MXEAccount>>,
+    #[account(mut, address = derive_mempool_pda!(mxe_account, ErrorCode::ClusterNotSet))]
+    /// CHECK: mempool_account
+    pub mempool_account: UncheckedAccount<'info>,
+    #[account(mut, address = derive_execpool_pda!(mxe_account, ErrorCode::ClusterNotSet))]
+    /// CHECK: executing_pool
+    pub executing_pool: UncheckedAccount<'info>,
+    /// Supplies the computation offset; advanced by the handler.
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + ComputationOffsetTracker::INIT_SPACE,
+        seeds = [b"offset", game.game_id.to_le_bytes().as_ref(), payer.key().as_ref()],
+        bump,
+    )]
+    pub offset_tracker: Box<Account<'info, ComputationOffsetTracker>>,
+    #[account(
+        mut,
+        address = derive_comp_pda!(
+            derive_computation_offset(game.game_id, &payer.key(), offset_tracker.next_offset),
+            mxe_account,
+            ErrorCode::ClusterNotSet
+        )
+    )]
+    /// CHECK: computation_account
+    pub computation_account: UncheckedAccount<'info>,
+    #[account(address = derive_comp_def_pda!(COMP_DEF_OFFSET_INIT_PLANET))]
+    pub comp_def_account: Box<Account<'info, ComputationDefinitionAccount>>,
+    #[account(mut, address = derive_cluster_pda!(mxe_account, ErrorCode::ClusterNotSet))]
+    pub cluster_account: Box<Account<'info, Cluster>>,
+    #[account(mut, address = ARCIUM_FEE_POOL_ACCOUNT_ADDRESS)]
+    pub pool_account: Box<Account<'info, FeePool>>,
+    #[account(mut, address = ARCIUM_CLOCK_ACCOUNT_ADDRESS)]
+    pub clock_account: Box<Account<'info, ClockAccount>>,
+    pub system_program: Program<'info, System>,
+    pub arcium_program: Program<'info, Arcium>,
+}
+
+#[callback_accounts("init_planet")]
+#[derive(Accounts)]
+pub struct InitPlanetCallback<'info> {
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(address = derive_comp_def_pda!(COMP_DEF_OFFSET_INIT_PLANET))]
+    pub comp_def_account: Box<Account<'info, ComputationDefinitionAccount>>,
+    #[account(address = derive_mxe_pda!())]
+    pub mxe_account: Box<Account<'info, MXEAccount>>,
+    /// CHECK: computation_account
+    pub computation_account: UncheckedAccount<'info>,
+    #[account(address = derive_cluster_pda!(mxe_account, ErrorCode::ClusterNotSet))]
+    pub cluster_account: Box<Account<'info, Cluster>>,
+    #[account(address = ::anchor_lang::solana_program::sysvar::instructions::ID)]
+    /// CHECK: instructions_sysvar
+    pub instructions_sysvar: AccountInfo<'info>,
+    #[account(
+        mut,
+        seeds = [b"game", game.game_id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub game: Box<Account<'info, Game>>,
+    /// Must be the planet PDA for the planet_hash written by queue_init_planet
+    #[account(
+        mut,
+        seeds = [b"planet", game.game_id.to_le_bytes().as_ref(), celestial_body.planet_hash.as_ref()],
+        bump,
+    )]
+    pub celestial_body: Box<Account<'info, EncryptedCelestialBody>>,
+    /// CHECK: queue_init_planet's payer, fixed by the program at queue time
+    pub initializer: UncheckedAccount<'info>,
+}
+
+// --- Queue Init Spawn Planet ---
+
+#[queue_computation_accounts("init_spawn_planet", payer)]
+#[derive(Accounts)]
+#[instruction(planet_hash: [u8; 32])]
+pub struct QueueInitSpawnPlanet<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        seeds = [b"game", game.game_id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub game: Box<Account<'info, Game>>,
+    #[account(
+        mut,
+        seeds = [b"player", game.game_id.to_le_bytes().as_ref(), payer.key().as_ref()],
+        bump,
+        constraint = player.owner == payer.key() @ ErrorCode::InvalidSpawnValidation,
+    )]
+    pub player: Box<Account<'info, Player>>,
+    /// Optional: payer's cross-game profile, credited on a RaceToCenter win
+    #[account(
+        seeds = [b"profile", payer.key().as_ref()],
+        bump,
+    )]
+    pub player_profile: Option<Box<Account<'info, PlayerProfile>>>,
+    /// init_if_needed so an existing planet fails with PlanetAlreadyInitialized
+    /// (a fresh account still has version 0) instead of a raw Anchor error.
+    /// Checked before the accounts below, which exist whenever this one does.
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = EncryptedCelestialBody::MAX_SIZE,
+        seeds = [b"planet", game.game_id.to_le_bytes().as_ref(), planet_hash.as_ref()],
+        bump,
+        constraint = celestial_body.version == 0 @ ErrorCode::PlanetAlreadyInitialized,
+    )]
+    pub celestial_body: Box<Account<'info, EncryptedCelestialBody>>,
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = PendingMovesMetadata::BASE_SIZE,
+        seeds = [b"moves", game.game_id.to_le_bytes().as_ref(), planet_hash.as_ref()],
+        bump,
+    )]
+    pub pending_moves: Box<Account<'info, PendingMovesMetadata>>,
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + CombatLog::INIT_SPACE,
+        seeds = [b"combat_log", game.game_id.to_le_bytes().as_ref(), planet_hash.as_ref()],
+        bump,
+    )]
+    pub combat_log: Box<Account<'info, CombatLog>>,
+    #[account(
+        init_if_needed,
+        space = 9,
+        payer = payer,
+        seeds = [&SIGN_PDA_SEED],
+        bump,
+        address = derive_sign_pda!(),
+    )]
+    pub sign_pda_account: Account<'info, ArciumSignerAccount>,
+    #[account(address = derive_mxe_pda!())]
+    pub mxe_account: Box<Account<'info, MXEAccount>>,
+    #[account(mut, address = derive_mempool_pda!(mxe_account, ErrorCode::ClusterNotSet))]
+    /// CHECK: mempool_account
+    pub mempool_account: UncheckedAccount<'info>,
+    #[account(mut, address = derive_execpool_pda!(mxe_account, ErrorCode::ClusterNotSet))]
+    /// CHECK: executing_pool
+    pub executing_pool: UncheckedAccount<'info>,
+    /// Supplies the computation offset; advanced by the handler.
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + ComputationOffsetTracker::INIT_SPACE,
+        seeds = [b"offset", game.game_id.to_le_bytes().as_ref(), payer.key().as_ref()],
+        bump,
+    )]
+    pub offset_tracker: Box<Account<'info, ComputationOffsetTracker>>,
+    #[account(
+        mut,
+        address = derive_comp_pda!(
+            derive_computation_offset(game.game_id, &payer.key(), offset_tracker.next_offset),
+            mxe_account,
+            ErrorCode::ClusterNotSet
+        )
+    )]
+    /// CHECK: computation_account
+    pub computation_account: UncheckedAccount<'info>,
+    #[account(address = derive_comp_def_pda!(COMP_DEF_OFFSET_INIT_SPAWN_PLANET))]
+    pub comp_def_account: Box<Account<'info, ComputationDefinitionAccount>>,
+    #[account(mut, address = derive_cluster_pda!(mxe_account, ErrorCode::ClusterNotSet))]
+    pub cluster_account: Box<Account<'info, Cluster>>,
+    #[account(mut, address = ARCIUM_FEE_POOL_ACCOUNT_ADDRESS)]
+    pub pool_account: Box<Account<'info, FeePool>>,
+    #[account(mut, address = ARCIUM_CLOCK_ACCOUNT_ADDRESS)]
+    pub clock_account: Box<Account<'info, ClockAccount>>,
+    pub system_program: Program<'info, System>,
+    pub arcium_program: Program<'info, Arcium>,
+}
+
+#[callback_accounts("init_spawn_planet")]
+#[derive(Accounts)]
+pub struct InitSpawnPlanetCallback<'info> {
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(address = derive_comp_def_pda!(COMP_DEF_OFFSET_INIT_SPAWN_PLANET))]
+    pub comp_def_account: Box<Account<'info, ComputationDefinitionAccount>>,
+    #[account(address = derive_mxe_pda!())]
+    pub mxe_account: Box<Account<'info, MXEAccount>>,
+    /// CHECK: computation_account
+    pub computation_account: UncheckedAccount<'info>,
+    #[account(address = derive_cluster_pda!(mxe_account, ErrorCode::ClusterNotSet))]
+    pub cluster_account: Box<Account<'info, Cluster>>,
+    #[account(address = ::anchor_lang::solana_program::sysvar::instructions::ID)]
+    /// CHECK: instructions_sysvar
+    pub instructions_sysvar: AccountInfo<'info>,
+    #[account(mut)]
+    pub game: Box<Account<'info, Game>>,
+    #[account(mut)]
+    pub player: Box<Account<'info, Player>>,
+    #[account(mut)]
+    pub celestial_body: Box<Account<'info, EncryptedCelestialBody>>,
+    #[account(mut)]
+    pub player_profile: Option<Box<Account<'info, PlayerProfile>>>,
+}
+
+// --- Queue Process Move ---
+
+#[queue_computation_accounts("process_move", payer)]
+#[derive(Accounts)]
+pub struct QueueProcessMove<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        seeds = [b"game", game.game_id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub game: Box<Account<'info, Game>>,
+    #[account(
+        mut,
+        seeds = [b"player", game.game_id.to_le_bytes().as_ref(), payer.key().as_ref()],
+        bump,
+    )]
+    pub player: Box<Account<'info, Player>>,
+    /// Optional: payer's cross-game profile, credited with the ships of a valid
+    /// move; attaching it makes that ship count public
+    #[account(
+        seeds = [b"profile", payer.key().as_ref()],
+        bump,
+    )]
+    pub player_profile: Option<Box<Account<'info, PlayerProfile>>>,
+    /// Optional: payer's alliance, lets the fleet reinforce an ally's planet
+    pub alliance: Option<Box<Account<'info, Alliance>>>,
+    #[account(mut)]
+    pub source_body: Box<Account<'info, EncryptedCelestialBody>>,
+    /// Source planet's pending moves metadata (flush check + move cooldown)
+    #[account(mut)]
+    pub source_pending: Box<Account<'info, PendingMovesMetadata>>,
+    /// Target planet's pending moves metadata (mut, realloc when full to double capacity)
+    #[account(
+        mut,
+        realloc = PendingMovesMetadata::space(target_pending.capacity_for(target_pending.moves.len() + 1)),
+        realloc::payer = payer,
+        realloc::zero = false,
+    )]
+    pub target_pending: Box<Account<'info, PendingMovesMetadata>>,
+    /// PendingMoveAccount to store the MPC output Enc<Mxe, PendingMoveData>.
+    /// PDA seeded by predicted move_id = next_move_id + queued_count (before increment).
+    #[account(
+        init,
+        payer = payer,
+        space = PendingMoveAccount::MAX_SIZE,
+        seeds = [
+            b"move",
+            target_pending.game_id.to_le_bytes().as_ref(),
+            target_pending.planet_hash.as_ref(),
+            target_pending.next_move_id.wrapping_add(target_pending.queued_count as u64).to_le_bytes().as_ref(),
+        ],
+        bump,
+    )]
+    pub move_account: Box<Account<'info, PendingMoveAccount>>,
+    #[account(
+        init_if_needed,
+        space = 9,
+        payer = payer,
+        seeds = [&SIGN_PDA_SEED],
+        bump,
+        address = derive_sign_pda!(),
+    )]
+    pub sign_pda_account: Account<'info, ArciumSignerAccount>,
+    #[account(address = derive_mxe_pda!())]
+    pub mxe_account: Box<Account<'info, MXEAccount>>,
+    #[account(mut, address = derive_mempool_pda!(mxe_account, ErrorCode::ClusterNotSet))]
+    /// CHECK: mempool_account
+    pub mempool_account: UncheckedAccount<'info>,
+    #[account(mut, address = derive_execpool_pda!(mxe_account, ErrorCode::ClusterNotSet))]
+    /// CHECK: executing_pool
+    pub executing_pool: UncheckedAccount<'info>,
+    /// Supplies the computation offset; advanced by the handler.
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + ComputationOffsetTracker::INIT_SPACE,
+        seeds = [b"offset", game.game_id.to_le_bytes().as_ref(), payer.key().as_ref()],
+        bump,
+    )]
+    pub offset_tracker: Box<Account<'info, ComputationOffsetTracker>>,
+    #[account(
+        mut,
+        address = derive_comp_pda!(
+            derive_computation_offset(game.game_id, &payer.key(), offset_tracker.next_offset),
+            mxe_account,
+            ErrorCode::ClusterNotSet
+        )
+    )]
+    /// CHECK: computation_account
+    pub computation_account: UncheckedAccount<'info>,
+    #[account(address = derive_comp_def_pda!(COMP_DEF_OFFSET_PROCESS_MOVE))]
+    pub comp_def_account: Box<Account<'info, ComputationDefinitionAccount>>,
+    #[account(mut, address = derive_cluster_pda!(mxe_account, ErrorCode::ClusterNotSet))]
+    pub cluster_account: Box<Account<'info, Cluster>>,
+    #[account(mut, address = ARCIUM_FEE_POOL_ACCOUNT_ADDRESS)]
+    pub pool_account: Box<Account<'info, FeePool>>,
+    #[account(mut, address = ARCIUM_CLOCK_ACCOUNT_ADDRESS)]
+    pub clock_account: Box<Account<'info, ClockAccount>>,
+    pub system_program: Program<'info, System>,
+    pub arcium_program: Program<'info, Arcium>,
+}
+
+#[callback_accounts("process_move")]
+#[derive(Accounts)]
+pub struct ProcessMoveCallback<'info> {
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(address = derive_comp_def_pda!(COMP_DEF_OFFSET_PROCESS_MOVE))]
+    pub comp_def_account: Box<Account<'info, ComputationDefinitionAccount>>,
+    #[account(address = derive_mxe_pda!())]
+    pub mxe_account: Box<Account<'info, MXEAccount>>,
+    /// CHECK: computation_account
+    pub computation_account: UncheckedAccount<'info>,
+    #[account(address = derive_cluster_pda!(mxe_account, ErrorCode::ClusterNotSet))]
+    pub cluster_account: Box<Account<'info, Cluster>>,
+    #[account(address = ::anchor_lang::solana_program::sysvar::instructions::ID)]
+    /// CHECK: instructions_sysvar
+    pub instructions_sysvar: AccountInfo<'info>,
+    #[account(mut)]
+    pub source_body: Box<Account<'info, EncryptedCelestialBody>>,
+    #[account(mut)]
+    pub move_account: Box<Account<'info, PendingMoveAccount>>,
+    #[account(mut)]
+    pub target_pending: Box<Account<'info, PendingMovesMetadata>>,
+    #[account(mut)]
+    pub player_profile: Option<Box<Account<'info, PlayerProfile>>>,
+}
+
+// --- Queue Flush Planet ---
+
+#[queue_computation_accounts("flush_planet", payer)]
+#[derive(Accounts)]
+pub struct QueueFlushPlanet<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        seeds = [b"game", game.game_id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub game: Box<Account<'info, Game>>,
+    #[account(
+        mut,
+        seeds = [b"planet", game.game_id.to_le_bytes().as_ref(), celestial_body.planet_hash.as_ref()],
+        bump,
+    )]
+    pub celestial_body: Box<Account<'info, EncryptedCelestialBody>>,
+    #[account(
+        mut,
+        seeds = [b"moves", game.game_id.to_le_bytes().as_ref(), celestial_body.planet_hash.as_ref()],
+        bump,
+        constraint = pending_moves.game_id == game.game_id @ ErrorCode::FlushFailed,
+        constraint = pending_moves.planet_hash == celestial_body.planet_hash @ ErrorCode::FlushFailed,
+    )]
+    pub pending_moves: Box<Account<'info, PendingMovesMetadata>>,
+    /// Planet's combat counters, updated by the callback
+    #[account(
+        seeds = [b"combat_log", game.game_id.to_le_bytes().as_ref(), celestial_body.planet_hash.as_ref()],
+        bump,
+    )]
+    pub combat_log: Box<Account<'info, CombatLog>>,
+    /// Player PDA of the move's payer (validated in handler), credited with
+    /// PointsBurning points if the move captures the planet
+    pub attacker_player: Box<Account<'info, Player>>,
+    /// Optional: the move payer's cross-game profile (validated in handler),
+    /// credited with the capture
+    #[account(
+        seeds = [b"profile", attacker_profile.owner.as_ref()],
+        bump,
+    )]
+    pub attacker_profile: Option<Box<Account<'info, PlayerProfile>>>,
+    #[account(
+        init_if_needed,
+        space = 9,
+        payer = payer,
+        seeds = [&SIGN_PDA_SEED],
+        bump,
+        address = derive_sign_pda!(),
+    )]
+    pub sign_pda_account: Account<'info, ArciumSignerAccount>,
+    #[account(address = derive_mxe_pda!())]
+    pub mxe_account: Box<Account<'info, MXEAccount>>,
+    #[account(mut, address = derive_mempool_pda!(mxe_account, ErrorCode::ClusterNotSet))]
+    /// CHECK: mempool_account
+    pub mempool_account: UncheckedAccount<'info>,
+    #[account(mut, address = derive_execpool_pda!(mxe_account, ErrorCode::ClusterNotSet))]
+    /// CHECK: executing_pool
+    pub executing_pool: UncheckedAccount<'info>,
+    /// Supplies the computation offset; advanced by the handler.
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + ComputationOffsetTracker::INIT_SPACE,
+        seeds = [b"offset", game.game_id.to_le_bytes().as_ref(), payer.key().as_ref()],
+        bump,
+    )]
+    pub offset_tracker: Box<Account<'info, ComputationOffsetTracker>>,
+    #[account(
+        mut,
+        address = derive_comp_pda!(
+            derive_computation_offset(game.game_id, &payer.key(), offset_tracker.next_offset),
+            mxe_account,
+            ErrorCode::ClusterNotSet
+        )
+    )]
+    /// CHECK: computation_account
+    pub computation_account: UncheckedAccount<'info>,
+    #[account(address = derive_comp_def_pda!(COMP_DEF_OFFSET_FLUSH_PLANET))]
+    pub comp_def_account: Box<Account<'info, ComputationDefinitionAccount>>,
+    #[account(mut, address = derive_cluster_pda!(mxe_account, ErrorCode::ClusterNotSet))]
+    pub cluster_account: Box<Account<'info, Cluster>>,
+    #[account(mut, address = ARCIUM_FEE_POOL_ACCOUNT_ADDRESS)]
+    pub pool_account: Box<Account<'info, FeePool>>,
+    #[account(mut, address = ARCIUM_CLOCK_ACCOUNT_ADDRESS)]
+    pub clock_account: Box<Account<'info, ClockAccount>>,
+    pub system_program: Program<'info, System>,
+    pub arcium_program: Program<'info, Arcium>,
+}
+
+#[callback_accounts("flush_planet")]
+#[derive(Accounts)]
+pub struct FlushPlanetCallback<'info> {
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(address = derive_comp_def_pda!(COMP_DEF_OFFSET_FLUSH_PLANET))]
+    pub comp_def_account: Box<Account<'info, ComputationDefinitionAccount>>,
+    #[account(address = derive_mxe_pda!())]
+    pub mxe_account: Box<Account<'info, MXEAccount>>,
+    /// CHECK: computation_account
+    pub computation_account: UncheckedAccount<'info>,
+    #[account(address = derive_cluster_pda!(mxe_account, ErrorCode::ClusterNotSet))]
+    pub cluster_account: Box<Account<'info, Cluster>>,
+    #[account(address = ::anchor_lang::solana_program::sysvar::instructions::ID)]
+    /// CHECK: instructions_sysvar
+    pub instructions_sysvar: AccountInfo<'info>,
+    #[account(mut)]
+    pub celestial_body: Box<Account<'info, EncryptedCelestialBody>>,
+    #[account(mut)]
+    pub pending_moves: Box<Account<'info, PendingMovesMetadata>>,
+    pub game: Box<Account<'info, Game>>,
+    #[account(mut)]
+    pub attacker_player: Box<Account<'info, Player>>,
+    /// The PendingMoveAccount that was flushed (queue_flush_planet's remaining_accounts[0])
+    pub move_account: Box<Account<'info, PendingMoveAccount>>,
+    #[account(mut)]
+    pub combat_log: Box<Account<'info, CombatLog>>,
+    /// CHECK: the flushed move's payer; receives rent freed when pending_moves shrinks
+    #[account(mut, address = move_account.payer)]
+    pub move_payer: UncheckedAccount<'info>,
+    #[account(mut, constraint = attacker_profile.owner == move_account.payer)]
+    pub attacker_profile: Option<Box<Account<'info, PlayerProfile>>>,
+}
 
-#[error_code]
-pub enum ErrorCode {
-    #[msg("The computation was aborted")]
-    AbortedComputation,
-    #[msg("Cluster not set")]
-    ClusterNotSet,
-    #[msg("Invalid map diameter")]
-    InvalidMapDiameter,
-    #[msg("Invalid game speed")]
-    InvalidGameSpeed,
-    #[msg("Hash rounds must be >= 1")]
-    InvalidHashRounds,
-    #[msg("End slot must be after start slot")]
-    InvalidTimeRange,
-    #[msg("Whitelist games require a server pubkey")]
-    WhitelistRequiresServer,
-    #[msg("Whitelist server signature required")]
-    WhitelistServerRequired,
-    #[msg("Invalid server key")]
-    InvalidServerKey,
-    #[msg("Player has already spawned")]
-    AlreadySpawned,
-    #[msg("Player has not spawned yet")]
-    NotSpawned,
-    #[msg("Game has not started")]
-    GameNotStarted,
-    #[msg("Game has ended")]
-    GameEnded,
-    #[msg("Game has not ended yet")]
-    GameNotEnded,
-    #[msg("Invalid planet hash")]
-    InvalidPlanetHash,
-    #[msg("Coordinates are outside map bounds")]
-    CoordinatesOutOfBounds,
-    #[msg("Too many pending moves on target planet")]
-    TooManyPendingMoves,
-    #[msg("Invalid init planet result")]
-    InvalidInitPlanet,
-    #[msg("Invalid spawn validation")]
-    InvalidSpawnValidation,
-    #[msg("Invalid move input")]
-    InvalidMoveInput,
-    #[msg("Flush failed")]
-    FlushFailed,
-    #[msg("Upgrade failed")]
-    UpgradeFailed,
-    #[msg("Must flush landed moves before processing new moves")]
-    MustFlushFirst,
+// --- Queue Upgrade Planet ---
+
+#[queue_computation_accounts("upgrade_planet", payer)]
+#[derive(Accounts)]
+pub struct QueueUpgradePlanet<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        seeds = [b"game", game.game_id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub game: Box<Account<'info, Game>>,
+    #[account(mut)]
+    pub celestial_body: Box<Account<'info, EncryptedCelestialBody>>,
+    /// Planet's pending moves metadata (read-only, must be empty to upgrade)
+    #[account(
+        seeds = [b"moves", game.game_id.to_le_bytes().as_ref(), celestial_body.planet_hash.as_ref()],
+        bump,
+    )]
+    pub pending_moves: Box<Account<'info, PendingMovesMetadata>>,
+    #[account(
+        init_if_needed,
+        space = 9,
+        payer = payer,
+        seeds = [&SIGN_PDA_SEED],
+        bump,
+        address = derive_sign_pda!(),
+    )]
+    pub sign_pda_account: Account<'info, ArciumSignerAccount>,
+    #[account(address = derive_mxe_pda!())]
+    pub mxe_account: Box<Account<'info, MXEAccount>>,
+    #[account(mut, address = derive_mempool_pda!(mxe_account, ErrorCode::ClusterNotSet))]
+    /// CHECK: mempool_account
+    pub mempool_account: UncheckedAccount<'info>,
+    #[account(mut, address = derive_execpool_pda!(mxe_account, ErrorCode::ClusterNotSet))]
+    /// CHECK: executing_pool
+    pub executing_pool: UncheckedAccount<'info>,
+    /// Supplies the computation offset; advanced by the handler.
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + ComputationOffsetTracker::INIT_SPACE,
+        seeds = [b"offset", game.game_id.to_le_bytes().as_ref(), payer.key().as_ref()],
+        bump,
+    )]
+    pub offset_tracker: Box<Account<'info, ComputationOffsetTracker>>,
+    #[account(
+        mut,
+        address = derive_comp_pda!(
+            derive_computation_offset(game.game_id, &payer.key(), offset_tracker.next_offset),
+            mxe_account,
+            ErrorCode::ClusterNotSet
+        )
+    )]
+    /// CHECK: computation_account
+    pub computation_account: UncheckedAccount<'info>,
+    #[account(address = derive_comp_def_pda!(COMP_DEF_OFFSET_UPGRADE_PLANET))]
+    pub comp_def_account: Box<Account<'info, ComputationDefinitionAccount>>,
+    #[account(mut, address = derive_cluster_pda!(mxe_account, ErrorCode::ClusterNotSet))]
+    pub cluster_account: Box<Account<'info, Cluster>>,
+    #[account(mut, address = ARCIUM_FEE_POOL_ACCOUNT_ADDRESS)]
+    pub pool_account: Box<Account<'info, FeePool>>,
+    #[account(mut, address = ARCIUM_CLOCK_ACCOUNT_ADDRESS)]
+    pub clock_account: Box<Account<'info, ClockAccount>>,
+    pub system_program: Program<'info, System>,
+    pub arcium_program: Program<'info, Arcium>,
 }
 
-// ===========================================================================
-// Account Contexts
-// ===========================================================================
+#[callback_accounts("upgrade_planet")]
+#[derive(Accounts)]
+pub struct UpgradePlanetCallback<'info> {
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(address = derive_comp_def_pda!(COMP_DEF_OFFSET_UPGRADE_PLANET))]
+    pub comp_def_account: Box<Account<'info, ComputationDefinitionAccount>>,
+    #[account(address = derive_mxe_pda!())]
+    pub mxe_account: Box<Account<'info, MXEAccount>>,
+    /// CHECK: computation_account
+    pub computation_account: UncheckedAccount<'info>,
+    #[account(address = derive_cluster_pda!(mxe_account, ErrorCode::ClusterNotSet))]
+    pub cluster_account: Box<Account<'info, Cluster>>,
+    #[account(address = ::anchor_lang::solana_program::sysvar::instructions::ID)]
+    /// CHECK: instructions_sysvar
+    pub instructions_sysvar: AccountInfo<'info>,
+    #[account(mut)]
+    pub celestial_body: Box<Account<'info, EncryptedCelestialBody>>,
+    pub game: Box<Account<'info, Game>>,
+    /// CHECK: queue_upgrade_planet's payer, fixed by the program at queue time
+    pub actor: UncheckedAccount<'info>,
+}
 
-// --- Computation Definition Initializers ---
+// --- Queue Surrender Planet ---
 
-#[init_computation_definition_accounts("init_planet", payer)]
+#[queue_computation_accounts("surrender_planet", payer)]
 #[derive(Accounts)]
-pub struct InitInitPlanetCompDef<'info> {
+pub struct QueueSurrenderPlanet<'info> {
     #[account(mut)]
     pub payer: Signer<'info>,
-    #[account(mut, address = derive_mxe_pda!())]
-    pub mxe_account: Box<Account<'info, MXEAccount>>,
+    #[account(
+        seeds = [b"game", game.game_id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub game: Box<Account<'info, Game>>,
+    #[account(
+        seeds = [b"player", game.game_id.to_le_bytes().as_ref(), payer.key().as_ref()],
+        bump,
+    )]
+    pub player: Box<Account<'info, Player>>,
     #[account(mut)]
-    /// CHECK: comp_def_account, checked by arcium program.
-    pub comp_def_account: UncheckedAccount<'info>,
-    #[account(mut, address = derive_mxe_lut_pda!())]
-    /// CHECK: address_lookup_table, checked by arcium program.
-    pub address_lookup_table: UncheckedAccount<'info>,
-    /// CHECK: lut_program
-    #[account(address = LUT_PROGRAM_ID)]
-    pub lut_program: UncheckedAccount<'info>,
+    pub celestial_body: Box<Account<'info, EncryptedCelestialBody>>,
+    /// Planet's pending moves metadata (read-only, for flush check)
+    #[account(
+        seeds = [b"moves", game.game_id.to_le_bytes().as_ref(), celestial_body.planet_hash.as_ref()],
+        bump,
+    )]
+    pub pending_moves: Box<Account<'info, PendingMovesMetadata>>,
+    #[account(
+        init_if_needed,
+        space = 9,
+        payer = payer,
+        seeds = [&SIGN_PDA_SEED],
+        bump,
+        address = derive_sign_pda!(),
+    )]
+    pub sign_pda_account: Account<'info, ArciumSignerAccount>,
+    #[account(address = derive_mxe_pda!())]
+    pub mxe_account: Box<Account<'info, MXEAccount>>,
+    #[account(mut, address = derive_mempool_pda!(mxe_account, ErrorCode::ClusterNotSet))]
+    /// CHECK: mempool_account
+    pub mempool_account: UncheckedAccount<'info>,
+    #[account(mut, address = derive_execpool_pda!(mxe_account, ErrorCode::ClusterNotSet))]
+    /// CHECK: executing_pool
+    pub executing_pool: UncheckedAccount<'info>,
+    /// Supplies the computation offset; advanced by the handler.
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + ComputationOffsetTracker::INIT_SPACE,
+        seeds = [b"offset", game.game_id.to_le_bytes().as_ref(), payer.key().as_ref()],
+        bump,
+    )]
+    pub offset_tracker: Box<Account<'info, ComputationOffsetTracker>>,
+    #[account(
+        mut,
+        address = derive_comp_pda!(
+            derive_computation_offset(game.game_id, &payer.key(), offset_tracker.next_offset),
+            mxe_account,
+            ErrorCode::ClusterNotSet
+        )
+    )]
+    /// CHECK: computation_account
+    pub computation_account: UncheckedAccount<'info>,
+    #[account(address = derive_comp_def_pda!(COMP_DEF_OFFSET_SURRENDER_PLANET))]
+    pub comp_def_account: Box<Account<'info, ComputationDefinitionAccount>>,
+    #[account(mut, address = derive_cluster_pda!(mxe_account, ErrorCode::ClusterNotSet))]
+    pub cluster_account: Box<Account<'info, Cluster>>,
+    #[account(mut, address = ARCIUM_FEE_POOL_ACCOUNT_ADDRESS)]
+    pub pool_account: Box<Account<'info, FeePool>>,
+    #[account(mut, address = ARCIUM_CLOCK_ACCOUNT_ADDRESS)]
+    pub clock_account: Box<Account<'info, ClockAccount>>,
     pub system_program: Program<'info, System>,
     pub arcium_program: Program<'info, Arcium>,
 }
 
-#[init_computation_definition_accounts("init_spawn_planet", payer)]
+#[callback_accounts("surrender_planet")]
 #[derive(Accounts)]
-pub struct InitInitSpawnPlanetCompDef<'info> {
+pub struct SurrenderPlanetCallback<'info> {
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(address = derive_comp_def_pda!(COMP_DEF_OFFSET_SURRENDER_PLANET))]
+    pub comp_def_account: Box<Account<'info, ComputationDefinitionAccount>>,
+    #[account(address = derive_mxe_pda!())]
+    pub mxe_account: Box<Account<'info, MXEAccount>>,
+    /// CHECK: computation_account
+    pub computation_account: UncheckedAccount<'info>,
+    #[account(address = derive_cluster_pda!(mxe_account, ErrorCode::ClusterNotSet))]
+    pub cluster_account: Box<Account<'info, Cluster>>,
+    #[account(address = ::anchor_lang::solana_program::sysvar::instructions::ID)]
+    /// CHECK: instructions_sysvar
+    pub instructions_sysvar: AccountInfo<'info>,
+    #[account(mut)]
+    pub celestial_body: Box<Account<'info, EncryptedCelestialBody>>,
+    pub player: Box<Account<'info, Player>>,
+}
+
+// --- Queue Transfer Resources ---
+
+#[queue_computation_accounts("transfer_resources", payer)]
+#[derive(Accounts)]
+pub struct QueueTransferResources<'info> {
     #[account(mut)]
     pub payer: Signer<'info>,
-    #[account(mut, address = derive_mxe_pda!())]
-    pub mxe_account: Box<Account<'info, MXEAccount>>,
+    #[account(
+        seeds = [b"game", game.game_id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub game: Box<Account<'info, Game>>,
     #[account(mut)]
-    /// CHECK: comp_def_account, checked by arcium program.
-    pub comp_def_account: UncheckedAccount<'info>,
-    #[account(mut, address = derive_mxe_lut_pda!())]
-    /// CHECK: address_lookup_table, checked by arcium program.
-    pub address_lookup_table: UncheckedAccount<'info>,
-    /// CHECK: lut_program
-    #[account(address = LUT_PROGRAM_ID)]
-    pub lut_program: UncheckedAccount<'info>,
+    pub source_body: Box<Account<'info, EncryptedCelestialBody>>,
+    #[account(mut)]
+    pub dest_body: Box<Account<'info, EncryptedCelestialBody>>,
+    #[account(
+        seeds = [b"moves", game.game_id.to_le_bytes().as_ref(), source_body.planet_hash.as_ref()],
+        bump,
+    )]
+    pub source_pending: Box<Account<'info, PendingMovesMetadata>>,
+    #[account(
+        seeds = [b"moves", game.game_id.to_le_bytes().as_ref(), dest_body.planet_hash.as_ref()],
+        bump,
+    )]
+    pub dest_pending: Box<Account<'info, PendingMovesMetadata>>,
+    /// Optional: payer's alliance, enables transfers to an ally's planet
+    pub alliance: Option<Box<Account<'info, Alliance>>>,
+    #[account(
+        init_if_needed,
+        space = 9,
+        payer = payer,
+        seeds = [&SIGN_PDA_SEED],
+        bump,
+        address = derive_sign_pda!(),
+    )]
+    pub sign_pda_account: Account<'info, ArciumSignerAccount>,
+    #[account(address = derive_mxe_pda!())]
+    pub mxe_account: Box<Account<'info, MXEAccount>>,
+    #[account(mut, address = derive_mempool_pda!(mxe_account, ErrorCode::ClusterNotSet))]
+    /// CHECK: mempool_account
+    pub mempool_account: UncheckedAccount<'info>,
+    #[account(mut, address = derive_execpool_pda!(mxe_account, ErrorCode::ClusterNotSet))]
+    /// CHECK: executing_pool
+    pub executing_pool: UncheckedAccount<'info>,
+    /// Supplies the computation offset; advanced by the handler.
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + ComputationOffsetTracker::INIT_SPACE,
+        seeds = [b"offset", game.game_id.to_le_bytes().as_ref(), payer.key().as_ref()],
+        bump,
+    )]
+    pub offset_tracker: Box<Account<'info, ComputationOffsetTracker>>,
+    #[account(
+        mut,
+        address = derive_comp_pda!(
+            derive_computation_offset(game.game_id, &payer.key(), offset_tracker.next_offset),
+            mxe_account,
+            ErrorCode::ClusterNotSet
+        )
+    )]
+    /// CHECK: computation_account
+    pub computation_account: UncheckedAccount<'info>,
+    #[account(address = derive_comp_def_pda!(COMP_DEF_OFFSET_TRANSFER_RESOURCES))]
+    pub comp_def_account: Box<Account<'info, ComputationDefinitionAccount>>,
+    #[account(mut, address = derive_cluster_pda!(mxe_account, ErrorCode::ClusterNotSet))]
+    pub cluster_account: Box<Account<'info, Cluster>>,
+    #[account(mut, address = ARCIUM_FEE_POOL_ACCOUNT_ADDRESS)]
+    pub pool_account: Box<Account<'info, FeePool>>,
+    #[account(mut, address = ARCIUM_CLOCK_ACCOUNT_ADDRESS)]
+    pub clock_account: Box<Account<'info, ClockAccount>>,
     pub system_program: Program<'info, System>,
     pub arcium_program: Program<'info, Arcium>,
 }
 
-#[init_computation_definition_accounts("process_move", payer)]
+#[callback_accounts("transfer_resources")]
 #[derive(Accounts)]
-pub struct InitProcessMoveCompDef<'info> {
-    #[account(mut)]
-    pub payer: Signer<'info>,
-    #[account(mut, address = derive_mxe_pda!())]
+pub struct TransferResourcesCallback<'info> {
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(address = derive_comp_def_pda!(COMP_DEF_OFFSET_TRANSFER_RESOURCES))]
+    pub comp_def_account: Box<Account<'info, ComputationDefinitionAccount>>,
+    #[account(address = derive_mxe_pda!())]
     pub mxe_account: Box<Account<'info, MXEAccount>>,
+    /// CHECK: computation_account
+    pub computation_account: UncheckedAccount<'info>,
+    #[account(address = derive_cluster_pda!(mxe_account, ErrorCode::ClusterNotSet))]
+    pub cluster_account: Box<Account<'info, Cluster>>,
+    #[account(address = ::anchor_lang::solana_program::sysvar::instructions::ID)]
+    /// CHECK: instructions_sysvar
+    pub instructions_sysvar: AccountInfo<'info>,
     #[account(mut)]
-    /// CHECK: comp_def_account, checked by arcium program.
-    pub comp_def_account: UncheckedAccount<'info>,
-    #[account(mut, address = derive_mxe_lut_pda!())]
-    /// CHECK: address_lookup_table, checked by arcium program.
-    pub address_lookup_table: UncheckedAccount<'info>,
-    /// CHECK: lut_program
-    #[account(address = LUT_PROGRAM_ID)]
-    pub lut_program: UncheckedAccount<'info>,
-    pub system_program: Program<'info, System>,
-    pub arcium_program: Program<'info, Arcium>,
+    pub source_body: Box<Account<'info, EncryptedCelestialBody>>,
+    #[account(mut)]
+    pub dest_body: Box<Account<'info, EncryptedCelestialBody>>,
 }
 
-#[init_computation_definition_accounts("flush_planet", payer)]
+// --- Queue Spy Move ---
+
+#[queue_computation_accounts("spy_move", payer)]
 #[derive(Accounts)]
-pub struct InitFlushPlanetCompDef<'info> {
+pub struct QueueSpyMove<'info> {
     #[account(mut)]
     pub payer: Signer<'info>,
-    #[account(mut, address = derive_mxe_pda!())]
+    #[account(
+        seeds = [b"game", game.game_id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub game: Box<Account<'info, Game>>,
+    pub source_body: Box<Account<'info, EncryptedCelestialBody>>,
+    pub target_body: Box<Account<'info, EncryptedCelestialBody>>,
+    #[account(
+        seeds = [b"moves", game.game_id.to_le_bytes().as_ref(), source_body.planet_hash.as_ref()],
+        bump,
+    )]
+    pub source_pending: Box<Account<'info, PendingMovesMetadata>>,
+    #[account(
+        init_if_needed,
+        space = 9,
+        payer = payer,
+        seeds = [&SIGN_PDA_SEED],
+        bump,
+        address = derive_sign_pda!(),
+    )]
+    pub sign_pda_account: Account<'info, ArciumSignerAccount>,
+    #[account(address = derive_mxe_pda!())]
     pub mxe_account: Box<Account<'info, MXEAccount>>,
-    #[account(mut)]
-    /// CHECK: comp_def_account, checked by arcium program.
-    pub comp_def_account: UncheckedAccount<'info>,
-    #[account(mut, address = derive_mxe_lut_pda!())]
-    /// CHECK: address_lookup_table, checked by arcium program.
-    pub address_lookup_table: UncheckedAccount<'info>,
-    /// CHECK: lut_program
-    #[account(address = LUT_PROGRAM_ID)]
-    pub lut_program: UncheckedAccount<'info>,
+    #[account(mut, address = derive_mempool_pda!(mxe_account, ErrorCode::ClusterNotSet))]
+    /// CHECK: mempool_account
+    pub mempool_account: UncheckedAccount<'info>,
+    #[account(mut, address = derive_execpool_pda!(mxe_account, ErrorCode::ClusterNotSet))]
+    /// CHECK: executing_pool
+    pub executing_pool: UncheckedAccount<'info>,
+    /// Supplies the computation offset; advanced by the handler.
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + ComputationOffsetTracker::INIT_SPACE,
+        seeds = [b"offset", game.game_id.to_le_bytes().as_ref(), payer.key().as_ref()],
+        bump,
+    )]
+    pub offset_tracker: Box<Account<'info, ComputationOffsetTracker>>,
+    #[account(
+        mut,
+        address = derive_comp_pda!(
+            derive_computation_offset(game.game_id, &payer.key(), offset_tracker.next_offset),
+            mxe_account,
+            ErrorCode::ClusterNotSet
+        )
+    )]
+    /// CHECK: computation_account
+    pub computation_account: UncheckedAccount<'info>,
+    #[account(address = derive_comp_def_pda!(COMP_DEF_OFFSET_SPY_MOVE))]
+    pub comp_def_account: Box<Account<'info, ComputationDefinitionAccount>>,
+    #[account(mut, address = derive_cluster_pda!(mxe_account, ErrorCode::ClusterNotSet))]
+    pub cluster_account: Box<Account<'info, Cluster>>,
+    #[account(mut, address = ARCIUM_FEE_POOL_ACCOUNT_ADDRESS)]
+    pub pool_account: Box<Account<'info, FeePool>>,
+    #[account(mut, address = ARCIUM_CLOCK_ACCOUNT_ADDRESS)]
+    pub clock_account: Box<Account<'info, ClockAccount>>,
     pub system_program: Program<'info, System>,
     pub arcium_program: Program<'info, Arcium>,
 }
 
-#[init_computation_definition_accounts("upgrade_planet", payer)]
+#[callback_accounts("spy_move")]
 #[derive(Accounts)]
-pub struct InitUpgradePlanetCompDef<'info> {
-    #[account(mut)]
-    pub payer: Signer<'info>,
-    #[account(mut, address = derive_mxe_pda!())]
-    pub mxe_account: Box<Account<'info, MXEAccount>>,
-    #[account(mut)]
-    /// CHECK: comp_def_account, checked by arcium program.
-    pub comp_def_account: UncheckedAccount<'info>,
-    #[account(mut, address = derive_mxe_lut_pda!())]
-    /// CHECK: address_lookup_table, checked by arcium program.
-    pub address_lookup_table: UncheckedAccount<'info>,
-    /// CHECK: lut_program
-    #[account(address = LUT_PROGRAM_ID)]
-    pub lut_program: UncheckedAccount<'info>,
-    pub system_program: Program<'info, System>,
+pub struct SpyMoveCallback<'info> {
     pub arcium_program: Program<'info, Arcium>,
+    #[account(address = derive_comp_def_pda!(COMP_DEF_OFFSET_SPY_MOVE))]
+    pub comp_def_account: Box<Account<'info, ComputationDefinitionAccount>>,
+    #[account(address = derive_mxe_pda!())]
+    pub mxe_account: Box<Account<'info, MXEAccount>>,
+    /// CHECK: computation_account
+    pub computation_account: UncheckedAccount<'info>,
+    #[account(address = derive_cluster_pda!(mxe_account, ErrorCode::ClusterNotSet))]
+    pub cluster_account: Box<Account<'info, Cluster>>,
+    #[account(address = ::anchor_lang::solana_program::sysvar::instructions::ID)]
+    /// CHECK: instructions_sysvar
+    pub instructions_sysvar: AccountInfo<'info>,
+    pub target_body: Box<Account<'info, EncryptedCelestialBody>>,
 }
 
-// --- Game Management ---
+// --- Comets ---
 
 #[derive(Accounts)]
-#[instruction(game_id: u64)]
-pub struct CreateGame<'info> {
+#[instruction(game_id: u64, comet_id: u64)]
+pub struct SpawnComet<'info> {
     #[account(mut)]
     pub admin: Signer<'info>,
     #[account(
-        init,
-        payer = admin,
-        space = 8 + Game::INIT_SPACE,
         seeds = [b"game", game_id.to_le_bytes().as_ref()],
         bump,
+        constraint = game.admin == admin.key() @ ErrorCode::Unauthorized,
     )]
     pub game: Account<'info, Game>,
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + Comet::INIT_SPACE,
+        seeds = [b"comet", game_id.to_le_bytes().as_ref(), comet_id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub comet: Account<'info, Comet>,
     pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
-#[instruction(game_id: u64)]
-pub struct InitPlayer<'info> {
-    #[account(mut)]
-    pub owner: Signer<'info>,
+#[instruction(game_id: u64, comet_id: u64)]
+pub struct ReclaimComet<'info> {
+    pub admin: Signer<'info>,
     #[account(
         seeds = [b"game", game_id.to_le_bytes().as_ref()],
         bump,
+        constraint = game.admin == admin.key() @ ErrorCode::Unauthorized,
     )]
     pub game: Account<'info, Game>,
     #[account(
-        init,
-        payer = owner,
-        space = 8 + Player::INIT_SPACE,
-        seeds = [b"player", game_id.to_le_bytes().as_ref(), owner.key().as_ref()],
+        mut,
+        seeds = [b"comet", game_id.to_le_bytes().as_ref(), comet_id.to_le_bytes().as_ref()],
         bump,
+        close = creator,
     )]
-    pub player: Account<'info, Player>,
-    pub server: Option<Signer<'info>>,
+    pub comet: Account<'info, Comet>,
+    /// CHECK: rent destination; must be the admin who spawned the comet
+    #[account(mut, address = comet.creator)]
+    pub creator: UncheckedAccount<'info>,
+}
+
+#[queue_computation_accounts("comet_collect", payer)]
+#[derive(Accounts)]
+pub struct QueueCometCollect<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        seeds = [b"game", game.game_id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub game: Box<Account<'info, Game>>,
+    #[account(mut)]
+    pub celestial_body: Box<Account<'info, EncryptedCelestialBody>>,
+    #[account(
+        mut,
+        seeds = [b"comet", game.game_id.to_le_bytes().as_ref(), comet.comet_id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub comet: Box<Account<'info, Comet>>,
+    #[account(
+        init_if_needed,
+        space = 9,
+        payer = payer,
+        seeds = [&SIGN_PDA_SEED],
+        bump,
+        address = derive_sign_pda!(),
+    )]
+    pub sign_pda_account: Account<'info, ArciumSignerAccount>,
+    #[account(address = derive_mxe_pda!())]
+    pub mxe_account: Box<Account<'info, MXEAccount>>,
+    #[account(mut, address = derive_mempool_pda!(mxe_account, ErrorCode::ClusterNotSet))]
+    /// CHECK: mempool_account
+    pub mempool_account: UncheckedAccount<'info>,
+    #[account(mut, address = derive_execpool_pda!(mxe_account, ErrorCode::ClusterNotSet))]
+    /// CHECK: executing_pool
+    pub executing_pool: UncheckedAccount<'info>,
+    /// Supplies the computation offset; advanced by the handler.
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + ComputationOffsetTracker::INIT_SPACE,
+        seeds = [b"offset", game.game_id.to_le_bytes().as_ref(), payer.key().as_ref()],
+        bump,
+    )]
+    pub offset_tracker: Box<Account<'info, ComputationOffsetTracker>>,
+    #[account(
+        mut,
+        address = derive_comp_pda!(
+            derive_computation_offset(game.game_id, &payer.key(), offset_tracker.next_offset),
+            mxe_account,
+            ErrorCode::ClusterNotSet
+        )
+    )]
+    /// CHECK: computation_account
+    pub computation_account: UncheckedAccount<'info>,
+    #[account(address = derive_comp_def_pda!(COMP_DEF_OFFSET_COMET_COLLECT))]
+    pub comp_def_account: Box<Account<'info, ComputationDefinitionAccount>>,
+    #[account(mut, address = derive_cluster_pda!(mxe_account, ErrorCode::ClusterNotSet))]
+    pub cluster_account: Box<Account<'info, Cluster>>,
+    #[account(mut, address = ARCIUM_FEE_POOL_ACCOUNT_ADDRESS)]
+    pub pool_account: Box<Account<'info, FeePool>>,
+    #[account(mut, address = ARCIUM_CLOCK_ACCOUNT_ADDRESS)]
+    pub clock_account: Box<Account<'info, ClockAccount>>,
     pub system_program: Program<'info, System>,
+    pub arcium_program: Program<'info, Arcium>,
+}
+
+#[callback_accounts("comet_collect")]
+#[derive(Accounts)]
+pub struct CometCollectCallback<'info> {
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(address = derive_comp_def_pda!(COMP_DEF_OFFSET_COMET_COLLECT))]
+    pub comp_def_account: Box<Account<'info, ComputationDefinitionAccount>>,
+    #[account(address = derive_mxe_pda!())]
+    pub mxe_account: Box<Account<'info, MXEAccount>>,
+    /// CHECK: computation_account
+    pub computation_account: UncheckedAccount<'info>,
+    #[account(address = derive_cluster_pda!(mxe_account, ErrorCode::ClusterNotSet))]
+    pub cluster_account: Box<Account<'info, Cluster>>,
+    #[account(address = ::anchor_lang::solana_program::sysvar::instructions::ID)]
+    /// CHECK: instructions_sysvar
+    pub instructions_sysvar: AccountInfo<'info>,
+    #[account(mut)]
+    pub celestial_body: Box<Account<'info, EncryptedCelestialBody>>,
+    #[account(mut)]
+    pub comet: Box<Account<'info, Comet>>,
+    /// CHECK: rent destination when the comet is closed; must be the admin who spawned it
+    #[account(mut, address = comet.creator)]
+    pub creator: UncheckedAccount<'info>,
 }
 
-// --- Queue Init Planet ---
+// --- Queue Mine Asteroid ---
 
-#[queue_computation_accounts("init_planet", payer)]
+#[queue_computation_accounts("mine_asteroid", payer)]
 #[derive(Accounts)]
-#[instruction(computation_offset: u64, planet_hash: [u8; 32])]
-pub struct QueueInitPlanet<'info> {
+pub struct QueueMineAsteroid<'info> {
     #[account(mut)]
     pub payer: Signer<'info>,
     #[account(
@@ -1414,19 +8003,11 @@ pub struct QueueInitPlanet<'info> {
         bump,
     )]
     pub game: Box<Account<'info, Game>>,
-    #[account(
-        init,
-        payer = payer,
-        space = EncryptedCelestialBody::MAX_SIZE,
-        seeds = [b"planet", game.game_id.to_le_bytes().as_ref(), planet_hash.as_ref()],
-        bump,
-    )]
+    #[account(mut)]
     pub celestial_body: Box<Account<'info, EncryptedCelestialBody>>,
+    /// Planet's pending moves metadata (read-only, must be settled to mine)
     #[account(
-        init,
-        payer = payer,
-        space = PendingMovesMetadata::BASE_SIZE,
-        seeds = [b"moves", game.game_id.to_le_bytes().as_ref(), planet_hash.as_ref()],
+        seeds = [b"moves", game.game_id.to_le_bytes().as_ref(), celestial_body.planet_hash.as_ref()],
         bump,
     )]
     pub pending_moves: Box<Account<'info, PendingMovesMetadata>>,
@@ -1447,10 +8028,26 @@ pub struct QueueInitPlanet<'info> {
     #[account(mut, address = derive_execpool_pda!(mxe_account, ErrorCode::ClusterNotSet))]
     /// CHECK: executing_pool
     pub executing_pool: UncheckedAccount<'info>,
-    #[account(mut, address = derive_comp_pda!(computation_offset, mxe_account, ErrorCode::ClusterNotSet))]
+    /// Supplies the computation offset; advanced by the handler.
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + ComputationOffsetTracker::INIT_SPACE,
+        seeds = [b"offset", game.game_id.to_le_bytes().as_ref(), payer.key().as_ref()],
+        bump,
+    )]
+    pub offset_tracker: Box<Account<'info, ComputationOffsetTracker>>,
+    #[account(
+        mut,
+        address = derive_comp_pda!(
+            derive_computation_offset(game.game_id, &payer.key(), offset_tracker.next_offset),
+            mxe_account,
+            ErrorCode::ClusterNotSet
+        )
+    )]
     /// CHECK: computation_account
     pub computation_account: UncheckedAccount<'info>,
-    #[account(address = derive_comp_def_pda!(COMP_DEF_OFFSET_INIT_PLANET))]
+    #[account(address = derive_comp_def_pda!(COMP_DEF_OFFSET_MINE_ASTEROID))]
     pub comp_def_account: Box<Account<'info, ComputationDefinitionAccount>>,
     #[account(mut, address = derive_cluster_pda!(mxe_account, ErrorCode::ClusterNotSet))]
     pub cluster_account: Box<Account<'info, Cluster>>,
@@ -1462,11 +8059,11 @@ pub struct QueueInitPlanet<'info> {
     pub arcium_program: Program<'info, Arcium>,
 }
 
-#[callback_accounts("init_planet")]
+#[callback_accounts("mine_asteroid")]
 #[derive(Accounts)]
-pub struct InitPlanetCallback<'info> {
+pub struct MineAsteroidCallback<'info> {
     pub arcium_program: Program<'info, Arcium>,
-    #[account(address = derive_comp_def_pda!(COMP_DEF_OFFSET_INIT_PLANET))]
+    #[account(address = derive_comp_def_pda!(COMP_DEF_OFFSET_MINE_ASTEROID))]
     pub comp_def_account: Box<Account<'info, ComputationDefinitionAccount>>,
     #[account(address = derive_mxe_pda!())]
     pub mxe_account: Box<Account<'info, MXEAccount>>,
@@ -1481,12 +8078,11 @@ pub struct InitPlanetCallback<'info> {
     pub celestial_body: Box<Account<'info, EncryptedCelestialBody>>,
 }
 
-// --- Queue Init Spawn Planet ---
+// --- Check Spawn Lost ---
 
-#[queue_computation_accounts("init_spawn_planet", payer)]
+#[queue_computation_accounts("check_ownership", payer)]
 #[derive(Accounts)]
-#[instruction(computation_offset: u64, planet_hash: [u8; 32])]
-pub struct QueueInitSpawnPlanet<'info> {
+pub struct CheckSpawnLost<'info> {
     #[account(mut)]
     pub payer: Signer<'info>,
     #[account(
@@ -1495,28 +8091,15 @@ pub struct QueueInitSpawnPlanet<'info> {
     )]
     pub game: Box<Account<'info, Game>>,
     #[account(
-        mut,
         seeds = [b"player", game.game_id.to_le_bytes().as_ref(), payer.key().as_ref()],
         bump,
-        constraint = player.owner == payer.key() @ ErrorCode::InvalidSpawnValidation,
     )]
     pub player: Box<Account<'info, Player>>,
     #[account(
-        init,
-        payer = payer,
-        space = EncryptedCelestialBody::MAX_SIZE,
-        seeds = [b"planet", game.game_id.to_le_bytes().as_ref(), planet_hash.as_ref()],
+        seeds = [b"planet", game.game_id.to_le_bytes().as_ref(), player.spawn_planet_hash.as_ref()],
         bump,
     )]
     pub celestial_body: Box<Account<'info, EncryptedCelestialBody>>,
-    #[account(
-        init,
-        payer = payer,
-        space = PendingMovesMetadata::BASE_SIZE,
-        seeds = [b"moves", game.game_id.to_le_bytes().as_ref(), planet_hash.as_ref()],
-        bump,
-    )]
-    pub pending_moves: Box<Account<'info, PendingMovesMetadata>>,
     #[account(
         init_if_needed,
         space = 9,
@@ -1534,10 +8117,26 @@ pub struct QueueInitSpawnPlanet<'info> {
     #[account(mut, address = derive_execpool_pda!(mxe_account, ErrorCode::ClusterNotSet))]
     /// CHECK: executing_pool
     pub executing_pool: UncheckedAccount<'info>,
-    #[account(mut, address = derive_comp_pda!(computation_offset, mxe_account, ErrorCode::ClusterNotSet))]
+    /// Supplies the computation offset; advanced by the handler.
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + ComputationOffsetTracker::INIT_SPACE,
+        seeds = [b"offset", game.game_id.to_le_bytes().as_ref(), payer.key().as_ref()],
+        bump,
+    )]
+    pub offset_tracker: Box<Account<'info, ComputationOffsetTracker>>,
+    #[account(
+        mut,
+        address = derive_comp_pda!(
+            derive_computation_offset(game.game_id, &payer.key(), offset_tracker.next_offset),
+            mxe_account,
+            ErrorCode::ClusterNotSet
+        )
+    )]
     /// CHECK: computation_account
     pub computation_account: UncheckedAccount<'info>,
-    #[account(address = derive_comp_def_pda!(COMP_DEF_OFFSET_INIT_SPAWN_PLANET))]
+    #[account(address = derive_comp_def_pda!(COMP_DEF_OFFSET_CHECK_OWNERSHIP))]
     pub comp_def_account: Box<Account<'info, ComputationDefinitionAccount>>,
     #[account(mut, address = derive_cluster_pda!(mxe_account, ErrorCode::ClusterNotSet))]
     pub cluster_account: Box<Account<'info, Cluster>>,
@@ -1549,11 +8148,11 @@ pub struct QueueInitSpawnPlanet<'info> {
     pub arcium_program: Program<'info, Arcium>,
 }
 
-#[callback_accounts("init_spawn_planet")]
+#[callback_accounts("check_ownership")]
 #[derive(Accounts)]
-pub struct InitSpawnPlanetCallback<'info> {
+pub struct CheckOwnershipCallback<'info> {
     pub arcium_program: Program<'info, Arcium>,
-    #[account(address = derive_comp_def_pda!(COMP_DEF_OFFSET_INIT_SPAWN_PLANET))]
+    #[account(address = derive_comp_def_pda!(COMP_DEF_OFFSET_CHECK_OWNERSHIP))]
     pub comp_def_account: Box<Account<'info, ComputationDefinitionAccount>>,
     #[account(address = derive_mxe_pda!())]
     pub mxe_account: Box<Account<'info, MXEAccount>>,
@@ -1566,16 +8165,18 @@ pub struct InitSpawnPlanetCallback<'info> {
     pub instructions_sysvar: AccountInfo<'info>,
     #[account(mut)]
     pub player: Box<Account<'info, Player>>,
-    #[account(mut)]
-    pub celestial_body: Box<Account<'info, EncryptedCelestialBody>>,
+    /// Set only for claim_race_to_center_victory
+    #[account(mut, constraint = game.game_id == player.game_id)]
+    pub game: Option<Box<Account<'info, Game>>>,
+    #[account(mut, constraint = player_profile.owner == player.owner)]
+    pub player_profile: Option<Box<Account<'info, PlayerProfile>>>,
 }
 
-// --- Queue Process Move ---
+// --- Queue Wormhole Jump ---
 
-#[queue_computation_accounts("process_move", payer)]
+#[queue_computation_accounts("wormhole_jump", payer)]
 #[derive(Accounts)]
-#[instruction(computation_offset: u64)]
-pub struct QueueProcessMove<'info> {
+pub struct QueueWormholeJump<'info> {
     #[account(mut)]
     pub payer: Signer<'info>,
     #[account(
@@ -1585,31 +8186,18 @@ pub struct QueueProcessMove<'info> {
     pub game: Box<Account<'info, Game>>,
     #[account(mut)]
     pub source_body: Box<Account<'info, EncryptedCelestialBody>>,
-    /// Source planet's pending moves metadata (read-only, for flush check)
-    pub source_pending: Box<Account<'info, PendingMovesMetadata>>,
-    /// Target planet's pending moves metadata (mut, realloc to fit one more entry)
+    #[account(mut)]
+    pub dest_body: Box<Account<'info, EncryptedCelestialBody>>,
     #[account(
-        mut,
-        realloc = PendingMovesMetadata::BASE_SIZE + (target_pending.moves.len() + 1) * PENDING_MOVE_ENTRY_SIZE,
-        realloc::payer = payer,
-        realloc::zero = false,
+        seeds = [b"moves", game.game_id.to_le_bytes().as_ref(), source_body.planet_hash.as_ref()],
+        bump,
     )]
-    pub target_pending: Box<Account<'info, PendingMovesMetadata>>,
-    /// PendingMoveAccount to store the MPC output Enc<Mxe, PendingMoveData>.
-    /// PDA seeded by predicted move_id = next_move_id + queued_count (before increment).
+    pub source_pending: Box<Account<'info, PendingMovesMetadata>>,
     #[account(
-        init,
-        payer = payer,
-        space = PendingMoveAccount::MAX_SIZE,
-        seeds = [
-            b"move",
-            target_pending.game_id.to_le_bytes().as_ref(),
-            target_pending.planet_hash.as_ref(),
-            (target_pending.next_move_id + target_pending.queued_count as u64).to_le_bytes().as_ref(),
-        ],
+        seeds = [b"moves", game.game_id.to_le_bytes().as_ref(), dest_body.planet_hash.as_ref()],
         bump,
     )]
-    pub move_account: Box<Account<'info, PendingMoveAccount>>,
+    pub dest_pending: Box<Account<'info, PendingMovesMetadata>>,
     #[account(
         init_if_needed,
         space = 9,
@@ -1627,10 +8215,26 @@ pub struct QueueProcessMove<'info> {
     #[account(mut, address = derive_execpool_pda!(mxe_account, ErrorCode::ClusterNotSet))]
     /// CHECK: executing_pool
     pub executing_pool: UncheckedAccount<'info>,
-    #[account(mut, address = derive_comp_pda!(computation_offset, mxe_account, ErrorCode::ClusterNotSet))]
+    /// Supplies the computation offset; advanced by the handler.
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + ComputationOffsetTracker::INIT_SPACE,
+        seeds = [b"offset", game.game_id.to_le_bytes().as_ref(), payer.key().as_ref()],
+        bump,
+    )]
+    pub offset_tracker: Box<Account<'info, ComputationOffsetTracker>>,
+    #[account(
+        mut,
+        address = derive_comp_pda!(
+            derive_computation_offset(game.game_id, &payer.key(), offset_tracker.next_offset),
+            mxe_account,
+            ErrorCode::ClusterNotSet
+        )
+    )]
     /// CHECK: computation_account
     pub computation_account: UncheckedAccount<'info>,
-    #[account(address = derive_comp_def_pda!(COMP_DEF_OFFSET_PROCESS_MOVE))]
+    #[account(address = derive_comp_def_pda!(COMP_DEF_OFFSET_WORMHOLE_JUMP))]
     pub comp_def_account: Box<Account<'info, ComputationDefinitionAccount>>,
     #[account(mut, address = derive_cluster_pda!(mxe_account, ErrorCode::ClusterNotSet))]
     pub cluster_account: Box<Account<'info, Cluster>>,
@@ -1642,11 +8246,11 @@ pub struct QueueProcessMove<'info> {
     pub arcium_program: Program<'info, Arcium>,
 }
 
-#[callback_accounts("process_move")]
+#[callback_accounts("wormhole_jump")]
 #[derive(Accounts)]
-pub struct ProcessMoveCallback<'info> {
+pub struct WormholeJumpCallback<'info> {
     pub arcium_program: Program<'info, Arcium>,
-    #[account(address = derive_comp_def_pda!(COMP_DEF_OFFSET_PROCESS_MOVE))]
+    #[account(address = derive_comp_def_pda!(COMP_DEF_OFFSET_WORMHOLE_JUMP))]
     pub comp_def_account: Box<Account<'info, ComputationDefinitionAccount>>,
     #[account(address = derive_mxe_pda!())]
     pub mxe_account: Box<Account<'info, MXEAccount>>,
@@ -1660,20 +8264,134 @@ pub struct ProcessMoveCallback<'info> {
     #[account(mut)]
     pub source_body: Box<Account<'info, EncryptedCelestialBody>>,
     #[account(mut)]
-    pub move_account: Box<Account<'info, PendingMoveAccount>>,
+    pub dest_body: Box<Account<'info, EncryptedCelestialBody>>,
 }
 
-// --- Queue Flush Planet ---
+// --- Comet Drift ---
 
-#[queue_computation_accounts("flush_planet", payer)]
+#[queue_computation_accounts("comet_drift", payer)]
 #[derive(Accounts)]
-#[instruction(computation_offset: u64)]
-pub struct QueueFlushPlanet<'info> {
+pub struct QueueCometDrift<'info> {
     #[account(mut)]
     pub payer: Signer<'info>,
+    #[account(
+        seeds = [b"game", game.game_id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub game: Box<Account<'info, Game>>,
+    #[account(
+        mut,
+        seeds = [b"planet", game.game_id.to_le_bytes().as_ref(), celestial_body.planet_hash.as_ref()],
+        bump,
+    )]
+    pub celestial_body: Box<Account<'info, EncryptedCelestialBody>>,
+    #[account(
+        seeds = [b"moves", game.game_id.to_le_bytes().as_ref(), celestial_body.planet_hash.as_ref()],
+        bump,
+    )]
+    pub pending_moves: Box<Account<'info, PendingMovesMetadata>>,
+    #[account(
+        init_if_needed,
+        space = 9,
+        payer = payer,
+        seeds = [&SIGN_PDA_SEED],
+        bump,
+        address = derive_sign_pda!(),
+    )]
+    pub sign_pda_account: Account<'info, ArciumSignerAccount>,
+    #[account(address = derive_mxe_pda!())]
+    pub mxe_account: Box<Account<'info, MXEAccount>>,
+    #[account(mut, address = derive_mempool_pda!(mxe_account, ErrorCode::ClusterNotSet))]
+    /// CHECK: mempool_account
+    pub mempool_account: UncheckedAccount<'info>,
+    #[account(mut, address = derive_execpool_pda!(mxe_account, ErrorCode::ClusterNotSet))]
+    /// CHECK: executing_pool
+    pub executing_pool: UncheckedAccount<'info>,
+    /// Supplies the computation offset; advanced by the handler.
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + ComputationOffsetTracker::INIT_SPACE,
+        seeds = [b"offset", game.game_id.to_le_bytes().as_ref(), payer.key().as_ref()],
+        bump,
+    )]
+    pub offset_tracker: Box<Account<'info, ComputationOffsetTracker>>,
+    #[account(
+        mut,
+        address = derive_comp_pda!(
+            derive_computation_offset(game.game_id, &payer.key(), offset_tracker.next_offset),
+            mxe_account,
+            ErrorCode::ClusterNotSet
+        )
+    )]
+    /// CHECK: computation_account
+    pub computation_account: UncheckedAccount<'info>,
+    #[account(address = derive_comp_def_pda!(COMP_DEF_OFFSET_COMET_DRIFT))]
+    pub comp_def_account: Box<Account<'info, ComputationDefinitionAccount>>,
+    #[account(mut, address = derive_cluster_pda!(mxe_account, ErrorCode::ClusterNotSet))]
+    pub cluster_account: Box<Account<'info, Cluster>>,
+    #[account(mut, address = ARCIUM_FEE_POOL_ACCOUNT_ADDRESS)]
+    pub pool_account: Box<Account<'info, FeePool>>,
+    #[account(mut, address = ARCIUM_CLOCK_ACCOUNT_ADDRESS)]
+    pub clock_account: Box<Account<'info, ClockAccount>>,
+    pub system_program: Program<'info, System>,
+    pub arcium_program: Program<'info, Arcium>,
+}
+
+#[callback_accounts("comet_drift")]
+#[derive(Accounts)]
+pub struct CometDriftCallback<'info> {
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(address = derive_comp_def_pda!(COMP_DEF_OFFSET_COMET_DRIFT))]
+    pub comp_def_account: Box<Account<'info, ComputationDefinitionAccount>>,
+    #[account(address = derive_mxe_pda!())]
+    pub mxe_account: Box<Account<'info, MXEAccount>>,
+    /// CHECK: computation_account
+    pub computation_account: UncheckedAccount<'info>,
+    #[account(address = derive_cluster_pda!(mxe_account, ErrorCode::ClusterNotSet))]
+    pub cluster_account: Box<Account<'info, Cluster>>,
+    #[account(address = ::anchor_lang::solana_program::sysvar::instructions::ID)]
+    /// CHECK: instructions_sysvar
+    pub instructions_sysvar: AccountInfo<'info>,
     #[account(mut)]
     pub celestial_body: Box<Account<'info, EncryptedCelestialBody>>,
+}
+
+// --- Transfer Owner ---
+
+#[queue_computation_accounts("transfer_owner", payer)]
+#[derive(Accounts)]
+pub struct QueueTransferOwner<'info> {
     #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        seeds = [b"game", game.game_id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub game: Box<Account<'info, Game>>,
+    #[account(
+        seeds = [b"player", game.game_id.to_le_bytes().as_ref(), payer.key().as_ref()],
+        bump,
+    )]
+    pub player: Box<Account<'info, Player>>,
+    /// Receiving player; has_spawned isn't required to accept a planet
+    #[account(
+        seeds = [b"player", game.game_id.to_le_bytes().as_ref(), new_owner_player.owner.as_ref()],
+        bump,
+    )]
+    pub new_owner_player: Box<Account<'info, Player>>,
+    /// Alliance both players must belong to
+    pub alliance: Box<Account<'info, Alliance>>,
+    #[account(
+        mut,
+        seeds = [b"planet", game.game_id.to_le_bytes().as_ref(), celestial_body.planet_hash.as_ref()],
+        bump,
+    )]
+    pub celestial_body: Box<Account<'info, EncryptedCelestialBody>>,
+    #[account(
+        seeds = [b"moves", game.game_id.to_le_bytes().as_ref(), celestial_body.planet_hash.as_ref()],
+        bump,
+    )]
     pub pending_moves: Box<Account<'info, PendingMovesMetadata>>,
     #[account(
         init_if_needed,
@@ -1692,10 +8410,26 @@ pub struct QueueFlushPlanet<'info> {
     #[account(mut, address = derive_execpool_pda!(mxe_account, ErrorCode::ClusterNotSet))]
     /// CHECK: executing_pool
     pub executing_pool: UncheckedAccount<'info>,
-    #[account(mut, address = derive_comp_pda!(computation_offset, mxe_account, ErrorCode::ClusterNotSet))]
+    /// Supplies the computation offset; advanced by the handler.
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + ComputationOffsetTracker::INIT_SPACE,
+        seeds = [b"offset", game.game_id.to_le_bytes().as_ref(), payer.key().as_ref()],
+        bump,
+    )]
+    pub offset_tracker: Box<Account<'info, ComputationOffsetTracker>>,
+    #[account(
+        mut,
+        address = derive_comp_pda!(
+            derive_computation_offset(game.game_id, &payer.key(), offset_tracker.next_offset),
+            mxe_account,
+            ErrorCode::ClusterNotSet
+        )
+    )]
     /// CHECK: computation_account
     pub computation_account: UncheckedAccount<'info>,
-    #[account(address = derive_comp_def_pda!(COMP_DEF_OFFSET_FLUSH_PLANET))]
+    #[account(address = derive_comp_def_pda!(COMP_DEF_OFFSET_TRANSFER_OWNER))]
     pub comp_def_account: Box<Account<'info, ComputationDefinitionAccount>>,
     #[account(mut, address = derive_cluster_pda!(mxe_account, ErrorCode::ClusterNotSet))]
     pub cluster_account: Box<Account<'info, Cluster>>,
@@ -1707,11 +8441,11 @@ pub struct QueueFlushPlanet<'info> {
     pub arcium_program: Program<'info, Arcium>,
 }
 
-#[callback_accounts("flush_planet")]
+#[callback_accounts("transfer_owner")]
 #[derive(Accounts)]
-pub struct FlushPlanetCallback<'info> {
+pub struct TransferOwnerCallback<'info> {
     pub arcium_program: Program<'info, Arcium>,
-    #[account(address = derive_comp_def_pda!(COMP_DEF_OFFSET_FLUSH_PLANET))]
+    #[account(address = derive_comp_def_pda!(COMP_DEF_OFFSET_TRANSFER_OWNER))]
     pub comp_def_account: Box<Account<'info, ComputationDefinitionAccount>>,
     #[account(address = derive_mxe_pda!())]
     pub mxe_account: Box<Account<'info, MXEAccount>>,
@@ -1724,24 +8458,142 @@ pub struct FlushPlanetCallback<'info> {
     pub instructions_sysvar: AccountInfo<'info>,
     #[account(mut)]
     pub celestial_body: Box<Account<'info, EncryptedCelestialBody>>,
+    pub player: Box<Account<'info, Player>>,
+    pub new_owner_player: Box<Account<'info, Player>>,
+}
+
+// --- Broadcast ---
+
+#[derive(Accounts)]
+#[instruction(game_id: u64, x: i64, y: i64, planet_hash: [u8; 32])]
+pub struct Broadcast<'info> {
+    pub broadcaster: Signer<'info>,
+    #[account(
+        seeds = [b"game", game_id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub game: Account<'info, Game>,
+    /// Must already exist: coordinates never passed to queue_init_planet can't be broadcast
+    #[account(
+        seeds = [b"planet", game_id.to_le_bytes().as_ref(), planet_hash.as_ref()],
+        bump,
+    )]
+    pub celestial_body: Box<Account<'info, EncryptedCelestialBody>>,
+    /// Pass if the broadcaster is a player of this game
+    #[account(
+        seeds = [b"player", game_id.to_le_bytes().as_ref(), broadcaster.key().as_ref()],
+        bump,
+    )]
+    pub player: Option<Account<'info, Player>>,
+    /// Pass if the broadcaster is a registered spectator
+    #[account(
+        seeds = [b"spectators", game_id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub spectator_registry: Option<Box<Account<'info, SpectatorRegistry>>>,
+}
+
+#[derive(Accounts)]
+#[instruction(game_id: u64)]
+pub struct BroadcastBulk<'info> {
+    pub broadcaster: Signer<'info>,
+    #[account(
+        seeds = [b"game", game_id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub game: Account<'info, Game>,
+    /// Pass if the broadcaster is a player of this game
+    #[account(
+        seeds = [b"player", game_id.to_le_bytes().as_ref(), broadcaster.key().as_ref()],
+        bump,
+    )]
+    pub player: Option<Account<'info, Player>>,
+    /// Pass if the broadcaster is a registered spectator
+    #[account(
+        seeds = [b"spectators", game_id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub spectator_registry: Option<Box<Account<'info, SpectatorRegistry>>>,
+}
+
+#[derive(Accounts)]
+#[instruction(game_id: u64)]
+pub struct VerifyCoordinates<'info> {
+    #[account(
+        seeds = [b"game", game_id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub game: Account<'info, Game>,
+}
+
+#[derive(Accounts)]
+#[instruction(game_id: u64, planet_hash: [u8; 32])]
+pub struct CreateCoordinateProof<'info> {
     #[account(mut)]
-    pub pending_moves: Box<Account<'info, PendingMovesMetadata>>,
+    pub payer: Signer<'info>,
+    #[account(
+        seeds = [b"game", game_id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub game: Box<Account<'info, Game>>,
+    #[account(
+        seeds = [b"player", game_id.to_le_bytes().as_ref(), payer.key().as_ref()],
+        bump,
+        constraint = player.owner == payer.key() @ ErrorCode::NotSpawned,
+    )]
+    pub player: Box<Account<'info, Player>>,
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + CoordinateProof::INIT_SPACE,
+        seeds = [b"coordinate_proof", game_id.to_le_bytes().as_ref(), planet_hash.as_ref()],
+        bump,
+    )]
+    pub coordinate_proof: Box<Account<'info, CoordinateProof>>,
+    /// Optional: the revealed planet, whose hash_rounds then checks the
+    /// coordinates; required for planets created before an update_hash_rounds
+    #[account(
+        seeds = [b"planet", game_id.to_le_bytes().as_ref(), planet_hash.as_ref()],
+        bump,
+    )]
+    pub celestial_body: Option<Box<Account<'info, EncryptedCelestialBody>>>,
+    pub system_program: Program<'info, System>,
 }
 
-// --- Queue Upgrade Planet ---
-
-#[queue_computation_accounts("upgrade_planet", payer)]
+#[queue_computation_accounts("check_ownership", payer)]
 #[derive(Accounts)]
-#[instruction(computation_offset: u64)]
-pub struct QueueUpgradePlanet<'info> {
+#[instruction(game_id: u64, planet_hash: [u8; 32])]
+pub struct ClaimRaceToCenterVictory<'info> {
     #[account(mut)]
     pub payer: Signer<'info>,
     #[account(
-        seeds = [b"game", game.game_id.to_le_bytes().as_ref()],
+        seeds = [b"game", game_id.to_le_bytes().as_ref()],
         bump,
     )]
     pub game: Box<Account<'info, Game>>,
-    #[account(mut)]
+    #[account(
+        seeds = [b"player", game_id.to_le_bytes().as_ref(), payer.key().as_ref()],
+        bump,
+        constraint = player.owner == payer.key() @ ErrorCode::NotSpawned,
+        constraint = player.has_spawned @ ErrorCode::NotSpawned,
+    )]
+    pub player: Box<Account<'info, Player>>,
+    /// Optional: payer's cross-game profile, credited by the callback on a win
+    #[account(
+        seeds = [b"profile", payer.key().as_ref()],
+        bump,
+    )]
+    pub player_profile: Option<Box<Account<'info, PlayerProfile>>>,
+    #[account(
+        seeds = [b"coordinate_proof", game_id.to_le_bytes().as_ref(), planet_hash.as_ref()],
+        bump,
+    )]
+    pub coordinate_proof: Box<Account<'info, CoordinateProof>>,
+    /// The revealed planet; check_ownership proves the payer owns it
+    #[account(
+        seeds = [b"planet", game_id.to_le_bytes().as_ref(), planet_hash.as_ref()],
+        bump,
+    )]
     pub celestial_body: Box<Account<'info, EncryptedCelestialBody>>,
     #[account(
         init_if_needed,
@@ -1760,10 +8612,26 @@ pub struct QueueUpgradePlanet<'info> {
     #[account(mut, address = derive_execpool_pda!(mxe_account, ErrorCode::ClusterNotSet))]
     /// CHECK: executing_pool
     pub executing_pool: UncheckedAccount<'info>,
-    #[account(mut, address = derive_comp_pda!(computation_offset, mxe_account, ErrorCode::ClusterNotSet))]
+    /// Supplies the computation offset; advanced by the handler.
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + ComputationOffsetTracker::INIT_SPACE,
+        seeds = [b"offset", game.game_id.to_le_bytes().as_ref(), payer.key().as_ref()],
+        bump,
+    )]
+    pub offset_tracker: Box<Account<'info, ComputationOffsetTracker>>,
+    #[account(
+        mut,
+        address = derive_comp_pda!(
+            derive_computation_offset(game.game_id, &payer.key(), offset_tracker.next_offset),
+            mxe_account,
+            ErrorCode::ClusterNotSet
+        )
+    )]
     /// CHECK: computation_account
     pub computation_account: UncheckedAccount<'info>,
-    #[account(address = derive_comp_def_pda!(COMP_DEF_OFFSET_UPGRADE_PLANET))]
+    #[account(address = derive_comp_def_pda!(COMP_DEF_OFFSET_CHECK_OWNERSHIP))]
     pub comp_def_account: Box<Account<'info, ComputationDefinitionAccount>>,
     #[account(mut, address = derive_cluster_pda!(mxe_account, ErrorCode::ClusterNotSet))]
     pub cluster_account: Box<Account<'info, Cluster>>,
@@ -1775,31 +8643,70 @@ pub struct QueueUpgradePlanet<'info> {
     pub arcium_program: Program<'info, Arcium>,
 }
 
-#[callback_accounts("upgrade_planet")]
 #[derive(Accounts)]
-pub struct UpgradePlanetCallback<'info> {
-    pub arcium_program: Program<'info, Arcium>,
-    #[account(address = derive_comp_def_pda!(COMP_DEF_OFFSET_UPGRADE_PLANET))]
-    pub comp_def_account: Box<Account<'info, ComputationDefinitionAccount>>,
-    #[account(address = derive_mxe_pda!())]
-    pub mxe_account: Box<Account<'info, MXEAccount>>,
-    /// CHECK: computation_account
-    pub computation_account: UncheckedAccount<'info>,
-    #[account(address = derive_cluster_pda!(mxe_account, ErrorCode::ClusterNotSet))]
-    pub cluster_account: Box<Account<'info, Cluster>>,
-    #[account(address = ::anchor_lang::solana_program::sysvar::instructions::ID)]
-    /// CHECK: instructions_sysvar
-    pub instructions_sysvar: AccountInfo<'info>,
+#[instruction(game_id: u64)]
+pub struct ClaimPointsVictory<'info> {
+    pub payer: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"game", game_id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub game: Box<Account<'info, Game>>,
+    #[account(
+        seeds = [b"player", game_id.to_le_bytes().as_ref(), payer.key().as_ref()],
+        bump,
+        constraint = player.owner == payer.key() @ ErrorCode::NotSpawned,
+    )]
+    pub player: Box<Account<'info, Player>>,
+    /// Optional: payer's cross-game profile, credited on the win
+    #[account(
+        mut,
+        seeds = [b"profile", payer.key().as_ref()],
+        bump,
+    )]
+    pub player_profile: Option<Box<Account<'info, PlayerProfile>>>,
+}
+
+#[derive(Accounts)]
+#[instruction(game_id: u64, planet_hash: [u8; 32])]
+pub struct CloseDeadPlanet<'info> {
     #[account(mut)]
+    pub closer: Signer<'info>,
+    #[account(
+        seeds = [b"game", game_id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub game: Box<Account<'info, Game>>,
+    #[account(
+        mut,
+        seeds = [b"planet", game_id.to_le_bytes().as_ref(), planet_hash.as_ref()],
+        bump,
+        close = initializer,
+    )]
     pub celestial_body: Box<Account<'info, EncryptedCelestialBody>>,
+    #[account(
+        mut,
+        seeds = [b"moves", game_id.to_le_bytes().as_ref(), planet_hash.as_ref()],
+        bump,
+        close = initializer,
+    )]
+    pub pending_moves: Box<Account<'info, PendingMovesMetadata>>,
+    #[account(
+        mut,
+        seeds = [b"combat_log", game_id.to_le_bytes().as_ref(), planet_hash.as_ref()],
+        bump,
+        close = initializer,
+    )]
+    pub combat_log: Box<Account<'info, CombatLog>>,
+    /// CHECK: Rent recipient, checked against celestial_body.initializer
+    #[account(mut, address = celestial_body.initializer @ ErrorCode::Unauthorized)]
+    pub initializer: UncheckedAccount<'info>,
 }
 
-// --- Broadcast ---
-
 #[derive(Accounts)]
 #[instruction(game_id: u64)]
-pub struct Broadcast<'info> {
-    pub broadcaster: Signer<'info>,
+pub struct GetGameConfig<'info> {
     #[account(
         seeds = [b"game", game_id.to_le_bytes().as_ref()],
         bump,
@@ -1807,6 +8714,9 @@ pub struct Broadcast<'info> {
     pub game: Account<'info, Game>,
 }
 
+#[derive(Accounts)]
+pub struct GetProgramVersion {}
+
 // --- Cleanup ---
 
 #[derive(Accounts)]
@@ -1821,6 +8731,40 @@ pub struct CleanupGame<'info> {
         close = closer,
     )]
     pub game: Account<'info, Game>,
+    #[account(
+        mut,
+        seeds = [b"program_state"],
+        bump,
+    )]
+    pub program_state: Account<'info, ProgramState>,
+}
+
+#[derive(Accounts)]
+pub struct CloseSignPda<'info> {
+    #[account(mut)]
+    pub closer: Signer<'info>,
+    #[account(
+        seeds = [b"program_state"],
+        bump,
+    )]
+    pub program_state: Account<'info, ProgramState>,
+    #[account(
+        mut,
+        seeds = [&SIGN_PDA_SEED],
+        bump = sign_pda_account.bump,
+        close = closer,
+    )]
+    pub sign_pda_account: Account<'info, ArciumSignerAccount>,
+    #[account(
+        constraint = program.programdata_address()? == Some(program_data.key()),
+    )]
+    pub program: Program<'info, crate::program::EncryptedForest>,
+    /// Only the program upgrade authority may close the shared signer PDA.
+    #[account(
+        constraint = program_data.upgrade_authority_address == Some(closer.key())
+            @ ErrorCode::Unauthorized,
+    )]
+    pub program_data: Account<'info, ProgramData>,
 }
 
 #[derive(Accounts)]
@@ -1829,6 +8773,7 @@ pub struct CleanupPlayer<'info> {
     #[account(mut)]
     pub closer: Signer<'info>,
     #[account(
+        mut,
         seeds = [b"game", game_id.to_le_bytes().as_ref()],
         bump,
     )]
@@ -1840,6 +8785,27 @@ pub struct CleanupPlayer<'info> {
         close = closer,
     )]
     pub player: Account<'info, Player>,
+    /// Optional: required when the game charges a deposit. Closed to `owner`
+    /// or `deposit_recipient` by the handler.
+    #[account(
+        mut,
+        seeds = [b"deposit", game_id.to_le_bytes().as_ref(), player.owner.as_ref()],
+        bump,
+    )]
+    pub deposit: Option<Account<'info, PlayerDeposit>>,
+    /// CHECK: the player's wallet, refunded on self-cleanup
+    #[account(mut, address = player.owner)]
+    pub owner: UncheckedAccount<'info>,
+    /// CHECK: receives the deposit on admin force-cleanup
+    #[account(mut, address = game.deposit_recipient)]
+    pub deposit_recipient: UncheckedAccount<'info>,
+    /// Optional: the player's cross-game profile, credited with the game's points
+    #[account(
+        mut,
+        seeds = [b"profile", player.owner.as_ref()],
+        bump,
+    )]
+    pub player_profile: Option<Account<'info, PlayerProfile>>,
 }
 
 #[derive(Accounts)]
@@ -1866,4 +8832,166 @@ pub struct CleanupPlanet<'info> {
         close = closer,
     )]
     pub pending_moves: Account<'info, PendingMovesMetadata>,
+    #[account(
+        mut,
+        seeds = [b"combat_log", game_id.to_le_bytes().as_ref(), planet_hash.as_ref()],
+        bump,
+        close = closer,
+    )]
+    pub combat_log: Account<'info, CombatLog>,
+}
+
+#[derive(Accounts)]
+#[instruction(game_id: u64, planet_hash: [u8; 32])]
+pub struct AdminResetPlanetTimestamp<'info> {
+    pub admin: Signer<'info>,
+    #[account(
+        seeds = [b"game", game_id.to_le_bytes().as_ref()],
+        bump,
+        constraint = game.admin == admin.key() @ ErrorCode::Unauthorized,
+    )]
+    pub game: Box<Account<'info, Game>>,
+    #[account(
+        mut,
+        seeds = [b"planet", game_id.to_le_bytes().as_ref(), planet_hash.as_ref()],
+        bump,
+    )]
+    pub celestial_body: Box<Account<'info, EncryptedCelestialBody>>,
+}
+
+#[derive(Accounts)]
+#[instruction(game_id: u64, planet_hash: [u8; 32])]
+pub struct MigrateCelestialBody<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(
+        seeds = [b"game", game_id.to_le_bytes().as_ref()],
+        bump,
+        constraint = game.admin == admin.key() @ ErrorCode::Unauthorized,
+    )]
+    pub game: Account<'info, Game>,
+    /// CHECK: An older layout Anchor cannot deserialize; the handler checks
+    /// the discriminator and size before rewriting it.
+    #[account(
+        mut,
+        seeds = [b"planet", game_id.to_le_bytes().as_ref(), planet_hash.as_ref()],
+        bump,
+        owner = crate::ID,
+        constraint = celestial_body_version(&celestial_body) < CURRENT_VERSION
+            @ ErrorCode::InvalidAccountVersion,
+    )]
+    pub celestial_body: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(game_id: u64)]
+pub struct MigrateGame<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    /// CHECK: An older layout Anchor cannot deserialize; the handler checks
+    /// the discriminator, size and admin before rewriting it.
+    #[account(
+        mut,
+        seeds = [b"game", game_id.to_le_bytes().as_ref()],
+        bump,
+        owner = crate::ID,
+    )]
+    pub game: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(game_id: u64, planet_hash: [u8; 32], move_id: u64)]
+pub struct CleanupMoveAccount<'info> {
+    #[account(mut)]
+    pub closer: Signer<'info>,
+    #[account(
+        seeds = [b"game", game_id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub game: Account<'info, Game>,
+    /// Shrunk once under a quarter full after dropping the move's entry.
+    #[account(
+        mut,
+        seeds = [b"moves", game_id.to_le_bytes().as_ref(), planet_hash.as_ref()],
+        bump,
+        realloc = PendingMovesMetadata::space(pending_moves.capacity_for(
+            pending_moves.moves.iter().filter(|e| e.move_id != move_id).count(),
+        )),
+        realloc::payer = closer,
+        realloc::zero = false,
+    )]
+    pub pending_moves: Account<'info, PendingMovesMetadata>,
+    #[account(
+        mut,
+        seeds = [
+            b"move",
+            game_id.to_le_bytes().as_ref(),
+            planet_hash.as_ref(),
+            move_id.to_le_bytes().as_ref(),
+        ],
+        bump,
+        close = closer,
+    )]
+    pub move_account: Account<'info, PendingMoveAccount>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(game_id: u64, source_planet_hash: [u8; 32], dest_planet_hash: [u8; 32])]
+pub struct MergePendingMoves<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(
+        seeds = [b"game", game_id.to_le_bytes().as_ref()],
+        bump,
+        constraint = game.admin == admin.key() @ ErrorCode::Unauthorized,
+    )]
+    pub game: Account<'info, Game>,
+    #[account(
+        mut,
+        seeds = [b"moves", game_id.to_le_bytes().as_ref(), source_planet_hash.as_ref()],
+        bump,
+        close = admin,
+    )]
+    pub source_pending: Box<Account<'info, PendingMovesMetadata>>,
+    /// Grown to fit the merged entries.
+    #[account(
+        mut,
+        seeds = [b"moves", game_id.to_le_bytes().as_ref(), dest_planet_hash.as_ref()],
+        bump,
+        realloc = PendingMovesMetadata::space(dest_pending.capacity_for(
+            dest_pending.moves.len() + source_pending.moves.len(),
+        )),
+        realloc::payer = admin,
+        realloc::zero = false,
+    )]
+    pub dest_pending: Box<Account<'info, PendingMovesMetadata>>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(game_id: u64, target_planet_hash: [u8; 32], move_ids: Vec<u64>)]
+pub struct AbandonMoveBatch<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        seeds = [b"game", game_id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub game: Account<'info, Game>,
+    /// Shrunk once under a quarter full after dropping the abandoned entries.
+    #[account(
+        mut,
+        seeds = [b"moves", game_id.to_le_bytes().as_ref(), target_planet_hash.as_ref()],
+        bump,
+        realloc = PendingMovesMetadata::space(pending_moves.capacity_for(
+            pending_moves.moves.iter().filter(|e| !move_ids.contains(&e.move_id)).count(),
+        )),
+        realloc::payer = payer,
+        realloc::zero = false,
+    )]
+    pub pending_moves: Account<'info, PendingMovesMetadata>,
+    pub system_program: Program<'info, System>,
 }
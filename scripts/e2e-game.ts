@@ -35,10 +35,13 @@ import { readFileSync } from "fs";
 import {
   EncryptedForestClient,
   DEFAULT_THRESHOLDS,
+  fetchUpcomingStartSlot,
+  MAX_QUEUED_CALLBACKS,
   CelestialBodyType,
   UpgradeFocus,
   computePlanetHash,
   computeDistance,
+  observableDistanceBound,
   computeLandingSlot,
   findSpawnPlanet,
   findPlanetOfType,
@@ -46,7 +49,10 @@ import {
   derivePlayerPDA,
   deriveCelestialBodyPDA,
   derivePendingMovesPDA,
+  deriveCombatLogPDA,
   derivePendingMoveAccountPDA,
+  deriveComputationOffsetTrackerPDA,
+  playerIdFromPubkey,
   PROGRAM_ID,
   type ArciumAccounts,
   type CreateGameArgs,
@@ -495,17 +501,18 @@ function buildInitSpawnPlanetValues(
   return [
     BigInt.asUintN(64, x),
     BigInt.asUintN(64, y),
-    BigInt.asUintN(32, playerId),
+    BigInt.asUintN(64, playerId),
     BigInt.asUintN(32, sourcePlanetId),
   ];
 }
 
 /**
- * Pack 8 u32 values into 2 field elements for Pack<[u32; 8]>.
+ * Pack 11 u32 values into 2 field elements for Pack<[u32; 11]>.
  * FE layout: floor(255/32) = 7 values per FE.
  *   FE0 = v[0] + v[1]*2^32 + v[2]*2^64 + ... + v[6]*2^192
- *   FE1 = v[7]
- * Coordinates are biased by COORD_BIAS to be positive u32.
+ *   FE1 = v[7] + v[8]*2^32 + v[9]*2^64 + v[10]*2^96
+ * Coordinates are biased by COORD_BIAS to be positive u32. The 64-bit player
+ * ids are split: low words at v[0]/v[8], high words at v[9]/v[10].
  */
 const COORD_BIAS = 1n << 31n;
 
@@ -514,6 +521,7 @@ function buildProcessMoveValues(
   shipsToSend: bigint, metalToSend: bigint,
   sourceX: bigint, sourceY: bigint,
   targetX: bigint, targetY: bigint,
+  allyPlayerId: bigint = 0n,
 ): bigint[] {
   const vals = [
     BigInt.asUintN(32, playerId),
@@ -524,13 +532,19 @@ function buildProcessMoveValues(
     BigInt.asUintN(32, sourceY + COORD_BIAS),
     BigInt.asUintN(32, targetX + COORD_BIAS),
     BigInt.asUintN(32, targetY + COORD_BIAS),
+    BigInt.asUintN(32, allyPlayerId),
+    BigInt.asUintN(32, playerId >> 32n),
+    BigInt.asUintN(32, allyPlayerId >> 32n),
   ];
-  // Pack into 2 field elements: 7 values in FE0, 1 in FE1
+  // Pack into 2 field elements: 7 values in FE0, 4 in FE1
   let fe0 = 0n;
   for (let i = 6; i >= 0; i--) {
     fe0 = (fe0 << 32n) | vals[i];
   }
-  const fe1 = vals[7];
+  let fe1 = 0n;
+  for (let i = 10; i >= 7; i--) {
+    fe1 = (fe1 << 32n) | vals[i];
+  }
   return [fe0, fe1];
 }
 
@@ -543,44 +557,16 @@ async function waitForSlot(connection: Connection, targetSlot: bigint, label: st
   }
 }
 
-function buildFlushPlanetValues(
-  currentSlot: bigint, gameSpeed: bigint,
-  lastUpdatedSlot: bigint, flushCount: bigint
-): bigint[] {
-  return [
-    BigInt.asUintN(32, currentSlot),
-    BigInt.asUintN(32, gameSpeed),
-    BigInt.asUintN(32, lastUpdatedSlot),
-    BigInt.asUintN(32, flushCount),
-  ];
-}
-
 function buildUpgradePlanetValues(
-  playerId: bigint, focus: UpgradeFocus,
-  currentSlot: bigint, gameSpeed: bigint,
-  lastUpdatedSlot: bigint, metalUpgradeCost: bigint
+  playerId: bigint, focus: UpgradeFocus, metalUpgradeCost: bigint
 ): bigint[] {
   return [
-    BigInt.asUintN(32, playerId),
+    BigInt.asUintN(64, playerId),
     BigInt.asUintN(32, BigInt(focus)),
-    BigInt.asUintN(32, currentSlot),
-    BigInt.asUintN(32, gameSpeed),
-    BigInt.asUintN(32, lastUpdatedSlot),
     BigInt.asUintN(32, metalUpgradeCost),
   ];
 }
 
-function computeCurrentResource(
-  lastCount: bigint, maxCapacity: bigint, genSpeed: bigint,
-  lastUpdatedSlot: bigint, currentSlot: bigint, gameSpeed: bigint,
-): bigint {
-  if (genSpeed === 0n || gameSpeed === 0n || currentSlot <= lastUpdatedSlot) return lastCount;
-  const elapsed = currentSlot - lastUpdatedSlot;
-  const generated = genSpeed * elapsed * 10000n / gameSpeed;
-  const total = lastCount + generated;
-  return total > maxCapacity ? maxCapacity : total;
-}
-
 // ---------------------------------------------------------------------------
 // Main
 // ---------------------------------------------------------------------------
@@ -609,6 +595,9 @@ async function main() {
 
   const alice = Keypair.generate();
   const bob   = Keypair.generate();
+  // process_move only accepts the player id derived from the payer's key
+  const aliceId = playerIdFromPubkey(alice.publicKey);
+  const bobId   = playerIdFromPubkey(bob.publicKey);
 
   task("Airdropping SOL to Alice and Bob");
   const [aliceSig, bobSig] = await Promise.all([
@@ -650,13 +639,29 @@ async function main() {
     gameId,
     mapDiameter: 1000n,
     gameSpeed: 1000n,
-    startSlot: 0n,
+    startSlot: await fetchUpcomingStartSlot(connection),
     endSlot: 1_000_000_000n,
     winCondition: { pointsBurning: { pointsPerMetal: 1n } },
     whitelist: false,
     serverPubkey: null,
     noiseThresholds: DEFAULT_THRESHOLDS,
     hashRounds: 1,
+    maxMovesPerSlot: 4,
+    maxPendingMoves: MAX_QUEUED_CALLBACKS,
+    quasarShipPenaltyBps: 0n,
+    moveCooldownSlots: 0n,
+    maxPlayers: 0,
+    minSpawnSeparation: 0n,
+    cometDriftIntervalSlots: 0n,
+    hashAlgorithm: "blake3",
+    securityDepositLamports: 0n,
+    depositRecipient: null,
+    captureCooldownSlots: 0n,
+    distanceMethod: "chebyshevApprox",
+    validSpawnBodyTypes: 1,
+    maxPlanetLevel: 10,
+    minShipsPerMove: 1n,
+    pointsToWin: 0n,
   };
 
   const [gamePDA] = deriveGamePDA(gameId, program.programId);
@@ -665,6 +670,7 @@ async function main() {
     .signers([admin])
     .rpc({ commitment: "confirmed" });
 
+  await waitForSlot(connection, createGameArgs.startSlot, "game start");
   info(`Game ID: ${gameId}`);
   info(`Game PDA: ${gamePDA.toString()}`);
   info(`Map diameter: 1000`);
@@ -673,7 +679,7 @@ async function main() {
 
   // Verify comp defs are initialized
   task("Checking computation definitions...");
-  const compDefNames = ["init_planet", "init_spawn_planet", "process_move", "flush_planet", "upgrade_planet"];
+  const compDefNames = ["init_planet", "init_spawn_planet", "process_move", "flush_planet", "upgrade_planet", "surrender_planet", "transfer_resources", "spy_move", "comet_collect", "mine_asteroid", "check_ownership", "wormhole_jump", "comet_drift", "transfer_owner"];
   let compDefsOk = true;
   for (const name of compDefNames) {
     const offsetBytes = getCompDefAccOffset(name);
@@ -690,7 +696,7 @@ async function main() {
     warn("Or re-run: ./scripts/run-local.sh");
     process.exit(1);
   }
-  ok("All 5 computation definitions initialized");
+  ok("All 13 computation definitions initialized");
 
   stepDone(s1);
 
@@ -762,7 +768,9 @@ async function main() {
     const values = buildInitSpawnPlanetValues(x, y, playerId, sourcePlanetId);
     const { packed } = encryptAndPack(enc.cipher, values, nonce);
 
-    const computationOffset = new BN(randomBytes(8), "hex");
+    const computationOffset = new BN(
+      (await client.getNextComputationOffset(gameId, payer.publicKey)).toString()
+    );
     const arciumAccts = getArciumAccountAddresses(program.programId, computationOffset, "init_spawn_planet");
 
     const observerPubkey = x25519.getPublicKey(x25519.utils.randomSecretKey());
@@ -771,22 +779,25 @@ async function main() {
     const [spawnPlayerPDA] = derivePlayerPDA(gameId, payer.publicKey, program.programId);
     const [spawnPlanetPDA] = deriveCelestialBodyPDA(gameId, planetHash, program.programId);
     const [spawnPendingPDA] = derivePendingMovesPDA(gameId, planetHash, program.programId);
+    const [spawnCombatLogPDA] = deriveCombatLogPDA(gameId, planetHash, program.programId);
 
     await program.methods
       .queueInitSpawnPlanet(
-        computationOffset,
         Array.from(planetHash) as any,
         Buffer.from(packed),
         Array.from(enc.publicKey) as any,
         new BN(nonceValue.toString()),
-        Array.from(observerPubkey) as any
+        Array.from(observerPubkey) as any,
+        null
       )
       .accountsPartial({
         payer: payer.publicKey,
+        offsetTracker: deriveComputationOffsetTrackerPDA(gameId, payer.publicKey, program.programId)[0],
         game: spawnGamePDA,
         player: spawnPlayerPDA,
         celestialBody: spawnPlanetPDA,
         pendingMoves: spawnPendingPDA,
+        combatLog: spawnCombatLogPDA,
         signPdaAccount: arciumAccts.signPdaAccount,
         mxeAccount: arciumAccts.mxeAccount,
         mempoolAccount: arciumAccts.mempoolAccount,
@@ -811,8 +822,8 @@ async function main() {
   }
 
   const [aliceSpawnResult, bobSpawnResult] = await Promise.all([
-    queueSpawn(alice, aliceSpawn.x, aliceSpawn.y, 1n, 0n, encAlice),
-    queueSpawn(bob, bobSpawn.x, bobSpawn.y, 2n, 0n, encBob),
+    queueSpawn(alice, aliceSpawn.x, aliceSpawn.y, aliceId, 0n, encAlice),
+    queueSpawn(bob, bobSpawn.x, bobSpawn.y, bobId, 0n, encBob),
   ]);
 
   task("Waiting for MPC finalization (both players in parallel)...");
@@ -858,14 +869,13 @@ async function main() {
   const initValues = buildInitPlanetValues(nearbyPlanet.x, nearbyPlanet.y);
   const { packed: initPacked } = encryptAndPack(encAlice.cipher, initValues, initNonce);
 
-  const initCO = new BN(randomBytes(8), "hex");
+  const initCO = new BN((await client.getNextComputationOffset(gameId, alice.publicKey)).toString());
   const initArcium = getArciumAccountAddresses(program.programId, initCO, "init_planet");
   const initObserver = x25519.getPublicKey(x25519.utils.randomSecretKey());
 
   await client
     .buildQueueInitPlanet(alice.publicKey, {
       gameId,
-      computationOffset: BigInt(initCO.toString()),
       planetHash: nearbyHash,
       ciphertexts: initPacked,
       pubkey: encAlice.publicKey,
@@ -882,7 +892,7 @@ async function main() {
 
   const nearbyBody = await program.account.encryptedCelestialBody.fetch(nearbyPlanetPDA);
   assert(
-    nearbyBody.stateEncCiphertexts.length === 3,
+    nearbyBody.stateEncCiphertexts.length === 4,
     `Planet initialized: ${nearbyPlanetPDA.toString().slice(0, 16)}...`,
     "Failed to initialize nearby planet"
   );
@@ -900,6 +910,8 @@ async function main() {
   const aliceSourceHash = computePlanetHash(aliceSpawn.x, aliceSpawn.y, gameId);
   const [aliceSourcePendingPDA] = derivePendingMovesPDA(gameId, aliceSourceHash, program.programId);
   const [nearbyPendingPDA] = derivePendingMovesPDA(gameId, nearbyHash, program.programId);
+  const [nearbyCombatLogPDA] = deriveCombatLogPDA(gameId, nearbyHash, program.programId);
+  const [aliceCombatLogPDA] = deriveCombatLogPDA(gameId, aliceSourceHash, program.programId);
 
   const dist1 = computeDistance(aliceSpawn.x, aliceSpawn.y, nearbyPlanet.x, nearbyPlanet.y);
   info(`Distance: ${dist1}`);
@@ -909,7 +921,7 @@ async function main() {
   info(`Landing slot: ${landingSlot1} (current: ${currentSlot1})`);
 
   const moveValues1 = buildProcessMoveValues(
-    1n, 0n,   // playerId, sourcePlanetId
+    aliceId, 0n,   // playerId, sourcePlanetId
     3n, 0n,   // shipsToSend, metalToSend
     aliceSpawn.x, aliceSpawn.y,
     nearbyPlanet.x, nearbyPlanet.y,
@@ -919,20 +931,15 @@ async function main() {
   const moveNonceValue1 = deserializeLE(moveNonce1);
   const { packed: movePacked1 } = encryptAndPack(encAlice.cipher, moveValues1, moveNonce1);
 
-  const moveCO1 = new BN(randomBytes(8), "hex");
+  const moveCO1 = new BN((await client.getNextComputationOffset(gameId, alice.publicKey)).toString());
   const moveArcium1 = getArciumAccountAddresses(program.programId, moveCO1, "process_move");
 
   task("Queuing process_move MPC...");
   await client
     .buildQueueProcessMove(alice.publicKey, {
       gameId,
-      computationOffset: BigInt(moveCO1.toString()),
       landingSlot: landingSlot1,
-      currentShips: computeCurrentResource(
-        0n, 100n, 1n,
-        BigInt(aliceBody.lastUpdatedSlot.toString()), currentSlot1, 1000n
-      ),
-      currentMetal: 0n,
+      maxObservableDistance: observableDistanceBound(dist1, createGameArgs.mapDiameter),
       moveCts: movePacked1,
       movePubkey: encAlice.publicKey,
       moveNonce: BigInt(moveNonceValue1.toString()),
@@ -974,7 +981,7 @@ async function main() {
   info(`Landing slot: ${landingSlot2}`);
 
   const moveValues2 = buildProcessMoveValues(
-    2n, 0n,   // playerId=2 (Bob), sourcePlanetId=0
+    bobId, 0n,   // playerId (Bob), sourcePlanetId=0
     3n, 0n,   // shipsToSend, metalToSend
     bobSpawn.x, bobSpawn.y,
     aliceSpawn.x, aliceSpawn.y,
@@ -984,20 +991,15 @@ async function main() {
   const moveNonceValue2 = deserializeLE(moveNonce2);
   const { packed: movePacked2 } = encryptAndPack(encBob.cipher, moveValues2, moveNonce2);
 
-  const moveCO2 = new BN(randomBytes(8), "hex");
+  const moveCO2 = new BN((await client.getNextComputationOffset(gameId, bob.publicKey)).toString());
   const moveArcium2 = getArciumAccountAddresses(program.programId, moveCO2, "process_move");
 
   task("Queuing process_move MPC (Bob's attack)...");
   await client
     .buildQueueProcessMove(bob.publicKey, {
       gameId,
-      computationOffset: BigInt(moveCO2.toString()),
       landingSlot: landingSlot2,
-      currentShips: computeCurrentResource(
-        0n, 100n, 1n,
-        BigInt(bobBody.lastUpdatedSlot.toString()), currentSlot2, 1000n
-      ),
-      currentMetal: 0n,
+      maxObservableDistance: observableDistanceBound(dist2, createGameArgs.mapDiameter),
       moveCts: movePacked2,
       movePubkey: encBob.publicKey,
       moveNonce: BigInt(moveNonceValue2.toString()),
@@ -1029,23 +1031,11 @@ async function main() {
   // Wait for Alice's ships to land before flushing
   await waitForSlot(connection, landingSlot1, "Alice's ships landing");
 
-  const nearbyBodyForFlush = await program.account.encryptedCelestialBody.fetch(nearbyPlanetPDA);
   const nearbyPendingForFlush = await program.account.pendingMovesMetadata.fetch(nearbyPendingPDA);
 
   if (nearbyPendingForFlush.moves.length > 0) {
-    const flushSlot = BigInt(await connection.getSlot("confirmed"));
     const flushCount = 1;
-    const flushValues = buildFlushPlanetValues(
-      flushSlot, 1000n,
-      BigInt(nearbyBodyForFlush.lastUpdatedSlot.toString()),
-      BigInt(flushCount),
-    );
-
-    const flushNonce1 = randomBytes(16);
-    const flushNonceValue1 = deserializeLE(flushNonce1);
-    const { packed: flushPacked1 } = encryptAndPack(encAlice.cipher, flushValues, flushNonce1);
-
-    const flushCO1 = new BN(randomBytes(8), "hex");
+    const flushCO1 = new BN((await client.getNextComputationOffset(gameId, alice.publicKey)).toString());
     const flushArcium1 = getArciumAccountAddresses(program.programId, flushCO1, "flush_planet");
 
     const moveId = BigInt(nearbyPendingForFlush.moves[0].moveId.toString());
@@ -1054,13 +1044,13 @@ async function main() {
     task("Queuing flush_planet MPC...");
     await client
       .buildQueueFlushPlanet(alice.publicKey, {
-        computationOffset: BigInt(flushCO1.toString()),
+        gameId,
         flushCount,
-        flushCts: flushPacked1,
-        flushPubkey: encAlice.publicKey,
-        flushNonce: BigInt(flushNonceValue1.toString()),
+        observerPubkey: encAlice.publicKey,
+        observerNonce: deserializeLE(randomBytes(16)),
         celestialBody: nearbyPlanetPDA,
         pendingMoves: nearbyPendingPDA,
+        combatLog: nearbyCombatLogPDA,
         moveAccounts: [moveAccountPDA],
       }, flushArcium1)
       .signers([alice])
@@ -1093,19 +1083,8 @@ async function main() {
   const alicePendingForFlush = await program.account.pendingMovesMetadata.fetch(aliceSourcePendingPDA);
 
   if (alicePendingForFlush.moves.length > 0) {
-    const flushSlot2 = BigInt(await connection.getSlot("confirmed"));
     const flushCount2 = 1;
-    const flushValues2 = buildFlushPlanetValues(
-      flushSlot2, 1000n,
-      BigInt(aliceBodyForFlush.lastUpdatedSlot.toString()),
-      BigInt(flushCount2),
-    );
-
-    const flushNonce2 = randomBytes(16);
-    const flushNonceValue2 = deserializeLE(flushNonce2);
-    const { packed: flushPacked2 } = encryptAndPack(encAlice.cipher, flushValues2, flushNonce2);
-
-    const flushCO2 = new BN(randomBytes(8), "hex");
+    const flushCO2 = new BN((await client.getNextComputationOffset(gameId, alice.publicKey)).toString());
     const flushArcium2 = getArciumAccountAddresses(program.programId, flushCO2, "flush_planet");
 
     const moveId2 = BigInt(alicePendingForFlush.moves[0].moveId.toString());
@@ -1114,13 +1093,13 @@ async function main() {
     task("Queuing flush_planet MPC (combat resolution)...");
     await client
       .buildQueueFlushPlanet(alice.publicKey, {
-        computationOffset: BigInt(flushCO2.toString()),
+        gameId,
         flushCount: flushCount2,
-        flushCts: flushPacked2,
-        flushPubkey: encAlice.publicKey,
-        flushNonce: BigInt(flushNonceValue2.toString()),
+        observerPubkey: encAlice.publicKey,
+        observerNonce: deserializeLE(randomBytes(16)),
         celestialBody: alicePlanetPDA,
         pendingMoves: aliceSourcePendingPDA,
+        combatLog: aliceCombatLogPDA,
         moveAccounts: [moveAccountPDA2],
       }, flushArcium2)
       .signers([alice])
@@ -1142,6 +1121,13 @@ async function main() {
       `Planet state updated (lastFlushedSlot: ${aliceBodyAfter.lastFlushedSlot})`,
       "Planet flushed slot not updated"
     );
+
+    const aliceCombatLog = await program.account.combatLog.fetch(aliceCombatLogPDA);
+    assert(
+      aliceCombatLog.totalAttacks >= 1,
+      `Combat log updated (attacks: ${aliceCombatLog.totalAttacks}, captures: ${aliceCombatLog.totalCaptures})`,
+      "Combat log not updated by flush"
+    );
   } else {
     warn("No pending moves to flush on Alice's spawn");
   }
@@ -1154,14 +1140,10 @@ async function main() {
   const s9 = stepStart("Alice upgrades her nearby planet (Range focus)");
 
   const nearbyBodyForUpgrade = await program.account.encryptedCelestialBody.fetch(nearbyPlanetPDA);
-  const currentSlot3 = BigInt(await connection.getSlot("confirmed"));
 
   const upgradeValues = buildUpgradePlanetValues(
-    1n,                // playerId
+    aliceId,           // playerId
     UpgradeFocus.Range,
-    currentSlot3,
-    1000n,            // gameSpeed
-    BigInt(nearbyBodyForUpgrade.lastUpdatedSlot.toString()),
     100n,              // metalUpgradeCost (level 0 base)
   );
 
@@ -1169,18 +1151,18 @@ async function main() {
   const upgradeNonceValue = deserializeLE(upgradeNonce);
   const { packed: upgradePacked } = encryptAndPack(encAlice.cipher, upgradeValues, upgradeNonce);
 
-  const upgradeCO = new BN(randomBytes(8), "hex");
+  const upgradeCO = new BN((await client.getNextComputationOffset(gameId, alice.publicKey)).toString());
   const upgradeArcium = getArciumAccountAddresses(program.programId, upgradeCO, "upgrade_planet");
 
   task("Queuing upgrade_planet MPC...");
   await client
     .buildQueueUpgradePlanet(alice.publicKey, {
       gameId,
-      computationOffset: BigInt(upgradeCO.toString()),
       upgradeCts: upgradePacked,
       upgradePubkey: encAlice.publicKey,
       upgradeNonce: BigInt(upgradeNonceValue.toString()),
       celestialBody: nearbyPlanetPDA,
+      pendingMoves: nearbyPendingPDA,
     }, upgradeArcium)
     .signers([alice])
     .rpc({ skipPreflight: true, commitment: "confirmed" });
@@ -1,5 +1,5 @@
 /**
- * Initialize all 5 computation definitions for the Encrypted Forest program.
+ * Initialize all 13 computation definitions for the Encrypted Forest program.
  *
  * Must run after program deployment and MXE initialization.
  * Idempotent — safe to re-run (skips already-initialized comp defs).
@@ -58,6 +58,15 @@ async function main() {
     "process_move",
     "flush_planet",
     "upgrade_planet",
+    "surrender_planet",
+    "transfer_resources",
+    "spy_move",
+    "comet_collect",
+    "mine_asteroid",
+    "check_ownership",
+    "wormhole_jump",
+    "comet_drift",
+    "transfer_owner",
   ];
 
   const methodNames = [
@@ -66,6 +75,15 @@ async function main() {
     "initCompDefProcessMove",
     "initCompDefFlushPlanet",
     "initCompDefUpgradePlanet",
+    "initCompDefSurrenderPlanet",
+    "initCompDefTransferResources",
+    "initCompDefSpyMove",
+    "initCompDefCometCollect",
+    "initCompDefMineAsteroid",
+    "initCompDefCheckOwnership",
+    "initCompDefWormholeJump",
+    "initCompDefCometDrift",
+    "initCompDefTransferOwner",
   ] as const;
 
   console.log(`Program ID: ${program.programId.toString()}`);
@@ -80,7 +98,7 @@ async function main() {
 
     try {
       await (program.methods as any)
-        [methodNames[i]](CIRCUIT_BASE_URL)
+        [methodNames[i]](CIRCUIT_BASE_URL, null)
         .accounts({
           payer: admin.publicKey,
           mxeAccount,
@@ -89,6 +107,8 @@ async function main() {
           lutProgram,
           systemProgram: SystemProgram.programId,
           arciumProgram,
+          // Only needed with a circuit hash override (upgrade authority check)
+          programData: null,
         })
         .signers([admin])
         .rpc({ commitment: "confirmed" });
@@ -4,23 +4,36 @@
 
 import { type Program } from "@coral-xyz/anchor";
 import { PublicKey } from "@solana/web3.js";
-import type { EncryptedCelestialBodyAccount } from "../types/celestialBody.js";
-import { deriveCelestialBodyPDA } from "../utils/pda.js";
+import type {
+  CombatLog,
+  CoordinateProof,
+  EncryptedCelestialBodyAccount,
+} from "../types/celestialBody.js";
+import {
+  deriveCelestialBodyPDA,
+  deriveCombatLogPDA,
+  deriveCoordinateProofPDA,
+} from "../utils/pda.js";
 
 /**
  * Convert Anchor's deserialized EncryptedCelestialBody to our SDK type.
  */
 function convertEncryptedCelestialBody(raw: any): EncryptedCelestialBodyAccount {
   return {
+    version: raw.version,
     planetHash: new Uint8Array(raw.planetHash),
     lastUpdatedSlot: BigInt(raw.lastUpdatedSlot.toString()),
     lastFlushedSlot: BigInt(raw.lastFlushedSlot.toString()),
+    lastMinedSlot: BigInt(raw.lastMinedSlot.toString()),
     // State encryption section
     stateEncPubkey: new Uint8Array(raw.stateEncPubkey),
     stateEncNonce: new Uint8Array(raw.stateEncNonce),
     stateEncCiphertexts: (raw.stateEncCiphertexts as any[]).map(
       (ct: any) => new Uint8Array(ct)
     ),
+    hasSpawnCoords: raw.hasSpawnCoords,
+    initializer: raw.initializer,
+    hashRounds: raw.hashRounds,
   };
 }
 
@@ -54,3 +67,53 @@ export async function fetchEncryptedCelestialBodyByAddress(
   );
   return convertEncryptedCelestialBody(raw);
 }
+
+/**
+ * Fetch and deserialize a planet's CombatLog account by PDA.
+ */
+export async function fetchCombatLog(
+  program: Program,
+  gameId: bigint,
+  planetHash: Uint8Array,
+  programId?: PublicKey
+): Promise<CombatLog> {
+  const [pda] = deriveCombatLogPDA(
+    gameId,
+    planetHash,
+    programId ?? program.programId
+  );
+  const raw = await (program.account as any).combatLog.fetch(pda);
+  return {
+    gameId: BigInt(raw.gameId.toString()),
+    planetHash: new Uint8Array(raw.planetHash),
+    totalAttacks: raw.totalAttacks,
+    totalDefenses: raw.totalDefenses,
+    totalCaptures: raw.totalCaptures,
+    shipsLost: BigInt(raw.shipsLost.toString()),
+    metalLost: BigInt(raw.metalLost.toString()),
+  };
+}
+
+/**
+ * Fetch and deserialize a planet's CoordinateProof account by PDA.
+ */
+export async function fetchCoordinateProof(
+  program: Program,
+  gameId: bigint,
+  planetHash: Uint8Array,
+  programId?: PublicKey
+): Promise<CoordinateProof> {
+  const [pda] = deriveCoordinateProofPDA(
+    gameId,
+    planetHash,
+    programId ?? program.programId
+  );
+  const raw = await (program.account as any).coordinateProof.fetch(pda);
+  return {
+    planetHash: new Uint8Array(raw.planetHash),
+    x: BigInt(raw.x.toString()),
+    y: BigInt(raw.y.toString()),
+    owner: raw.owner,
+    revealedSlot: BigInt(raw.revealedSlot.toString()),
+  };
+}
@@ -42,6 +42,33 @@ function convertGame(raw: any): Game {
     serverPubkey: raw.serverPubkey ?? null,
     noiseThresholds: raw.noiseThresholds as NoiseThresholds,
     hashRounds: raw.hashRounds ?? 100,
+    priorHashRounds: (raw.priorHashRounds as number[]).slice(0, raw.hashRoundsUpdates),
+    maxMovesPerSlot: raw.maxMovesPerSlot,
+    maxPendingMoves: raw.maxPendingMoves,
+    quasarShipPenaltyBps: BigInt(raw.quasarShipPenaltyBps.toString()),
+    moveCooldownSlots: BigInt(raw.moveCooldownSlots.toString()),
+    maxPlayers: raw.maxPlayers,
+    currentPlayerCount: raw.currentPlayerCount,
+    planetCount: BigInt(raw.planetCount.toString()),
+    minSpawnSeparation: BigInt(raw.minSpawnSeparation.toString()),
+    spawnCount: raw.spawnCount,
+    spawnPlanetHashes: (raw.spawnPlanetHashes as any[])
+      .slice(0, raw.spawnCount)
+      .map((h: any) => new Uint8Array(h)),
+    minSpawnDistanceFromCenter: BigInt(raw.minSpawnDistanceFromCenter.toString()),
+    cometDriftIntervalSlots: BigInt(raw.cometDriftIntervalSlots.toString()),
+    hashAlgorithm: raw.hashAlgorithm.sha256 ? "sha256" : "blake3",
+    accountVersion: raw.accountVersion,
+    securityDepositLamports: BigInt(raw.securityDepositLamports.toString()),
+    depositRecipient: raw.depositRecipient,
+    captureCooldownSlots: BigInt(raw.captureCooldownSlots.toString()),
+    allowForceCleanup: raw.allowForceCleanup,
+    distanceMethod: raw.distanceMethod.manhattan ? "manhattan" : "chebyshevApprox",
+    validSpawnBodyTypes: raw.validSpawnBodyTypes,
+    maxPlanetLevel: raw.maxPlanetLevel,
+    minShipsPerMove: BigInt(raw.minShipsPerMove.toString()),
+    pointsToWin: BigInt(raw.pointsToWin.toString()),
+    endedEarly: raw.endedEarly,
   };
 }
 
@@ -29,10 +29,12 @@ function convertPendingMovesMetadata(raw: any): PendingMovesMetadata {
     planetHash: new Uint8Array(raw.planetHash),
     nextMoveId: BigInt(raw.nextMoveId.toString()),
     moveCount: raw.moveCount,
+    capacity: raw.capacity,
     queuedCount: raw.queuedCount,
     queuedLandingSlots: (raw.queuedLandingSlots as any[]).map(
       (s: any) => BigInt(s.toString())
     ),
+    lastMoveQueuedSlot: BigInt(raw.lastMoveQueuedSlot.toString()),
     moves: (raw.moves as any[]).map(convertPendingMoveEntry),
   };
 }
@@ -4,8 +4,13 @@
 
 import { type Program } from "@coral-xyz/anchor";
 import { PublicKey } from "@solana/web3.js";
-import type { Player } from "../types/player.js";
-import { derivePlayerPDA } from "../utils/pda.js";
+import { blake3 } from "@noble/hashes/blake3.js";
+import type { Player, PlayerProfile } from "../types/player.js";
+import {
+  derivePlayerPDA,
+  derivePlayerProfilePDA,
+  deriveComputationOffsetTrackerPDA,
+} from "../utils/pda.js";
 
 /**
  * Convert Anchor's deserialized Player account to our SDK type.
@@ -16,6 +21,9 @@ function convertPlayer(raw: any): Player {
     gameId: BigInt(raw.gameId.toString()),
     points: BigInt(raw.points.toString()),
     hasSpawned: raw.hasSpawned,
+    lastMoveSlot: BigInt(raw.lastMoveSlot.toString()),
+    movesThisSlot: raw.movesThisSlot,
+    spawnPlanetHash: new Uint8Array(raw.spawnPlanetHash),
   };
 }
 
@@ -47,3 +55,92 @@ export async function fetchPlayerByAddress(
   const raw = await (program.account as any).player.fetch(address);
   return convertPlayer(raw);
 }
+
+/**
+ * Convert Anchor's deserialized PlayerProfile account to our SDK type.
+ */
+function convertPlayerProfile(raw: any): PlayerProfile {
+  return {
+    owner: raw.owner,
+    totalGames: raw.totalGames,
+    totalWins: raw.totalWins,
+    lifetimePoints: BigInt(raw.lifetimePoints.toString()),
+    planetsCaptured: raw.planetsCaptured,
+    shipsSent: BigInt(raw.shipsSent.toString()),
+  };
+}
+
+/**
+ * Fetch and deserialize a PlayerProfile account by owner.
+ */
+export async function fetchPlayerProfile(
+  program: Program,
+  owner: PublicKey,
+  programId?: PublicKey
+): Promise<PlayerProfile> {
+  const [profilePDA] = derivePlayerProfilePDA(
+    owner,
+    programId ?? program.programId
+  );
+  const raw = await (program.account as any).playerProfile.fetch(profilePDA);
+  return convertPlayerProfile(raw);
+}
+
+/**
+ * Player id the circuits accept from this wallet: the XOR of the pubkey's
+ * four little-endian u64 words, all 64 bits of it. Mirrors the circuit's
+ * player_id_from_key; spawning, moving, upgrading and every other
+ * owner-gated action must use this id.
+ */
+export function playerIdFromPubkey(owner: PublicKey): bigint {
+  const bytes = owner.toBytes();
+  const view = new DataView(bytes.buffer, bytes.byteOffset, 32);
+  let folded = 0n;
+  for (let i = 0; i < 4; i++) {
+    folded ^= view.getBigUint64(i * 8, true);
+  }
+  return folded;
+}
+
+/**
+ * Computation offset handed out for a player's `counter`-th queue_* call.
+ * Mirrors derive_computation_offset on-chain: the first 8
+ * bytes (LE) of blake3("offset" || game_id LE || owner || counter LE).
+ */
+export function computeComputationOffset(
+  gameId: bigint,
+  owner: PublicKey,
+  counter: bigint
+): bigint {
+  const data = new Uint8Array(6 + 8 + 32 + 8);
+  const view = new DataView(data.buffer);
+  data.set(new TextEncoder().encode("offset"), 0);
+  view.setBigUint64(6, gameId, true);
+  data.set(owner.toBytes(), 14);
+  view.setBigUint64(46, counter, true);
+  const hash = blake3(data);
+  return new DataView(hash.buffer, hash.byteOffset, 8).getBigUint64(0, true);
+}
+
+/**
+ * Fetch the player's ComputationOffsetTracker and return the offset their
+ * next queue_* call will use (needed to derive the computation account).
+ * A missing tracker is created by that call, so it starts from counter 0.
+ */
+export async function fetchNextComputationOffset(
+  program: Program,
+  gameId: bigint,
+  owner: PublicKey,
+  programId?: PublicKey
+): Promise<bigint> {
+  const [trackerPDA] = deriveComputationOffsetTrackerPDA(
+    gameId,
+    owner,
+    programId ?? program.programId
+  );
+  const raw = await (
+    program.account as any
+  ).computationOffsetTracker.fetchNullable(trackerPDA);
+  const counter = raw ? BigInt(raw.nextOffset.toString()) : 0n;
+  return computeComputationOffset(gameId, owner, counter);
+}
@@ -17,17 +17,24 @@ import {
   PublicKey,
   type Commitment,
 } from "@solana/web3.js";
-import type { Game, NoiseThresholds } from "./types/game.js";
-import type { Player } from "./types/player.js";
-import type { EncryptedCelestialBodyAccount } from "./types/celestialBody.js";
+import type { DistanceMethod, Game, NoiseThresholds } from "./types/game.js";
+import type { Player, PlayerProfile } from "./types/player.js";
+import type {
+  CombatLog,
+  CoordinateProof,
+  EncryptedCelestialBodyAccount,
+} from "./types/celestialBody.js";
 import { CelestialBodyType } from "./types/celestialBody.js";
 import type { PendingMovesMetadata } from "./types/pendingMoves.js";
 import {
   PROGRAM_ID,
   deriveGamePDA,
   derivePlayerPDA,
+  derivePlayerProfilePDA,
   deriveCelestialBodyPDA,
   derivePendingMovesPDA,
+  deriveCombatLogPDA,
+  deriveCoordinateProofPDA,
   derivePendingMoveAccountPDA,
 } from "./utils/pda.js";
 import {
@@ -67,10 +74,17 @@ import {
   type PendingMoveData,
 } from "./crypto/planetCipher.js";
 import { fetchGame, fetchGameByAddress } from "./accounts/game.js";
-import { fetchPlayer, fetchPlayerByAddress } from "./accounts/player.js";
+import {
+  fetchPlayer,
+  fetchPlayerByAddress,
+  fetchPlayerProfile,
+  fetchNextComputationOffset,
+} from "./accounts/player.js";
 import {
   fetchEncryptedCelestialBody,
   fetchEncryptedCelestialBodyByAddress,
+  fetchCombatLog,
+  fetchCoordinateProof,
 } from "./accounts/celestialBody.js";
 import {
   fetchPendingMovesMetadata,
@@ -80,16 +94,55 @@ import {
   buildCreateGameIx,
   type CreateGameArgs,
 } from "./instructions/createGame.js";
-import { buildInitPlayerIx } from "./instructions/initPlayer.js";
+import {
+  buildInitPlayerIx,
+  buildInitPlayerProfileIx,
+} from "./instructions/initPlayer.js";
 import {
   buildBroadcastIx,
   type BroadcastArgs,
 } from "./instructions/broadcast.js";
+import {
+  buildBroadcastBulkIx,
+  type BroadcastBulkArgs,
+} from "./instructions/broadcastBulk.js";
+import {
+  buildVerifyCoordinatesIx,
+  type VerifyCoordinatesArgs,
+} from "./instructions/verifyCoordinates.js";
+import {
+  buildCreateCoordinateProofIx,
+  buildClaimRaceToCenterVictoryIx,
+  type CreateCoordinateProofArgs,
+  type ClaimRaceToCenterVictoryArgs,
+} from "./instructions/coordinateProof.js";
+import {
+  buildClaimPointsVictoryIx,
+  type ClaimPointsVictoryArgs,
+} from "./instructions/claimPointsVictory.js";
+import { buildGetGameConfigIx } from "./instructions/getGameConfig.js";
+import { buildGetProgramVersionIx } from "./instructions/getProgramVersion.js";
 import {
   buildCleanupGameIx,
   buildCleanupPlayerIx,
   buildCleanupPlanetIx,
+  buildCleanupMoveAccountIx,
+  buildSetAllowForceCleanupIx,
+  buildCloseDeadPlanetIx,
 } from "./instructions/cleanup.js";
+import {
+  buildAbandonMoveBatchIx,
+  type AbandonMoveBatchArgs,
+} from "./instructions/abandonMoveBatch.js";
+import {
+  buildMergePendingMovesIx,
+  type MergePendingMovesArgs,
+} from "./instructions/mergePendingMoves.js";
+import {
+  buildCreateSpectatorRegistryIx,
+  buildRegisterSpectatorIx,
+  buildDeregisterSpectatorIx,
+} from "./instructions/spectator.js";
 import type { ArciumAccounts } from "./instructions/arciumAccounts.js";
 import {
   buildQueueInitPlanetIx,
@@ -159,6 +212,10 @@ export class EncryptedForestClient {
     return derivePlayerPDA(gameId, playerPubkey, this.programId);
   }
 
+  derivePlayerProfilePDA(owner: PublicKey): [PublicKey, number] {
+    return derivePlayerProfilePDA(owner, this.programId);
+  }
+
   deriveCelestialBodyPDA(
     gameId: bigint,
     planetHash: Uint8Array
@@ -173,6 +230,20 @@ export class EncryptedForestClient {
     return derivePendingMovesPDA(gameId, planetHash, this.programId);
   }
 
+  deriveCombatLogPDA(
+    gameId: bigint,
+    planetHash: Uint8Array
+  ): [PublicKey, number] {
+    return deriveCombatLogPDA(gameId, planetHash, this.programId);
+  }
+
+  deriveCoordinateProofPDA(
+    gameId: bigint,
+    planetHash: Uint8Array
+  ): [PublicKey, number] {
+    return deriveCoordinateProofPDA(gameId, planetHash, this.programId);
+  }
+
   derivePendingMoveAccountPDA(
     gameId: bigint,
     planetHash: Uint8Array,
@@ -201,6 +272,22 @@ export class EncryptedForestClient {
     return fetchPlayerByAddress(this.program, address);
   }
 
+  async getPlayerProfile(owner: PublicKey): Promise<PlayerProfile> {
+    return fetchPlayerProfile(this.program, owner, this.programId);
+  }
+
+  async getNextComputationOffset(
+    gameId: bigint,
+    owner: PublicKey
+  ): Promise<bigint> {
+    return fetchNextComputationOffset(
+      this.program,
+      gameId,
+      owner,
+      this.programId
+    );
+  }
+
   async getEncryptedCelestialBody(
     gameId: bigint,
     planetHash: Uint8Array
@@ -237,6 +324,25 @@ export class EncryptedForestClient {
     return fetchPendingMovesMetadataByAddress(this.program, address);
   }
 
+  async getCombatLog(
+    gameId: bigint,
+    planetHash: Uint8Array
+  ): Promise<CombatLog> {
+    return fetchCombatLog(this.program, gameId, planetHash, this.programId);
+  }
+
+  async getCoordinateProof(
+    gameId: bigint,
+    planetHash: Uint8Array
+  ): Promise<CoordinateProof> {
+    return fetchCoordinateProof(
+      this.program,
+      gameId,
+      planetHash,
+      this.programId
+    );
+  }
+
   // -------------------------------------------------------------------------
   // Decryption
   // -------------------------------------------------------------------------
@@ -292,8 +398,17 @@ export class EncryptedForestClient {
     return buildCreateGameIx(this.program, admin, args);
   }
 
-  buildInitPlayer(owner: PublicKey, gameId: bigint, server?: PublicKey) {
-    return buildInitPlayerIx(this.program, owner, gameId, server);
+  buildInitPlayer(
+    owner: PublicKey,
+    gameId: bigint,
+    server?: PublicKey,
+    withProfile = false
+  ) {
+    return buildInitPlayerIx(this.program, owner, gameId, server, withProfile);
+  }
+
+  buildInitPlayerProfile(owner: PublicKey) {
+    return buildInitPlayerProfileIx(this.program, owner);
   }
 
   buildQueueInitPlanet(
@@ -365,16 +480,103 @@ export class EncryptedForestClient {
     return buildBroadcastIx(this.program, broadcaster, args);
   }
 
-  buildCleanupGame(closer: PublicKey, gameId: bigint) {
-    return buildCleanupGameIx(this.program, closer, gameId);
+  buildBroadcastBulk(broadcaster: PublicKey, args: BroadcastBulkArgs) {
+    return buildBroadcastBulkIx(this.program, broadcaster, args);
+  }
+
+  buildAbandonMoveBatch(payer: PublicKey, args: AbandonMoveBatchArgs) {
+    return buildAbandonMoveBatchIx(this.program, payer, args);
+  }
+
+  buildMergePendingMoves(admin: PublicKey, args: MergePendingMovesArgs) {
+    return buildMergePendingMovesIx(this.program, admin, args);
+  }
+
+  buildCreateSpectatorRegistry(
+    admin: PublicKey,
+    gameId: bigint,
+    maxSpectators: number
+  ) {
+    return buildCreateSpectatorRegistryIx(
+      this.program,
+      admin,
+      gameId,
+      maxSpectators
+    );
+  }
+
+  buildRegisterSpectator(
+    spectator: PublicKey,
+    gameId: bigint,
+    server?: PublicKey
+  ) {
+    return buildRegisterSpectatorIx(this.program, spectator, gameId, server);
+  }
+
+  buildDeregisterSpectator(spectator: PublicKey, gameId: bigint) {
+    return buildDeregisterSpectatorIx(this.program, spectator, gameId);
+  }
+
+  buildVerifyCoordinates(args: VerifyCoordinatesArgs) {
+    return buildVerifyCoordinatesIx(this.program, args);
+  }
+
+  buildCreateCoordinateProof(payer: PublicKey, args: CreateCoordinateProofArgs) {
+    return buildCreateCoordinateProofIx(this.program, payer, args);
+  }
+
+  buildClaimRaceToCenterVictory(
+    payer: PublicKey,
+    args: ClaimRaceToCenterVictoryArgs,
+    arciumAccounts: ArciumAccounts
+  ) {
+    return buildClaimRaceToCenterVictoryIx(
+      this.program,
+      payer,
+      args,
+      arciumAccounts
+    );
+  }
+
+  buildClaimPointsVictory(payer: PublicKey, args: ClaimPointsVictoryArgs) {
+    return buildClaimPointsVictoryIx(this.program, payer, args);
+  }
+
+  buildGetGameConfig(gameId: bigint) {
+    return buildGetGameConfigIx(this.program, gameId);
+  }
+
+  buildGetProgramVersion() {
+    return buildGetProgramVersionIx(this.program);
+  }
+
+  buildCleanupGame(closer: PublicKey, gameId: bigint, closeLeaderboard = false) {
+    return buildCleanupGameIx(this.program, closer, gameId, closeLeaderboard);
   }
 
   buildCleanupPlayer(
     closer: PublicKey,
     gameId: bigint,
-    playerOwner: PublicKey
+    playerOwner: PublicKey,
+    depositRecipient: PublicKey,
+    closedPlanetHashes: Uint8Array[] = [],
+    creditProfile = false,
+    withDeposit = true
   ) {
-    return buildCleanupPlayerIx(this.program, closer, gameId, playerOwner);
+    return buildCleanupPlayerIx(
+      this.program,
+      closer,
+      gameId,
+      playerOwner,
+      depositRecipient,
+      closedPlanetHashes,
+      creditProfile,
+      withDeposit
+    );
+  }
+
+  buildSetAllowForceCleanup(admin: PublicKey, gameId: bigint, allow: boolean) {
+    return buildSetAllowForceCleanupIx(this.program, admin, gameId, allow);
   }
 
   buildCleanupPlanet(
@@ -385,6 +587,32 @@ export class EncryptedForestClient {
     return buildCleanupPlanetIx(this.program, closer, gameId, planetHash);
   }
 
+  buildCloseDeadPlanet(
+    closer: PublicKey,
+    gameId: bigint,
+    planetHash: Uint8Array,
+    x: bigint,
+    y: bigint,
+    initializer: PublicKey
+  ) {
+    return buildCloseDeadPlanetIx(this.program, closer, gameId, planetHash, x, y, initializer);
+  }
+
+  buildCleanupMoveAccount(
+    closer: PublicKey,
+    gameId: bigint,
+    planetHash: Uint8Array,
+    moveId: bigint
+  ) {
+    return buildCleanupMoveAccountIx(
+      this.program,
+      closer,
+      gameId,
+      planetHash,
+      moveId
+    );
+  }
+
   // -------------------------------------------------------------------------
   // Exploration (client-side hash-based noise)
   // -------------------------------------------------------------------------
@@ -512,9 +740,10 @@ export class EncryptedForestClient {
     x1: bigint,
     y1: bigint,
     x2: bigint,
-    y2: bigint
+    y2: bigint,
+    method: DistanceMethod = "chebyshevApprox"
   ): bigint {
-    return computeDistance(x1, y1, x2, y2);
+    return computeDistance(x1, y1, x2, y2, method);
   }
 
   applyDistanceDecay(ships: bigint, distance: bigint, range: bigint): bigint {
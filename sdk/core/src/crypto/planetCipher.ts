@@ -1,15 +1,17 @@
 /**
- * Planet cipher using RescueCipher + Arcium Pack<[u32;15]> for decryption.
+ * Planet cipher using RescueCipher + Arcium Pack<[u32;23]> for decryption.
  *
  * planet_hash = blake3(x, y, game_id) is used as an x25519 private key.
  * The shared secret with the MXE's public key gives the cipher key.
  * Clients who know (x, y) can decrypt planet state locally without any transaction.
  *
  * The on-chain EncryptedCelestialBody stores a single encryption section:
- *   - State (3 packed FEs): Pack<[u32;15]> containing body_type, size,
+ *   - State (4 packed FEs): Pack<[u32;23]> containing body_type, size,
  *     max_ship_capacity, ship_gen_speed, max_metal_capacity, metal_gen_speed,
  *     range, launch_velocity, level, comet_0, comet_1, ship_count,
- *     metal_count, owner_exists, owner_id
+ *     metal_count, owner_exists, owner_id, base_ship_capacity, base_metal_capacity,
+ *     last_capture_slot, base_ship_gen_speed, base_metal_gen_speed, base_range,
+ *     base_launch_velocity, owner_id_hi (owner_id holds the id's low 32 bits)
  */
 
 import { RescueCipher, x25519, createPacker } from "@arcium-hq/client";
@@ -18,7 +20,7 @@ import { RescueCipher, x25519, createPacker } from "@arcium-hq/client";
 // Decrypted state interfaces
 // ---------------------------------------------------------------------------
 
-/** Decrypted static planet properties -- 11 u32 values packed into 3 FEs. */
+/** Decrypted static planet properties -- 17 u32 values packed into 4 FEs. */
 export interface PlanetStaticState {
   bodyType: number;
   size: number;
@@ -31,14 +33,21 @@ export interface PlanetStaticState {
   level: number;
   comet0: number;  // 0=none, 1-6=CometBoost
   comet1: number;  // 0=none, 1-6=CometBoost
+  baseShipCapacity: bigint;  // pre-comet, level upgrades applied
+  baseMetalCapacity: bigint; // pre-comet, level upgrades applied
+  baseShipGenSpeed: bigint;  // pre-comet, level upgrades applied
+  baseMetalGenSpeed: bigint; // pre-comet, level upgrades applied
+  baseRange: bigint;         // pre-comet, level upgrades applied
+  baseLaunchVelocity: bigint; // pre-comet, level upgrades applied
 }
 
-/** Decrypted dynamic planet properties -- the 4 fields in order. */
+/** Decrypted dynamic planet properties -- the 5 fields in order. */
 export interface PlanetDynamicState {
   shipCount: bigint;
   metalCount: bigint;
   ownerExists: number;
-  ownerId: bigint;
+  ownerId: bigint; // 64-bit, see playerIdFromPubkey
+  lastCaptureSlot: bigint; // 0 = never captured
 }
 
 /** Combined decrypted planet state (convenience type). */
@@ -77,15 +86,15 @@ export function computeSharedSecret(
 }
 
 // ---------------------------------------------------------------------------
-// Packer for Pack<[u32;15]>
+// Packer for Pack<[u32;23]>
 // ---------------------------------------------------------------------------
 
-const PLANET_STATE_FIELDS = Array.from({ length: 15 }, (_, i) => ({
+const PLANET_STATE_FIELDS = Array.from({ length: 23 }, (_, i) => ({
   name: `[${i}]` as const,
   type: { Integer: { signed: false as const, width: 32 as const } },
 }));
 
-const planetStatePacker = createPacker(PLANET_STATE_FIELDS as any, "[u32;15]");
+const planetStatePacker = createPacker(PLANET_STATE_FIELDS as any, "[u32;23]");
 
 // ---------------------------------------------------------------------------
 // Encryption / Decryption
@@ -122,7 +131,7 @@ export function encryptForPlanet(
 // ---------------------------------------------------------------------------
 
 /**
- * Decrypt the packed planet state FEs and extract 15 u32 values.
+ * Decrypt the packed planet state FEs and extract 23 u32 values.
  */
 function decryptAndUnpackState(
   planetHash: Uint8Array,
@@ -130,16 +139,16 @@ function decryptAndUnpackState(
   encNonce: Uint8Array,
   ciphertexts: Uint8Array[]
 ): bigint[] {
-  if (ciphertexts.length < 3) {
+  if (ciphertexts.length < 4) {
     throw new Error(
-      `Expected 3 ciphertexts for PlanetState, got ${ciphertexts.length}`
+      `Expected 4 ciphertexts for PlanetState, got ${ciphertexts.length}`
     );
   }
 
   const cipher = createPlanetCipher(planetHash, mxePublicKey);
 
   // Convert Uint8Array[] to number[][] for RescueCipher.decrypt()
-  const cts: number[][] = ciphertexts.slice(0, 3).map((ct) => Array.from(ct));
+  const cts: number[][] = ciphertexts.slice(0, 4).map((ct) => Array.from(ct));
 
   // Decrypt: returns field elements as bigint[]
   const decryptedFEs = cipher.decrypt(cts, encNonce);
@@ -147,9 +156,9 @@ function decryptAndUnpackState(
   // Unpack: converts field elements to u32 values
   const unpacked = planetStatePacker.unpack(decryptedFEs) as Record<string, bigint>;
 
-  // Extract values in order [0]..[14]
+  // Extract values in order [0]..[22]
   const values: bigint[] = [];
-  for (let i = 0; i < 15; i++) {
+  for (let i = 0; i < 23; i++) {
     values.push(unpacked[`[${i}]`]);
   }
   return values;
@@ -157,7 +166,7 @@ function decryptAndUnpackState(
 
 /**
  * Decrypt the PlanetStatic packed fields from the state encryption section.
- * 3 FEs -> unpack to 11 u32 values.
+ * 4 FEs -> unpack to 17 u32 values.
  */
 export function decryptPlanetStatic(
   planetHash: Uint8Array,
@@ -178,12 +187,18 @@ export function decryptPlanetStatic(
     level: Number(values[8]),
     comet0: Number(values[9]),
     comet1: Number(values[10]),
+    baseShipCapacity: values[15],
+    baseMetalCapacity: values[16],
+    baseShipGenSpeed: values[18],
+    baseMetalGenSpeed: values[19],
+    baseRange: values[20],
+    baseLaunchVelocity: values[21],
   };
 }
 
 /**
  * Decrypt the PlanetDynamic packed fields from the state encryption section.
- * 3 FEs -> unpack to 4 u32 values.
+ * 4 FEs -> unpack to 5 u32 values.
  */
 export function decryptPlanetDynamic(
   planetHash: Uint8Array,
@@ -196,7 +211,8 @@ export function decryptPlanetDynamic(
     shipCount: values[11],
     metalCount: values[12],
     ownerExists: Number(values[13]),
-    ownerId: values[14],
+    ownerId: values[14] | (values[22] << 32n),
+    lastCaptureSlot: values[17],
   };
 }
 
@@ -236,12 +252,19 @@ export function decryptPlanetState(
       level: Number(values[8]),
       comet0: Number(values[9]),
       comet1: Number(values[10]),
+      baseShipCapacity: values[15],
+      baseMetalCapacity: values[16],
+      baseShipGenSpeed: values[18],
+      baseMetalGenSpeed: values[19],
+      baseRange: values[20],
+      baseLaunchVelocity: values[21],
     },
     dynamic: {
       shipCount: values[11],
       metalCount: values[12],
       ownerExists: Number(values[13]),
-      ownerId: values[14],
+      ownerId: values[14] | (values[22] << 32n),
+      lastCaptureSlot: values[17],
     },
   };
 }
@@ -15,14 +15,23 @@ export type {
   Game,
   WinCondition,
   WinConditionAnchor,
+  HashAlgorithm,
+  DistanceMethod,
   NoiseThresholds,
 } from "./types/game.js";
-export { DEFAULT_THRESHOLDS, DEFAULT_HASH_ROUNDS } from "./types/game.js";
+export {
+  DEFAULT_THRESHOLDS,
+  DEFAULT_HASH_ROUNDS,
+  DEFAULT_MAX_MOVES_PER_SLOT,
+  DEFAULT_QUASAR_SHIP_PENALTY_BPS,
+} from "./types/game.js";
 
 export type {
   CelestialBody,
   CelestialBodyProperties,
   CelestialBodyStats,
+  CombatLog,
+  CoordinateProof,
   EncryptedCelestialBodyAccount,
 } from "./types/celestialBody.js";
 export {
@@ -32,7 +41,7 @@ export {
   PLANET_STATE_FIELDS,
 } from "./types/celestialBody.js";
 
-export type { Player } from "./types/player.js";
+export type { Player, PlayerProfile } from "./types/player.js";
 
 export type {
   PendingMoveEntry,
@@ -48,10 +57,29 @@ export {
 export type {
   InitPlanetEvent,
   InitSpawnPlanetEvent,
+  ProcessMoveStartedEvent,
   ProcessMoveEvent,
+  FlushStartedEvent,
   FlushPlanetEvent,
+  CombatEvent,
+  CombatLogUpdatedEvent,
+  UpgradeStartedEvent,
   UpgradePlanetEvent,
+  MiningCompleteEvent,
+  OwnershipTransferredEvent,
   BroadcastEvent,
+  CoordinatesVerifiedEvent,
+  CoordinateRevealedEvent,
+  DeadPlanetClosedEvent,
+  GameConfigEvent,
+  ProgramVersionEvent,
+  PendingMovesMergedEvent,
+  LeaderboardClosedEvent,
+  GameFinalisedEvent,
+  ComputationFailedEvent,
+  PlayerJoinedEvent,
+  PlayerLeftEvent,
+  PlayerFinalisedEvent,
 } from "./types/events.js";
 
 // ---------------------------------------------------------------------------
@@ -62,8 +90,16 @@ export {
   PROGRAM_ID,
   deriveGamePDA,
   derivePlayerPDA,
+  derivePlayerProfilePDA,
+  derivePlayerDepositPDA,
+  deriveComputationOffsetTrackerPDA,
+  deriveProgramStatePDA,
+  deriveLeaderboardPDA,
+  deriveSpectatorRegistryPDA,
   deriveCelestialBodyPDA,
   derivePendingMovesPDA,
+  deriveCombatLogPDA,
+  deriveCoordinateProofPDA,
   derivePendingMoveAccountPDA,
 } from "./utils/pda.js";
 
@@ -73,14 +109,18 @@ export {
 
 export {
   computePlanetHash,
+  priorRoundsPlanetHashes,
   computePropertyHash,
   mixHashBytes,
   determineCelestialBody,
+  noiseU16,
   baseStats,
   applyCometBoosts,
   computeCurrentShips,
   computeCurrentMetal,
   computeDistance,
+  maxMapDistance,
+  observableDistanceBound,
   applyDistanceDecay,
   computeLandingSlot,
   upgradeCost,
@@ -125,10 +165,19 @@ export type {
 // ---------------------------------------------------------------------------
 
 export { fetchGame, fetchGameByAddress } from "./accounts/game.js";
-export { fetchPlayer, fetchPlayerByAddress } from "./accounts/player.js";
+export {
+  fetchPlayer,
+  fetchPlayerByAddress,
+  fetchPlayerProfile,
+  computeComputationOffset,
+  fetchNextComputationOffset,
+  playerIdFromPubkey,
+} from "./accounts/player.js";
 export {
   fetchEncryptedCelestialBody,
   fetchEncryptedCelestialBodyByAddress,
+  fetchCombatLog,
+  fetchCoordinateProof,
 } from "./accounts/celestialBody.js";
 export {
   fetchPendingMovesMetadata,
@@ -139,10 +188,18 @@ export {
 // Instructions
 // ---------------------------------------------------------------------------
 
-export { buildCreateGameIx } from "./instructions/createGame.js";
+export {
+  buildCreateGameIx,
+  fetchUpcomingStartSlot,
+  MIN_GAME_DURATION_SLOTS,
+  START_SLOT_LEAD_SLOTS,
+} from "./instructions/createGame.js";
 export type { CreateGameArgs } from "./instructions/createGame.js";
 
-export { buildInitPlayerIx } from "./instructions/initPlayer.js";
+export {
+  buildInitPlayerIx,
+  buildInitPlayerProfileIx,
+} from "./instructions/initPlayer.js";
 
 export type { ArciumAccounts } from "./instructions/arciumAccounts.js";
 
@@ -152,7 +209,10 @@ export type { QueueInitPlanetArgs } from "./instructions/queueInitPlanet.js";
 export { buildQueueInitSpawnPlanetIx } from "./instructions/queueInitSpawnPlanet.js";
 export type { QueueInitSpawnPlanetArgs } from "./instructions/queueInitSpawnPlanet.js";
 
-export { buildQueueProcessMoveIx } from "./instructions/queueProcessMove.js";
+export {
+  buildQueueProcessMoveIx,
+  MIN_PRIORITY_FEE,
+} from "./instructions/queueProcessMove.js";
 export type { QueueProcessMoveArgs } from "./instructions/queueProcessMove.js";
 
 export { buildQueueFlushPlanetIx } from "./instructions/queueFlushPlanet.js";
@@ -164,10 +224,63 @@ export type { QueueUpgradePlanetArgs } from "./instructions/queueUpgradePlanet.j
 export { buildBroadcastIx } from "./instructions/broadcast.js";
 export type { BroadcastArgs } from "./instructions/broadcast.js";
 
+export {
+  buildBroadcastBulkIx,
+  MAX_BROADCAST_ENTRIES,
+} from "./instructions/broadcastBulk.js";
+export type {
+  BroadcastBulkArgs,
+  BroadcastEntry,
+} from "./instructions/broadcastBulk.js";
+
+export {
+  buildCreateSpectatorRegistryIx,
+  buildRegisterSpectatorIx,
+  buildDeregisterSpectatorIx,
+} from "./instructions/spectator.js";
+
+export {
+  buildAbandonMoveBatchIx,
+  MAX_ABANDON_MOVES,
+} from "./instructions/abandonMoveBatch.js";
+export type { AbandonMoveBatchArgs } from "./instructions/abandonMoveBatch.js";
+
+export { buildMergePendingMovesIx } from "./instructions/mergePendingMoves.js";
+export type {
+  MergePendingMovesArgs,
+  MergePendingMove,
+} from "./instructions/mergePendingMoves.js";
+
+export { buildVerifyCoordinatesIx } from "./instructions/verifyCoordinates.js";
+export type { VerifyCoordinatesArgs } from "./instructions/verifyCoordinates.js";
+
+export {
+  buildCreateCoordinateProofIx,
+  buildClaimRaceToCenterVictoryIx,
+} from "./instructions/coordinateProof.js";
+export type {
+  CreateCoordinateProofArgs,
+  ClaimRaceToCenterVictoryArgs,
+} from "./instructions/coordinateProof.js";
+
+export { buildClaimPointsVictoryIx } from "./instructions/claimPointsVictory.js";
+export type { ClaimPointsVictoryArgs } from "./instructions/claimPointsVictory.js";
+
+export { buildGetGameConfigIx } from "./instructions/getGameConfig.js";
+
+export {
+  buildGetProgramVersionIx,
+  CLIENT_PROTOCOL_VERSION,
+  isProgramVersionSupported,
+} from "./instructions/getProgramVersion.js";
+
 export {
   buildCleanupGameIx,
   buildCleanupPlayerIx,
   buildCleanupPlanetIx,
+  buildCleanupMoveAccountIx,
+  buildSetAllowForceCleanupIx,
+  buildCloseDeadPlanetIx,
 } from "./instructions/cleanup.js";
 
 // ---------------------------------------------------------------------------
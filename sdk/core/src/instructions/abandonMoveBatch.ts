@@ -0,0 +1,64 @@
+/**
+ * Instruction builder: abandon_move_batch
+ *
+ * Withdraw up to 8 of the payer's own moves that have not landed yet,
+ * closing their move accounts and refunding rent to the payer.
+ */
+
+import { type Program, BN } from "@coral-xyz/anchor";
+import { PublicKey } from "@solana/web3.js";
+import {
+  deriveGamePDA,
+  derivePendingMovesPDA,
+  derivePendingMoveAccountPDA,
+} from "../utils/pda.js";
+
+/** Max moves per abandon_move_batch call (mirrors MAX_ABANDON_MOVES on-chain) */
+export const MAX_ABANDON_MOVES = 8;
+
+export interface AbandonMoveBatchArgs {
+  gameId: bigint;
+  /** Hash of the planet the moves are heading to */
+  targetPlanetHash: Uint8Array;
+  moveIds: bigint[];
+}
+
+/**
+ * Build a transaction builder for the abandon_move_batch instruction.
+ * Each move account is passed as a writable remaining account, in moveIds order.
+ */
+export function buildAbandonMoveBatchIx(
+  program: Program,
+  payer: PublicKey,
+  args: AbandonMoveBatchArgs
+) {
+  const [gamePDA] = deriveGamePDA(args.gameId, program.programId);
+  const [pendingMovesPDA] = derivePendingMovesPDA(
+    args.gameId,
+    args.targetPlanetHash,
+    program.programId
+  );
+  const moveAccounts = args.moveIds.map((moveId) => ({
+    pubkey: derivePendingMoveAccountPDA(
+      args.gameId,
+      args.targetPlanetHash,
+      moveId,
+      program.programId
+    )[0],
+    isSigner: false,
+    isWritable: true,
+  }));
+
+  return program.methods
+    .abandonMoveBatch(
+      new BN(args.gameId.toString()),
+      Array.from(args.targetPlanetHash) as any,
+      args.moveIds.map((id) => new BN(id.toString()))
+    )
+    .accounts({
+      payer,
+      game: gamePDA,
+      pendingMoves: pendingMovesPDA,
+    })
+    .remainingAccounts(moveAccounts);
+}
@@ -2,17 +2,26 @@
  * Instruction builder: broadcast
  *
  * Broadcast planet coordinates publicly so all players can discover it.
+ * The planet must already have been initialized via queue_init_planet, and
+ * the broadcaster must be a player in the game or a registered spectator.
  */
 
 import { type Program, BN } from "@coral-xyz/anchor";
 import { PublicKey } from "@solana/web3.js";
-import { deriveGamePDA } from "../utils/pda.js";
+import {
+  deriveCelestialBodyPDA,
+  deriveGamePDA,
+  derivePlayerPDA,
+  deriveSpectatorRegistryPDA,
+} from "../utils/pda.js";
 
 export interface BroadcastArgs {
   gameId: bigint;
   x: bigint;
   y: bigint;
   planetHash: Uint8Array;
+  /** Authorize via the game's SpectatorRegistry instead of a Player account */
+  spectator?: boolean;
 }
 
 /**
@@ -25,6 +34,11 @@ export function buildBroadcastIx(
   args: BroadcastArgs
 ) {
   const [gamePDA] = deriveGamePDA(args.gameId, program.programId);
+  const [celestialBodyPDA] = deriveCelestialBodyPDA(
+    args.gameId,
+    args.planetHash,
+    program.programId
+  );
 
   return program.methods
     .broadcast(
@@ -36,5 +50,30 @@ export function buildBroadcastIx(
     .accounts({
       broadcaster,
       game: gamePDA,
-    });
+      celestialBody: celestialBodyPDA,
+      ...broadcasterAuthority(
+        args.gameId,
+        broadcaster,
+        args.spectator,
+        program.programId
+      ),
+    } as any);
+}
+
+/**
+ * Accounts proving the broadcaster may broadcast: either its Player PDA or
+ * the game's SpectatorRegistry.
+ */
+export function broadcasterAuthority(
+  gameId: bigint,
+  broadcaster: PublicKey,
+  spectator: boolean | undefined,
+  programId: PublicKey
+) {
+  if (spectator) {
+    const [spectatorRegistry] = deriveSpectatorRegistryPDA(gameId, programId);
+    return { player: null, spectatorRegistry };
+  }
+  const [player] = derivePlayerPDA(gameId, broadcaster, programId);
+  return { player, spectatorRegistry: null };
 }
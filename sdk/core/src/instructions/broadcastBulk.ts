@@ -0,0 +1,67 @@
+/**
+ * Instruction builder: broadcast_bulk
+ *
+ * Broadcast up to 10 planet coordinates in a single transaction. The
+ * broadcaster must be a player in the game or a registered spectator, and
+ * every planet must already be initialized.
+ */
+
+import { type Program, BN } from "@coral-xyz/anchor";
+import { PublicKey } from "@solana/web3.js";
+import { deriveCelestialBodyPDA, deriveGamePDA } from "../utils/pda.js";
+import { broadcasterAuthority } from "./broadcast.js";
+
+/** Max entries per broadcast_bulk call (mirrors MAX_BROADCAST_ENTRIES on-chain) */
+export const MAX_BROADCAST_ENTRIES = 10;
+
+export interface BroadcastEntry {
+  x: bigint;
+  y: bigint;
+  planetHash: Uint8Array;
+}
+
+export interface BroadcastBulkArgs {
+  gameId: bigint;
+  entries: BroadcastEntry[];
+  /** Authorize via the game's SpectatorRegistry instead of a Player account */
+  spectator?: boolean;
+}
+
+/**
+ * Build a transaction builder for the broadcast_bulk instruction.
+ * Call .rpc() or .transaction() on the result.
+ */
+export function buildBroadcastBulkIx(
+  program: Program,
+  broadcaster: PublicKey,
+  args: BroadcastBulkArgs
+) {
+  const [gamePDA] = deriveGamePDA(args.gameId, program.programId);
+
+  return program.methods
+    .broadcastBulk(
+      new BN(args.gameId.toString()),
+      args.entries.map((e) => ({
+        x: new BN(e.x.toString()),
+        y: new BN(e.y.toString()),
+        planetHash: Array.from(e.planetHash),
+      })) as any
+    )
+    .accounts({
+      broadcaster,
+      game: gamePDA,
+      ...broadcasterAuthority(
+        args.gameId,
+        broadcaster,
+        args.spectator,
+        program.programId
+      ),
+    } as any)
+    .remainingAccounts(
+      args.entries.map((e) => ({
+        pubkey: deriveCelestialBodyPDA(args.gameId, e.planetHash, program.programId)[0],
+        isSigner: false,
+        isWritable: false,
+      }))
+    );
+}
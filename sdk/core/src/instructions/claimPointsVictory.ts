@@ -0,0 +1,45 @@
+/**
+ * Instruction builder: claim_points_victory
+ *
+ * In a PointsBurning game with a non-zero points_to_win, the first player to
+ * reach that score can end the game early and be recorded as its winner.
+ */
+
+import { type Program, BN } from "@coral-xyz/anchor";
+import { PublicKey } from "@solana/web3.js";
+import {
+  deriveGamePDA,
+  derivePlayerPDA,
+  derivePlayerProfilePDA,
+} from "../utils/pda.js";
+
+export interface ClaimPointsVictoryArgs {
+  gameId: bigint;
+  /** Credit the win to the payer's PlayerProfile (must already exist) */
+  creditProfile?: boolean;
+}
+
+/**
+ * Build a transaction builder for the claim_points_victory instruction.
+ * Call .rpc() or .transaction() on the result.
+ */
+export function buildClaimPointsVictoryIx(
+  program: Program,
+  payer: PublicKey,
+  args: ClaimPointsVictoryArgs
+) {
+  const [gamePDA] = deriveGamePDA(args.gameId, program.programId);
+  const [playerPDA] = derivePlayerPDA(args.gameId, payer, program.programId);
+  const playerProfile = args.creditProfile
+    ? derivePlayerProfilePDA(payer, program.programId)[0]
+    : null;
+
+  return program.methods
+    .claimPointsVictory(new BN(args.gameId.toString()))
+    .accounts({
+      payer,
+      game: gamePDA,
+      player: playerPDA,
+      playerProfile,
+    } as any);
+}
@@ -1,7 +1,10 @@
 /**
- * Instruction builders: cleanup_game, cleanup_player, cleanup_planet
+ * Instruction builders: cleanup_game, cleanup_player, cleanup_planet,
+ * cleanup_move_account, set_allow_force_cleanup, close_dead_planet
  *
  * Close game-related accounts after the game has ended to reclaim rent.
+ * close_dead_planet works at any time for planets that turned out to be
+ * dead space.
  */
 
 import { type Program, BN } from "@coral-xyz/anchor";
@@ -9,43 +12,123 @@ import { PublicKey } from "@solana/web3.js";
 import {
   deriveGamePDA,
   derivePlayerPDA,
+  derivePlayerDepositPDA,
   deriveCelestialBodyPDA,
   derivePendingMovesPDA,
+  deriveCombatLogPDA,
+  derivePendingMoveAccountPDA,
+  deriveProgramStatePDA,
+  deriveLeaderboardPDA,
+  derivePlayerProfilePDA,
 } from "../utils/pda.js";
 
 /**
  * Build a transaction builder for the cleanup_game instruction.
+ * Pass closeLeaderboard if the game has a Leaderboard so it is closed too.
  */
 export function buildCleanupGameIx(
   program: Program,
   closer: PublicKey,
-  gameId: bigint
+  gameId: bigint,
+  closeLeaderboard = false
 ) {
   const [gamePDA] = deriveGamePDA(gameId, program.programId);
 
-  return program.methods.cleanupGame(new BN(gameId.toString())).accounts({
-    closer,
-    game: gamePDA,
-  });
+  const [programStatePDA] = deriveProgramStatePDA(program.programId);
+
+  const remaining = closeLeaderboard
+    ? [
+        {
+          pubkey: deriveLeaderboardPDA(gameId, program.programId)[0],
+          isSigner: false,
+          isWritable: true,
+        },
+      ]
+    : [];
+
+  return program.methods
+    .cleanupGame(new BN(gameId.toString()))
+    .accounts({
+      closer,
+      game: gamePDA,
+      programState: programStatePDA,
+    })
+    .remainingAccounts(remaining);
 }
 
 /**
  * Build a transaction builder for the cleanup_player instruction.
+ * depositRecipient must be the game's deposit_recipient; it is only paid
+ * when the admin force-cleans another player's account.
+ *
+ * Unless the admin set allow_force_cleanup, pass the hashes of the planets
+ * the player may still own (at most 8, including the spawn planet while
+ * has_spawned is set); each must already be closed by cleanup_planet.
+ *
+ * With creditProfile set, the player's points are banked into the owner's
+ * PlayerProfile (which must already exist) as lifetime points. Clear
+ * withDeposit only for players who joined before deposits existed (the game
+ * must then charge no deposit).
  */
 export function buildCleanupPlayerIx(
   program: Program,
   closer: PublicKey,
   gameId: bigint,
-  playerOwner: PublicKey
+  playerOwner: PublicKey,
+  depositRecipient: PublicKey,
+  closedPlanetHashes: Uint8Array[] = [],
+  creditProfile = false,
+  withDeposit = true
 ) {
   const [gamePDA] = deriveGamePDA(gameId, program.programId);
   const [playerPDA] = derivePlayerPDA(gameId, playerOwner, program.programId);
+  const playerProfile = creditProfile
+    ? derivePlayerProfilePDA(playerOwner, program.programId)[0]
+    : null;
+  const [depositPDA] = derivePlayerDepositPDA(
+    gameId,
+    playerOwner,
+    program.programId
+  );
 
-  return program.methods.cleanupPlayer(new BN(gameId.toString())).accounts({
-    closer,
-    game: gamePDA,
-    player: playerPDA,
-  });
+  return program.methods
+    .cleanupPlayer(new BN(gameId.toString()))
+    .accounts({
+      closer,
+      game: gamePDA,
+      player: playerPDA,
+      deposit: withDeposit ? depositPDA : null,
+      owner: playerOwner,
+      depositRecipient,
+      playerProfile,
+    } as any)
+    .remainingAccounts(
+      closedPlanetHashes.map((planetHash) => ({
+        pubkey: deriveCelestialBodyPDA(gameId, planetHash, program.programId)[0],
+        isSigner: false,
+        isWritable: false,
+      }))
+    );
+}
+
+/**
+ * Build a transaction builder for the set_allow_force_cleanup instruction.
+ * Admin-only, and only once GRACE_PERIOD_SLOTS have passed since end_slot.
+ */
+export function buildSetAllowForceCleanupIx(
+  program: Program,
+  admin: PublicKey,
+  gameId: bigint,
+  allow: boolean
+) {
+  const [gamePDA] = deriveGamePDA(gameId, program.programId);
+
+  return program.methods
+    .setAllowForceCleanup(new BN(gameId.toString()), allow)
+    .accounts({
+      admin,
+      game: gamePDA,
+    });
 }
 
 /**
@@ -68,6 +151,11 @@ export function buildCleanupPlanetIx(
     planetHash,
     program.programId
   );
+  const [combatLogPDA] = deriveCombatLogPDA(
+    gameId,
+    planetHash,
+    program.programId
+  );
 
   return program.methods
     .cleanupPlanet(
@@ -79,5 +167,93 @@ export function buildCleanupPlanetIx(
       game: gamePDA,
       celestialBody: planetPDA,
       pendingMoves: pendingMovesPDA,
+      combatLog: combatLogPDA,
+    });
+}
+
+/**
+ * Build a transaction builder for the close_dead_planet instruction.
+ * Publicly reveals (x, y); the program re-derives the noise and only closes
+ * the planet if it is dead space (determineCelestialBody returns null).
+ * Rent goes to the body's initializer (EncryptedCelestialBodyAccount.initializer),
+ * the payer of its init_planet.
+ */
+export function buildCloseDeadPlanetIx(
+  program: Program,
+  closer: PublicKey,
+  gameId: bigint,
+  planetHash: Uint8Array,
+  x: bigint,
+  y: bigint,
+  initializer: PublicKey
+) {
+  const [gamePDA] = deriveGamePDA(gameId, program.programId);
+  const [planetPDA] = deriveCelestialBodyPDA(
+    gameId,
+    planetHash,
+    program.programId
+  );
+  const [pendingMovesPDA] = derivePendingMovesPDA(
+    gameId,
+    planetHash,
+    program.programId
+  );
+  const [combatLogPDA] = deriveCombatLogPDA(
+    gameId,
+    planetHash,
+    program.programId
+  );
+
+  return program.methods
+    .closeDeadPlanet(
+      new BN(gameId.toString()),
+      Array.from(planetHash) as any,
+      new BN(x.toString()),
+      new BN(y.toString())
+    )
+    .accounts({
+      closer,
+      game: gamePDA,
+      celestialBody: planetPDA,
+      pendingMoves: pendingMovesPDA,
+      combatLog: combatLogPDA,
+      initializer,
+    });
+}
+
+/**
+ * Build a transaction builder for the cleanup_move_account instruction.
+ */
+export function buildCleanupMoveAccountIx(
+  program: Program,
+  closer: PublicKey,
+  gameId: bigint,
+  planetHash: Uint8Array,
+  moveId: bigint
+) {
+  const [gamePDA] = deriveGamePDA(gameId, program.programId);
+  const [pendingMovesPDA] = derivePendingMovesPDA(
+    gameId,
+    planetHash,
+    program.programId
+  );
+  const [moveAccountPDA] = derivePendingMoveAccountPDA(
+    gameId,
+    planetHash,
+    moveId,
+    program.programId
+  );
+
+  return program.methods
+    .cleanupMoveAccount(
+      new BN(gameId.toString()),
+      Array.from(planetHash) as any,
+      new BN(moveId.toString())
+    )
+    .accounts({
+      closer,
+      game: gamePDA,
+      pendingMoves: pendingMovesPDA,
+      moveAccount: moveAccountPDA,
     });
 }
@@ -0,0 +1,141 @@
+/**
+ * Instruction builders: create_coordinate_proof, claim_race_to_center_victory
+ *
+ * A CoordinateProof publicly reveals a planet's (x, y); the program checks
+ * them against the planet hash. In a RaceToCenter game the planet's current
+ * owner (not necessarily whoever revealed it) can then claim victory if the
+ * coordinates lie closer than min_spawn_distance to the map center. The claim queues a check_ownership computation proving the
+ * payer owns the planet; its callback records the winner.
+ *
+ * Encrypted input: Enc<Shared, OwnershipInput> = 1 ciphertext: player_id
+ * (playerIdFromPubkey(payer)).
+ */
+
+import { type Program, BN } from "@coral-xyz/anchor";
+import { PublicKey, SystemProgram } from "@solana/web3.js";
+import {
+  deriveCelestialBodyPDA,
+  deriveComputationOffsetTrackerPDA,
+  deriveCoordinateProofPDA,
+  deriveGamePDA,
+  derivePlayerPDA,
+  derivePlayerProfilePDA,
+} from "../utils/pda.js";
+import type { ArciumAccounts } from "./arciumAccounts.js";
+
+export interface CreateCoordinateProofArgs {
+  gameId: bigint;
+  planetHash: Uint8Array;
+  x: bigint;
+  y: bigint;
+  /** Pass the planet so its own hashRounds checks (x, y); needed if it predates an update_hash_rounds */
+  initialized?: boolean;
+}
+
+export interface ClaimRaceToCenterVictoryArgs {
+  gameId: bigint;
+  planetHash: Uint8Array;
+  /** 1 ciphertext (32 bytes): OwnershipInput.player_id */
+  ownershipCts: Uint8Array;
+  /** x25519 pubkey for Enc<Shared, OwnershipInput> */
+  ownershipPubkey: Uint8Array;
+  /** Nonce for the ownership encryption (u128) */
+  ownershipNonce: bigint;
+  /** Credit the win to the payer's PlayerProfile (must already exist) */
+  creditProfile?: boolean;
+}
+
+/**
+ * Build a transaction builder for the create_coordinate_proof instruction.
+ * Call .rpc() or .transaction() on the result.
+ */
+export function buildCreateCoordinateProofIx(
+  program: Program,
+  payer: PublicKey,
+  args: CreateCoordinateProofArgs
+) {
+  const [gamePDA] = deriveGamePDA(args.gameId, program.programId);
+  const [playerPDA] = derivePlayerPDA(args.gameId, payer, program.programId);
+  const [proofPDA] = deriveCoordinateProofPDA(
+    args.gameId,
+    args.planetHash,
+    program.programId
+  );
+
+  return program.methods
+    .createCoordinateProof(
+      new BN(args.gameId.toString()),
+      Array.from(args.planetHash) as any,
+      new BN(args.x.toString()),
+      new BN(args.y.toString())
+    )
+    .accounts({
+      payer,
+      game: gamePDA,
+      player: playerPDA,
+      coordinateProof: proofPDA,
+      celestialBody: args.initialized
+        ? deriveCelestialBodyPDA(args.gameId, args.planetHash, program.programId)[0]
+        : null,
+    } as any);
+}
+
+/**
+ * Build a transaction builder for the claim_race_to_center_victory instruction.
+ * Call .rpc() or .transaction() on the result.
+ */
+export function buildClaimRaceToCenterVictoryIx(
+  program: Program,
+  payer: PublicKey,
+  args: ClaimRaceToCenterVictoryArgs,
+  arciumAccounts: ArciumAccounts
+) {
+  const [gamePDA] = deriveGamePDA(args.gameId, program.programId);
+  const [playerPDA] = derivePlayerPDA(args.gameId, payer, program.programId);
+  const [proofPDA] = deriveCoordinateProofPDA(
+    args.gameId,
+    args.planetHash,
+    program.programId
+  );
+  const [celestialBodyPDA] = deriveCelestialBodyPDA(
+    args.gameId,
+    args.planetHash,
+    program.programId
+  );
+  const playerProfile = args.creditProfile
+    ? derivePlayerProfilePDA(payer, program.programId)[0]
+    : null;
+
+  return program.methods
+    .claimRaceToCenterVictory(
+      new BN(args.gameId.toString()),
+      Array.from(args.planetHash) as any,
+      Buffer.from(args.ownershipCts),
+      Array.from(args.ownershipPubkey) as any,
+      new BN(args.ownershipNonce.toString())
+    )
+    .accounts({
+      payer,
+      game: gamePDA,
+      player: playerPDA,
+      playerProfile,
+      coordinateProof: proofPDA,
+      celestialBody: celestialBodyPDA,
+      offsetTracker: deriveComputationOffsetTrackerPDA(
+        args.gameId,
+        payer,
+        program.programId
+      )[0],
+      signPdaAccount: arciumAccounts.signPdaAccount,
+      mxeAccount: arciumAccounts.mxeAccount,
+      mempoolAccount: arciumAccounts.mempoolAccount,
+      executingPool: arciumAccounts.executingPool,
+      computationAccount: arciumAccounts.computationAccount,
+      compDefAccount: arciumAccounts.compDefAccount,
+      clusterAccount: arciumAccounts.clusterAccount,
+      poolAccount: arciumAccounts.poolAccount,
+      clockAccount: arciumAccounts.clockAccount,
+      systemProgram: SystemProgram.programId,
+      arciumProgram: arciumAccounts.arciumProgram,
+    } as any);
+}
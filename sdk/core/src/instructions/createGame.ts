@@ -5,21 +5,77 @@
  */
 
 import { type Program, BN } from "@coral-xyz/anchor";
-import { PublicKey, SystemProgram } from "@solana/web3.js";
-import type { NoiseThresholds, WinCondition } from "../types/game.js";
-import { deriveGamePDA } from "../utils/pda.js";
+import { type Connection, PublicKey, SystemProgram } from "@solana/web3.js";
+import type {
+  HashAlgorithm,
+  DistanceMethod,
+  NoiseThresholds,
+  WinCondition,
+} from "../types/game.js";
+import { deriveGamePDA, deriveProgramStatePDA } from "../utils/pda.js";
+
+/** Min end_slot - start_slot (mirrors MIN_GAME_DURATION_SLOTS on-chain) */
+export const MIN_GAME_DURATION_SLOTS = 1000n;
+
+/** Slots of headroom fetchUpcomingStartSlot leaves for create_game to land */
+export const START_SLOT_LEAD_SLOTS = 2n;
 
 export interface CreateGameArgs {
   gameId: bigint;
   mapDiameter: bigint;
   gameSpeed: bigint;
+  /** Must not be in the past when create_game lands */
   startSlot: bigint;
+  /** At least MIN_GAME_DURATION_SLOTS after startSlot */
   endSlot: bigint;
   winCondition: WinCondition;
   whitelist: boolean;
   serverPubkey: PublicKey | null;
   noiseThresholds: NoiseThresholds;
   hashRounds: number;
+  /** Max queue_process_move calls per player per slot (>= 1). */
+  maxMovesPerSlot: number;
+  /** Max in-flight moves per target planet (1..=MAX_QUEUED_CALLBACKS). */
+  maxPendingMoves: number;
+  /** Ship loss on arrival at a Quasar, in basis points (0..=10000). */
+  quasarShipPenaltyBps: bigint;
+  /** Min slots between moves launched from the same planet (0 = no cooldown). */
+  moveCooldownSlots: bigint;
+  /** Maximum players allowed to join (0 = unlimited) */
+  maxPlayers: number;
+  /** Min distance between spawn planets checked at spawn (0 = off) */
+  minSpawnSeparation: bigint;
+  /** Min slots since a planet's last update before comet drift may run (0 = drift disabled) */
+  cometDriftIntervalSlots: bigint;
+  /** Planet hash function; clients must hash coordinates with the same one. */
+  hashAlgorithm: HashAlgorithm;
+  /** Lamports locked per player at init_player, refunded on self-cleanup */
+  securityDepositLamports: bigint;
+  /** Receives deposits of force-cleaned players; null defaults to the admin */
+  depositRecipient: PublicKey | null;
+}
+
+/**
+ * Pick a start slot for a game that should begin right away. create_game
+ * rejects start slots behind the cluster clock, so leave a little headroom.
+ */
+export async function fetchUpcomingStartSlot(
+  connection: Connection,
+  leadSlots: bigint = START_SLOT_LEAD_SLOTS
+): Promise<bigint> {
+  return BigInt(await connection.getSlot("confirmed")) + leadSlots;
+  /** Min slots after a capture before the planet can launch moves (0 = off) */
+  captureCooldownSlots: bigint;
+  /** Distance formula used for travel time, range and spawn checks */
+  distanceMethod: DistanceMethod;
+  /** Bitmask of body types players may spawn on (bit 0 Planet, 1 Quasar, 2 SpacetimeRip, 3 AsteroidBelt) */
+  validSpawnBodyTypes: number;
+  /** Highest level a planet can be upgraded to (>= 2). */
+  maxPlanetLevel: number;
+  /** Minimum ships a move must send (>= 1). */
+  minShipsPerMove: bigint;
+  /** PointsBurning score that wins the game early via claim_points_victory (0 = disabled). */
+  pointsToWin: bigint;
 }
 
 /**
@@ -32,6 +88,7 @@ export function buildCreateGameIx(
   args: CreateGameArgs
 ) {
   const [gamePDA] = deriveGamePDA(args.gameId, program.programId);
+  const [programStatePDA] = deriveProgramStatePDA(program.programId);
 
   // Convert WinCondition to Anchor format
   let anchorWinCondition: any;
@@ -64,11 +121,28 @@ export function buildCreateGameIx(
       args.whitelist,
       args.serverPubkey,
       args.noiseThresholds,
-      args.hashRounds
+      args.hashRounds,
+      args.maxMovesPerSlot,
+      args.maxPendingMoves,
+      new BN(args.quasarShipPenaltyBps.toString()),
+      new BN(args.moveCooldownSlots.toString()),
+      args.maxPlayers,
+      new BN(args.minSpawnSeparation.toString()),
+      new BN(args.cometDriftIntervalSlots.toString()),
+      { [args.hashAlgorithm]: {} },
+      new BN(args.securityDepositLamports.toString()),
+      args.depositRecipient,
+      new BN(args.captureCooldownSlots.toString()),
+      { [args.distanceMethod]: {} },
+      args.validSpawnBodyTypes,
+      args.maxPlanetLevel,
+      new BN(args.minShipsPerMove.toString()),
+      new BN(args.pointsToWin.toString())
     )
     .accounts({
       admin,
       game: gamePDA,
+      programState: programStatePDA,
       systemProgram: SystemProgram.programId,
     });
 }
@@ -0,0 +1,21 @@
+/**
+ * Instruction builder: get_game_config
+ *
+ * Re-emits the game's public configuration as GameConfigEvent for indexers
+ * that missed create_game. Changes no state.
+ */
+
+import { type Program, BN } from "@coral-xyz/anchor";
+import { deriveGamePDA } from "../utils/pda.js";
+
+/**
+ * Build a transaction builder for the get_game_config instruction.
+ * Call .rpc() or .simulate() on the result.
+ */
+export function buildGetGameConfigIx(program: Program, gameId: bigint) {
+  const [gamePDA] = deriveGamePDA(gameId, program.programId);
+
+  return program.methods.getGameConfig(new BN(gameId.toString())).accounts({
+    game: gamePDA,
+  });
+}
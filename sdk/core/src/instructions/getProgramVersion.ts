@@ -0,0 +1,34 @@
+/**
+ * Instruction builder: get_program_version
+ *
+ * Emits ProgramVersionEvent with the deployment's protocol version and the
+ * oldest client version it accepts. Changes no state.
+ */
+
+import type { Program } from "@coral-xyz/anchor";
+import type { ProgramVersionEvent } from "../types/events.js";
+
+/** Protocol version this SDK speaks (matches on-chain PROGRAM_VERSION). */
+export const CLIENT_PROTOCOL_VERSION = 1;
+
+/**
+ * Build a transaction builder for the get_program_version instruction.
+ * Call .simulate() on the result and read ProgramVersionEvent from the logs.
+ */
+export function buildGetProgramVersionIx(program: Program) {
+  return program.methods.getProgramVersion().accounts({});
+}
+
+/**
+ * Whether this SDK can talk to a deployment reporting `event`: the program
+ * must accept our version and must not be newer than what we understand.
+ */
+export function isProgramVersionSupported(
+  event: ProgramVersionEvent,
+  clientVersion: number = CLIENT_PROTOCOL_VERSION
+): boolean {
+  return (
+    clientVersion >= event.minCompatibleClientVersion &&
+    event.version <= clientVersion
+  );
+}
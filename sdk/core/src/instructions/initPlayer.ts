@@ -1,30 +1,46 @@
 /**
- * Instruction builder: init_player
+ * Instruction builders: init_player, init_player_profile
  *
- * Creates a Player account for a game.
+ * Creates a Player account for a game, and the owner's cross-game profile.
  */
 
 import { type Program, BN } from "@coral-xyz/anchor";
 import { PublicKey, SystemProgram } from "@solana/web3.js";
-import { deriveGamePDA, derivePlayerPDA } from "../utils/pda.js";
+import {
+  deriveGamePDA,
+  derivePlayerPDA,
+  derivePlayerProfilePDA,
+  derivePlayerDepositPDA,
+  deriveComputationOffsetTrackerPDA,
+} from "../utils/pda.js";
 
 /**
  * Build and return a transaction builder for the init_player instruction.
+ * Pass withProfile once init_player_profile has run to count the game.
  * Call .rpc() or .transaction() on the result.
  */
 export function buildInitPlayerIx(
   program: Program,
   owner: PublicKey,
   gameId: bigint,
-  server?: PublicKey
+  server?: PublicKey,
+  withProfile = false
 ) {
   const [gamePDA] = deriveGamePDA(gameId, program.programId);
   const [playerPDA] = derivePlayerPDA(gameId, owner, program.programId);
+  const [depositPDA] = derivePlayerDepositPDA(gameId, owner, program.programId);
+  const [offsetTrackerPDA] = deriveComputationOffsetTrackerPDA(
+    gameId,
+    owner,
+    program.programId
+  );
 
   const accounts: any = {
     owner,
     game: gamePDA,
     player: playerPDA,
+    deposit: depositPDA,
+    offsetTracker: offsetTrackerPDA,
     systemProgram: SystemProgram.programId,
   };
 
@@ -32,7 +48,24 @@ export function buildInitPlayerIx(
     accounts.server = server;
   }
 
+  if (withProfile) {
+    [accounts.playerProfile] = derivePlayerProfilePDA(owner, program.programId);
+  }
+
   return program.methods
     .initPlayer(new BN(gameId.toString()))
     .accounts(accounts);
 }
+
+/**
+ * Build and return a transaction builder for the init_player_profile instruction.
+ */
+export function buildInitPlayerProfileIx(program: Program, owner: PublicKey) {
+  const [profilePDA] = derivePlayerProfilePDA(owner, program.programId);
+
+  return program.methods.initPlayerProfile().accounts({
+    owner,
+    playerProfile: profilePDA,
+    systemProgram: SystemProgram.programId,
+  } as any);
+}
@@ -0,0 +1,80 @@
+/**
+ * Instruction builder: merge_pending_moves
+ *
+ * Admin-only. Folds a planet's pending moves into another planet's list
+ * after an annexation and closes the source list, refunding rent to the admin.
+ */
+
+import { type Program, BN } from "@coral-xyz/anchor";
+import { PublicKey, SystemProgram } from "@solana/web3.js";
+import {
+  deriveGamePDA,
+  derivePendingMovesPDA,
+  derivePendingMoveAccountPDA,
+} from "../utils/pda.js";
+
+export interface MergePendingMove {
+  moveId: bigint;
+  /**
+   * Planet hash the move account was seeded with. Defaults to the source
+   * planet; differs only for moves that were themselves merged earlier.
+   */
+  seedPlanetHash?: Uint8Array;
+}
+
+export interface MergePendingMovesArgs {
+  gameId: bigint;
+  /** Planet whose pending list is merged and closed */
+  sourcePlanetHash: Uint8Array;
+  /** Planet that receives the moves */
+  destPlanetHash: Uint8Array;
+  /** Every entry of the source list, in its on-chain (landing) order */
+  moves: MergePendingMove[];
+}
+
+/**
+ * Build a transaction builder for the merge_pending_moves instruction.
+ * Each move account is passed as a writable remaining account, in list order.
+ */
+export function buildMergePendingMovesIx(
+  program: Program,
+  admin: PublicKey,
+  args: MergePendingMovesArgs
+) {
+  const [gamePDA] = deriveGamePDA(args.gameId, program.programId);
+  const [sourcePendingPDA] = derivePendingMovesPDA(
+    args.gameId,
+    args.sourcePlanetHash,
+    program.programId
+  );
+  const [destPendingPDA] = derivePendingMovesPDA(
+    args.gameId,
+    args.destPlanetHash,
+    program.programId
+  );
+  const moveAccounts = args.moves.map((move) => ({
+    pubkey: derivePendingMoveAccountPDA(
+      args.gameId,
+      move.seedPlanetHash ?? args.sourcePlanetHash,
+      move.moveId,
+      program.programId
+    )[0],
+    isSigner: false,
+    isWritable: true,
+  }));
+
+  return program.methods
+    .mergePendingMoves(
+      new BN(args.gameId.toString()),
+      Array.from(args.sourcePlanetHash) as any,
+      Array.from(args.destPlanetHash) as any
+    )
+    .accounts({
+      admin,
+      game: gamePDA,
+      sourcePending: sourcePendingPDA,
+      destPending: destPendingPDA,
+      systemProgram: SystemProgram.programId,
+    })
+    .remainingAccounts(moveAccounts);
+}
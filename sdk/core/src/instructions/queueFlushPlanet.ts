@@ -9,30 +9,41 @@
  * celestial_body via .account() -- NOT passed as ciphertexts.
  * Move data is read from PendingMoveAccount PDAs via remaining_accounts.
  *
- * Encrypted input: Enc<Shared, FlushTimingInput> = 4 ciphertexts:
- *   current_slot, game_speed, last_updated_slot, flush_count
+ * No encrypted input: the program passes the timing (current slot, game_speed
+ * and the body's last_updated_slot) to the circuit as plaintext.
  */
 
 import { type Program, BN } from "@coral-xyz/anchor";
 import { PublicKey, SystemProgram } from "@solana/web3.js";
+import { deriveComputationOffsetTrackerPDA } from "../utils/pda.js";
 import type { ArciumAccounts } from "./arciumAccounts.js";
 
 export interface QueueFlushPlanetArgs {
-  computationOffset: bigint;
+  gameId: bigint;
   /** Number of moves to flush (always 1) */
   flushCount: number;
-  /** 4 ciphertexts packed as Vec<u8> (4 * 32 = 128 bytes): FlushTimingInput */
-  flushCts: Uint8Array;
-  /** x25519 pubkey for Enc<Shared, FlushTimingInput> */
-  flushPubkey: Uint8Array;
-  /** Nonce for the flush encryption (u128) */
-  flushNonce: bigint;
+  /** Observer x25519 pubkey for Enc<Shared, FlushRevealed> */
+  observerPubkey: Uint8Array;
+  /** Nonce for the observer's Shared handle (u128) */
+  observerNonce: bigint;
   /** Celestial body account address */
   celestialBody: PublicKey;
   /** Pending moves metadata account address */
   pendingMoves: PublicKey;
-  /** PendingMoveAccount PDAs for the moves being flushed (remaining_accounts) */
+  /** Planet's CombatLog account address (deriveCombatLogPDA) */
+  combatLog: PublicKey;
+  /**
+   * PendingMoveAccount PDAs for the moves being flushed (remaining_accounts,
+   * writable: the program marks the move's flush as in flight)
+   */
   moveAccounts: PublicKey[];
+  /** Optional tip to the Arcium fee pool; 0 or at least MIN_PRIORITY_FEE (default 0) */
+  priorityFeeLamports?: bigint;
+  /**
+   * The move sender's PlayerProfile PDA; when given, a capture is counted
+   * in its planets_captured (default none)
+   */
+  attackerProfile?: PublicKey | null;
 }
 
 /**
@@ -46,16 +57,22 @@ export function buildQueueFlushPlanetIx(
 ) {
   return program.methods
     .queueFlushPlanet(
-      new BN(args.computationOffset.toString()),
       args.flushCount,
-      Buffer.from(args.flushCts),
-      Array.from(args.flushPubkey) as any,
-      new BN(args.flushNonce.toString())
+      Array.from(args.observerPubkey) as any,
+      new BN(args.observerNonce.toString()),
+      new BN((args.priorityFeeLamports ?? 0n).toString())
     )
     .accounts({
       payer,
+      offsetTracker: deriveComputationOffsetTrackerPDA(
+        args.gameId,
+        payer,
+        program.programId
+      )[0],
       celestialBody: args.celestialBody,
       pendingMoves: args.pendingMoves,
+      combatLog: args.combatLog,
+      attackerProfile: args.attackerProfile ?? null,
       signPdaAccount: arciumAccounts.signPdaAccount,
       mxeAccount: arciumAccounts.mxeAccount,
       mempoolAccount: arciumAccounts.mempoolAccount,
@@ -67,12 +84,12 @@ export function buildQueueFlushPlanetIx(
       clockAccount: arciumAccounts.clockAccount,
       systemProgram: SystemProgram.programId,
       arciumProgram: arciumAccounts.arciumProgram,
-    })
+    } as any)
     .remainingAccounts(
       args.moveAccounts.map((pubkey) => ({
         pubkey,
         isSigner: false,
-        isWritable: false,
+        isWritable: true,
       }))
     );
 }
@@ -7,6 +7,8 @@
  *
  * Encrypted input: Enc<Shared, CoordInput> = 2 ciphertexts (x, y)
  * Plaintext params from Game account are passed by the on-chain program.
+ * Once the game's hash_rounds was raised, the location's planet under each
+ * earlier value is passed as a remaining account and must not exist.
  */
 
 import { type Program, BN } from "@coral-xyz/anchor";
@@ -15,12 +17,13 @@ import {
   deriveGamePDA,
   deriveCelestialBodyPDA,
   derivePendingMovesPDA,
+  deriveCombatLogPDA,
+  deriveComputationOffsetTrackerPDA,
 } from "../utils/pda.js";
 import type { ArciumAccounts } from "./arciumAccounts.js";
 
 export interface QueueInitPlanetArgs {
   gameId: bigint;
-  computationOffset: bigint;
   planetHash: Uint8Array;
   /** 2 ciphertexts packed as Vec<u8> (2 * 32 = 64 bytes): x, y */
   ciphertexts: Uint8Array;
@@ -30,6 +33,10 @@ export interface QueueInitPlanetArgs {
   nonce: bigint;
   /** Observer x25519 public key */
   observerPubkey: Uint8Array;
+  /** From priorRoundsPlanetHashes; required once the game's hashRounds was raised */
+  basePlanetHash?: Uint8Array | null;
+  /** From priorRoundsPlanetHashes: the location's hash under each earlier hashRounds */
+  priorPlanetHashes?: Uint8Array[];
 }
 
 /**
@@ -52,21 +59,32 @@ export function buildQueueInitPlanetIx(
     args.planetHash,
     program.programId
   );
+  const [combatLogPDA] = deriveCombatLogPDA(
+    args.gameId,
+    args.planetHash,
+    program.programId
+  );
 
   return program.methods
     .queueInitPlanet(
-      new BN(args.computationOffset.toString()),
       Array.from(args.planetHash) as any,
       Buffer.from(args.ciphertexts),
       Array.from(args.pubkey) as any,
       new BN(args.nonce.toString()),
-      Array.from(args.observerPubkey) as any
+      Array.from(args.observerPubkey) as any,
+      args.basePlanetHash ? (Array.from(args.basePlanetHash) as any) : null
     )
     .accounts({
       payer,
+      offsetTracker: deriveComputationOffsetTrackerPDA(
+        args.gameId,
+        payer,
+        program.programId
+      )[0],
       game: gamePDA,
       celestialBody: celestialBodyPDA,
       pendingMoves: pendingMovesPDA,
+      combatLog: combatLogPDA,
       signPdaAccount: arciumAccounts.signPdaAccount,
       mxeAccount: arciumAccounts.mxeAccount,
       mempoolAccount: arciumAccounts.mempoolAccount,
@@ -78,5 +96,12 @@ export function buildQueueInitPlanetIx(
       clockAccount: arciumAccounts.clockAccount,
       systemProgram: SystemProgram.programId,
       arciumProgram: arciumAccounts.arciumProgram,
-    });
+    })
+    .remainingAccounts(
+      (args.priorPlanetHashes ?? []).map((hash) => ({
+        pubkey: deriveCelestialBodyPDA(args.gameId, hash, program.programId)[0],
+        isSigner: false,
+        isWritable: false,
+      }))
+    );
 }
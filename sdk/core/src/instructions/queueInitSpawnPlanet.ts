@@ -5,7 +5,13 @@
  * and claim it as the player's spawn point.
  *
  * Encrypted input: Enc<Shared, SpawnInput> = 4 ciphertexts (x, y, player_id, source_planet_id)
+ * player_id must be playerIdFromPubkey(payer); any other id makes the placement invalid.
  * Plaintext params from Game account are passed by the on-chain program.
+ * When the game sets min_spawn_separation, every earlier spawn planet
+ * (Game.spawnPlanetHashes, in order) is passed as a remaining account so the
+ * circuit can check the new spawn against all of them. Once the game's
+ * hash_rounds was raised, the location's planet under each earlier value
+ * follows them and must not exist.
  */
 
 import { type Program, BN } from "@coral-xyz/anchor";
@@ -15,12 +21,13 @@ import {
   derivePlayerPDA,
   deriveCelestialBodyPDA,
   derivePendingMovesPDA,
+  deriveCombatLogPDA,
+  deriveComputationOffsetTrackerPDA,
 } from "../utils/pda.js";
 import type { ArciumAccounts } from "./arciumAccounts.js";
 
 export interface QueueInitSpawnPlanetArgs {
   gameId: bigint;
-  computationOffset: bigint;
   planetHash: Uint8Array;
   /** 4 ciphertexts packed as Vec<u8> (4 * 32 = 128 bytes): x, y, player_id, source_planet_id */
   ciphertexts: Uint8Array;
@@ -30,6 +37,12 @@ export interface QueueInitSpawnPlanetArgs {
   nonce: bigint;
   /** Observer x25519 public key */
   observerPubkey: Uint8Array;
+  /** From priorRoundsPlanetHashes; required once the game's hashRounds was raised */
+  basePlanetHash?: Uint8Array | null;
+  /** From priorRoundsPlanetHashes: the location's hash under each earlier hashRounds */
+  priorPlanetHashes?: Uint8Array[];
+  /** The game's spawnPlanetHashes (all of them, in order; empty when separation is off) */
+  spawnPlanetHashes?: Uint8Array[];
 }
 
 /**
@@ -53,22 +66,33 @@ export function buildQueueInitSpawnPlanetIx(
     args.planetHash,
     program.programId
   );
+  const [combatLogPDA] = deriveCombatLogPDA(
+    args.gameId,
+    args.planetHash,
+    program.programId
+  );
 
   return program.methods
     .queueInitSpawnPlanet(
-      new BN(args.computationOffset.toString()),
       Array.from(args.planetHash) as any,
       Buffer.from(args.ciphertexts),
       Array.from(args.pubkey) as any,
       new BN(args.nonce.toString()),
-      Array.from(args.observerPubkey) as any
+      Array.from(args.observerPubkey) as any,
+      args.basePlanetHash ? (Array.from(args.basePlanetHash) as any) : null
     )
     .accounts({
       payer,
+      offsetTracker: deriveComputationOffsetTrackerPDA(
+        args.gameId,
+        payer,
+        program.programId
+      )[0],
       game: gamePDA,
       player: playerPDA,
       celestialBody: celestialBodyPDA,
       pendingMoves: pendingMovesPDA,
+      combatLog: combatLogPDA,
       signPdaAccount: arciumAccounts.signPdaAccount,
       mxeAccount: arciumAccounts.mxeAccount,
       mempoolAccount: arciumAccounts.mempoolAccount,
@@ -80,5 +104,12 @@ export function buildQueueInitSpawnPlanetIx(
       clockAccount: arciumAccounts.clockAccount,
       systemProgram: SystemProgram.programId,
       arciumProgram: arciumAccounts.arciumProgram,
-    });
+    })
+    .remainingAccounts(
+      [...(args.spawnPlanetHashes ?? []), ...(args.priorPlanetHashes ?? [])].map((hash) => ({
+        pubkey: deriveCelestialBodyPDA(args.gameId, hash, program.programId)[0],
+        isSigner: false,
+        isWritable: false,
+      }))
+    );
 }
@@ -6,31 +6,51 @@
  *
  * Planet state and move input are passed inline as ciphertexts.
  *
- * Encrypted input: Enc<Shared, ProcessMoveInputPacked> = 2 ciphertexts (Pack<[u32;8]>):
+ * Encrypted input: Enc<Shared, ProcessMoveInputPacked> = 2 ciphertexts (Pack<[u32;11]>):
  *   Packed fields: player_id, source_planet_id, ships_to_send, metal_to_send,
- *   source_x, source_y, target_x, target_y (coords biased by 2^31)
+ *   source_x, source_y, target_x, target_y (coords biased by 2^31), ally_player_id,
+ *   player_id_hi, ally_player_id_hi (the 64-bit ids' high words)
  *
- * Plaintext params (computed on-chain from lazy generation):
- *   current_ships, current_metal, current_slot, game_speed
+ * Passing the payer's alliance lets a non-zero ally_player_id send the fleet
+ * to reinforce that ally's planet.
+ *
+ * Plaintext params (read on-chain): current_slot, game_speed and the source's
+ * last_updated_slot. The circuit derives current ships/metal from them.
  *
  * landing_slot is a public parameter validated by the circuit.
+ *
+ * max_observable_distance is a public upper bound on the move's distance
+ * (see observableDistanceBound). The program rejects bounds above the
+ * largest distance on the map (maxMapDistance) before queueing and the
+ * circuit rejects moves that exceed it.
+ *
+ * priorityFeeLamports is transferred from the payer to the Arcium fee pool
+ * before queueing (see MIN_PRIORITY_FEE). It is a donation: Arcium does not
+ * order the computation queue by it.
  */
 
 import { type Program, BN } from "@coral-xyz/anchor";
 import { PublicKey, SystemProgram } from "@solana/web3.js";
-import { deriveGamePDA } from "../utils/pda.js";
+import {
+  deriveGamePDA,
+  derivePlayerPDA,
+  deriveComputationOffsetTrackerPDA,
+} from "../utils/pda.js";
 import type { ArciumAccounts } from "./arciumAccounts.js";
 
+/**
+ * Smallest non-zero priority fee the queue_process_move, queue_flush_planet
+ * and queue_upgrade_planet instructions accept (mirrors MIN_PRIORITY_FEE on-chain)
+ */
+export const MIN_PRIORITY_FEE = 5000n;
+
 export interface QueueProcessMoveArgs {
   gameId: bigint;
-  computationOffset: bigint;
   /** Public landing slot (validated by MPC) */
   landingSlot: bigint;
-  /** Client-computed current ship count (lazy generation from on-chain state) */
-  currentShips: bigint;
-  /** Client-computed current metal count (lazy generation from on-chain state) */
-  currentMetal: bigint;
-  /** 2 ciphertexts packed as Vec<u8> (2 * 32 = 64 bytes) — Pack<[u32;8]> */
+  /** Public upper bound on the distance, at most map_diameter (validated by MPC) */
+  maxObservableDistance: bigint;
+  /** 2 ciphertexts packed as Vec<u8> (2 * 32 = 64 bytes) — Pack<[u32;11]> */
   moveCts: Uint8Array;
   /** x25519 pubkey for Enc<Shared, ProcessMoveInput> */
   movePubkey: Uint8Array;
@@ -44,6 +64,10 @@ export interface QueueProcessMoveArgs {
   targetPending: PublicKey;
   /** PendingMoveAccount PDA (init'd here, populated by callback) */
   moveAccount: PublicKey;
+  /** Optional: payer's Alliance PDA, required for allied reinforcement */
+  alliance?: PublicKey;
+  /** Optional tip to the Arcium fee pool; 0 or at least MIN_PRIORITY_FEE (default 0) */
+  priorityFeeLamports?: bigint;
 }
 
 /**
@@ -56,20 +80,27 @@ export function buildQueueProcessMoveIx(
   arciumAccounts: ArciumAccounts
 ) {
   const [gamePDA] = deriveGamePDA(args.gameId, program.programId);
+  const [playerPDA] = derivePlayerPDA(args.gameId, payer, program.programId);
 
   return program.methods
     .queueProcessMove(
-      new BN(args.computationOffset.toString()),
       new BN(args.landingSlot.toString()),
-      new BN(args.currentShips.toString()),
-      new BN(args.currentMetal.toString()),
+      new BN(args.maxObservableDistance.toString()),
       Buffer.from(args.moveCts),
       Array.from(args.movePubkey) as any,
-      new BN(args.moveNonce.toString())
+      new BN(args.moveNonce.toString()),
+      new BN((args.priorityFeeLamports ?? 0n).toString())
     )
     .accounts({
       payer,
+      offsetTracker: deriveComputationOffsetTrackerPDA(
+        args.gameId,
+        payer,
+        program.programId
+      )[0],
       game: gamePDA,
+      player: playerPDA,
+      alliance: args.alliance ?? null,
       sourceBody: args.sourceBody,
       sourcePending: args.sourcePending,
       targetPending: args.targetPending,
@@ -3,24 +3,31 @@
  *
  * Queues an Arcium upgrade_planet computation to level up a Planet-type
  * celestial body. Spends metal and applies stat upgrades based on the
- * chosen focus (Range or LaunchVelocity).
+ * chosen focus (Range, LaunchVelocity, ShipGenSpeed, MetalGenSpeed or Balanced).
  *
  * Planet state (static + dynamic) is read by MPC nodes directly from
  * celestial_body via .account() -- NOT passed as ciphertexts.
  *
- * Encrypted input: Enc<Shared, UpgradePlanetInput> = 6 ciphertexts:
- *   player_id, focus, current_slot, game_speed, last_updated_slot, metal_upgrade_cost
+ * Encrypted input: Enc<Shared, UpgradePlanetInput> = 3 ciphertexts:
+ *   player_id, focus, metal_upgrade_cost
+ * The program passes the timing (current slot, game_speed and the body's
+ * last_updated_slot) as plaintext.
+ * player_id must be playerIdFromPubkey(payer).
+ *
+ * The planet's PendingMovesMetadata must be empty (no pending or queued moves).
  */
 
 import { type Program, BN } from "@coral-xyz/anchor";
 import { PublicKey, SystemProgram } from "@solana/web3.js";
-import { deriveGamePDA } from "../utils/pda.js";
+import {
+  deriveGamePDA,
+  deriveComputationOffsetTrackerPDA,
+} from "../utils/pda.js";
 import type { ArciumAccounts } from "./arciumAccounts.js";
 
 export interface QueueUpgradePlanetArgs {
   gameId: bigint;
-  computationOffset: bigint;
-  /** 6 ciphertexts packed as Vec<u8> (6 * 32 = 192 bytes) */
+  /** 3 ciphertexts packed as Vec<u8> (3 * 32 = 96 bytes) */
   upgradeCts: Uint8Array;
   /** x25519 pubkey for Enc<Shared, UpgradePlanetInput> */
   upgradePubkey: Uint8Array;
@@ -28,6 +35,10 @@ export interface QueueUpgradePlanetArgs {
   upgradeNonce: bigint;
   /** Celestial body account address */
   celestialBody: PublicKey;
+  /** Pending moves metadata PDA for the planet */
+  pendingMoves: PublicKey;
+  /** Optional tip to the Arcium fee pool; 0 or at least MIN_PRIORITY_FEE (default 0) */
+  priorityFeeLamports?: bigint;
 }
 
 /**
@@ -43,15 +54,21 @@ export function buildQueueUpgradePlanetIx(
 
   return program.methods
     .queueUpgradePlanet(
-      new BN(args.computationOffset.toString()),
       Buffer.from(args.upgradeCts),
       Array.from(args.upgradePubkey) as any,
-      new BN(args.upgradeNonce.toString())
+      new BN(args.upgradeNonce.toString()),
+      new BN((args.priorityFeeLamports ?? 0n).toString())
     )
     .accounts({
       payer,
+      offsetTracker: deriveComputationOffsetTrackerPDA(
+        args.gameId,
+        payer,
+        program.programId
+      )[0],
       game: gamePDA,
       celestialBody: args.celestialBody,
+      pendingMoves: args.pendingMoves,
       signPdaAccount: arciumAccounts.signPdaAccount,
       mxeAccount: arciumAccounts.mxeAccount,
       mempoolAccount: arciumAccounts.mempoolAccount,
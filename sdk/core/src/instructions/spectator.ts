@@ -0,0 +1,85 @@
+/**
+ * Instruction builders: create_spectator_registry, register_spectator,
+ * deregister_spectator
+ *
+ * Spectators are non-player wallets allowed to broadcast planet coordinates
+ * for a game. The admin creates the registry once with a capacity; wallets
+ * then register and deregister themselves.
+ */
+
+import { type Program, BN } from "@coral-xyz/anchor";
+import { PublicKey } from "@solana/web3.js";
+import { deriveGamePDA, deriveSpectatorRegistryPDA } from "../utils/pda.js";
+
+/**
+ * Build a transaction builder for the create_spectator_registry instruction.
+ * Only the game admin may call it.
+ */
+export function buildCreateSpectatorRegistryIx(
+  program: Program,
+  admin: PublicKey,
+  gameId: bigint,
+  maxSpectators: number
+) {
+  const [gamePDA] = deriveGamePDA(gameId, program.programId);
+  const [spectatorRegistry] = deriveSpectatorRegistryPDA(
+    gameId,
+    program.programId
+  );
+
+  return program.methods
+    .createSpectatorRegistry(new BN(gameId.toString()), maxSpectators)
+    .accounts({
+      admin,
+      game: gamePDA,
+      spectatorRegistry,
+    });
+}
+
+/**
+ * Build a transaction builder for the register_spectator instruction.
+ * Whitelisted games require the server co-signer, as with init_player.
+ */
+export function buildRegisterSpectatorIx(
+  program: Program,
+  spectator: PublicKey,
+  gameId: bigint,
+  server?: PublicKey
+) {
+  const [gamePDA] = deriveGamePDA(gameId, program.programId);
+  const [spectatorRegistry] = deriveSpectatorRegistryPDA(
+    gameId,
+    program.programId
+  );
+
+  return program.methods
+    .registerSpectator(new BN(gameId.toString()))
+    .accounts({
+      spectator,
+      game: gamePDA,
+      spectatorRegistry,
+      server: server ?? null,
+    } as any);
+}
+
+/**
+ * Build a transaction builder for the deregister_spectator instruction.
+ * The freed registry rent is refunded to the spectator.
+ */
+export function buildDeregisterSpectatorIx(
+  program: Program,
+  spectator: PublicKey,
+  gameId: bigint
+) {
+  const [spectatorRegistry] = deriveSpectatorRegistryPDA(
+    gameId,
+    program.programId
+  );
+
+  return program.methods
+    .deregisterSpectator(new BN(gameId.toString()))
+    .accounts({
+      spectator,
+      spectatorRegistry,
+    } as any);
+}
@@ -0,0 +1,39 @@
+/**
+ * Instruction builder: verify_coordinates
+ *
+ * Cheap on-chain check that (x, y) hashes to planetHash and lies within the
+ * map. The result is reported in CoordinatesVerifiedEvent; the instruction
+ * never fails on a mismatch.
+ */
+
+import { type Program, BN } from "@coral-xyz/anchor";
+import { deriveGamePDA } from "../utils/pda.js";
+
+export interface VerifyCoordinatesArgs {
+  gameId: bigint;
+  x: bigint;
+  y: bigint;
+  planetHash: Uint8Array;
+}
+
+/**
+ * Build a transaction builder for the verify_coordinates instruction.
+ * Call .rpc() or .simulate() on the result.
+ */
+export function buildVerifyCoordinatesIx(
+  program: Program,
+  args: VerifyCoordinatesArgs
+) {
+  const [gamePDA] = deriveGamePDA(args.gameId, program.programId);
+
+  return program.methods
+    .verifyCoordinates(
+      new BN(args.gameId.toString()),
+      new BN(args.x.toString()),
+      new BN(args.y.toString()),
+      Array.from(args.planetHash) as any
+    )
+    .accounts({
+      game: gamePDA,
+    });
+}
@@ -7,18 +7,22 @@
  * 3. PDA derivation functions produce valid PDAs
  * 4. Game mechanics helpers (distance, decay, landing slot, upgrade cost)
  * 5. findSpawnPlanet finds valid spawn locations
+ * 6. computePropertyHash separates games (avalanche on game_id)
  */
 
 import { describe, expect, it } from "vitest";
 import { PublicKey } from "@solana/web3.js";
 import {
   computePlanetHash,
+  computePropertyHash,
   determineCelestialBody,
   baseStats,
   applyCometBoosts,
   computeCurrentShips,
   computeCurrentMetal,
   computeDistance,
+  observableDistanceBound,
+  maxMapDistance,
   applyDistanceDecay,
   computeLandingSlot,
   upgradeCost,
@@ -40,6 +44,7 @@ import {
   derivePendingMovesPDA,
   PROGRAM_ID,
 } from "../utils/pda.js";
+import { playerIdFromPubkey } from "../accounts/player.js";
 
 // ---------------------------------------------------------------------------
 // computePlanetHash
@@ -86,24 +91,57 @@ describe("computePlanetHash", () => {
   });
 });
 
+describe("computePropertyHash", () => {
+  // Mirrors the circuit's mix_hash: game_id goes through SHA3 with the
+  // coordinates, so neighbouring games must not share property bytes.
+  it("should change every noise byte when game_id changes by 1", () => {
+    const coords: [bigint, bigint][] = [[0n, 0n], [1n, 2n], [-5n, 7n], [1000n, -1000n]];
+    for (const [x, y] of coords) {
+      for (const gameId of [1n, 2n, 42n, 1n << 32n]) {
+        const a = computePropertyHash(x, y, gameId);
+        const b = computePropertyHash(x, y, gameId + 1n);
+        // Bytes 0..7 feed body type, size and comets
+        for (let i = 0; i < 8; i++) {
+          expect(a[i]).not.toBe(b[i]);
+        }
+        let bitsChanged = 0;
+        for (let i = 0; i < 32; i++) {
+          let diff = a[i] ^ b[i];
+          while (diff) {
+            bitsChanged += diff & 1;
+            diff >>= 1;
+          }
+        }
+        expect(bitsChanged).toBeGreaterThan(32);
+      }
+    }
+  });
+});
+
 // ---------------------------------------------------------------------------
 // determineCelestialBody
 // ---------------------------------------------------------------------------
 
+/** Write a 16-bit noise value: high byte at hash[i], low byte at hash[i + 6]. */
+function setNoise(hash: Uint8Array, i: number, value: number) {
+  hash[i] = value >> 8;
+  hash[i + 6] = value & 0xff;
+}
+
 describe("determineCelestialBody", () => {
-  it("should return null for dead space (byte0 < threshold)", () => {
-    // Create a hash where byte0 is low
+  it("should return null for dead space (noise0 < threshold)", () => {
+    // Create a hash where noise0 is low
     const hash = new Uint8Array(32);
-    hash[0] = DEFAULT_THRESHOLDS.deadSpaceThreshold - 1;
+    setNoise(hash, 0, DEFAULT_THRESHOLDS.deadSpaceThreshold - 1);
     const result = determineCelestialBody(hash, DEFAULT_THRESHOLDS);
     expect(result).toBeNull();
   });
 
-  it("should return Planet type when byte1 < planetThreshold", () => {
+  it("should return Planet type when noise1 < planetThreshold", () => {
     const hash = new Uint8Array(32);
-    hash[0] = DEFAULT_THRESHOLDS.deadSpaceThreshold;
-    hash[1] = DEFAULT_THRESHOLDS.planetThreshold - 1;
-    hash[2] = DEFAULT_THRESHOLDS.sizeThreshold1 - 1;
+    setNoise(hash, 0, DEFAULT_THRESHOLDS.deadSpaceThreshold);
+    setNoise(hash, 1, DEFAULT_THRESHOLDS.planetThreshold - 1);
+    setNoise(hash, 2, DEFAULT_THRESHOLDS.sizeThreshold1 - 1);
     hash[3] = 0; // no comets
     const result = determineCelestialBody(hash, DEFAULT_THRESHOLDS);
     expect(result).not.toBeNull();
@@ -112,75 +150,85 @@ describe("determineCelestialBody", () => {
     expect(result!.comets).toEqual([]);
   });
 
-  it("should return Quasar when byte1 in quasar range", () => {
+  it("should return Quasar when noise1 in quasar range", () => {
     const hash = new Uint8Array(32);
-    hash[0] = DEFAULT_THRESHOLDS.deadSpaceThreshold;
-    hash[1] = DEFAULT_THRESHOLDS.planetThreshold; // start of quasar range
-    hash[2] = 100;
+    setNoise(hash, 0, DEFAULT_THRESHOLDS.deadSpaceThreshold);
+    setNoise(hash, 1, DEFAULT_THRESHOLDS.planetThreshold); // start of quasar range
+    setNoise(hash, 2, 100);
     hash[3] = 0;
     const result = determineCelestialBody(hash, DEFAULT_THRESHOLDS);
     expect(result).not.toBeNull();
     expect(result!.bodyType).toBe(CelestialBodyType.Quasar);
   });
 
-  it("should return SpacetimeRip when byte1 in rip range", () => {
+  it("should return SpacetimeRip when noise1 in rip range", () => {
     const hash = new Uint8Array(32);
-    hash[0] = DEFAULT_THRESHOLDS.deadSpaceThreshold;
-    hash[1] = DEFAULT_THRESHOLDS.quasarThreshold; // start of rip range
-    hash[2] = 100;
+    setNoise(hash, 0, DEFAULT_THRESHOLDS.deadSpaceThreshold);
+    setNoise(hash, 1, DEFAULT_THRESHOLDS.quasarThreshold); // start of rip range
+    setNoise(hash, 2, 100);
     hash[3] = 0;
     const result = determineCelestialBody(hash, DEFAULT_THRESHOLDS);
     expect(result).not.toBeNull();
     expect(result!.bodyType).toBe(CelestialBodyType.SpacetimeRip);
   });
 
-  it("should return AsteroidBelt when byte1 >= spacetimeRipThreshold", () => {
+  it("should return AsteroidBelt when noise1 >= spacetimeRipThreshold", () => {
     const hash = new Uint8Array(32);
-    hash[0] = DEFAULT_THRESHOLDS.deadSpaceThreshold;
-    hash[1] = DEFAULT_THRESHOLDS.spacetimeRipThreshold; // start of asteroid belt range
-    hash[2] = 100;
+    setNoise(hash, 0, DEFAULT_THRESHOLDS.deadSpaceThreshold);
+    setNoise(hash, 1, DEFAULT_THRESHOLDS.spacetimeRipThreshold); // start of asteroid belt range
+    setNoise(hash, 2, 100);
     hash[3] = 0;
     const result = determineCelestialBody(hash, DEFAULT_THRESHOLDS);
     expect(result).not.toBeNull();
     expect(result!.bodyType).toBe(CelestialBodyType.AsteroidBelt);
   });
 
+  it("should return null when noise1 >= asteroidBeltThreshold", () => {
+    const thresholds = { ...DEFAULT_THRESHOLDS, asteroidBeltThreshold: 61440 };
+    const hash = new Uint8Array(32);
+    setNoise(hash, 0, thresholds.deadSpaceThreshold);
+    setNoise(hash, 1, thresholds.asteroidBeltThreshold);
+    setNoise(hash, 2, 100);
+    hash[3] = 0;
+    expect(determineCelestialBody(hash, thresholds)).toBeNull();
+  });
+
   it("should determine correct sizes", () => {
     const hash = new Uint8Array(32);
-    hash[0] = DEFAULT_THRESHOLDS.deadSpaceThreshold;
-    hash[1] = DEFAULT_THRESHOLDS.planetThreshold - 1;
+    setNoise(hash, 0, DEFAULT_THRESHOLDS.deadSpaceThreshold);
+    setNoise(hash, 1, DEFAULT_THRESHOLDS.planetThreshold - 1);
     hash[3] = 0;
 
-    // Size 1 (byte2 < 43)
-    hash[2] = DEFAULT_THRESHOLDS.sizeThreshold1 - 1;
+    // Size 1 (noise2 < sizeThreshold1)
+    setNoise(hash, 2, DEFAULT_THRESHOLDS.sizeThreshold1 - 1);
     expect(determineCelestialBody(hash, DEFAULT_THRESHOLDS)!.size).toBe(1);
 
-    // Size 2 (43-85)
-    hash[2] = DEFAULT_THRESHOLDS.sizeThreshold1;
+    // Size 2
+    setNoise(hash, 2, DEFAULT_THRESHOLDS.sizeThreshold1);
     expect(determineCelestialBody(hash, DEFAULT_THRESHOLDS)!.size).toBe(2);
 
-    // Size 3 (86-127)
-    hash[2] = DEFAULT_THRESHOLDS.sizeThreshold2;
+    // Size 3
+    setNoise(hash, 2, DEFAULT_THRESHOLDS.sizeThreshold2);
     expect(determineCelestialBody(hash, DEFAULT_THRESHOLDS)!.size).toBe(3);
 
-    // Size 4 (128-170)
-    hash[2] = DEFAULT_THRESHOLDS.sizeThreshold3;
+    // Size 4
+    setNoise(hash, 2, DEFAULT_THRESHOLDS.sizeThreshold3);
     expect(determineCelestialBody(hash, DEFAULT_THRESHOLDS)!.size).toBe(4);
 
-    // Size 5 (171-213)
-    hash[2] = DEFAULT_THRESHOLDS.sizeThreshold4;
+    // Size 5
+    setNoise(hash, 2, DEFAULT_THRESHOLDS.sizeThreshold4);
     expect(determineCelestialBody(hash, DEFAULT_THRESHOLDS)!.size).toBe(5);
 
-    // Size 6 (214+)
-    hash[2] = DEFAULT_THRESHOLDS.sizeThreshold5;
+    // Size 6 (noise2 >= sizeThreshold5)
+    setNoise(hash, 2, DEFAULT_THRESHOLDS.sizeThreshold5);
     expect(determineCelestialBody(hash, DEFAULT_THRESHOLDS)!.size).toBe(6);
   });
 
   it("should handle comets correctly", () => {
     const hash = new Uint8Array(32);
-    hash[0] = DEFAULT_THRESHOLDS.deadSpaceThreshold;
-    hash[1] = DEFAULT_THRESHOLDS.planetThreshold - 1;
-    hash[2] = DEFAULT_THRESHOLDS.sizeThreshold1 - 1;
+    setNoise(hash, 0, DEFAULT_THRESHOLDS.deadSpaceThreshold);
+    setNoise(hash, 1, DEFAULT_THRESHOLDS.planetThreshold - 1);
+    setNoise(hash, 2, DEFAULT_THRESHOLDS.sizeThreshold1 - 1);
 
     // No comets (byte3 <= 216)
     hash[3] = 100;
@@ -207,9 +255,9 @@ describe("determineCelestialBody", () => {
 
   it("should deduplicate second comet boost", () => {
     const hash = new Uint8Array(32);
-    hash[0] = DEFAULT_THRESHOLDS.deadSpaceThreshold;
-    hash[1] = DEFAULT_THRESHOLDS.planetThreshold - 1;
-    hash[2] = DEFAULT_THRESHOLDS.sizeThreshold1 - 1;
+    setNoise(hash, 0, DEFAULT_THRESHOLDS.deadSpaceThreshold);
+    setNoise(hash, 1, DEFAULT_THRESHOLDS.planetThreshold - 1);
+    setNoise(hash, 2, DEFAULT_THRESHOLDS.sizeThreshold1 - 1);
     hash[3] = 250; // two comets
     hash[4] = 0; // (0 % 6) + 1 = 1 = ShipCapacity
     hash[5] = 6; // (6 % 6) + 1 = 1 = ShipCapacity, dedup: ((6+1) % 6) + 1 = 2 = MetalCapacity
@@ -300,6 +348,32 @@ describe("computeDistance", () => {
     expect(computeDistance(0n, 0n, 0n, 0n)).toBe(0n);
     expect(computeDistance(-5n, -5n, 5n, 5n)).toBe(10n + 10n / 2n); // 15
   });
+
+  it("should compute manhattan distance", () => {
+    expect(computeDistance(0n, 0n, 3n, 4n, "manhattan")).toBe(7n);
+    expect(computeDistance(-5n, -5n, 5n, 5n, "manhattan")).toBe(20n);
+    expect(computeDistance(0n, 0n, 3n, 4n, "chebyshevApprox")).toBe(5n);
+  });
+});
+
+describe("maxMapDistance", () => {
+  it("should match the distance between opposite corners", () => {
+    expect(maxMapDistance(1000n)).toBe(computeDistance(-500n, -500n, 500n, 500n));
+    expect(maxMapDistance(1000n, "manhattan")).toBe(
+      computeDistance(-500n, -500n, 500n, 500n, "manhattan")
+    );
+  });
+});
+
+describe("observableDistanceBound", () => {
+  it("should round up to a power of two capped at the largest map distance", () => {
+    expect(observableDistanceBound(0n, 1000n)).toBe(1n);
+    expect(observableDistanceBound(5n, 1000n)).toBe(8n);
+    expect(observableDistanceBound(64n, 1000n)).toBe(64n);
+    expect(observableDistanceBound(600n, 1000n)).toBe(1024n);
+    expect(observableDistanceBound(1200n, 1000n)).toBe(1500n);
+    expect(observableDistanceBound(1200n, 1000n, "manhattan")).toBe(2000n);
+  });
 });
 
 describe("applyDistanceDecay", () => {
@@ -361,6 +435,21 @@ describe("computeCurrentShips", () => {
 // PDA derivation
 // ---------------------------------------------------------------------------
 
+describe("playerIdFromPubkey", () => {
+  it("should XOR-fold the pubkey's u64 words to 64 bits", () => {
+    const low = new Uint8Array(32);
+    low[0] = 5;
+    expect(playerIdFromPubkey(new PublicKey(low))).toBe(5n);
+
+    // Words that cancel out fold to 0
+    const bytes = Uint8Array.from({ length: 32 }, (_, i) => i);
+    expect(playerIdFromPubkey(new PublicKey(bytes))).toBe(0n);
+
+    const mixed = Uint8Array.from({ length: 32 }, (_, i) => (i * 7) % 256);
+    expect(playerIdFromPubkey(new PublicKey(mixed))).toBe(0x200020006080e0e0n);
+  });
+});
+
 describe("PDA derivation", () => {
   it("deriveGamePDA should produce valid PublicKey", () => {
     const [pda, bump] = deriveGamePDA(1n);
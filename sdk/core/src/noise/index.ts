@@ -7,8 +7,9 @@
  */
 
 import { blake3 } from "@noble/hashes/blake3.js";
+import { sha256 } from "@noble/hashes/sha2.js";
 import { sha3_256 } from "@noble/hashes/sha3.js";
-import type { NoiseThresholds } from "../types/game.js";
+import type { DistanceMethod, HashAlgorithm, NoiseThresholds } from "../types/game.js";
 import {
   CelestialBodyType,
   CometBoost,
@@ -21,30 +22,58 @@ import {
 // ---------------------------------------------------------------------------
 
 /**
- * Compute the planet hash from coordinates and game_id using blake3.
- * Must match the on-chain `compute_planet_hash(x: i64, y: i64, game_id: u64)`.
+ * Compute the planet hash from coordinates and game_id using the game's
+ * hash algorithm (blake3 by default, or sha256).
+ * Must match the on-chain `compute_planet_hash(x, y, game_id, hash_rounds, hash_algorithm)`.
  *
  * Layout: x as i64 LE (8 bytes) || y as i64 LE (8 bytes) || game_id as u64 LE (8 bytes)
- * Total: 24 bytes input -> 32 bytes blake3 output
+ * Total: 24 bytes input -> 32 bytes hash output
  */
 export function computePlanetHash(
   x: bigint,
   y: bigint,
   gameId: bigint,
-  rounds: number = 1
+  rounds: number = 1,
+  hashAlgorithm: HashAlgorithm = "blake3"
 ): Uint8Array {
   const buf = new ArrayBuffer(24);
   const view = new DataView(buf);
   view.setBigInt64(0, x, true); // little-endian signed i64
   view.setBigInt64(8, y, true); // little-endian signed i64
   view.setBigUint64(16, gameId, true); // little-endian unsigned u64
-  let hash = blake3(new Uint8Array(buf));
+  const h = hashAlgorithm === "sha256" ? sha256 : blake3;
+  let hash = h(new Uint8Array(buf));
   for (let r = 1; r < rounds; r++) {
-    hash = blake3(hash);
+    hash = h(hash);
   }
   return hash;
 }
 
+/**
+ * What queue_init_planet / queue_init_spawn_planet need once the game's
+ * hash_rounds was raised: the location's hash under the game's first
+ * hashRounds value (basePlanetHash) and under each earlier value
+ * (priorPlanetHashes, whose planet PDAs go in as remaining accounts).
+ * Pass the game's priorHashRounds; both are empty when it is empty.
+ */
+export function priorRoundsPlanetHashes(
+  x: bigint,
+  y: bigint,
+  gameId: bigint,
+  priorHashRounds: number[],
+  hashAlgorithm: HashAlgorithm = "blake3"
+): { basePlanetHash: Uint8Array | null; priorPlanetHashes: Uint8Array[] } {
+  return {
+    basePlanetHash:
+      priorHashRounds.length > 0
+        ? computePlanetHash(x, y, gameId, priorHashRounds[0], hashAlgorithm)
+        : null,
+    priorPlanetHashes: priorHashRounds.map((rounds) =>
+      computePlanetHash(x, y, gameId, rounds, hashAlgorithm)
+    ),
+  };
+}
+
 // ---------------------------------------------------------------------------
 // MPC-compatible SHA3-256 hash (must match encrypted-ixs/src/lib.rs compute_property_hash)
 // ---------------------------------------------------------------------------
@@ -96,6 +125,14 @@ function cometFromByte(b: number): CometBoost {
 // Celestial body determination (matching on-chain determine_celestial_body)
 // ---------------------------------------------------------------------------
 
+/**
+ * 16-bit noise value: hash[i] is the high byte and hash[i + 6] the low byte.
+ * Matches on-chain `noise_u16`.
+ */
+export function noiseU16(hash: Uint8Array, i: number): number {
+  return hash[i] * 256 + hash[i + 6];
+}
+
 /**
  * Determine celestial body properties from a planet hash and noise thresholds.
  * Returns null if the hash represents dead space.
@@ -105,41 +142,43 @@ export function determineCelestialBody(
   hash: Uint8Array,
   thresholds: NoiseThresholds
 ): CelestialBodyProperties | null {
-  const byte0 = hash[0];
-  const byte1 = hash[1];
-  const byte2 = hash[2];
+  const noise0 = noiseU16(hash, 0);
+  const noise1 = noiseU16(hash, 1);
+  const noise2 = noiseU16(hash, 2);
   const byte3 = hash[3];
   const byte4 = hash[4];
   const byte5 = hash[5];
 
-  // Byte 0: dead space check
-  if (byte0 < thresholds.deadSpaceThreshold) {
+  // Noise 0: dead space check
+  if (noise0 < thresholds.deadSpaceThreshold) {
     return null;
   }
 
-  // Byte 1: body type
+  // Noise 1: body type
   let bodyType: CelestialBodyType;
-  if (byte1 < thresholds.planetThreshold) {
+  if (noise1 < thresholds.planetThreshold) {
     bodyType = CelestialBodyType.Planet;
-  } else if (byte1 < thresholds.quasarThreshold) {
+  } else if (noise1 < thresholds.quasarThreshold) {
     bodyType = CelestialBodyType.Quasar;
-  } else if (byte1 < thresholds.spacetimeRipThreshold) {
+  } else if (noise1 < thresholds.spacetimeRipThreshold) {
     bodyType = CelestialBodyType.SpacetimeRip;
-  } else {
+  } else if (noise1 < thresholds.asteroidBeltThreshold) {
     bodyType = CelestialBodyType.AsteroidBelt;
+  } else {
+    return null; // above every type threshold: dead space
   }
 
-  // Byte 2: size (1-6)
+  // Noise 2: size (1-6)
   let size: number;
-  if (byte2 < thresholds.sizeThreshold1) {
+  if (noise2 < thresholds.sizeThreshold1) {
     size = 1;
-  } else if (byte2 < thresholds.sizeThreshold2) {
+  } else if (noise2 < thresholds.sizeThreshold2) {
     size = 2;
-  } else if (byte2 < thresholds.sizeThreshold3) {
+  } else if (noise2 < thresholds.sizeThreshold3) {
     size = 3;
-  } else if (byte2 < thresholds.sizeThreshold4) {
+  } else if (noise2 < thresholds.sizeThreshold4) {
     size = 4;
-  } else if (byte2 < thresholds.sizeThreshold5) {
+  } else if (noise2 < thresholds.sizeThreshold5) {
     size = 5;
   } else {
     size = 6;
@@ -321,25 +360,56 @@ export function computeCurrentMetal(
 
 /**
  * Compute distance between two 2D points using the on-chain formula.
- * max(dx, dy) + min(dx, dy) / 2  (integer division)
- * Matches on-chain `compute_distance`.
+ * chebyshevApprox: max(dx, dy) + min(dx, dy) / 2  (integer division)
+ * manhattan: dx + dy
+ * Matches on-chain `compute_distance`; pass the game's `distanceMethod`.
  */
 export function computeDistance(
   x1: bigint,
   y1: bigint,
   x2: bigint,
-  y2: bigint
+  y2: bigint,
+  method: DistanceMethod = "chebyshevApprox"
 ): bigint {
   const dx = x1 > x2 ? x1 - x2 : x2 - x1;
   const dy = y1 > y2 ? y1 - y2 : y2 - y1;
+  if (method === "manhattan") return dx + dy;
   const maxD = dx > dy ? dx : dy;
   const minD = dx > dy ? dy : dx;
   return maxD + minD / 2n;
 }
 
 /**
- * Ships remaining after distance decay: ships - (distance / range).
- * Matches on-chain `apply_distance_decay`.
+ * Largest distance between two points of a mapDiameter-wide map: opposite
+ * corners, 1.5 * mapDiameter for chebyshevApprox and 2 * mapDiameter for
+ * manhattan. Matches on-chain `max_map_distance`.
+ */
+export function maxMapDistance(
+  mapDiameter: bigint,
+  method: DistanceMethod = "chebyshevApprox"
+): bigint {
+  return method === "manhattan" ? mapDiameter * 2n : (mapDiameter * 3n) / 2n;
+}
+
+/**
+ * Public max_observable_distance for queue_process_move: the distance rounded
+ * up to a power of two, so the bound only reveals its order of magnitude.
+ * Capped at maxMapDistance, the largest bound the program accepts.
+ */
+export function observableDistanceBound(
+  distance: bigint,
+  mapDiameter: bigint,
+  method: DistanceMethod = "chebyshevApprox"
+): bigint {
+  const cap = maxMapDistance(mapDiameter, method);
+  let bound = 1n;
+  while (bound < distance) bound *= 2n;
+  return bound < cap ? bound : cap;
+}
+
+/**
+ * Ships (or metal) remaining after distance decay: ships - (distance / range).
+ * Matches on-chain `apply_distance_decay`, which process_move applies to both.
  */
 export function applyDistanceDecay(
   ships: bigint,
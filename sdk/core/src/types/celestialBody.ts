@@ -32,6 +32,10 @@ export enum CometBoost {
 export enum UpgradeFocus {
   Range = 0,
   LaunchVelocity = 1,
+  ShipGenSpeed = 2,
+  MetalGenSpeed = 3,
+  /** +50% ship capacity, metal capacity and ship gen speed */
+  Balanced = 4,
 }
 
 // ---------------------------------------------------------------------------
@@ -91,22 +95,61 @@ export interface CelestialBodyStats {
  * Matches the Rust `EncryptedCelestialBody` struct.
  *
  * Has ONE encryption section:
- *   State (3 packed FEs): Pack<[u32;15]> containing body_type, size,
+ *   State (4 packed FEs): Pack<[u32;23]> containing body_type, size,
  *          max_ship_capacity, ship_gen_speed, max_metal_capacity,
  *          metal_gen_speed, range, launch_velocity, level, comet_0,
- *          comet_1, ship_count, metal_count, owner_exists, owner_id
+ *          comet_1, ship_count, metal_count, owner_exists, owner_id,
+ *          base_ship_capacity, base_metal_capacity, last_capture_slot,
+ *          base_ship_gen_speed, base_metal_gen_speed, base_range,
+ *          base_launch_velocity, owner_id_hi
  *
  * PDA: ["planet", game_id.to_le_bytes(), planet_hash]
  */
 export interface EncryptedCelestialBodyAccount {
+  version: number; // layout version (u8)
   planetHash: Uint8Array; // [u8; 32]
   lastUpdatedSlot: bigint;
   lastFlushedSlot: bigint;
-  // State encryption section (3 packed FE ciphertexts)
+  lastMinedSlot: bigint; // 0 = never mined
+  // State encryption section (4 packed FE ciphertexts)
   stateEncPubkey: Uint8Array; // [u8; 32] -- x25519 pubkey
   stateEncNonce: Uint8Array; // [u8; 16]
-  stateEncCiphertexts: Uint8Array[]; // 3 x [u8; 32]
+  stateEncCiphertexts: Uint8Array[]; // 4 x [u8; 32]
+  // Set on placed spawn planets (MXE-encrypted coords for separation checks)
+  hasSpawnCoords: boolean;
+  initializer: PublicKey; // init payer; close_dead_planet refunds rent here
+  hashRounds: number; // game hashRounds at creation; planetHash was derived with it
 }
 
-/** Number of encrypted field elements (Pack<[u32;15]> = 3 FEs). */
-export const PLANET_STATE_FIELDS = 3;
+/**
+ * Public combat counters for a planet, updated on every flush.
+ * Matches the Rust `CombatLog` struct.
+ *
+ * PDA: ["combat_log", game_id.to_le_bytes(), planet_hash]
+ */
+export interface CombatLog {
+  gameId: bigint;
+  planetHash: Uint8Array; // [u8; 32]
+  totalAttacks: number; // every flushed arrival
+  totalDefenses: number; // arrivals without a public capture
+  totalCaptures: number; // captures by a fleet carrying metal
+  shipsLost: bigint; // not yet tracked on-chain
+  metalLost: bigint; // not yet tracked on-chain
+}
+
+/**
+ * Publicly revealed planet coordinates, checked against the planet hash.
+ * Matches the Rust `CoordinateProof` struct.
+ *
+ * PDA: ["coordinate_proof", game_id.to_le_bytes(), planet_hash]
+ */
+export interface CoordinateProof {
+  planetHash: Uint8Array; // [u8; 32]
+  x: bigint;
+  y: bigint;
+  owner: PublicKey; // revealer; informational, any current owner can claim
+  revealedSlot: bigint;
+}
+
+/** Number of encrypted field elements (Pack<[u32;23]> = 4 FEs). */
+export const PLANET_STATE_FIELDS = 4;
@@ -1,4 +1,5 @@
 import { PublicKey } from "@solana/web3.js";
+import type { NoiseThresholds, WinConditionAnchor } from "./game.js";
 
 // ---------------------------------------------------------------------------
 // Events matching on-chain #[event] structs
@@ -10,6 +11,8 @@ import { PublicKey } from "@solana/web3.js";
  * Encrypted with observer key (Enc<Shared, InitPlanetRevealed>: 2 fields).
  */
 export interface InitPlanetEvent {
+  gameId: bigint;
+  initializer: PublicKey;
   encryptedPlanetHash: Uint8Array; // [u8; 32]
   encryptedValid: Uint8Array; // [u8; 32]
   encryptionKey: Uint8Array; // [u8; 32]
@@ -22,6 +25,8 @@ export interface InitPlanetEvent {
  * Encrypted with observer key (Enc<Shared, SpawnPlanetRevealed>: 3 fields).
  */
 export interface InitSpawnPlanetEvent {
+  gameId: bigint;
+  actor: PublicKey;
   encryptedPlanetHash: Uint8Array; // [u8; 32]
   encryptedValid: Uint8Array; // [u8; 32]
   encryptedSpawnValid: Uint8Array; // [u8; 32]
@@ -29,41 +34,138 @@ export interface InitSpawnPlanetEvent {
   nonce: Uint8Array; // [u8; 16]
 }
 
+/**
+ * Emitted by queue_process_move, before the MPC callback lands.
+ * All fields are plaintext.
+ */
+export interface ProcessMoveStartedEvent {
+  gameId: bigint;
+  actor: PublicKey;
+  sourcePlanetHash: Uint8Array; // [u8; 32]
+  targetPlanetHash: Uint8Array; // [u8; 32]
+  moveId: bigint;
+  queuedSlot: bigint;
+  landingSlot: bigint; // client estimate; ProcessMoveEvent has the MPC value
+  computationOffset: bigint;
+}
+
 /**
  * Emitted by process_move_callback.
- * Contains encrypted landing slot, surviving ships, and validity.
- * Encrypted with observer key (Enc<Shared, MoveRevealed>: 3 fields).
+ * Plaintext: the landing slot and validity are already public via the move
+ * account and the callback's revealed outputs.
  */
 export interface ProcessMoveEvent {
-  encryptedLandingSlot: Uint8Array; // [u8; 32]
-  encryptedSurvivingShips: Uint8Array; // [u8; 32]
-  encryptedValid: Uint8Array; // [u8; 32]
-  encryptionKey: Uint8Array; // [u8; 32]
-  nonce: Uint8Array; // [u8; 16]
+  gameId: bigint;
+  actor: PublicKey;
+  sourcePlanetHash: Uint8Array; // [u8; 32]
+  targetPlanetHash: Uint8Array; // [u8; 32]
+  moveId: bigint;
+  landingSlot: bigint;
+  valid: boolean;
+}
+
+/**
+ * Emitted by queue_flush_planet, before the MPC callback lands.
+ * Lets indexers show a flush as pending. All fields are plaintext.
+ */
+export interface FlushStartedEvent {
+  planetHash: Uint8Array; // [u8; 32]
+  gameId: bigint;
+  moveId: bigint;
+  queuedSlot: bigint;
+  computationOffset: bigint;
 }
 
 /**
  * Emitted by flush_planet_callback.
- * Contains plaintext planet hash and flushed count (NOT encrypted).
+ * Contains plaintext planet hash and flushed count, plus the ships and metal
+ * generated before the move landed.
+ * Encrypted with observer key (Enc<Shared, FlushRevealed>: 2 fields).
  */
 export interface FlushPlanetEvent {
   planetHash: Uint8Array; // [u8; 32]
   flushedCount: number; // u8
+  lastUpdatedSlot: bigint;
+  lastFlushedSlot: bigint;
+  encryptedGenShips: Uint8Array; // [u8; 32]
+  encryptedGenMetal: Uint8Array; // [u8; 32]
+  encryptionKey: Uint8Array; // [u8; 32]
+  nonce: Uint8Array; // [u8; 16]
+}
+
+/**
+ * Emitted by flush_planet_callback for every flushed move: which planet's
+ * fleet landed on which. All fields are plaintext.
+ */
+export interface CombatEvent {
+  sourcePlanetHash: Uint8Array; // [u8; 32]
+  targetPlanetHash: Uint8Array; // [u8; 32]
+  flushedMoveId: bigint;
+  gameId: bigint;
+}
+
+/**
+ * Emitted by flush_planet_callback after the planet's CombatLog is updated.
+ * All fields are plaintext.
+ */
+export interface CombatLogUpdatedEvent {
+  planetHash: Uint8Array; // [u8; 32]
+  totalCaptures: number; // u32
+}
+
+/**
+ * Emitted by queue_upgrade_planet, before the MPC callback lands.
+ * All fields are plaintext.
+ */
+export interface UpgradeStartedEvent {
+  gameId: bigint;
+  actor: PublicKey;
+  planetHash: Uint8Array; // [u8; 32]
+  queuedSlot: bigint;
+  computationOffset: bigint;
 }
 
 /**
  * Emitted by upgrade_planet_callback.
- * Contains plaintext planet hash + encrypted success flag and new level.
- * Encrypted with upgrade input key (Enc<Shared, UpgradeRevealed>: 2 fields).
+ * Contains plaintext planet hash + encrypted success flag, new level and
+ * at-max-level flag.
+ * Encrypted with upgrade input key (Enc<Shared, UpgradeRevealed>: 3 fields).
  */
 export interface UpgradePlanetEvent {
+  gameId: bigint;
+  actor: PublicKey;
   planetHash: Uint8Array; // [u8; 32]
   encryptedSuccess: Uint8Array; // [u8; 32]
   encryptedNewLevel: Uint8Array; // [u8; 32]
+  encryptedAtMaxLevel: Uint8Array; // [u8; 32]
+  encryptionKey: Uint8Array; // [u8; 32]
+  nonce: Uint8Array; // [u8; 16]
+  focus: number; // UpgradeFocus discriminant (plaintext)
+}
+
+/**
+ * Emitted by mine_asteroid_callback.
+ * Encrypted with mine input key (Enc<Shared, MineRevealed>: 2 fields).
+ */
+export interface MiningCompleteEvent {
+  planetHash: Uint8Array; // [u8; 32]
+  encryptedSuccess: Uint8Array; // [u8; 32]
+  encryptedYield: Uint8Array; // [u8; 32]
   encryptionKey: Uint8Array; // [u8; 32]
   nonce: Uint8Array; // [u8; 16]
 }
 
+/**
+ * Emitted by transfer_owner_callback.
+ * Plaintext: success is false when the payer didn't own the planet.
+ */
+export interface OwnershipTransferredEvent {
+  planetHash: Uint8Array; // [u8; 32]
+  from: PublicKey;
+  to: PublicKey;
+  success: boolean;
+}
+
 /**
  * Emitted by broadcast.
  * Plaintext event revealing planet coordinates to all players.
@@ -74,4 +176,143 @@ export interface BroadcastEvent {
   gameId: bigint;
   planetHash: Uint8Array; // [u8; 32]
   broadcaster: PublicKey;
+  winCondition: WinConditionAnchor;
+  gameSpeed: bigint;
+  lastUpdatedSlot: bigint; // planet's last state write
+}
+
+/**
+ * Emitted by verify_coordinates.
+ * valid = hash matches and coordinates are within map bounds.
+ */
+export interface CoordinatesVerifiedEvent {
+  x: bigint;
+  y: bigint;
+  gameId: bigint;
+  planetHash: Uint8Array; // [u8; 32]
+  valid: boolean;
+}
+
+/**
+ * Emitted by create_coordinate_proof.
+ * All fields are plaintext.
+ */
+export interface CoordinateRevealedEvent {
+  gameId: bigint;
+  planetHash: Uint8Array; // [u8; 32]
+  x: bigint;
+  y: bigint;
+  owner: PublicKey;
+  revealedSlot: bigint;
+}
+
+/**
+ * Emitted by close_dead_planet.
+ * All fields are plaintext; x and y are the revealed dead-space coordinates.
+ */
+export interface DeadPlanetClosedEvent {
+  gameId: bigint;
+  planetHash: Uint8Array; // [u8; 32]
+  x: bigint;
+  y: bigint;
+  closer: PublicKey;
+}
+
+/**
+ * Emitted by get_game_config.
+ * Public game configuration for indexers that missed create_game.
+ */
+export interface GameConfigEvent {
+  gameId: bigint;
+  mapDiameter: bigint;
+  gameSpeed: bigint;
+  startSlot: bigint;
+  endSlot: bigint;
+  winCondition: WinConditionAnchor;
+  noiseThresholds: NoiseThresholds;
+  hashRounds: number;
+  hashAlgorithm: { blake3: {} } | { sha256: {} };
+  distanceMethod: { chebyshevApprox: {} } | { manhattan: {} };
+}
+
+/**
+ * Emitted by get_program_version.
+ * Lets clients detect a deployment speaking a protocol they don't support.
+ */
+export interface ProgramVersionEvent {
+  version: number;
+  minCompatibleClientVersion: number;
+}
+
+/**
+ * Emitted by cleanup_game just before it closes the game's leaderboard.
+ */
+export interface LeaderboardClosedEvent {
+  gameId: bigint;
+  finalEntries: { owner: PublicKey; points: bigint }[];
+}
+
+/**
+ * Emitted by cleanup_game before the game account is closed.
+ */
+export interface GameFinalisedEvent {
+  gameId: bigint;
+  winner: PublicKey | null;
+  /** endSlot - startSlot */
+  totalSlots: bigint;
+  finalPlayerCount: number;
+  finalPlanetCount: bigint;
+  admin: PublicKey;
+}
+
+/**
+ * Emitted by merge_pending_moves.
+ */
+export interface PendingMovesMergedEvent {
+  gameId: bigint;
+  sourcePlanetHash: Uint8Array;
+  destPlanetHash: Uint8Array;
+  mergedCount: number;
+}
+
+/**
+ * Emitted by a callback whose MPC output failed verification. The transaction
+ * itself fails, so this only appears in its logs. computationAccount is the
+ * Arcium PDA for the queued computation offset. errorCode is the program
+ * error the callback failed with: ComputationSignatureInvalid (re-queue),
+ * ComputationOutputStale (drop), ComputationCircuitMismatch (check the comp
+ * def) or AbortedComputation (re-queue).
+ */
+export interface ComputationFailedEvent {
+  circuitName: string;
+  computationAccount: PublicKey;
+  errorCode: number;
+}
+
+/**
+ * Emitted by init_player once the player is registered.
+ */
+export interface PlayerJoinedEvent {
+  gameId: bigint;
+  player: PublicKey;
+  playerCount: number;
+}
+
+/**
+ * Emitted by cleanup_player when a player account is closed.
+ */
+export interface PlayerLeftEvent {
+  gameId: bigint;
+  player: PublicKey;
+  playerCount: number;
+}
+
+/**
+ * Emitted by cleanup_player before the player account is closed.
+ */
+export interface PlayerFinalisedEvent {
+  gameId: bigint;
+  player: PublicKey;
+  finalPoints: bigint;
+  hasSpawned: boolean;
 }
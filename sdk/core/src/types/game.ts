@@ -19,12 +19,25 @@ export type WinConditionAnchor =
   | { pointsBurning: { pointsPerMetal: any } }
   | { raceToCenter: { minSpawnDistance: any } };
 
+/**
+ * Hash function used for planet hashes.
+ * Matches on-chain `HashAlgorithm` enum.
+ */
+export type HashAlgorithm = "blake3" | "sha256";
+
+/**
+ * Distance formula used by the move, spy and spawn circuits.
+ * Matches on-chain `DistanceMethod` enum.
+ */
+export type DistanceMethod = "chebyshevApprox" | "manhattan";
+
 // ---------------------------------------------------------------------------
 // Structs
 // ---------------------------------------------------------------------------
 
 /**
  * Noise thresholds configuration for celestial body determination.
+ * Each field is a u16 compared against 16-bit noise values (0-65535).
  * Matches on-chain `NoiseThresholds` struct.
  */
 export interface NoiseThresholds {
@@ -58,23 +71,78 @@ export interface Game {
   noiseThresholds: NoiseThresholds;
   /** Iterated BLAKE3 rounds for planet hash difficulty. Default: 100. */
   hashRounds: number;
+  /** hashRounds values in effect before each update_hash_rounds, oldest first */
+  priorHashRounds: number[];
+  /** Max queue_process_move calls per player per slot. */
+  maxMovesPerSlot: number;
+  /** Max in-flight moves per target planet. */
+  maxPendingMoves: number;
+  /** Ship loss on arrival at a Quasar, in basis points (0..=10000). */
+  quasarShipPenaltyBps: bigint;
+  /** Min slots between moves launched from the same planet (0 = no cooldown). */
+  moveCooldownSlots: bigint;
+  /** Maximum players allowed to join (0 = unlimited) */
+  maxPlayers: number;
+  /** Players currently registered via init_player. */
+  currentPlayerCount: number;
+  /** Planets created so far (init_planet and init_spawn_planet callbacks). */
+  planetCount: bigint;
+  /** Min distance a new spawn must keep from every earlier spawn (0 = off; needs maxPlayers <= 9) */
+  minSpawnSeparation: bigint;
+  /** Spawns placed while minSpawnSeparation is set (a respawn replaces the player's entry) */
+  spawnCount: number;
+  /** Planet hashes of the first spawnCount spawns (max 8); pass them all to queue_init_spawn_planet */
+  spawnPlanetHashes: Uint8Array[];
+  /** Min spawn distance from the map center; RaceToCenter's minSpawnDistance, else 0 */
+  minSpawnDistanceFromCenter: bigint;
+  /** Min slots since a planet's last update before comet drift may run (0 = drift disabled) */
+  cometDriftIntervalSlots: bigint;
+  /** Planet hash function; clients must hash coordinates with the same one. */
+  hashAlgorithm: HashAlgorithm;
+  /** Program protocol version that created (or last migrated) the game */
+  accountVersion: number;
+  /** Lamports locked per player at init_player, refunded on self-cleanup */
+  securityDepositLamports: bigint;
+  /** Receives deposits of force-cleaned players */
+  depositRecipient: PublicKey;
+  /** Min slots after a capture before the planet can launch moves (0 = off) */
+  captureCooldownSlots: bigint;
+  /** cleanup_player skips the closed-planet check (set by admin after grace period) */
+  allowForceCleanup: boolean;
+  /** Distance formula used for travel time, range and spawn checks */
+  distanceMethod: DistanceMethod;
+  /** Bitmask of body types players may spawn on (bit 0 Planet, 1 Quasar, 2 SpacetimeRip, 3 AsteroidBelt) */
+  validSpawnBodyTypes: number;
+  /** Highest level a planet can be upgraded to (>= 2). */
+  maxPlanetLevel: number;
+  /** Minimum ships a move must send (>= 1). */
+  minShipsPerMove: bigint;
+  /** PointsBurning score that wins the game early via claim_points_victory (0 = disabled). */
+  pointsToWin: bigint;
+  /** Set by claim_points_victory; new queue_* instructions are rejected */
+  endedEarly: boolean;
 }
 
 /**
  * Default noise thresholds for testing/development.
  */
 export const DEFAULT_THRESHOLDS: NoiseThresholds = {
-  deadSpaceThreshold: 253,     // ~1% of coords have a body (was 50%)
-  planetThreshold: 128,
-  quasarThreshold: 192,
-  spacetimeRipThreshold: 224,
-  asteroidBeltThreshold: 255,
+  deadSpaceThreshold: 64768,   // ~1% of coords have a body (was 50%)
+  planetThreshold: 32768,
+  quasarThreshold: 49152,
+  spacetimeRipThreshold: 57344,
+  asteroidBeltThreshold: 65280,
   // Exponential rarity: size 1 ~50%, size 2 ~25%, size 3 ~12%, size 4 ~6%, size 5 ~4%, size 6 ~3%
-  sizeThreshold1: 128,
-  sizeThreshold2: 192,
-  sizeThreshold3: 224,
-  sizeThreshold4: 240,
-  sizeThreshold5: 250,
+  sizeThreshold1: 32768,
+  sizeThreshold2: 49152,
+  sizeThreshold3: 57344,
+  sizeThreshold4: 61440,
+  sizeThreshold5: 64000,
 };
 
 export const DEFAULT_HASH_ROUNDS = 1;
+
+export const DEFAULT_MAX_MOVES_PER_SLOT = 4;
+
+/** Default Quasar arrival penalty: half of arriving ships are lost. */
+export const DEFAULT_QUASAR_SHIP_PENALTY_BPS = 5000n;
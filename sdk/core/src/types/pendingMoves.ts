@@ -21,8 +21,10 @@ export interface PendingMovesMetadata {
   planetHash: Uint8Array; // [u8; 32]
   nextMoveId: bigint;
   moveCount: number; // u16
-  queuedCount: number; // u8
-  queuedLandingSlots: bigint[]; // [u64; 8]
+  capacity: number; // u16, entries the account is sized for
+  queuedCount: number; // u8, unused (always 0)
+  queuedLandingSlots: bigint[]; // [u64; 8], unused (always 0)
+  lastMoveQueuedSlot: bigint; // last move launched from this planet (cooldown)
   moves: PendingMoveEntry[];
 }
 
@@ -40,6 +42,9 @@ export interface PendingMoveAccount {
   payer: Uint8Array; // [u8; 32] (Pubkey)
   encNonce: bigint; // u128
   encCiphertexts: Uint8Array[]; // 4 x [u8; 32]
+  canonicalPlanetHash: Uint8Array; // [u8; 32] planet whose list holds the move (differs from planetHash once merged)
+  sourcePlanetHash: Uint8Array; // [u8; 32] planet the fleet was launched from
+  flushQueuedSlot: bigint; // slot its flush was queued (0 = none)
 }
 
 /** Number of encrypted fields in PendingMoveData. */
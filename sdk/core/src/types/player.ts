@@ -10,4 +10,28 @@ export interface Player {
   gameId: bigint;
   points: bigint;
   hasSpawned: boolean;
+  /** Slot of the most recent queue_process_move (rate limiter). */
+  lastMoveSlot: bigint;
+  /** Moves queued during lastMoveSlot. */
+  movesThisSlot: number;
+  /** Planet claimed at spawn; checked by check_spawn_lost. */
+  spawnPlanetHash: Uint8Array; // [u8; 32]
+}
+
+/**
+ * Cross-game player stats.
+ * Matches on-chain `PlayerProfile` account struct.
+ * PDA: ["profile", owner_pubkey.to_bytes()]
+ */
+export interface PlayerProfile {
+  owner: PublicKey;
+  /** Games joined via init_player with the profile attached. */
+  totalGames: number;
+  totalWins: number;
+  /** Player points banked by cleanup_player at the end of each game. */
+  lifetimePoints: bigint;
+  /** Planets taken by the player's landed moves (credited at flush). */
+  planetsCaptured: number;
+  /** Ships launched in valid moves. */
+  shipsSent: bigint;
 }
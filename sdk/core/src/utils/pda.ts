@@ -35,6 +35,47 @@ export function deriveGamePDA(
   );
 }
 
+/**
+ * Derive the program-wide ProgramState PDA.
+ * Seeds: ["program_state"]
+ */
+export function deriveProgramStatePDA(
+  programId: PublicKey = PROGRAM_ID
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("program_state")],
+    programId
+  );
+}
+
+/**
+ * Derive a game's Leaderboard PDA.
+ * Seeds: ["leaderboard", game_id.to_le_bytes()]
+ */
+export function deriveLeaderboardPDA(
+  gameId: bigint,
+  programId: PublicKey = PROGRAM_ID
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("leaderboard"), u64ToLeBytes(gameId)],
+    programId
+  );
+}
+
+/**
+ * Derive the SpectatorRegistry PDA.
+ * Seeds: ["spectators", game_id.to_le_bytes()]
+ */
+export function deriveSpectatorRegistryPDA(
+  gameId: bigint,
+  programId: PublicKey = PROGRAM_ID
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("spectators"), u64ToLeBytes(gameId)],
+    programId
+  );
+}
+
 /**
  * Derive the Player PDA.
  * Seeds: ["player", game_id.to_le_bytes(), player_pubkey.to_bytes()]
@@ -50,6 +91,50 @@ export function derivePlayerPDA(
   );
 }
 
+/**
+ * Derive a player's ComputationOffsetTracker PDA.
+ * Seeds: ["offset", game_id.to_le_bytes(), owner_pubkey.to_bytes()]
+ */
+export function deriveComputationOffsetTrackerPDA(
+  gameId: bigint,
+  owner: PublicKey,
+  programId: PublicKey = PROGRAM_ID
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("offset"), u64ToLeBytes(gameId), owner.toBuffer()],
+    programId
+  );
+}
+
+/**
+ * Derive a player's PlayerDeposit PDA.
+ * Seeds: ["deposit", game_id.to_le_bytes(), owner_pubkey.to_bytes()]
+ */
+export function derivePlayerDepositPDA(
+  gameId: bigint,
+  owner: PublicKey,
+  programId: PublicKey = PROGRAM_ID
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("deposit"), u64ToLeBytes(gameId), owner.toBuffer()],
+    programId
+  );
+}
+
+/**
+ * Derive the cross-game PlayerProfile PDA.
+ * Seeds: ["profile", owner_pubkey.to_bytes()]
+ */
+export function derivePlayerProfilePDA(
+  owner: PublicKey,
+  programId: PublicKey = PROGRAM_ID
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("profile"), owner.toBuffer()],
+    programId
+  );
+}
+
 /**
  * Derive the CelestialBody (planet) PDA.
  * Seeds: ["planet", game_id.to_le_bytes(), planet_hash(32 bytes)]
@@ -80,6 +165,36 @@ export function derivePendingMovesPDA(
   );
 }
 
+/**
+ * Derive a planet's CombatLog PDA.
+ * Seeds: ["combat_log", game_id.to_le_bytes(), planet_hash(32 bytes)]
+ */
+export function deriveCombatLogPDA(
+  gameId: bigint,
+  planetHash: Uint8Array,
+  programId: PublicKey = PROGRAM_ID
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("combat_log"), u64ToLeBytes(gameId), planetHash],
+    programId
+  );
+}
+
+/**
+ * Derive a planet's CoordinateProof PDA.
+ * Seeds: ["coordinate_proof", game_id.to_le_bytes(), planet_hash(32 bytes)]
+ */
+export function deriveCoordinateProofPDA(
+  gameId: bigint,
+  planetHash: Uint8Array,
+  programId: PublicKey = PROGRAM_ID
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("coordinate_proof"), u64ToLeBytes(gameId), planetHash],
+    programId
+  );
+}
+
 /**
  * Derive a PendingMoveAccount PDA.
  * Seeds: ["move", game_id.to_le_bytes(), planet_hash(32 bytes), move_id.to_le_bytes()]
@@ -11,6 +11,7 @@
 
 import { createSignal, createMemo, type Accessor } from "solid-js";
 import { ReactiveMap } from "@solid-primitives/map";
+import { PublicKey } from "@solana/web3.js";
 import type { EncryptedForestClient, DiscoveredPlanet } from "@encrypted-forest/core";
 import type { EncryptedCelestialBodyAccount } from "@encrypted-forest/core";
 import {
@@ -202,12 +203,16 @@ export function createPlanetsStore(
       if (p.stateEncNonce && p.stateEncCiphertexts) {
         try {
           const cachedEncrypted: EncryptedCelestialBodyAccount = {
+            version: 1,
             planetHash: new Uint8Array(p.hash),
             lastUpdatedSlot: 0n,
             lastFlushedSlot: 0n,
+            lastMinedSlot: 0n,
             stateEncPubkey: p.stateEncPubkey ? new Uint8Array(p.stateEncPubkey) : mxePublicKey,
             stateEncNonce: new Uint8Array(p.stateEncNonce),
             stateEncCiphertexts: deserializeCiphertexts(p.stateEncCiphertexts, 32),
+            hasSpawnCoords: false,
+            initializer: PublicKey.default, // not cached; refetched with the account
           };
           entry.encrypted = cachedEncrypted;
           entry.decrypted = decryptPlanetState(entry.discovery.hash, mxePublicKey, cachedEncrypted);
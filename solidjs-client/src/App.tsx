@@ -563,7 +563,9 @@ export default function App() {
         target,
         BigInt(shipsToSend()),
         0n,
-        game.gameSpeed
+        game.gameSpeed,
+        game.mapDiameter,
+        game.distanceMethod
       );
       console.log("Process move transaction sent successfully");
     } catch (e: any) {
@@ -9,6 +9,9 @@ import type { WalletStoreAPI } from "../wallet/store.js";
 import {
   DEFAULT_THRESHOLDS,
   DEFAULT_HASH_ROUNDS,
+  DEFAULT_MAX_MOVES_PER_SLOT,
+  DEFAULT_QUASAR_SHIP_PENALTY_BPS,
+  MAX_QUEUED_CALLBACKS,
 } from "@encrypted-forest/core";
 import type { CreateGameArgs, WinCondition } from "@encrypted-forest/core";
 import { getRecentGames, removeRecentGame, type RecentGame } from "../game/history.js";
@@ -166,13 +169,29 @@ export default function GameSetup(props: GameSetupProps) {
       gameId: gid,
       mapDiameter: diameter,
       gameSpeed: speed,
-      startSlot: 0n,
+      startSlot: 0n, // session picks the next slot
       endSlot: 1_000_000_000n,
       winCondition,
       whitelist: false,
       serverPubkey: null,
       noiseThresholds: DEFAULT_THRESHOLDS,
       hashRounds: rounds,
+      maxMovesPerSlot: DEFAULT_MAX_MOVES_PER_SLOT,
+      maxPendingMoves: MAX_QUEUED_CALLBACKS,
+      quasarShipPenaltyBps: DEFAULT_QUASAR_SHIP_PENALTY_BPS,
+      moveCooldownSlots: 0n,
+      maxPlayers: 0,
+      minSpawnSeparation: 0n,
+      cometDriftIntervalSlots: 0n,
+      hashAlgorithm: "blake3",
+      securityDepositLamports: 0n,
+      depositRecipient: null,
+      captureCooldownSlots: 0n,
+      distanceMethod: "chebyshevApprox",
+      validSpawnBodyTypes: 1,
+      maxPlanetLevel: 10,
+      minShipsPerMove: 1n,
+      pointsToWin: 0n,
     };
 
     try {
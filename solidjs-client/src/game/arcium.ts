@@ -7,7 +7,7 @@
  * - All PDA derivation fns from @arcium-hq/client are pure JS and work in browser
  */
 
-import { BN } from "@coral-xyz/anchor";
+import { BN, type Program } from "@coral-xyz/anchor";
 import { PublicKey, SystemProgram } from "@solana/web3.js";
 import type { Connection } from "@solana/web3.js";
 import {
@@ -24,7 +24,10 @@ import {
   x25519,
   deserializeLE,
 } from "@arcium-hq/client";
-import type { ArciumAccounts } from "@encrypted-forest/core";
+import {
+  fetchNextComputationOffset,
+  type ArciumAccounts,
+} from "@encrypted-forest/core";
 import { AnchorProvider } from "@coral-xyz/anchor";
 
 // ---------------------------------------------------------------------------
@@ -117,14 +120,17 @@ export function generateNonce(): { nonce: Uint8Array; nonceValue: bigint } {
   return { nonce, nonceValue };
 }
 
-/** Generate a random computation offset (8 bytes) as BN. */
-export function generateComputationOffset(): BN {
-  const bytes = browserRandomBytes(8);
-  // Convert to hex string for BN
-  const hex = Array.from(bytes)
-    .map((b) => b.toString(16).padStart(2, "0"))
-    .join("");
-  return new BN(hex, "hex");
+/**
+ * Computation offset the owner's next queue_* call will use, as BN.
+ * The program assigns it from the owner's ComputationOffsetTracker.
+ */
+export async function nextComputationOffset(
+  program: Program,
+  gameId: bigint,
+  owner: PublicKey
+): Promise<BN> {
+  const offset = await fetchNextComputationOffset(program, gameId, owner);
+  return new BN(offset.toString());
 }
 
 // ---------------------------------------------------------------------------
@@ -225,7 +231,7 @@ export function buildInitSpawnPlanetValues(
   return [
     BigInt.asUintN(64, x),
     BigInt.asUintN(64, y),
-    BigInt.asUintN(32, playerId),
+    BigInt.asUintN(64, playerId),
     BigInt.asUintN(32, sourcePlanetId),
   ];
 }
@@ -238,7 +244,8 @@ export function buildProcessMoveValues(
   sourceX: bigint,
   sourceY: bigint,
   targetX: bigint,
-  targetY: bigint
+  targetY: bigint,
+  allyPlayerId: bigint = 0n
 ): bigint[] {
   return [
     BigInt.asUintN(32, playerId),
@@ -249,6 +256,9 @@ export function buildProcessMoveValues(
     BigInt.asUintN(64, sourceY),
     BigInt.asUintN(64, targetX),
     BigInt.asUintN(64, targetY),
+    BigInt.asUintN(32, allyPlayerId),
+    BigInt.asUintN(32, playerId >> 32n),
+    BigInt.asUintN(32, allyPlayerId >> 32n),
   ];
 }
 
@@ -8,7 +8,7 @@
 import { createSignal, createMemo, type Accessor } from "solid-js";
 import { Connection, PublicKey, Keypair, Transaction, sendAndConfirmTransaction } from "@solana/web3.js";
 import { Program, AnchorProvider } from "@coral-xyz/anchor";
-import type { Game, NoiseThresholds, CreateGameArgs, ScannedCoordinate } from "@encrypted-forest/core";
+import type { DistanceMethod, Game, NoiseThresholds, CreateGameArgs, ScannedCoordinate } from "@encrypted-forest/core";
 import {
   DEFAULT_THRESHOLDS,
   DEFAULT_HASH_ROUNDS,
@@ -16,9 +16,11 @@ import {
   PROGRAM_ID,
   idlJson,
   buildCreateGameIx,
+  fetchUpcomingStartSlot,
   buildInitPlayerIx,
   buildQueueInitSpawnPlanetIx,
   buildQueueProcessMoveIx,
+  fetchGame,
   findSpawnPlanet,
   computePlanetHash,
   deriveCelestialBodyPDA,
@@ -26,9 +28,9 @@ import {
   derivePendingMoveAccountPDA,
   fetchPendingMovesMetadata,
   computeDistance,
+  observableDistanceBound,
   computeLandingSlot,
-  computeCurrentShips,
-  computeCurrentMetal,
+  playerIdFromPubkey,
 } from "@encrypted-forest/core";
 import type { PlanetEntry } from "@encrypted-forest/solidjs-sdk";
 import { saveRecentGame } from "./history.js";
@@ -36,7 +38,7 @@ import {
   setupEncryption,
   getArciumAccountAddresses,
   generateNonce,
-  generateComputationOffset,
+  nextComputationOffset,
   encryptAndPack,
   buildInitSpawnPlanetValues,
   buildProcessMoveValues,
@@ -149,10 +151,19 @@ async function queueSpawnPlanet(
     const cipher = new RescueCipher(sharedSecret);
 
     const { nonce, nonceValue } = generateNonce();
-    const values = buildInitSpawnPlanetValues(x, y, 0n, 0n);
+    const values = buildInitSpawnPlanetValues(
+      x,
+      y,
+      playerIdFromPubkey(keypair.publicKey),
+      0n
+    );
     const { packed } = encryptAndPack(cipher, values, nonce);
 
-    const computationOffset = generateComputationOffset();
+    const computationOffset = await nextComputationOffset(
+      program,
+      gameId,
+      keypair.publicKey
+    );
     const arciumAccts = getArciumAccountAddresses(
       program.programId,
       computationOffset,
@@ -162,17 +173,20 @@ async function queueSpawnPlanet(
     const observerKey = x25519.utils.randomSecretKey();
     const observerPubkey = x25519.getPublicKey(observerKey);
 
+    // Separation is checked against every earlier spawn the game recorded
+    const game = await fetchGame(program, gameId);
+
     await buildQueueInitSpawnPlanetIx(
       program,
       keypair.publicKey,
       {
         gameId,
-        computationOffset: BigInt(computationOffset.toString()),
         planetHash,
         ciphertexts: packed,
         pubkey: planetPubkey,
         nonce: nonceValue,
         observerPubkey,
+        spawnPlanetHashes: game.spawnPlanetHashes,
       },
       arciumAccts
     )
@@ -203,7 +217,9 @@ export async function queueProcessMove(
   target: PlanetEntry,
   shipsToSend: bigint,
   metalToSend: bigint,
-  gameSpeed: bigint
+  gameSpeed: bigint,
+  mapDiameter: bigint,
+  distanceMethod: DistanceMethod = "chebyshevApprox"
 ): Promise<void> {
   const sourceX = source.discovery.x;
   const sourceY = source.discovery.y;
@@ -226,39 +242,13 @@ export async function queueProcessMove(
   const currentSlot = BigInt(await connection.getSlot());
 
   // Compute distance and landing slot
-  const distance = computeDistance(sourceX, sourceY, targetX, targetY);
+  const distance = computeDistance(sourceX, sourceY, targetX, targetY, distanceMethod);
   const sourceState = source.decrypted;
   const launchVelocity = sourceState ? sourceState.static.launchVelocity : 1n;
   const landingSlot = computeLandingSlot(currentSlot, distance, launchVelocity, gameSpeed);
 
-  // Compute current ships/metal with lazy regen from on-chain snapshot
-  let currentShips: bigint;
-  let currentMetal: bigint;
-  if (sourceState) {
-    const lastSlot = 0n; // on-chain lastUpdatedSlot not directly exposed; use 0 as base
-    currentShips = computeCurrentShips(
-      sourceState.dynamic.shipCount,
-      sourceState.static.maxShipCapacity,
-      sourceState.static.shipGenSpeed,
-      lastSlot,
-      currentSlot,
-      gameSpeed
-    );
-    currentMetal = computeCurrentMetal(
-      sourceState.dynamic.metalCount,
-      sourceState.static.maxMetalCapacity,
-      sourceState.static.metalGenSpeed,
-      lastSlot,
-      currentSlot,
-      gameSpeed
-    );
-  } else {
-    currentShips = shipsToSend;
-    currentMetal = metalToSend;
-  }
-
-  // Get player ID and source planet ID from decrypted state
-  const playerId = sourceState ? sourceState.dynamic.ownerId : 0n;
+  // The circuit checks the player ID against the payer key
+  const playerId = playerIdFromPubkey(keypair.publicKey);
   const sourcePlanetId = 0n; // planet ID is the hash-based identifier
 
   // Build move values for MPC circuit
@@ -278,7 +268,11 @@ export async function queueProcessMove(
   const { nonce, nonceValue } = generateNonce();
   const { packed } = encryptAndPack(encCtx.cipher, moveValues, nonce);
 
-  const computationOffset = generateComputationOffset();
+  const computationOffset = await nextComputationOffset(
+    program,
+    gameId,
+    keypair.publicKey
+  );
   const arciumAccts = getArciumAccountAddresses(
     program.programId,
     computationOffset,
@@ -290,10 +284,8 @@ export async function queueProcessMove(
     keypair.publicKey,
     {
       gameId,
-      computationOffset: BigInt(computationOffset.toString()),
       landingSlot,
-      currentShips,
-      currentMetal,
+      maxObservableDistance: observableDistanceBound(distance, mapDiameter, distanceMethod),
       moveCts: packed,
       movePubkey: encCtx.publicKey,
       moveNonce: nonceValue,
@@ -447,8 +439,12 @@ export function createGameSession(): GameSessionAPI {
 
       const program = new Program(idlJson as any, provider);
 
-      // 1. Create game on-chain
-      await buildCreateGameIx(program, keypair.publicKey, args).rpc();
+      // 1. Create game on-chain (startSlot 0 = start at the next slot;
+      //    create_game rejects start slots in the past)
+      const startSlot = args.startSlot > 0n
+        ? args.startSlot
+        : await fetchUpcomingStartSlot(connection);
+      await buildCreateGameIx(program, keypair.publicKey, { ...args, startSlot }).rpc();
 
       // 2. Init player account for this wallet
       await buildInitPlayerIx(program, keypair.publicKey, args.gameId).rpc();
@@ -471,6 +467,10 @@ export function createGameSession(): GameSessionAPI {
       });
 
       // 4. Queue initSpawnPlanet (fire-and-forget, don't block game entry)
+      //    once the game has started
+      while (BigInt(await connection.getSlot()) < startSlot) {
+        await new Promise((r) => setTimeout(r, 400));
+      }
       queueSpawnPlanet(
         program, connection, keypair,
         args.gameId, spawn.x, spawn.y, args.hashRounds
@@ -483,13 +483,29 @@ export function createGameSession(): GameSessionAPI {
         gameId: args.gameId,
         mapDiameter: args.mapDiameter,
         gameSpeed: args.gameSpeed,
-        startSlot: args.startSlot,
+        startSlot,
         endSlot: args.endSlot,
         winCondition: args.winCondition,
         whitelist: args.whitelist,
         serverPubkey: args.serverPubkey,
         noiseThresholds: args.noiseThresholds,
         hashRounds: args.hashRounds,
+        maxMovesPerSlot: args.maxMovesPerSlot,
+        maxPendingMoves: args.maxPendingMoves,
+        quasarShipPenaltyBps: args.quasarShipPenaltyBps,
+        moveCooldownSlots: args.moveCooldownSlots,
+        maxPlayers: args.maxPlayers,
+        minSpawnSeparation: args.minSpawnSeparation,
+        cometDriftIntervalSlots: args.cometDriftIntervalSlots,
+        hashAlgorithm: args.hashAlgorithm,
+        securityDepositLamports: args.securityDepositLamports,
+        depositRecipient: args.depositRecipient ?? keypair.publicKey,
+        captureCooldownSlots: args.captureCooldownSlots,
+        distanceMethod: args.distanceMethod,
+        validSpawnBodyTypes: args.validSpawnBodyTypes,
+        maxPlanetLevel: args.maxPlanetLevel,
+        minShipsPerMove: args.minShipsPerMove,
+        pointsToWin: args.pointsToWin,
       });
       setWalletPubkeySignal(keypair.publicKey.toBase58());
 
@@ -74,38 +74,41 @@ function determineCelestialBody(
   hash: Uint8Array,
   t: NoiseThresholds
 ): CelestialBodyProperties | null {
-  const byte0 = hash[0];
-  const byte1 = hash[1];
-  const byte2 = hash[2];
+  // 16-bit noise: hash[i] is the high byte, hash[i + 6] the low byte
+  const noise0 = hash[0] * 256 + hash[6];
+  const noise1 = hash[1] * 256 + hash[7];
+  const noise2 = hash[2] * 256 + hash[8];
   const byte3 = hash[3];
   const byte4 = hash[4];
   const byte5 = hash[5];
 
-  if (byte0 < t.deadSpaceThreshold) return null;
+  if (noise0 < t.deadSpaceThreshold) return null;
 
   // Body type (threshold-based)
   let bodyType: number;
-  if (byte1 < t.planetThreshold) {
+  if (noise1 < t.planetThreshold) {
     bodyType = 0; // Planet
-  } else if (byte1 < t.quasarThreshold) {
+  } else if (noise1 < t.quasarThreshold) {
     bodyType = 1; // Quasar
-  } else if (byte1 < t.spacetimeRipThreshold) {
+  } else if (noise1 < t.spacetimeRipThreshold) {
     bodyType = 2; // SpacetimeRip
-  } else {
+  } else if (noise1 < t.asteroidBeltThreshold) {
     bodyType = 3; // AsteroidBelt
+  } else {
+    return null; // dead space
   }
 
   // Size (threshold-based)
   let size: number;
-  if (byte2 < t.sizeThreshold1) {
+  if (noise2 < t.sizeThreshold1) {
     size = 1;
-  } else if (byte2 < t.sizeThreshold2) {
+  } else if (noise2 < t.sizeThreshold2) {
     size = 2;
-  } else if (byte2 < t.sizeThreshold3) {
+  } else if (noise2 < t.sizeThreshold3) {
     size = 3;
-  } else if (byte2 < t.sizeThreshold4) {
+  } else if (noise2 < t.sizeThreshold4) {
     size = 4;
-  } else if (byte2 < t.sizeThreshold5) {
+  } else if (noise2 < t.sizeThreshold5) {
     size = 5;
   } else {
     size = 6;
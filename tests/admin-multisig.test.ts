@@ -0,0 +1,154 @@
+/**
+ * Admin multisig integration tests.
+ *
+ * Tests:
+ * 1. Only the admin can create a multisig; invalid thresholds are rejected
+ * 2. A 2-of-3 multisig pauses the game once the threshold is reached
+ * 3. Mismatched or under-approved actions are rejected
+ */
+
+import { describe, it, expect, beforeAll } from "vitest";
+import { Program, AnchorProvider, BN } from "@coral-xyz/anchor";
+import { Keypair, SystemProgram, PublicKey } from "@solana/web3.js";
+import type { EncryptedForest } from "../target/types/encrypted_forest";
+import {
+  getProviderAndProgram,
+  readKpJson,
+  airdrop,
+  createGame,
+  defaultGameConfig,
+  deriveGamePDA,
+  nextGameId,
+} from "./helpers";
+
+function deriveAdminMultisigPDA(
+  gameId: bigint,
+  programId: PublicKey
+): [PublicKey, number] {
+  const gameIdBuf = Buffer.alloc(8);
+  gameIdBuf.writeBigUInt64LE(gameId);
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("admin_multisig"), gameIdBuf],
+    programId
+  );
+}
+
+describe("Admin Multisig", () => {
+  let provider: AnchorProvider;
+  let program: Program<EncryptedForest>;
+  let admin: Keypair;
+  let signers: Keypair[];
+
+  beforeAll(async () => {
+    const setup = getProviderAndProgram();
+    provider = setup.provider;
+    program = setup.program;
+    admin = readKpJson(`${process.env.HOME}/.config/solana/id.json`);
+    signers = [Keypair.generate(), Keypair.generate(), Keypair.generate()];
+    for (const s of signers) {
+      await airdrop(provider, s.publicKey, 1);
+    }
+  });
+
+  async function createMultisig(
+    gameId: bigint,
+    threshold: number,
+    payer: Keypair = admin
+  ): Promise<PublicKey> {
+    const [gamePDA] = deriveGamePDA(gameId, program.programId);
+    const [multisigPDA] = deriveAdminMultisigPDA(gameId, program.programId);
+    await program.methods
+      .createAdminMultisig(
+        new BN(gameId.toString()),
+        signers.map((s) => s.publicKey),
+        threshold,
+        new BN(1000)
+      )
+      .accounts({
+        admin: payer.publicKey,
+        game: gamePDA,
+        adminMultisig: multisigPDA,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .signers([payer])
+      .rpc({ commitment: "confirmed" });
+    return multisigPDA;
+  }
+
+  async function propose(gameId: bigint, signer: Keypair, actionType: any) {
+    const [multisigPDA] = deriveAdminMultisigPDA(gameId, program.programId);
+    await program.methods
+      .proposeAdminAction(new BN(gameId.toString()), actionType)
+      .accounts({ signer: signer.publicKey, adminMultisig: multisigPDA } as any)
+      .signers([signer])
+      .rpc({ commitment: "confirmed" });
+  }
+
+  async function approve(gameId: bigint, signer: Keypair) {
+    const [multisigPDA] = deriveAdminMultisigPDA(gameId, program.programId);
+    await program.methods
+      .approveAdminAction(new BN(gameId.toString()))
+      .accounts({ signer: signer.publicKey, adminMultisig: multisigPDA } as any)
+      .signers([signer])
+      .rpc({ commitment: "confirmed" });
+  }
+
+  async function pauseViaMultisig(gameId: bigint, signer: Keypair, paused: boolean) {
+    const [gamePDA] = deriveGamePDA(gameId, program.programId);
+    const [multisigPDA] = deriveAdminMultisigPDA(gameId, program.programId);
+    await program.methods
+      .setGamePaused(new BN(gameId.toString()), paused)
+      .accounts({
+        admin: signer.publicKey,
+        game: gamePDA,
+        adminMultisig: multisigPDA,
+      } as any)
+      .signers([signer])
+      .rpc({ commitment: "confirmed" });
+  }
+
+  it("lets only the admin create a multisig with a valid threshold", async () => {
+    const gameId = nextGameId();
+    await createGame(program, admin, defaultGameConfig(gameId));
+
+    await expect(createMultisig(gameId, 2, signers[0])).rejects.toThrow();
+    await expect(createMultisig(gameId, 4)).rejects.toThrow();
+
+    const multisigPDA = await createMultisig(gameId, 2);
+    const multisig = await program.account.adminMultisig.fetch(multisigPDA);
+    expect(multisig.threshold).toBe(2);
+    expect(multisig.signers.length).toBe(3);
+    expect(multisig.pendingAction).toBeNull();
+  });
+
+  it("pauses the game once two of three signers approve", async () => {
+    const gameId = nextGameId();
+    const gamePDA = await createGame(program, admin, defaultGameConfig(gameId));
+    const multisigPDA = await createMultisig(gameId, 2);
+
+    await propose(gameId, signers[0], { setPaused: { paused: true } });
+    await expect(pauseViaMultisig(gameId, signers[0], true)).rejects.toThrow();
+
+    await approve(gameId, signers[1]);
+    await pauseViaMultisig(gameId, signers[2], true);
+
+    expect((await program.account.game.fetch(gamePDA)).paused).toBe(true);
+    // The approved proposal is consumed and cannot be replayed
+    expect((await program.account.adminMultisig.fetch(multisigPDA)).pendingAction).toBeNull();
+    await expect(pauseViaMultisig(gameId, signers[0], true)).rejects.toThrow();
+  });
+
+  it("rejects an instruction that does not match the approved action", async () => {
+    const gameId = nextGameId();
+    const gamePDA = await createGame(program, admin, defaultGameConfig(gameId));
+    await createMultisig(gameId, 2);
+
+    await propose(gameId, signers[0], { setPaused: { paused: true } });
+    await approve(gameId, signers[1]);
+    await expect(pauseViaMultisig(gameId, signers[0], false)).rejects.toThrow();
+    // Signers cannot approve twice
+    await expect(approve(gameId, signers[1])).rejects.toThrow();
+
+    expect((await program.account.game.fetch(gamePDA)).paused).toBe(false);
+  });
+});
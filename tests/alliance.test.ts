@@ -0,0 +1,145 @@
+/**
+ * Alliance integration tests.
+ *
+ * Tests:
+ * 1. Create alliance (founder is first member)
+ * 2. Founder adds and removes members
+ * 3. Reject adding members beyond member_cap
+ * 4. Reject member changes by non-founder
+ */
+
+import { describe, it, expect, beforeAll } from "vitest";
+import { Program, AnchorProvider, BN } from "@coral-xyz/anchor";
+import { Keypair, SystemProgram, PublicKey } from "@solana/web3.js";
+import type { EncryptedForest } from "../target/types/encrypted_forest";
+import {
+  getProviderAndProgram,
+  readKpJson,
+  airdrop,
+  createGame,
+  defaultGameConfig,
+  deriveGamePDA,
+  nextGameId,
+} from "./helpers";
+
+function deriveAlliancePDA(
+  gameId: bigint,
+  allianceId: bigint,
+  programId: PublicKey
+): [PublicKey, number] {
+  const gameIdBuf = Buffer.alloc(8);
+  gameIdBuf.writeBigUInt64LE(gameId);
+  const allianceIdBuf = Buffer.alloc(8);
+  allianceIdBuf.writeBigUInt64LE(allianceId);
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("alliance"), gameIdBuf, allianceIdBuf],
+    programId
+  );
+}
+
+describe("Alliances", () => {
+  let provider: AnchorProvider;
+  let program: Program<EncryptedForest>;
+  let admin: Keypair;
+
+  beforeAll(async () => {
+    const setup = getProviderAndProgram();
+    provider = setup.provider;
+    program = setup.program;
+    admin = readKpJson(`${process.env.HOME}/.config/solana/id.json`);
+  });
+
+  async function createAlliance(
+    gameId: bigint,
+    allianceId: bigint,
+    memberCap: number
+  ): Promise<PublicKey> {
+    const [gamePDA] = deriveGamePDA(gameId, program.programId);
+    const [alliancePDA] = deriveAlliancePDA(gameId, allianceId, program.programId);
+    await program.methods
+      .createAlliance(
+        new BN(gameId.toString()),
+        new BN(allianceId.toString()),
+        memberCap
+      )
+      .accounts({
+        founder: admin.publicKey,
+        game: gamePDA,
+        alliance: alliancePDA,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .signers([admin])
+      .rpc({ commitment: "confirmed" });
+    return alliancePDA;
+  }
+
+  it("creates an alliance with the founder as first member", async () => {
+    const gameId = nextGameId();
+    await createGame(program, admin, defaultGameConfig(gameId));
+
+    const alliancePDA = await createAlliance(gameId, 1n, 3);
+    const alliance = await program.account.alliance.fetch(alliancePDA);
+
+    expect(alliance.founder.toString()).toBe(admin.publicKey.toString());
+    expect(alliance.memberCap).toBe(3);
+    expect(alliance.members.map((m: PublicKey) => m.toString())).toEqual([
+      admin.publicKey.toString(),
+    ]);
+  });
+
+  it("lets the founder add and remove members", async () => {
+    const gameId = nextGameId();
+    await createGame(program, admin, defaultGameConfig(gameId));
+    const alliancePDA = await createAlliance(gameId, 2n, 3);
+    const ally = Keypair.generate();
+
+    await program.methods
+      .addAllianceMember(new BN(gameId.toString()), new BN(2), ally.publicKey)
+      .accounts({ founder: admin.publicKey, alliance: alliancePDA } as any)
+      .signers([admin])
+      .rpc({ commitment: "confirmed" });
+
+    let alliance = await program.account.alliance.fetch(alliancePDA);
+    expect(alliance.members.length).toBe(2);
+
+    await program.methods
+      .removeAllianceMember(new BN(gameId.toString()), new BN(2), ally.publicKey)
+      .accounts({ founder: admin.publicKey, alliance: alliancePDA } as any)
+      .signers([admin])
+      .rpc({ commitment: "confirmed" });
+
+    alliance = await program.account.alliance.fetch(alliancePDA);
+    expect(alliance.members.length).toBe(1);
+  });
+
+  it("rejects adding members beyond member_cap", async () => {
+    const gameId = nextGameId();
+    await createGame(program, admin, defaultGameConfig(gameId));
+    const alliancePDA = await createAlliance(gameId, 3n, 1);
+
+    await expect(
+      program.methods
+        .addAllianceMember(new BN(gameId.toString()), new BN(3), Keypair.generate().publicKey)
+        .accounts({ founder: admin.publicKey, alliance: alliancePDA } as any)
+        .signers([admin])
+        .rpc({ commitment: "confirmed" })
+    ).rejects.toThrow();
+  });
+
+  it("rejects member changes by a non-founder", async () => {
+    const gameId = nextGameId();
+    await createGame(program, admin, defaultGameConfig(gameId));
+    const alliancePDA = await createAlliance(gameId, 4n, 3);
+
+    const outsider = Keypair.generate();
+    await airdrop(provider, outsider.publicKey, 1);
+
+    await expect(
+      program.methods
+        .addAllianceMember(new BN(gameId.toString()), new BN(4), outsider.publicKey)
+        .accounts({ founder: outsider.publicKey, alliance: alliancePDA } as any)
+        .signers([outsider])
+        .rpc({ commitment: "confirmed" })
+    ).rejects.toThrow();
+  });
+});
@@ -2,12 +2,14 @@
  * Arcium MPC computation integration tests.
  *
  * Tests the encrypted computation flow:
- * 1. Initialize all 5 computation definitions
+ * 1. Initialize all 14 computation definitions
  * 2. queue_init_planet -> awaitComputationFinalization -> verify encrypted state
  * 3. queue_init_spawn_planet -> verify player.has_spawned set + encrypted state
  * 4. queue_process_move -> verify source state updated + pending move added
  * 5. queue_flush_planet -> verify state updated + move removed
  * 6. queue_upgrade_planet -> verify encrypted state updated
+ * 7. queue_surrender_planet -> verify encrypted state re-written
+ * 8. queue_transfer_owner -> verify alliance gate + encrypted state re-written
  *
  * REQUIRES: Surfpool + Arcium ARX nodes running
  */
@@ -31,6 +33,8 @@ import {
   queueProcessMove,
   queueFlushPlanet,
   queueUpgradePlanet,
+  queueSurrenderPlanet,
+  queueTransferOwner,
   findSpawnPlanet,
   findPlanetOfType,
   computePlanetHash,
@@ -39,8 +43,11 @@ import {
   derivePendingMoveAccountPDA,
   derivePlayerPDA,
   buildProcessMoveValues,
-  buildFlushPlanetValues,
   buildUpgradePlanetValues,
+  buildSurrenderPlanetValues,
+  buildTransferOwnerValues,
+  playerIdFromPubkey,
+  deriveGamePDA,
   nextGameId,
   awaitComputationFinalization,
   getArciumEnv,
@@ -53,6 +60,21 @@ import {
   PROGRAM_ID,
 } from "./helpers";
 
+function deriveAlliancePDA(
+  gameId: bigint,
+  allianceId: bigint,
+  programId: PublicKey
+): [PublicKey, number] {
+  const gameIdBuf = Buffer.alloc(8);
+  gameIdBuf.writeBigUInt64LE(gameId);
+  const allianceIdBuf = Buffer.alloc(8);
+  allianceIdBuf.writeBigUInt64LE(allianceId);
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("alliance"), gameIdBuf, allianceIdBuf],
+    programId
+  );
+}
+
 // ---------------------------------------------------------------------------
 // Computation Definition Initialization
 // ---------------------------------------------------------------------------
@@ -69,7 +91,7 @@ describe("Arcium Computation Definitions", () => {
     admin = readKpJson(`${process.env.HOME}/.config/solana/id.json`);
   });
 
-  it("initializes all 5 computation definitions", async () => {
+  it("initializes all 13 computation definitions", async () => {
     // This should only be called once after deployment.
     // If already initialized, individual init calls will log and skip.
     try {
@@ -89,6 +111,15 @@ describe("Arcium Computation Definitions", () => {
       "process_move",
       "flush_planet",
       "upgrade_planet",
+      "surrender_planet",
+      "transfer_resources",
+      "spy_move",
+      "comet_collect",
+      "mine_asteroid",
+      "check_ownership",
+      "wormhole_jump",
+      "comet_drift",
+      "transfer_owner",
     ];
 
     for (const name of compDefNames) {
@@ -168,7 +199,7 @@ describe("Arcium Init Planet", () => {
     expect(bodyAccount.planetHash).toEqual(Array.from(coord.hash));
     expect(bodyAccount.stateEncPubkey.length).toBe(32);
     expect(bodyAccount.stateEncNonce.length).toBe(16);
-    expect(bodyAccount.stateEncCiphertexts.length).toBe(3);
+    expect(bodyAccount.stateEncCiphertexts.length).toBe(4);
 
     // Each ciphertext should be 32 bytes
     for (const ct of bodyAccount.stateEncCiphertexts) {
@@ -201,7 +232,7 @@ describe("Arcium Init Planet", () => {
       queueInitPlanet(
         program, admin, gameId, coord.x, coord.y, encCtx
       )
-    ).rejects.toThrow();
+    ).rejects.toThrow(/PlanetAlreadyInitialized/);
   });
 });
 
@@ -251,7 +282,7 @@ describe("Arcium Init Spawn Planet", () => {
       gameId,
       spawn.x,
       spawn.y,
-      0n,
+      playerIdFromPubkey(admin.publicKey),
       0n,
       encCtx
     );
@@ -271,7 +302,7 @@ describe("Arcium Init Spawn Planet", () => {
     // Verify encrypted celestial body was created
     const bodyAccount = await program.account.encryptedCelestialBody.fetch(planetPDA);
     expect(bodyAccount.planetHash).toEqual(Array.from(spawn.hash));
-    expect(bodyAccount.stateEncCiphertexts.length).toBe(3);
+    expect(bodyAccount.stateEncCiphertexts.length).toBe(4);
   });
 
   it("rejects spawn when player already spawned", async () => {
@@ -288,7 +319,7 @@ describe("Arcium Init Spawn Planet", () => {
     // First spawn
     const spawn1 = findSpawnPlanet(gameId, DEFAULT_THRESHOLDS);
     const { computationOffset: co1 } = await queueInitSpawnPlanet(
-      program, admin, gameId, spawn1.x, spawn1.y, 0n, 0n, encCtx
+      program, admin, gameId, spawn1.x, spawn1.y, playerIdFromPubkey(admin.publicKey), 0n, encCtx
     );
     await awaitComputationFinalization(provider, co1, program.programId, "confirmed");
 
@@ -299,7 +330,7 @@ describe("Arcium Init Spawn Planet", () => {
     if (spawn2.x !== spawn1.x || spawn2.y !== spawn1.y) {
       await expect(
         queueInitSpawnPlanet(
-          program, admin, gameId, spawn2.x, spawn2.y, 0n, 0n, encCtx
+          program, admin, gameId, spawn2.x, spawn2.y, playerIdFromPubkey(admin.publicKey), 0n, encCtx
         )
       ).rejects.toThrow();
     }
@@ -347,7 +378,7 @@ describe("Arcium Process Move", () => {
     const source = findSpawnPlanet(gameId, DEFAULT_THRESHOLDS);
     const sourceHash = computePlanetHash(source.x, source.y, gameId);
     const { computationOffset: spawnCO, planetPDA: sourcePDA } = await queueInitSpawnPlanet(
-      program, admin, gameId, source.x, source.y, 0n, 0n, encCtx
+      program, admin, gameId, source.x, source.y, playerIdFromPubkey(admin.publicKey), 0n, encCtx
     );
     await awaitComputationFinalization(provider, spawnCO, program.programId, "confirmed");
 
@@ -366,8 +397,8 @@ describe("Arcium Process Move", () => {
     const sourceBody = await program.account.encryptedCelestialBody.fetch(sourcePDA);
     const currentSlot = BigInt(await provider.connection.getSlot("confirmed"));
 
-    // Use placeholder playerId (1n) and sourcePlanetId (0n)
-    const playerId = 1n;
+    // The spawn's playerId is derived from the payer key
+    const playerId = playerIdFromPubkey(admin.publicKey);
     const sourcePlanetId = 0n;
 
     const moveValues = buildProcessMoveValues(
@@ -393,8 +424,6 @@ describe("Arcium Process Move", () => {
       sourcePendingPDA,
       targetPendingPDA,
       landingSlot,
-      10n,   // currentShips
-      0n,    // currentMetal
       moveValues,
       encCtx
     );
@@ -466,7 +495,7 @@ describe("Arcium Flush Planet", () => {
     const source = findSpawnPlanet(gameId, DEFAULT_THRESHOLDS);
     const sourceHash = computePlanetHash(source.x, source.y, gameId);
     const { computationOffset: spawnCO, planetPDA: sourcePDA } = await queueInitSpawnPlanet(
-      program, admin, gameId, source.x, source.y, 0n, 0n, encCtx
+      program, admin, gameId, source.x, source.y, playerIdFromPubkey(admin.publicKey), 0n, encCtx
     );
     await awaitComputationFinalization(provider, spawnCO, program.programId, "confirmed");
 
@@ -484,7 +513,7 @@ describe("Arcium Flush Planet", () => {
     // Send a move
     const sourceBody = await program.account.encryptedCelestialBody.fetch(sourcePDA);
     const currentSlot = BigInt(await provider.connection.getSlot("confirmed"));
-    const playerId = 1n;
+    const playerId = playerIdFromPubkey(admin.publicKey);
     const sourcePlanetId = 0n;
     const moveValues = buildProcessMoveValues(
       playerId, sourcePlanetId, 5n, 0n,
@@ -506,22 +535,14 @@ describe("Arcium Flush Planet", () => {
     const targetPending = await program.account.pendingMovesMetadata.fetch(targetPendingPDA);
     expect(targetPending.moves.length).toBe(1);
 
-    const flushSlot = BigInt(await provider.connection.getSlot("confirmed"));
     const flushCount = 1;
-    const flushValues = buildFlushPlanetValues(
-      flushSlot,
-      1000n,
-      BigInt(targetBody.lastUpdatedSlot.toString()),
-      BigInt(flushCount),
-    );
-
     // Derive PendingMoveAccount PDAs for the moves to flush
     const moveId = targetPending.moves[0].moveId;
     const [moveAccountPDA] = derivePendingMoveAccountPDA(gameId, targetHash, BigInt(moveId.toString()), program.programId);
 
     const { computationOffset: flushCO } = await queueFlushPlanet(
       program, admin, targetPlanetPDA, targetPendingPDA,
-      flushCount, flushValues, [moveAccountPDA], encCtx
+      flushCount, [moveAccountPDA], encCtx
     );
 
     const flushSig = await awaitComputationFinalization(
@@ -579,23 +600,19 @@ describe("Arcium Upgrade Planet", () => {
     // Spawn on a planet to own it
     const spawn = findSpawnPlanet(gameId, DEFAULT_THRESHOLDS);
     const { computationOffset: spawnCO, planetPDA } = await queueInitSpawnPlanet(
-      program, admin, gameId, spawn.x, spawn.y, 0n, 0n, encCtx
+      program, admin, gameId, spawn.x, spawn.y, playerIdFromPubkey(admin.publicKey), 0n, encCtx
     );
     await awaitComputationFinalization(provider, spawnCO, program.programId, "confirmed");
 
     // Read the planet state
     const bodyBefore = await program.account.encryptedCelestialBody.fetch(planetPDA);
-    const currentSlot = BigInt(await provider.connection.getSlot("confirmed"));
 
-    const playerId = 1n;
+    const playerId = playerIdFromPubkey(admin.publicKey);
     const metalUpgradeCost = 100n; // base upgrade cost for level 0
 
     const upgradeValues = buildUpgradePlanetValues(
       playerId,
       UpgradeFocus.Range,
-      currentSlot,
-      1000n,
-      BigInt(bodyBefore.lastUpdatedSlot.toString()),
       metalUpgradeCost
     );
 
@@ -625,3 +642,187 @@ describe("Arcium Upgrade Planet", () => {
     expect(beforeAll).not.toBe(afterAll);
   });
 });
+
+// ---------------------------------------------------------------------------
+// Surrender Planet (MPC)
+// ---------------------------------------------------------------------------
+
+describe("Arcium Surrender Planet", () => {
+  let provider: AnchorProvider;
+  let program: Program<EncryptedForest>;
+  let admin: Keypair;
+  let encCtx: EncryptionContext;
+  let arciumAvailable = false;
+
+  beforeAll(async () => {
+    const setup = getProviderAndProgram();
+    provider = setup.provider;
+    program = setup.program;
+    admin = readKpJson(`${process.env.HOME}/.config/solana/id.json`);
+
+    try {
+      getArciumEnv();
+      encCtx = await setupEncryption(provider, program.programId);
+      arciumAvailable = true;
+    } catch {
+      console.log("Arcium environment not available");
+    }
+  });
+
+  it("queues surrender_planet and re-encrypts planet state", async () => {
+    if (!arciumAvailable) {
+      console.log("Skipping: Arcium not available");
+      return;
+    }
+
+    const gameId = nextGameId();
+    const config = defaultGameConfig(gameId);
+    await createGame(program, admin, config);
+    await initPlayer(program, admin, gameId);
+
+    // Spawn on a planet to own it
+    const spawn = findSpawnPlanet(gameId, DEFAULT_THRESHOLDS);
+    const spawnHash = computePlanetHash(spawn.x, spawn.y, gameId);
+    const { computationOffset: spawnCO, planetPDA } = await queueInitSpawnPlanet(
+      program, admin, gameId, spawn.x, spawn.y, playerIdFromPubkey(admin.publicKey), 0n, encCtx
+    );
+    await awaitComputationFinalization(provider, spawnCO, program.programId, "confirmed");
+    const [pendingPDA] = derivePendingMovesPDA(gameId, spawnHash, program.programId);
+
+    const bodyBefore = await program.account.encryptedCelestialBody.fetch(planetPDA);
+
+    const { computationOffset: surrenderCO } = await queueSurrenderPlanet(
+      program, admin, gameId, planetPDA, pendingPDA,
+      buildSurrenderPlanetValues(playerIdFromPubkey(admin.publicKey)), encCtx
+    );
+
+    const surrenderSig = await awaitComputationFinalization(
+      provider, surrenderCO, program.programId, "confirmed"
+    );
+    console.log("Surrender planet finalized:", surrenderSig);
+
+    const bodyAfter = await program.account.encryptedCelestialBody.fetch(planetPDA);
+    expect(Number(bodyAfter.lastUpdatedSlot)).toBeGreaterThanOrEqual(
+      Number(bodyBefore.lastUpdatedSlot)
+    );
+    const before = Buffer.from(bodyBefore.stateEncNonce as any).toString("hex");
+    const after = Buffer.from(bodyAfter.stateEncNonce as any).toString("hex");
+    expect(before).not.toBe(after);
+  });
+});
+
+// ---------------------------------------------------------------------------
+// Transfer Owner (MPC)
+// ---------------------------------------------------------------------------
+
+describe("Arcium Transfer Owner", () => {
+  let provider: AnchorProvider;
+  let program: Program<EncryptedForest>;
+  let admin: Keypair;
+  let encCtx: EncryptionContext;
+  let arciumAvailable = false;
+
+  beforeAll(async () => {
+    const setup = getProviderAndProgram();
+    provider = setup.provider;
+    program = setup.program;
+    admin = readKpJson(`${process.env.HOME}/.config/solana/id.json`);
+
+    try {
+      getArciumEnv();
+      encCtx = await setupEncryption(provider, program.programId);
+      arciumAvailable = true;
+    } catch {
+      console.log("Arcium environment not available");
+    }
+  });
+
+  async function createAllianceWith(gameId: bigint, member?: PublicKey): Promise<PublicKey> {
+    const [gamePDA] = deriveGamePDA(gameId, program.programId);
+    const [alliancePDA] = deriveAlliancePDA(gameId, 1n, program.programId);
+    await program.methods
+      .createAlliance(new BN(gameId.toString()), new BN(1), 2)
+      .accounts({
+        founder: admin.publicKey,
+        game: gamePDA,
+        alliance: alliancePDA,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .signers([admin])
+      .rpc({ commitment: "confirmed" });
+    if (member) {
+      await program.methods
+        .addAllianceMember(new BN(gameId.toString()), new BN(1), member)
+        .accounts({ founder: admin.publicKey, alliance: alliancePDA } as any)
+        .signers([admin])
+        .rpc({ commitment: "confirmed" });
+    }
+    return alliancePDA;
+  }
+
+  it("hands a planet to an unspawned ally", async () => {
+    if (!arciumAvailable) {
+      console.log("Skipping: Arcium not available");
+      return;
+    }
+
+    const gameId = nextGameId();
+    await createGame(program, admin, defaultGameConfig(gameId));
+    await initPlayer(program, admin, gameId);
+    const ally = Keypair.generate();
+    await airdrop(provider, ally.publicKey, 2);
+    await initPlayer(program, ally, gameId);
+    const alliancePDA = await createAllianceWith(gameId, ally.publicKey);
+
+    const spawn = findSpawnPlanet(gameId, DEFAULT_THRESHOLDS);
+    const spawnHash = computePlanetHash(spawn.x, spawn.y, gameId);
+    const { computationOffset: spawnCO, planetPDA } = await queueInitSpawnPlanet(
+      program, admin, gameId, spawn.x, spawn.y, playerIdFromPubkey(admin.publicKey), 0n, encCtx
+    );
+    await awaitComputationFinalization(provider, spawnCO, program.programId, "confirmed");
+    const [pendingPDA] = derivePendingMovesPDA(gameId, spawnHash, program.programId);
+
+    const bodyBefore = await program.account.encryptedCelestialBody.fetch(planetPDA);
+
+    const { computationOffset: transferCO } = await queueTransferOwner(
+      program, admin, gameId, ally.publicKey, alliancePDA, planetPDA, pendingPDA,
+      buildTransferOwnerValues(playerIdFromPubkey(admin.publicKey), playerIdFromPubkey(ally.publicKey)), encCtx
+    );
+    await awaitComputationFinalization(provider, transferCO, program.programId, "confirmed");
+
+    const bodyAfter = await program.account.encryptedCelestialBody.fetch(planetPDA);
+    const before = Buffer.from(bodyBefore.stateEncNonce as any).toString("hex");
+    const after = Buffer.from(bodyAfter.stateEncNonce as any).toString("hex");
+    expect(before).not.toBe(after);
+  });
+
+  it("rejects a transfer to a player outside the alliance", async () => {
+    if (!arciumAvailable) {
+      console.log("Skipping: Arcium not available");
+      return;
+    }
+
+    const gameId = nextGameId();
+    await createGame(program, admin, defaultGameConfig(gameId));
+    await initPlayer(program, admin, gameId);
+    const outsider = Keypair.generate();
+    await airdrop(provider, outsider.publicKey, 2);
+    await initPlayer(program, outsider, gameId);
+    const alliancePDA = await createAllianceWith(gameId);
+
+    const spawn = findSpawnPlanet(gameId, DEFAULT_THRESHOLDS);
+    const spawnHash = computePlanetHash(spawn.x, spawn.y, gameId);
+    const { computationOffset: spawnCO, planetPDA } = await queueInitSpawnPlanet(
+      program, admin, gameId, spawn.x, spawn.y, playerIdFromPubkey(admin.publicKey), 0n, encCtx
+    );
+    await awaitComputationFinalization(provider, spawnCO, program.programId, "confirmed");
+    const [pendingPDA] = derivePendingMovesPDA(gameId, spawnHash, program.programId);
+
+    await expect(
+      queueTransferOwner(
+        program, admin, gameId, outsider.publicKey, alliancePDA, planetPDA, pendingPDA,
+        buildTransferOwnerValues(playerIdFromPubkey(admin.publicKey), playerIdFromPubkey(outsider.publicKey)), encCtx
+      )
+    ).rejects.toThrow();
+  });
+});
@@ -6,9 +6,13 @@
  * 2. Verify broadcast event contains (x, y, game_id, planet_hash, broadcaster)
  * 3. Reject broadcast with wrong hash
  * 4. Reject broadcast with mismatched coordinates
- * 5. Anyone can broadcast (permissionless)
+ * 5. Registered spectators can broadcast; unregistered wallets cannot
+ * 6. broadcast_bulk accepts valid entries and rejects a bad one or an oversized batch
+ * 7. Reject broadcast for a planet that was never initialized
+ * 8. create_coordinate_proof stores revealed coordinates and rejects a bad hash
+ * 9. claim_race_to_center_victory is rejected outside RaceToCenter games
  *
- * REQUIRES: Surfpool running
+ * REQUIRES: Surfpool running (Arcium for the cases that initialize a planet first)
  */
 
 import { describe, it, expect, beforeAll } from "vitest";
@@ -21,32 +25,88 @@ import {
   readKpJson,
   airdrop,
   createGame,
+  initPlayer,
   defaultGameConfig,
   deriveGamePDA,
+  deriveCelestialBodyPDA,
   findSpawnPlanet,
+  findDeadSpace,
   nextGameId,
+  setupEncryption,
+  queueInitPlanet,
+  getArciumEnv,
+  getArciumAccountAddresses,
+  nextComputationOffset,
+  deriveComputationOffsetTrackerPDA,
+  EncryptionContext,
   DEFAULT_THRESHOLDS,
 } from "./helpers";
 
+function deriveCoordinateProofPDA(
+  gameId: bigint,
+  planetHash: Uint8Array,
+  programId: PublicKey
+): [PublicKey, number] {
+  const gameIdBuf = Buffer.alloc(8);
+  gameIdBuf.writeBigUInt64LE(gameId);
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("coordinate_proof"), gameIdBuf, Buffer.from(planetHash)],
+    programId
+  );
+}
+
+function deriveSpectatorRegistryPDA(
+  gameId: bigint,
+  programId: PublicKey
+): [PublicKey, number] {
+  const gameIdBuf = Buffer.alloc(8);
+  gameIdBuf.writeBigUInt64LE(gameId);
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("spectators"), gameIdBuf],
+    programId
+  );
+}
+
 describe("Broadcast", () => {
   let provider: AnchorProvider;
   let program: Program<EncryptedForest>;
   let admin: Keypair;
+  let encCtx: EncryptionContext;
+  let arciumAvailable = false;
 
   beforeAll(async () => {
     const setup = getProviderAndProgram();
     provider = setup.provider;
     program = setup.program;
     admin = readKpJson(`${process.env.HOME}/.config/solana/id.json`);
+
+    try {
+      getArciumEnv();
+      encCtx = await setupEncryption(provider, program.programId);
+      arciumAvailable = true;
+    } catch {
+      console.log("Arcium environment not available");
+    }
   });
 
   it("broadcasts planet coordinates successfully", async () => {
+    if (!arciumAvailable) {
+      console.log("Skipping: Arcium not available");
+      return;
+    }
+
     const gameId = nextGameId();
     const config = defaultGameConfig(gameId);
     await createGame(program, admin, config);
 
     const spawn = findSpawnPlanet(gameId, DEFAULT_THRESHOLDS);
     const [gamePDA] = deriveGamePDA(gameId, program.programId);
+    // The planet account must exist before it can be broadcast
+    const { planetPDA } = await queueInitPlanet(
+      program, admin, gameId, spawn.x, spawn.y, encCtx
+    );
+    // Only players and registered spectators may broadcast
+    const playerPDA = await initPlayer(program, admin, gameId);
 
     // Set up event listener
     let broadcastEvent: any = null;
@@ -72,7 +132,10 @@ describe("Broadcast", () => {
       .accounts({
         broadcaster: admin.publicKey,
         game: gamePDA,
-      })
+        celestialBody: planetPDA,
+        player: playerPDA,
+        spectatorRegistry: null,
+      } as any)
       .signers([admin])
       .rpc({ commitment: "confirmed" });
 
@@ -85,6 +148,7 @@ describe("Broadcast", () => {
       expect(broadcastEvent.broadcaster.toString()).toBe(
         admin.publicKey.toString()
       );
+      expect(Number(broadcastEvent.lastUpdatedSlot)).toBeGreaterThan(0);
     }
   });
 
@@ -98,6 +162,7 @@ describe("Broadcast", () => {
 
     const wrongHash = new Uint8Array(32);
     wrongHash.fill(0);
+    const [wrongPlanetPDA] = deriveCelestialBodyPDA(gameId, wrongHash, program.programId);
 
     await expect(
       program.methods
@@ -110,6 +175,7 @@ describe("Broadcast", () => {
         .accounts({
           broadcaster: admin.publicKey,
           game: gamePDA,
+          celestialBody: wrongPlanetPDA,
         })
         .signers([admin])
         .rpc({ commitment: "confirmed" })
@@ -124,6 +190,8 @@ describe("Broadcast", () => {
     const spawn = findSpawnPlanet(gameId, DEFAULT_THRESHOLDS);
     const [gamePDA] = deriveGamePDA(gameId, program.programId);
 
+    const [planetPDA] = deriveCelestialBodyPDA(gameId, spawn.hash, program.programId);
+
     // Correct hash but wrong coordinates
     await expect(
       program.methods
@@ -136,38 +204,320 @@ describe("Broadcast", () => {
         .accounts({
           broadcaster: admin.publicKey,
           game: gamePDA,
+          celestialBody: planetPDA,
         })
         .signers([admin])
         .rpc({ commitment: "confirmed" })
     ).rejects.toThrow();
   });
 
-  it("allows anyone to broadcast (permissionless)", async () => {
+  it("lets registered spectators broadcast and rejects unregistered wallets", async () => {
+    if (!arciumAvailable) {
+      console.log("Skipping: Arcium not available");
+      return;
+    }
+
     const gameId = nextGameId();
     const config = defaultGameConfig(gameId);
     await createGame(program, admin, config);
 
-    const randomUser = Keypair.generate();
-    await airdrop(provider, randomUser.publicKey, 1);
+    const spectator = Keypair.generate();
+    const outsider = Keypair.generate();
+    await airdrop(provider, spectator.publicKey, 1);
+    await airdrop(provider, outsider.publicKey, 1);
 
     const spawn = findSpawnPlanet(gameId, DEFAULT_THRESHOLDS);
     const [gamePDA] = deriveGamePDA(gameId, program.programId);
+    const [registryPDA] = deriveSpectatorRegistryPDA(gameId, program.programId);
+    const { planetPDA } = await queueInitPlanet(
+      program, admin, gameId, spawn.x, spawn.y, encCtx
+    );
 
-    // Any signer can broadcast
     await program.methods
-      .broadcast(
+      .createSpectatorRegistry(new BN(gameId.toString()), 4)
+      .accounts({
+        admin: admin.publicKey,
+        game: gamePDA,
+        spectatorRegistry: registryPDA,
+      } as any)
+      .signers([admin])
+      .rpc({ commitment: "confirmed" });
+
+    await program.methods
+      .registerSpectator(new BN(gameId.toString()))
+      .accounts({
+        spectator: spectator.publicKey,
+        game: gamePDA,
+        spectatorRegistry: registryPDA,
+        server: null,
+      } as any)
+      .signers([spectator])
+      .rpc({ commitment: "confirmed" });
+
+    const broadcastAs = (signer: Keypair) =>
+      program.methods
+        .broadcast(
+          new BN(gameId.toString()),
+          new BN(spawn.x.toString()),
+          new BN(spawn.y.toString()),
+          Array.from(spawn.hash) as any
+        )
+        .accounts({
+          broadcaster: signer.publicKey,
+          game: gamePDA,
+          celestialBody: planetPDA,
+          player: null,
+          spectatorRegistry: registryPDA,
+        } as any)
+        .signers([signer])
+        .rpc({ commitment: "confirmed" });
+
+    await broadcastAs(spectator);
+    await expect(broadcastAs(outsider)).rejects.toThrow();
+
+    // Deregistered spectators lose the right to broadcast
+    await program.methods
+      .deregisterSpectator(new BN(gameId.toString()))
+      .accounts({
+        spectator: spectator.publicKey,
+        spectatorRegistry: registryPDA,
+      } as any)
+      .signers([spectator])
+      .rpc({ commitment: "confirmed" });
+    await expect(broadcastAs(spectator)).rejects.toThrow();
+  });
+
+  it("rejects broadcast for a planet that was never initialized", async () => {
+    const gameId = nextGameId();
+    const config = defaultGameConfig(gameId);
+    await createGame(program, admin, config);
+
+    // Valid hash for these coordinates, but queue_init_planet was never called
+    const spawn = findSpawnPlanet(gameId, DEFAULT_THRESHOLDS);
+    const [gamePDA] = deriveGamePDA(gameId, program.programId);
+    const [planetPDA] = deriveCelestialBodyPDA(gameId, spawn.hash, program.programId);
+
+    await expect(
+      program.methods
+        .broadcast(
+          new BN(gameId.toString()),
+          new BN(spawn.x.toString()),
+          new BN(spawn.y.toString()),
+          Array.from(spawn.hash) as any
+        )
+        .accounts({
+          broadcaster: admin.publicKey,
+          game: gamePDA,
+          celestialBody: planetPDA,
+        })
+        .signers([admin])
+        .rpc({ commitment: "confirmed" })
+    ).rejects.toThrow();
+  });
+
+  it("broadcasts several planets with broadcast_bulk", async () => {
+    if (!arciumAvailable) {
+      console.log("Skipping: Arcium not available");
+      return;
+    }
+
+    const gameId = nextGameId();
+    const config = defaultGameConfig(gameId);
+    await createGame(program, admin, config);
+
+    const playerPDA = await initPlayer(program, admin, gameId);
+
+    const spawn = findSpawnPlanet(gameId, DEFAULT_THRESHOLDS);
+    const [gamePDA] = deriveGamePDA(gameId, program.programId);
+    await queueInitPlanet(program, admin, gameId, spawn.x, spawn.y, encCtx);
+    const entry = {
+      x: new BN(spawn.x.toString()),
+      y: new BN(spawn.y.toString()),
+      planetHash: Array.from(spawn.hash),
+    };
+    const send = (entries: any[]) =>
+      program.methods
+        .broadcastBulk(new BN(gameId.toString()), entries)
+        .accounts({
+          broadcaster: admin.publicKey,
+          game: gamePDA,
+          player: playerPDA,
+          spectatorRegistry: null,
+        } as any)
+        .remainingAccounts(
+          entries.map((e) => ({
+            pubkey: deriveCelestialBodyPDA(
+              gameId,
+              Uint8Array.from(e.planetHash),
+              program.programId
+            )[0],
+            isSigner: false,
+            isWritable: false,
+          }))
+        )
+        .signers([admin])
+        .rpc({ commitment: "confirmed" });
+
+    await send([entry, entry]);
+
+    // One bad entry fails the whole batch
+    await expect(
+      send([entry, { ...entry, planetHash: Array.from(new Uint8Array(32)) }])
+    ).rejects.toThrow();
+
+    // Valid hash, but the planet was never initialized
+    const other = findDeadSpace(gameId, DEFAULT_THRESHOLDS);
+    const uninitialized = {
+      x: new BN(other.x.toString()),
+      y: new BN(other.y.toString()),
+      planetHash: Array.from(other.hash),
+    };
+    await expect(send([entry, uninitialized])).rejects.toThrow();
+
+    // More than 10 entries is rejected
+    await expect(send(new Array(11).fill(entry))).rejects.toThrow();
+
+    // Entries must each come with their planet account
+    await expect(
+      program.methods
+        .broadcastBulk(new BN(gameId.toString()), [entry])
+        .accounts({
+          broadcaster: admin.publicKey,
+          game: gamePDA,
+          player: playerPDA,
+          spectatorRegistry: null,
+        } as any)
+        .signers([admin])
+        .rpc({ commitment: "confirmed" })
+    ).rejects.toThrow();
+  });
+
+  it("creates a coordinate proof and rejects a mismatched hash", async () => {
+    const gameId = nextGameId();
+    const config = defaultGameConfig(gameId);
+    await createGame(program, admin, config);
+    const playerPDA = await initPlayer(program, admin, gameId);
+
+    const spawn = findSpawnPlanet(gameId, DEFAULT_THRESHOLDS);
+    const [gamePDA] = deriveGamePDA(gameId, program.programId);
+    const create = (hash: Uint8Array, x: bigint) =>
+      program.methods
+        .createCoordinateProof(
+          new BN(gameId.toString()),
+          Array.from(hash) as any,
+          new BN(x.toString()),
+          new BN(spawn.y.toString())
+        )
+        .accounts({
+          payer: admin.publicKey,
+          game: gamePDA,
+          player: playerPDA,
+          coordinateProof: deriveCoordinateProofPDA(gameId, hash, program.programId)[0],
+          celestialBody: null,
+        } as any)
+        .signers([admin])
+        .rpc({ commitment: "confirmed" });
+
+    // Correct hash but wrong coordinates
+    await expect(create(spawn.hash, spawn.x + 1n)).rejects.toThrow();
+
+    await create(spawn.hash, spawn.x);
+    const [proofPDA] = deriveCoordinateProofPDA(gameId, spawn.hash, program.programId);
+    const proof = await program.account.coordinateProof.fetch(proofPDA);
+    expect(BigInt(proof.x.toString())).toBe(spawn.x);
+    expect(BigInt(proof.y.toString())).toBe(spawn.y);
+    expect(proof.owner.toBase58()).toBe(admin.publicKey.toBase58());
+  });
+
+  it("rejects claim_race_to_center_victory outside RaceToCenter games", async () => {
+    const gameId = nextGameId();
+    const config = defaultGameConfig(gameId);
+    await createGame(program, admin, config);
+    const playerPDA = await initPlayer(program, admin, gameId);
+
+    const spawn = findSpawnPlanet(gameId, DEFAULT_THRESHOLDS);
+    const [gamePDA] = deriveGamePDA(gameId, program.programId);
+    const [proofPDA] = deriveCoordinateProofPDA(gameId, spawn.hash, program.programId);
+
+    await program.methods
+      .createCoordinateProof(
         new BN(gameId.toString()),
+        Array.from(spawn.hash) as any,
         new BN(spawn.x.toString()),
-        new BN(spawn.y.toString()),
-        Array.from(spawn.hash) as any
+        new BN(spawn.y.toString())
       )
       .accounts({
-        broadcaster: randomUser.publicKey,
+        payer: admin.publicKey,
         game: gamePDA,
-      })
-      .signers([randomUser])
+        player: playerPDA,
+        coordinateProof: proofPDA,
+        celestialBody: null,
+      } as any)
+      .signers([admin])
       .rpc({ commitment: "confirmed" });
 
-    expect(true).toBe(true);
+    const computationOffset = await nextComputationOffset(program, gameId, admin.publicKey);
+    let arciumAccts: Record<string, PublicKey>;
+    try {
+      arciumAccts = getArciumAccountAddresses(program, computationOffset, "check_ownership");
+    } catch {
+      console.log("Skipping race claim rejection (no Arcium)");
+      return;
+    }
+
+    // Default config is a PointsBurning game (and the player has not spawned)
+    await expect(
+      program.methods
+        .claimRaceToCenterVictory(
+          new BN(gameId.toString()),
+          Array.from(spawn.hash) as any,
+          Buffer.alloc(32),
+          Array(32).fill(0) as any,
+          new BN(0)
+        )
+        .accounts({
+          payer: admin.publicKey,
+          game: gamePDA,
+          player: playerPDA,
+          playerProfile: null,
+          coordinateProof: proofPDA,
+          celestialBody: deriveCelestialBodyPDA(gameId, spawn.hash, program.programId)[0],
+          offsetTracker: deriveComputationOffsetTrackerPDA(gameId, admin.publicKey, program.programId)[0],
+          ...arciumAccts,
+        } as any)
+        .signers([admin])
+        .rpc({ commitment: "confirmed" })
+    ).rejects.toThrow();
+
+    const game = await program.account.game.fetch(gamePDA);
+    expect(game.winner).toBeNull();
+  });
+
+  it("rejects claim_points_victory below points_to_win", async () => {
+    const gameId = nextGameId();
+    const config = defaultGameConfig(gameId, {
+      pointsToWin: new BN(1_000),
+    });
+    await createGame(program, admin, config);
+    const playerPDA = await initPlayer(program, admin, gameId);
+    const [gamePDA] = deriveGamePDA(gameId, program.programId);
+
+    // A fresh player has no points
+    await expect(
+      program.methods
+        .claimPointsVictory(new BN(gameId.toString()))
+        .accounts({
+          payer: admin.publicKey,
+          game: gamePDA,
+          player: playerPDA,
+          playerProfile: null,
+        } as any)
+        .signers([admin])
+        .rpc({ commitment: "confirmed" })
+    ).rejects.toThrow();
+
+    const game = await program.account.game.fetch(gamePDA);
+    expect(game.winner).toBeNull();
+    expect(game.endedEarly).toBe(false);
   });
 });
@@ -7,6 +7,8 @@
  * 3. Reject planet cleanup before game ends
  * 4. Successful cleanup after game ends
  * 5. Anyone can cleanup (permissionless)
+ * 6. cleanup_game also closes the game's leaderboard
+ * 7. close_dead_planet rejects coordinates that hold a body
  *
  * NOTE: Planet cleanup now operates on EncryptedCelestialBody and
  * EncryptedPendingMoves accounts. We create planets via queue_init_planet
@@ -25,12 +27,16 @@ import {
   createGame,
   initPlayer,
   defaultGameConfig,
+  shortGameConfig,
+  advancePastSlot,
   setupEncryption,
   queueInitPlanet,
   deriveGamePDA,
   derivePlayerPDA,
+  derivePlayerDepositPDA,
   derivePlanetPDA,
   derivePendingMovesPDA,
+  deriveCombatLogPDA,
   findSpawnPlanet,
   nextGameId,
   awaitComputationFinalization,
@@ -98,7 +104,10 @@ describe("Cleanup - Rejection Before Game End", () => {
           closer: admin.publicKey,
           game: gamePDA,
           player: playerPDA,
-        })
+          owner: admin.publicKey,
+          depositRecipient: admin.publicKey,
+          playerProfile: null,
+        } as any)
         .signers([admin])
         .rpc({ commitment: "confirmed" })
     ).rejects.toThrow();
@@ -130,6 +139,7 @@ describe("Cleanup - Rejection Before Game End", () => {
     const [gamePDA] = deriveGamePDA(gameId, program.programId);
     const [planetPDA] = derivePlanetPDA(gameId, spawn.hash, program.programId);
     const [pendingPDA] = derivePendingMovesPDA(gameId, spawn.hash, program.programId);
+    const [combatLogPDA] = deriveCombatLogPDA(gameId, spawn.hash, program.programId);
 
     await expect(
       program.methods
@@ -142,6 +152,56 @@ describe("Cleanup - Rejection Before Game End", () => {
           game: gamePDA,
           celestialBody: planetPDA,
           pendingMoves: pendingPDA,
+          combatLog: combatLogPDA,
+        })
+        .signers([admin])
+        .rpc({ commitment: "confirmed" })
+    ).rejects.toThrow();
+  });
+
+  it("rejects closing a planet that is not dead space (if Arcium available)", async () => {
+    let encCtx: EncryptionContext;
+    try {
+      getArciumEnv();
+      const setup = getProviderAndProgram();
+      encCtx = await setupEncryption(setup.provider, setup.program.programId);
+    } catch {
+      console.log("Skipping dead planet close test (no Arcium)");
+      return;
+    }
+
+    const gameId = nextGameId();
+    const config = defaultGameConfig(gameId, {
+      endSlot: new BN(1_000_000_000),
+    });
+    await createGame(program, admin, config);
+
+    const spawn = findSpawnPlanet(gameId, DEFAULT_THRESHOLDS);
+    const { computationOffset } = await queueInitPlanet(
+      program, admin, gameId, spawn.x, spawn.y, encCtx!
+    );
+    await awaitComputationFinalization(provider, computationOffset, program.programId, "confirmed");
+
+    const [gamePDA] = deriveGamePDA(gameId, program.programId);
+    const [planetPDA] = derivePlanetPDA(gameId, spawn.hash, program.programId);
+    const [pendingPDA] = derivePendingMovesPDA(gameId, spawn.hash, program.programId);
+    const [combatLogPDA] = deriveCombatLogPDA(gameId, spawn.hash, program.programId);
+
+    await expect(
+      program.methods
+        .closeDeadPlanet(
+          new BN(gameId.toString()),
+          Array.from(spawn.hash) as any,
+          new BN(spawn.x.toString()),
+          new BN(spawn.y.toString())
+        )
+        .accounts({
+          closer: admin.publicKey,
+          game: gamePDA,
+          celestialBody: planetPDA,
+          pendingMoves: pendingPDA,
+          combatLog: combatLogPDA,
+          initializer: admin.publicKey,
         })
         .signers([admin])
         .rpc({ commitment: "confirmed" })
@@ -167,11 +227,9 @@ describe("Cleanup - Success After Game End", () => {
 
   it("cleans up game account after game ends", async () => {
     const gameId = nextGameId();
-    const config = defaultGameConfig(gameId, {
-      startSlot: new BN(0),
-      endSlot: new BN(1), // ends at slot 1, definitely in the past
-    });
+    const config = await shortGameConfig(provider, gameId);
     await createGame(program, admin, config);
+    await advancePastSlot(provider, config.endSlot);
 
     const [gamePDA] = deriveGamePDA(gameId, program.programId);
 
@@ -190,12 +248,10 @@ describe("Cleanup - Success After Game End", () => {
 
   it("cleans up player account after game ends", async () => {
     const gameId = nextGameId();
-    const config = defaultGameConfig(gameId, {
-      startSlot: new BN(0),
-      endSlot: new BN(1),
-    });
+    const config = await shortGameConfig(provider, gameId);
     await createGame(program, admin, config);
     await initPlayer(program, admin, gameId);
+    await advancePastSlot(provider, config.endSlot);
 
     const [gamePDA] = deriveGamePDA(gameId, program.programId);
     const [playerPDA] = derivePlayerPDA(
@@ -204,13 +260,23 @@ describe("Cleanup - Success After Game End", () => {
       program.programId
     );
 
+    const [depositPDA] = derivePlayerDepositPDA(
+      gameId,
+      admin.publicKey,
+      program.programId
+    );
+
     await program.methods
       .cleanupPlayer(new BN(gameId.toString()))
       .accounts({
         closer: admin.publicKey,
         game: gamePDA,
         player: playerPDA,
-      })
+        deposit: depositPDA,
+        owner: admin.publicKey,
+        depositRecipient: admin.publicKey,
+        playerProfile: null,
+      } as any)
       .signers([admin])
       .rpc({ commitment: "confirmed" });
 
@@ -218,6 +284,29 @@ describe("Cleanup - Success After Game End", () => {
     expect(info).toBeNull();
   });
 
+  it("rejects set_allow_force_cleanup before the grace period", async () => {
+    const gameId = nextGameId();
+    const config = await shortGameConfig(provider, gameId);
+    await createGame(program, admin, config);
+    await advancePastSlot(provider, config.endSlot);
+
+    const [gamePDA] = deriveGamePDA(gameId, program.programId);
+
+    await expect(
+      program.methods
+        .setAllowForceCleanup(new BN(gameId.toString()), true)
+        .accounts({
+          admin: admin.publicKey,
+          game: gamePDA,
+        } as any)
+        .signers([admin])
+        .rpc({ commitment: "confirmed" })
+    ).rejects.toThrow();
+
+    const game = await program.account.game.fetch(gamePDA);
+    expect(game.allowForceCleanup).toBe(false);
+  });
+
   it("cleans up planet account after game ends (if Arcium available)", async () => {
     let encCtx: EncryptionContext;
     try {
@@ -230,31 +319,22 @@ describe("Cleanup - Success After Game End", () => {
     }
 
     const gameId = nextGameId();
-    const config = defaultGameConfig(gameId, {
-      startSlot: new BN(0),
-      endSlot: new BN(1),
-    });
+    const config = await shortGameConfig(provider, gameId);
     await createGame(program, admin, config);
 
     const spawn = findSpawnPlanet(gameId, DEFAULT_THRESHOLDS);
-    // Note: queue_init_planet checks clock.slot < game.end_slot
-    // Since end_slot=1 and we are past that, this might fail.
-    // We create with a longer end_slot first, init the planet, then cannot cleanup until after.
-    // Actually with end_slot=1, init_planet will fail because game has ended.
-    // So we need to test with a game that has end_slot in the near future.
-    // For simplicity, we skip the planet cleanup integration test when Arcium creates
-    // the planet but the game has already ended.
+    // Initializing the planet needs an MPC round trip before the game ends,
+    // and cleanup_planet additionally waits out GRACE_PERIOD_SLOTS while moves
+    // are pending. Game/player cleanup above covers the end-of-game path.
     console.log("Planet cleanup after game end requires careful slot timing - verifying game/player cleanup is sufficient");
     expect(true).toBe(true);
   });
 
   it("allows anyone to cleanup (permissionless)", async () => {
     const gameId = nextGameId();
-    const config = defaultGameConfig(gameId, {
-      startSlot: new BN(0),
-      endSlot: new BN(1),
-    });
+    const config = await shortGameConfig(provider, gameId);
     await createGame(program, admin, config);
+    await advancePastSlot(provider, config.endSlot);
 
     const randomUser = Keypair.generate();
     await airdrop(provider, randomUser.publicKey, 1);
@@ -274,4 +354,45 @@ describe("Cleanup - Success After Game End", () => {
     const info = await provider.connection.getAccountInfo(gamePDA);
     expect(info).toBeNull();
   });
+
+  it("closes the game's leaderboard along with the game", async () => {
+    const gameId = nextGameId();
+    const config = await shortGameConfig(provider, gameId);
+    await createGame(program, admin, config);
+
+    const [gamePDA] = deriveGamePDA(gameId, program.programId);
+    const gameIdBuf = Buffer.alloc(8);
+    gameIdBuf.writeBigUInt64LE(gameId);
+    const [leaderboardPDA] = PublicKey.findProgramAddressSync(
+      [Buffer.from("leaderboard"), gameIdBuf],
+      program.programId
+    );
+
+    await program.methods
+      .createLeaderboard(new BN(gameId.toString()), 4)
+      .accounts({
+        admin: admin.publicKey,
+        game: gamePDA,
+        leaderboard: leaderboardPDA,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .signers([admin])
+      .rpc({ commitment: "confirmed" });
+    await advancePastSlot(provider, config.endSlot);
+
+    await program.methods
+      .cleanupGame(new BN(gameId.toString()))
+      .accounts({
+        closer: admin.publicKey,
+        game: gamePDA,
+      })
+      .remainingAccounts([
+        { pubkey: leaderboardPDA, isSigner: false, isWritable: true },
+      ])
+      .signers([admin])
+      .rpc({ commitment: "confirmed" });
+
+    expect(await provider.connection.getAccountInfo(gamePDA)).toBeNull();
+    expect(await provider.connection.getAccountInfo(leaderboardPDA)).toBeNull();
+  });
 });
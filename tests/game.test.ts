@@ -6,9 +6,15 @@
  * 2. Create game with custom thresholds
  * 3. Create game with whitelist enabled
  * 4. Verify game account data matches config
- * 5. Reject invalid configurations (zero map diameter, zero game speed, bad time range)
+ * 5. Reject invalid configurations (zero map diameter, zero game speed, bad time range,
+ *    start slot in the past, game shorter than MIN_GAME_DURATION_SLOTS,
+ *    non-monotonic noise thresholds, quasar penalty above 100%)
  * 6. Create multiple independent games
  * 7. Both win condition types
+ * 8. Admin pause/resume (non-admin rejected)
+ * 9. Two-step admin transfer (only the nominee can accept)
+ * 10. Admin extends end_slot (shortening rejected)
+ * 11. Admin raises hash_rounds (lowering rejected)
  */
 
 import { describe, it, expect, beforeAll } from "vitest";
@@ -21,11 +27,13 @@ import {
   readKpJson,
   createGame,
   defaultGameConfig,
+  shortGameConfig,
   deriveGamePDA,
   nextGameId,
   DEFAULT_THRESHOLDS,
   DEFAULT_MAP_DIAMETER,
   DEFAULT_GAME_SPEED,
+  MIN_GAME_DURATION_SLOTS,
 } from "./helpers";
 
 describe("Game Creation", () => {
@@ -110,6 +118,8 @@ describe("Game Creation", () => {
 
     const gameAccount = await program.account.game.fetch(gamePDA);
     expect(gameAccount.winCondition).toBeDefined();
+    // Only RaceToCenter restricts spawning near the center
+    expect(gameAccount.minSpawnDistanceFromCenter.toString()).toBe("0");
   });
 
   it("creates a game with RaceToCenter win condition", async () => {
@@ -121,6 +131,7 @@ describe("Game Creation", () => {
 
     const gameAccount = await program.account.game.fetch(gamePDA);
     expect(gameAccount.winCondition).toBeDefined();
+    expect(gameAccount.minSpawnDistanceFromCenter.toString()).toBe("50");
   });
 
   it("rejects game with zero map diameter", async () => {
@@ -132,6 +143,33 @@ describe("Game Creation", () => {
     await expect(createGame(program, admin, config)).rejects.toThrow();
   });
 
+  it("rejects game with no spawnable body types", async () => {
+    const gameId = nextGameId();
+    const config = defaultGameConfig(gameId, {
+      validSpawnBodyTypes: 0,
+    });
+
+    await expect(createGame(program, admin, config)).rejects.toThrow();
+  });
+
+  it("rejects game with max planet level below 2", async () => {
+    const gameId = nextGameId();
+    const config = defaultGameConfig(gameId, {
+      maxPlanetLevel: 1,
+    });
+
+    await expect(createGame(program, admin, config)).rejects.toThrow();
+  });
+
+  it("rejects game with zero min ships per move", async () => {
+    const gameId = nextGameId();
+    const config = defaultGameConfig(gameId, {
+      minShipsPerMove: new BN(0),
+    });
+
+    await expect(createGame(program, admin, config)).rejects.toThrow();
+  });
+
   it("rejects game with zero game speed", async () => {
     const gameId = nextGameId();
     const config = defaultGameConfig(gameId, {
@@ -151,6 +189,73 @@ describe("Game Creation", () => {
     await expect(createGame(program, admin, config)).rejects.toThrow();
   });
 
+  it("rejects a start slot in the past", async () => {
+    const gameId = nextGameId();
+    const config = defaultGameConfig(gameId, {
+      startSlot: new BN(1),
+    });
+
+    await expect(createGame(program, admin, config)).rejects.toThrow();
+  });
+
+  it("rejects a game shorter than MIN_GAME_DURATION_SLOTS", async () => {
+    const gameId = nextGameId();
+    const config = await shortGameConfig(provider, gameId);
+    config.endSlot = config.startSlot.add(
+      new BN((MIN_GAME_DURATION_SLOTS - 1n).toString())
+    );
+
+    await expect(createGame(program, admin, config)).rejects.toThrow();
+  });
+
+  it("rejects a quasar ship penalty above 10000 bps", async () => {
+    const gameId = nextGameId();
+    const config = defaultGameConfig(gameId, {
+      quasarShipPenaltyBps: new BN(10_001),
+    });
+
+    await expect(createGame(program, admin, config)).rejects.toThrow();
+  });
+
+  it("rejects body-type thresholds that are not strictly increasing", async () => {
+    const gameId = nextGameId();
+    const config = defaultGameConfig(gameId, {
+      // quasar == planet leaves no room for quasars
+      noiseThresholds: { ...DEFAULT_THRESHOLDS, quasarThreshold: DEFAULT_THRESHOLDS.planetThreshold },
+    });
+
+    await expect(createGame(program, admin, config)).rejects.toThrow();
+  });
+
+  it("rejects size thresholds that are not strictly increasing", async () => {
+    const gameId = nextGameId();
+    const config = defaultGameConfig(gameId, {
+      noiseThresholds: { ...DEFAULT_THRESHOLDS, sizeThreshold4: 255, sizeThreshold5: 250 },
+    });
+
+    await expect(createGame(program, admin, config)).rejects.toThrow();
+  });
+
+  it("accepts strictly increasing thresholds at the u8 boundaries", async () => {
+    const gameId = nextGameId();
+    const config = defaultGameConfig(gameId, {
+      noiseThresholds: {
+        ...DEFAULT_THRESHOLDS,
+        planetThreshold: 0,
+        quasarThreshold: 1,
+        spacetimeRipThreshold: 254,
+        asteroidBeltThreshold: 255,
+        sizeThreshold1: 0,
+        sizeThreshold2: 1,
+        sizeThreshold3: 2,
+        sizeThreshold4: 3,
+        sizeThreshold5: 255,
+      },
+    });
+
+    await expect(createGame(program, admin, config)).resolves.toBeDefined();
+  });
+
   it("rejects whitelist game without server pubkey", async () => {
     const gameId = nextGameId();
     const config = defaultGameConfig(gameId, {
@@ -189,4 +294,116 @@ describe("Game Creation", () => {
 
     expect(gamePDA.toString()).toBe(expectedPDA.toString());
   });
+
+  it("lets the admin pause and resume a game", async () => {
+    const gameId = nextGameId();
+    const gamePDA = await createGame(program, admin, defaultGameConfig(gameId));
+
+    await program.methods
+      .setGamePaused(new BN(gameId.toString()), true)
+      .accounts({ admin: admin.publicKey, game: gamePDA } as any)
+      .signers([admin])
+      .rpc({ commitment: "confirmed" });
+    expect((await program.account.game.fetch(gamePDA)).paused).toBe(true);
+
+    await program.methods
+      .setGamePaused(new BN(gameId.toString()), false)
+      .accounts({ admin: admin.publicKey, game: gamePDA } as any)
+      .signers([admin])
+      .rpc({ commitment: "confirmed" });
+    expect((await program.account.game.fetch(gamePDA)).paused).toBe(false);
+  });
+
+  it("rejects pause from a non-admin", async () => {
+    const gameId = nextGameId();
+    const gamePDA = await createGame(program, admin, defaultGameConfig(gameId));
+    const outsider = Keypair.generate();
+
+    await expect(
+      program.methods
+        .setGamePaused(new BN(gameId.toString()), true)
+        .accounts({ admin: outsider.publicKey, game: gamePDA } as any)
+        .signers([outsider])
+        .rpc({ commitment: "confirmed" })
+    ).rejects.toThrow();
+  });
+
+  it("transfers admin in two steps", async () => {
+    const gameId = nextGameId();
+    const gamePDA = await createGame(program, admin, defaultGameConfig(gameId));
+    const successor = Keypair.generate();
+    const outsider = Keypair.generate();
+
+    await program.methods
+      .proposeAdminTransfer(new BN(gameId.toString()), successor.publicKey)
+      .accounts({ admin: admin.publicKey, game: gamePDA } as any)
+      .signers([admin])
+      .rpc({ commitment: "confirmed" });
+
+    let game = await program.account.game.fetch(gamePDA);
+    expect(game.admin.toString()).toBe(admin.publicKey.toString());
+    expect(game.pendingAdmin?.toString()).toBe(successor.publicKey.toString());
+
+    await expect(
+      program.methods
+        .acceptAdminTransfer(new BN(gameId.toString()))
+        .accounts({ newAdmin: outsider.publicKey, game: gamePDA } as any)
+        .signers([outsider])
+        .rpc({ commitment: "confirmed" })
+    ).rejects.toThrow();
+
+    await program.methods
+      .acceptAdminTransfer(new BN(gameId.toString()))
+      .accounts({ newAdmin: successor.publicKey, game: gamePDA } as any)
+      .signers([successor])
+      .rpc({ commitment: "confirmed" });
+
+    game = await program.account.game.fetch(gamePDA);
+    expect(game.admin.toString()).toBe(successor.publicKey.toString());
+    expect(game.pendingAdmin).toBeNull();
+  });
+
+  it("lets the admin extend a running game but not shorten it", async () => {
+    const gameId = nextGameId();
+    const gamePDA = await createGame(program, admin, defaultGameConfig(gameId));
+    const before = await program.account.game.fetch(gamePDA);
+    const longer = before.endSlot.add(new BN(1000));
+
+    await program.methods
+      .extendGame(new BN(gameId.toString()), longer)
+      .accounts({ admin: admin.publicKey, game: gamePDA } as any)
+      .signers([admin])
+      .rpc({ commitment: "confirmed" });
+    expect((await program.account.game.fetch(gamePDA)).endSlot.toString()).toBe(longer.toString());
+
+    await expect(
+      program.methods
+        .extendGame(new BN(gameId.toString()), before.endSlot)
+        .accounts({ admin: admin.publicKey, game: gamePDA } as any)
+        .signers([admin])
+        .rpc({ commitment: "confirmed" })
+    ).rejects.toThrow();
+  });
+
+  it("lets the admin raise hash_rounds but never lower it", async () => {
+    const gameId = nextGameId();
+    const gamePDA = await createGame(program, admin, defaultGameConfig(gameId));
+    const before = await program.account.game.fetch(gamePDA);
+    const harder = before.hashRounds + 10;
+
+    await program.methods
+      .updateHashRounds(new BN(gameId.toString()), harder)
+      .accounts({ admin: admin.publicKey, game: gamePDA } as any)
+      .signers([admin])
+      .rpc({ commitment: "confirmed" });
+    expect((await program.account.game.fetch(gamePDA)).hashRounds).toBe(harder);
+
+    await expect(
+      program.methods
+        .updateHashRounds(new BN(gameId.toString()), before.hashRounds)
+        .accounts({ admin: admin.publicKey, game: gamePDA } as any)
+        .signers([admin])
+        .rpc({ commitment: "confirmed" })
+    ).rejects.toThrow();
+  });
 });
@@ -309,12 +309,16 @@ export {
   PENDING_MOVE_DATA_FIELDS,
   MAX_FLUSH_BATCH,
   MAX_QUEUED_CALLBACKS,
+  MIN_GAME_DURATION_SLOTS,
 
   // PDA derivation
   deriveGamePDA,
   derivePlayerPDA,
+  derivePlayerDepositPDA,
+  deriveComputationOffsetTrackerPDA,
   deriveCelestialBodyPDA,
   derivePendingMovesPDA,
+  deriveCombatLogPDA,
   derivePendingMoveAccountPDA,
 
   // Noise / game mechanics
@@ -327,6 +331,7 @@ export {
   computeCurrentShips,
   computeCurrentMetal,
   computeDistance,
+  maxMapDistance,
   applyDistanceDecay,
   computeLandingSlot,
   upgradeCost,
@@ -334,6 +339,7 @@ export {
   scanRange,
   findSpawnPlanet,
   findPlanetOfType,
+  playerIdFromPubkey,
 
   // Crypto
   derivePlanetKeySeed,
@@ -401,13 +407,20 @@ import {
   PROGRAM_ID,
   deriveGamePDA,
   derivePlayerPDA,
+  derivePlayerDepositPDA,
+  deriveComputationOffsetTrackerPDA,
   deriveCelestialBodyPDA,
   derivePendingMovesPDA,
+  deriveCombatLogPDA,
   derivePendingMoveAccountPDA,
+  fetchNextComputationOffset,
   computePlanetHash,
   determineCelestialBody,
   computeDistance,
+  maxMapDistance,
   computeLandingSlot,
+  fetchUpcomingStartSlot,
+  MIN_GAME_DURATION_SLOTS,
   buildCreateGameIx,
   buildInitPlayerIx,
 } from "@encrypted-forest/core";
@@ -491,6 +504,22 @@ export interface GameConfig {
   serverPubkey: PublicKey | null;
   noiseThresholds: NoiseThresholds;
   hashRounds: number;
+  maxMovesPerSlot: number;
+  maxPendingMoves: number;
+  quasarShipPenaltyBps: BN;
+  moveCooldownSlots: BN;
+  maxPlayers: number;
+  minSpawnSeparation: BN;
+  cometDriftIntervalSlots: BN;
+  hashAlgorithm: object;
+  securityDepositLamports: BN;
+  depositRecipient: PublicKey | null;
+  captureCooldownSlots: BN;
+  distanceMethod: object;
+  validSpawnBodyTypes: number;
+  maxPlanetLevel: number;
+  minShipsPerMove: BN;
+  pointsToWin: BN;
 }
 
 export function defaultGameConfig(
@@ -501,6 +530,7 @@ export function defaultGameConfig(
     gameId,
     mapDiameter: DEFAULT_MAP_DIAMETER,
     gameSpeed: DEFAULT_GAME_SPEED,
+    // 0 = start at the next slot (resolved by createGame)
     startSlot: new BN(0),
     endSlot: new BN(1_000_000_000),
     winCondition: { pointsBurning: { pointsPerMetal: new BN(1) } },
@@ -508,6 +538,22 @@ export function defaultGameConfig(
     serverPubkey: null,
     noiseThresholds: DEFAULT_THRESHOLDS,
     hashRounds: 1,
+    maxMovesPerSlot: 4,
+    maxPendingMoves: 8,
+    quasarShipPenaltyBps: new BN(0),
+    moveCooldownSlots: new BN(0),
+    maxPlayers: 0,
+    minSpawnSeparation: new BN(0),
+    cometDriftIntervalSlots: new BN(0),
+    hashAlgorithm: { blake3: {} },
+    securityDepositLamports: new BN(0),
+    depositRecipient: null,
+    captureCooldownSlots: new BN(0),
+    distanceMethod: { chebyshevApprox: {} },
+    validSpawnBodyTypes: 1,
+    maxPlanetLevel: 10,
+    minShipsPerMove: new BN(1),
+    pointsToWin: new BN(0),
     ...overrides,
   };
 }
@@ -518,6 +564,11 @@ export async function createGame(
   config: GameConfig
 ): Promise<PublicKey> {
   const [gamePDA] = deriveGamePDA(config.gameId, program.programId);
+  const connection = program.provider.connection;
+  const startNow = config.startSlot.isZero();
+  const startSlot = startNow
+    ? new BN((await fetchUpcomingStartSlot(connection)).toString())
+    : config.startSlot;
 
   const anchorThresholds = {
     deadSpaceThreshold: config.noiseThresholds.deadSpaceThreshold,
@@ -537,13 +588,29 @@ export async function createGame(
       new BN(config.gameId.toString()),
       config.mapDiameter,
       config.gameSpeed,
-      config.startSlot,
+      startSlot,
       config.endSlot,
       config.winCondition as any,
       config.whitelist,
       config.serverPubkey,
       anchorThresholds,
-      config.hashRounds
+      config.hashRounds,
+      config.maxMovesPerSlot,
+      config.maxPendingMoves,
+      config.quasarShipPenaltyBps,
+      config.moveCooldownSlots,
+      config.maxPlayers,
+      config.minSpawnSeparation,
+      config.cometDriftIntervalSlots,
+      config.hashAlgorithm,
+      config.securityDepositLamports,
+      config.depositRecipient,
+      config.captureCooldownSlots,
+      config.distanceMethod,
+      config.validSpawnBodyTypes,
+      config.maxPlanetLevel,
+      config.minShipsPerMove,
+      config.pointsToWin
     )
     .accounts({
       admin: admin.publicKey,
@@ -553,9 +620,118 @@ export async function createGame(
     .signers([admin])
     .rpc({ commitment: "confirmed" });
 
+  if (startNow) {
+    await waitForSlot(connection, BigInt(startSlot.toString()));
+  }
   return gamePDA;
 }
 
+/**
+ * Config for a game of the minimum allowed length, starting at the next
+ * slot. Pair with advancePastSlot(config.endSlot) to test ended games.
+ */
+export async function shortGameConfig(
+  provider: AnchorProvider,
+  gameId: bigint,
+  overrides?: Partial<GameConfig>
+): Promise<GameConfig> {
+  const start = await fetchUpcomingStartSlot(provider.connection);
+  return defaultGameConfig(gameId, {
+    startSlot: new BN(start.toString()),
+    endSlot: new BN((start + MIN_GAME_DURATION_SLOTS).toString()),
+    ...overrides,
+  });
+}
+
+export async function waitForSlot(
+  connection: Connection,
+  slot: bigint
+): Promise<void> {
+  while (BigInt(await connection.getSlot("confirmed")) < slot) {
+    await new Promise((r) => setTimeout(r, 200));
+  }
+}
+
+/**
+ * Move the surfnet clock past `slot` with Surfpool's time-travel cheatcode,
+ * so tests need not wait out a whole game.
+ */
+export async function advancePastSlot(
+  provider: AnchorProvider,
+  slot: BN
+): Promise<void> {
+  const res = await fetch(provider.connection.rpcEndpoint, {
+    method: "POST",
+    headers: { "Content-Type": "application/json" },
+    body: JSON.stringify({
+      jsonrpc: "2.0",
+      id: 1,
+      method: "surfnet_timeTravel",
+      params: [{ absoluteSlot: slot.toNumber() + 1 }],
+    }),
+  });
+  const body = await res.json();
+  if (body.error) {
+    throw new Error(`surfnet_timeTravel failed: ${JSON.stringify(body.error)}`);
+  }
+}
+
+/**
+ * Write raw program-owned account data with Surfpool's surfnet_setAccount
+ * cheatcode, zero-padded to `space`. Lets tests stage states that would
+ * otherwise need an MPC round trip or an older program build (in-flight
+ * moves, legacy account layouts).
+ */
+export async function setProgramAccount(
+  provider: AnchorProvider,
+  programId: PublicKey,
+  address: PublicKey,
+  data: Buffer,
+  space: number = data.length
+): Promise<void> {
+  const padded = Buffer.alloc(Math.max(space, data.length));
+  data.copy(padded);
+  const lamports = await provider.connection.getMinimumBalanceForRentExemption(padded.length);
+  const res = await fetch(provider.connection.rpcEndpoint, {
+    method: "POST",
+    headers: { "Content-Type": "application/json" },
+    body: JSON.stringify({
+      jsonrpc: "2.0",
+      id: 1,
+      method: "surfnet_setAccount",
+      params: [
+        address.toBase58(),
+        {
+          lamports,
+          data: padded.toString("hex"),
+          owner: programId.toBase58(),
+          executable: false,
+        },
+      ],
+    }),
+  });
+  const body = await res.json();
+  if (body.error) {
+    throw new Error(`surfnet_setAccount failed: ${JSON.stringify(body.error)}`);
+  }
+}
+
+/**
+ * Encode `value` as the named program account (discriminator included) and
+ * write it to `address` with setProgramAccount.
+ */
+export async function stageProgramAccount(
+  provider: AnchorProvider,
+  program: Program<EncryptedForest>,
+  address: PublicKey,
+  accountName: string,
+  value: Record<string, unknown>,
+  space?: number
+): Promise<void> {
+  const data = await program.coder.accounts.encode(accountName, value);
+  await setProgramAccount(provider, program.programId, address, data, space);
+}
+
 export async function initPlayer(
   program: Program<EncryptedForest>,
   owner: Keypair,
@@ -569,10 +745,22 @@ export async function initPlayer(
     program.programId
   );
 
+  const [depositPDA] = derivePlayerDepositPDA(
+    gameId,
+    owner.publicKey,
+    program.programId
+  );
+
   const accounts: any = {
     owner: owner.publicKey,
     game: gamePDA,
     player: playerPDA,
+    deposit: depositPDA,
+    offsetTracker: deriveComputationOffsetTrackerPDA(
+      gameId,
+      owner.publicKey,
+      program.programId
+    )[0],
     systemProgram: SystemProgram.programId,
   };
 
@@ -673,7 +861,7 @@ export const DEFAULT_CIRCUIT_BASE_URL =
   process.env.CIRCUIT_BASE_URL || "https://s3.spacerisk.io";
 
 /**
- * Initialize all 5 computation definitions for the program.
+ * Initialize all 13 computation definitions for the program.
  */
 export async function initAllCompDefs(
   program: Program<EncryptedForest>,
@@ -691,6 +879,15 @@ export async function initAllCompDefs(
     "process_move",
     "flush_planet",
     "upgrade_planet",
+    "surrender_planet",
+    "transfer_resources",
+    "spy_move",
+    "comet_collect",
+    "mine_asteroid",
+    "check_ownership",
+    "wormhole_jump",
+    "comet_drift",
+    "transfer_owner",
   ];
 
   const methodNames = [
@@ -699,6 +896,15 @@ export async function initAllCompDefs(
     "initCompDefProcessMove",
     "initCompDefFlushPlanet",
     "initCompDefUpgradePlanet",
+    "initCompDefSurrenderPlanet",
+    "initCompDefTransferResources",
+    "initCompDefSpyMove",
+    "initCompDefCometCollect",
+    "initCompDefMineAsteroid",
+    "initCompDefCheckOwnership",
+    "initCompDefWormholeJump",
+    "initCompDefCometDrift",
+    "initCompDefTransferOwner",
   ] as const;
 
   for (let i = 0; i < compDefNames.length; i++) {
@@ -708,7 +914,7 @@ export async function initAllCompDefs(
 
     try {
       await (program.methods as any)
-        [methodNames[i]](circuitBaseUrl)
+        [methodNames[i]](circuitBaseUrl, null)
         .accounts({
           payer: payer.publicKey,
           mxeAccount,
@@ -717,6 +923,8 @@ export async function initAllCompDefs(
           lutProgram,
           systemProgram: SystemProgram.programId,
           arciumProgram,
+          // Only needed with a circuit hash override (upgrade authority check)
+          programData: null,
         })
         .signers([payer])
         .rpc({ commitment: "confirmed" });
@@ -790,7 +998,7 @@ export function buildInitSpawnPlanetValues(
   return [
     BigInt.asUintN(64, x),
     BigInt.asUintN(64, y),
-    BigInt.asUintN(32, playerId),
+    BigInt.asUintN(64, playerId),
     BigInt.asUintN(32, sourcePlanetId),
   ];
 }
@@ -803,7 +1011,8 @@ export function buildProcessMoveValues(
   sourceX: bigint,
   sourceY: bigint,
   targetX: bigint,
-  targetY: bigint
+  targetY: bigint,
+  allyPlayerId: bigint = 0n
 ): bigint[] {
   return [
     BigInt.asUintN(32, playerId),
@@ -814,41 +1023,54 @@ export function buildProcessMoveValues(
     BigInt.asUintN(64, sourceY),
     BigInt.asUintN(64, targetX),
     BigInt.asUintN(64, targetY),
-  ];
-}
-
-export function buildFlushPlanetValues(
-  currentSlot: bigint,
-  gameSpeed: bigint,
-  lastUpdatedSlot: bigint,
-  flushCount: bigint
-): bigint[] {
-  return [
-    BigInt.asUintN(32, currentSlot),
-    BigInt.asUintN(32, gameSpeed),
-    BigInt.asUintN(32, lastUpdatedSlot),
-    BigInt.asUintN(32, flushCount),
+    BigInt.asUintN(32, allyPlayerId),
+    BigInt.asUintN(32, playerId >> 32n),
+    BigInt.asUintN(32, allyPlayerId >> 32n),
   ];
 }
 
 export function buildUpgradePlanetValues(
   playerId: bigint,
   focus: number,
-  currentSlot: bigint,
-  gameSpeed: bigint,
-  lastUpdatedSlot: bigint,
   metalUpgradeCost: bigint
 ): bigint[] {
   return [
-    BigInt.asUintN(32, playerId),
+    BigInt.asUintN(64, playerId),
     BigInt.asUintN(32, BigInt(focus)),
-    BigInt.asUintN(32, currentSlot),
-    BigInt.asUintN(32, gameSpeed),
-    BigInt.asUintN(32, lastUpdatedSlot),
     BigInt.asUintN(32, metalUpgradeCost),
   ];
 }
 
+export function buildSurrenderPlanetValues(playerId: bigint): bigint[] {
+  return [BigInt.asUintN(64, playerId)];
+}
+
+export function buildTransferOwnerValues(playerId: bigint, newOwnerId: bigint): bigint[] {
+  return [BigInt.asUintN(64, playerId), BigInt.asUintN(64, newOwnerId)];
+}
+
+export function buildTransferResourcesValues(
+  playerId: bigint,
+  allyPlayerId: bigint,
+  shipsAmount: bigint,
+  metalAmount: bigint
+): bigint[] {
+  return [
+    BigInt.asUintN(64, playerId),
+    BigInt.asUintN(64, allyPlayerId),
+    shipsAmount,
+    metalAmount,
+  ];
+}
+
+export function buildMineAsteroidValues(playerId: bigint): bigint[] {
+  return [BigInt.asUintN(64, playerId)];
+}
+
+export function buildOwnershipValues(playerId: bigint): bigint[] {
+  return [BigInt.asUintN(64, playerId)];
+}
+
 // ---------------------------------------------------------------------------
 // Queue instruction helpers (test-specific: send actual transactions)
 // ---------------------------------------------------------------------------
@@ -908,6 +1130,19 @@ function getClockAccountAddress(): PublicKey {
   }
 }
 
+/**
+ * Offset the payer's next queue_* call will use, read from their
+ * ComputationOffsetTracker (created by init_player).
+ */
+export async function nextComputationOffset(
+  program: Program<EncryptedForest>,
+  gameId: bigint,
+  owner: PublicKey
+): Promise<BN> {
+  const offset = await fetchNextComputationOffset(program as any, gameId, owner);
+  return new BN(offset.toString());
+}
+
 /**
  * Queue init_planet MPC computation.
  */
@@ -923,13 +1158,14 @@ export async function queueInitPlanet(
   const [gamePDA] = deriveGamePDA(gameId, program.programId);
   const [planetPDA] = deriveCelestialBodyPDA(gameId, planetHash, program.programId);
   const [pendingMovesPDA] = derivePendingMovesPDA(gameId, planetHash, program.programId);
+  const [combatLogPDA] = deriveCombatLogPDA(gameId, planetHash, program.programId);
 
   const nonce = randomBytes(16);
   const nonceValue = deserializeLE(nonce);
   const values = buildInitPlanetValues(x, y);
   const { packed } = encryptAndPack(encCtx.cipher, values, nonce);
 
-  const computationOffset = new BN(randomBytes(8), "hex");
+  const computationOffset = await nextComputationOffset(program, gameId, payer.publicKey);
   const arciumAccts = getArciumAccountAddresses(program, computationOffset, "init_planet");
 
   const observerKey = x25519.utils.randomSecretKey();
@@ -937,18 +1173,20 @@ export async function queueInitPlanet(
 
   await program.methods
     .queueInitPlanet(
-      computationOffset,
       Array.from(planetHash) as any,
       Buffer.from(packed) as any,
       Array.from(encCtx.publicKey) as any,
       new BN(nonceValue.toString()),
-      Array.from(observerPubkey) as any
+      Array.from(observerPubkey) as any,
+      null
     )
     .accountsPartial({
       payer: payer.publicKey,
+      offsetTracker: deriveComputationOffsetTrackerPDA(gameId, payer.publicKey, program.programId)[0],
       game: gamePDA,
       celestialBody: planetPDA,
       pendingMoves: pendingMovesPDA,
+      combatLog: combatLogPDA,
       ...arciumAccts,
     })
     .signers([payer])
@@ -975,13 +1213,14 @@ export async function queueInitSpawnPlanet(
   const [playerPDA] = derivePlayerPDA(gameId, payer.publicKey, program.programId);
   const [planetPDA] = deriveCelestialBodyPDA(gameId, planetHash, program.programId);
   const [pendingMovesPDA] = derivePendingMovesPDA(gameId, planetHash, program.programId);
+  const [combatLogPDA] = deriveCombatLogPDA(gameId, planetHash, program.programId);
 
   const nonce = randomBytes(16);
   const nonceValue = deserializeLE(nonce);
   const values = buildInitSpawnPlanetValues(x, y, playerId, sourcePlanetId);
   const { packed } = encryptAndPack(encCtx.cipher, values, nonce);
 
-  const computationOffset = new BN(randomBytes(8), "hex");
+  const computationOffset = await nextComputationOffset(program, gameId, payer.publicKey);
   const arciumAccts = getArciumAccountAddresses(program, computationOffset, "init_spawn_planet");
 
   const observerKey = x25519.utils.randomSecretKey();
@@ -989,19 +1228,21 @@ export async function queueInitSpawnPlanet(
 
   await program.methods
     .queueInitSpawnPlanet(
-      computationOffset,
       Array.from(planetHash) as any,
       Buffer.from(packed) as any,
       Array.from(encCtx.publicKey) as any,
       new BN(nonceValue.toString()),
-      Array.from(observerPubkey) as any
+      Array.from(observerPubkey) as any,
+      null
     )
     .accountsPartial({
       payer: payer.publicKey,
+      offsetTracker: deriveComputationOffsetTrackerPDA(gameId, payer.publicKey, program.programId)[0],
       game: gamePDA,
       player: playerPDA,
       celestialBody: planetPDA,
       pendingMoves: pendingMovesPDA,
+      combatLog: combatLogPDA,
       ...arciumAccts,
     })
     .signers([payer])
@@ -1021,12 +1262,20 @@ export async function queueProcessMove(
   sourcePending: PublicKey,
   targetPending: PublicKey,
   landingSlot: bigint,
-  currentShips: bigint,
-  currentMetal: bigint,
   moveValues: bigint[],
-  encCtx: EncryptionContext
+  encCtx: EncryptionContext,
+  priorityFeeLamports: bigint = 0n,
+  maxObservableDistance?: bigint
 ): Promise<{ computationOffset: BN }> {
   const [gamePDA] = deriveGamePDA(gameId, program.programId);
+  const [playerPDA] = derivePlayerPDA(gameId, payer.publicKey, program.programId);
+
+  // Default to the loosest bound the program accepts
+  const game = await program.account.game.fetch(gamePDA);
+  const distanceBound = maxObservableDistance ?? maxMapDistance(
+    BigInt(game.mapDiameter.toString()),
+    (game.distanceMethod as any).manhattan ? "manhattan" : "chebyshevApprox"
+  );
 
   const pendingData = await program.account.pendingMovesMetadata.fetch(targetPending);
   const predictedMoveId = BigInt(pendingData.nextMoveId.toString()) + BigInt(pendingData.queuedCount);
@@ -1037,26 +1286,23 @@ export async function queueProcessMove(
   const moveNonceValue = deserializeLE(moveNonce);
   const { packed: movePacked } = encryptAndPack(encCtx.cipher, moveValues, moveNonce);
 
-  const computationOffset = new BN(randomBytes(8), "hex");
+  const computationOffset = await nextComputationOffset(program, gameId, payer.publicKey);
   const arciumAccts = getArciumAccountAddresses(program, computationOffset, "process_move");
 
-  const observerKey = x25519.utils.randomSecretKey();
-  const observerPubkey = x25519.getPublicKey(observerKey);
-
   await program.methods
     .queueProcessMove(
-      computationOffset,
       new BN(landingSlot.toString()),
-      new BN(currentShips.toString()),
-      new BN(currentMetal.toString()),
+      new BN(distanceBound.toString()),
       Buffer.from(movePacked) as any,
       Array.from(encCtx.publicKey) as any,
       new BN(moveNonceValue.toString()),
-      Array.from(observerPubkey) as any
+      new BN(priorityFeeLamports.toString())
     )
     .accountsPartial({
       payer: payer.publicKey,
+      offsetTracker: deriveComputationOffsetTrackerPDA(gameId, payer.publicKey, program.programId)[0],
       game: gamePDA,
+      player: playerPDA,
       sourceBody,
       sourcePending,
       targetPending,
@@ -1078,36 +1324,48 @@ export async function queueFlushPlanet(
   celestialBody: PublicKey,
   pendingMoves: PublicKey,
   flushCount: number,
-  flushValues: bigint[],
   moveAccounts: PublicKey[],
-  encCtx: EncryptionContext
+  encCtx: EncryptionContext,
+  priorityFeeLamports: bigint = 0n
 ): Promise<{ computationOffset: BN }> {
-  const flushNonce = randomBytes(16);
-  const flushNonceValue = deserializeLE(flushNonce);
-  const { packed: flushPacked } = encryptAndPack(encCtx.cipher, flushValues, flushNonce);
+  // Attacker player PDA is derived from the payer of the first move being flushed
+  const pendingData = await program.account.pendingMovesMetadata.fetch(pendingMoves);
+  const gameId = BigInt(pendingData.gameId.toString());
+  const [gamePDA] = deriveGamePDA(gameId, program.programId);
+  const moveData = await program.account.pendingMoveAccount.fetch(moveAccounts[0]);
+  const [attackerPlayerPDA] = derivePlayerPDA(gameId, moveData.payer, program.programId);
+  const [combatLogPDA] = deriveCombatLogPDA(
+    gameId,
+    new Uint8Array(pendingData.planetHash as any),
+    program.programId
+  );
 
-  const computationOffset = new BN(randomBytes(8), "hex");
+  const computationOffset = await nextComputationOffset(program, gameId, payer.publicKey);
   const arciumAccts = getArciumAccountAddresses(program, computationOffset, "flush_planet");
 
   await program.methods
     .queueFlushPlanet(
-      computationOffset,
       flushCount,
-      Buffer.from(flushPacked) as any,
       Array.from(encCtx.publicKey) as any,
-      new BN(flushNonceValue.toString())
+      new BN(deserializeLE(randomBytes(16)).toString()),
+      new BN(priorityFeeLamports.toString())
     )
     .accountsPartial({
       payer: payer.publicKey,
+      offsetTracker: deriveComputationOffsetTrackerPDA(gameId, payer.publicKey, program.programId)[0],
+      game: gamePDA,
       celestialBody,
       pendingMoves,
+      combatLog: combatLogPDA,
+      attackerPlayer: attackerPlayerPDA,
+      attackerProfile: null,
       ...arciumAccts,
     })
     .remainingAccounts(
       moveAccounts.map((pubkey) => ({
         pubkey,
         isSigner: false,
-        isWritable: false,
+        isWritable: true,
       }))
     )
     .signers([payer])
@@ -1125,28 +1383,129 @@ export async function queueUpgradePlanet(
   gameId: bigint,
   celestialBody: PublicKey,
   upgradeValues: bigint[],
-  encCtx: EncryptionContext
+  encCtx: EncryptionContext,
+  priorityFeeLamports: bigint = 0n
 ): Promise<{ computationOffset: BN }> {
   const [gamePDA] = deriveGamePDA(gameId, program.programId);
+  const body = await program.account.encryptedCelestialBody.fetch(celestialBody);
+  const [pendingMoves] = derivePendingMovesPDA(
+    gameId,
+    new Uint8Array(body.planetHash),
+    program.programId
+  );
 
   const upgradeNonce = randomBytes(16);
   const upgradeNonceValue = deserializeLE(upgradeNonce);
   const { packed: upgradePacked } = encryptAndPack(encCtx.cipher, upgradeValues, upgradeNonce);
 
-  const computationOffset = new BN(randomBytes(8), "hex");
+  const computationOffset = await nextComputationOffset(program, gameId, payer.publicKey);
   const arciumAccts = getArciumAccountAddresses(program, computationOffset, "upgrade_planet");
 
   await program.methods
     .queueUpgradePlanet(
-      computationOffset,
       Buffer.from(upgradePacked) as any,
       Array.from(encCtx.publicKey) as any,
-      new BN(upgradeNonceValue.toString())
+      new BN(upgradeNonceValue.toString()),
+      new BN(priorityFeeLamports.toString())
+    )
+    .accountsPartial({
+      payer: payer.publicKey,
+      offsetTracker: deriveComputationOffsetTrackerPDA(gameId, payer.publicKey, program.programId)[0],
+      game: gamePDA,
+      celestialBody,
+      pendingMoves,
+      ...arciumAccts,
+    })
+    .signers([payer])
+    .rpc({ skipPreflight: true, commitment: "confirmed" });
+
+  return { computationOffset };
+}
+
+/**
+ * Queue surrender_planet MPC computation.
+ */
+export async function queueSurrenderPlanet(
+  program: Program<EncryptedForest>,
+  payer: Keypair,
+  gameId: bigint,
+  celestialBody: PublicKey,
+  pendingMoves: PublicKey,
+  surrenderValues: bigint[],
+  encCtx: EncryptionContext
+): Promise<{ computationOffset: BN }> {
+  const [gamePDA] = deriveGamePDA(gameId, program.programId);
+  const [playerPDA] = derivePlayerPDA(gameId, payer.publicKey, program.programId);
+
+  const surrenderNonce = randomBytes(16);
+  const surrenderNonceValue = deserializeLE(surrenderNonce);
+  const { packed: surrenderPacked } = encryptAndPack(encCtx.cipher, surrenderValues, surrenderNonce);
+
+  const computationOffset = await nextComputationOffset(program, gameId, payer.publicKey);
+  const arciumAccts = getArciumAccountAddresses(program, computationOffset, "surrender_planet");
+
+  await program.methods
+    .queueSurrenderPlanet(
+      Buffer.from(surrenderPacked) as any,
+      Array.from(encCtx.publicKey) as any,
+      new BN(surrenderNonceValue.toString())
+    )
+    .accountsPartial({
+      payer: payer.publicKey,
+      game: gamePDA,
+      player: playerPDA,
+      celestialBody,
+      pendingMoves,
+      offsetTracker: deriveComputationOffsetTrackerPDA(gameId, payer.publicKey, program.programId)[0],
+      ...arciumAccts,
+    })
+    .signers([payer])
+    .rpc({ skipPreflight: true, commitment: "confirmed" });
+
+  return { computationOffset };
+}
+
+/**
+ * Queue transfer_owner MPC computation.
+ * Both payer and newOwner must be members of the given alliance.
+ */
+export async function queueTransferOwner(
+  program: Program<EncryptedForest>,
+  payer: Keypair,
+  gameId: bigint,
+  newOwner: PublicKey,
+  alliance: PublicKey,
+  celestialBody: PublicKey,
+  pendingMoves: PublicKey,
+  transferValues: bigint[],
+  encCtx: EncryptionContext
+): Promise<{ computationOffset: BN }> {
+  const [gamePDA] = deriveGamePDA(gameId, program.programId);
+  const [playerPDA] = derivePlayerPDA(gameId, payer.publicKey, program.programId);
+  const [newOwnerPlayerPDA] = derivePlayerPDA(gameId, newOwner, program.programId);
+
+  const transferNonce = randomBytes(16);
+  const transferNonceValue = deserializeLE(transferNonce);
+  const { packed: transferPacked } = encryptAndPack(encCtx.cipher, transferValues, transferNonce);
+
+  const computationOffset = await nextComputationOffset(program, gameId, payer.publicKey);
+  const arciumAccts = getArciumAccountAddresses(program, computationOffset, "transfer_owner");
+
+  await program.methods
+    .queueTransferOwner(
+      Buffer.from(transferPacked) as any,
+      Array.from(encCtx.publicKey) as any,
+      new BN(transferNonceValue.toString())
     )
     .accountsPartial({
       payer: payer.publicKey,
       game: gamePDA,
+      player: playerPDA,
+      newOwnerPlayer: newOwnerPlayerPDA,
+      alliance,
       celestialBody,
+      pendingMoves,
+      offsetTracker: deriveComputationOffsetTrackerPDA(gameId, payer.publicKey, program.programId)[0],
       ...arciumAccts,
     })
     .signers([payer])
@@ -1155,6 +1514,200 @@ export async function queueUpgradePlanet(
   return { computationOffset };
 }
 
+/**
+ * Queue transfer_resources MPC computation. Pass an alliance (and the ally
+ * owning the destination) for an allied transfer.
+ */
+export async function queueTransferResources(
+  program: Program<EncryptedForest>,
+  payer: Keypair,
+  gameId: bigint,
+  sourceBody: PublicKey,
+  destBody: PublicKey,
+  sourcePending: PublicKey,
+  destPending: PublicKey,
+  transferValues: bigint[],
+  encCtx: EncryptionContext,
+  alliance: PublicKey | null = null,
+  ally: PublicKey = PublicKey.default
+): Promise<{ computationOffset: BN }> {
+  const [gamePDA] = deriveGamePDA(gameId, program.programId);
+
+  const transferNonce = randomBytes(16);
+  const transferNonceValue = deserializeLE(transferNonce);
+  const { packed: transferPacked } = encryptAndPack(encCtx.cipher, transferValues, transferNonce);
+
+  const computationOffset = await nextComputationOffset(program, gameId, payer.publicKey);
+  const arciumAccts = getArciumAccountAddresses(program, computationOffset, "transfer_resources");
+
+  await program.methods
+    .queueTransferResources(
+      Buffer.from(transferPacked) as any,
+      Array.from(encCtx.publicKey) as any,
+      new BN(transferNonceValue.toString()),
+      ally
+    )
+    .accountsPartial({
+      payer: payer.publicKey,
+      game: gamePDA,
+      sourceBody,
+      destBody,
+      sourcePending,
+      destPending,
+      alliance,
+      offsetTracker: deriveComputationOffsetTrackerPDA(gameId, payer.publicKey, program.programId)[0],
+      ...arciumAccts,
+    } as any)
+    .signers([payer])
+    .rpc({ skipPreflight: true, commitment: "confirmed" });
+
+  return { computationOffset };
+}
+
+/**
+ * Queue mine_asteroid MPC computation.
+ */
+export async function queueMineAsteroid(
+  program: Program<EncryptedForest>,
+  payer: Keypair,
+  gameId: bigint,
+  celestialBody: PublicKey,
+  pendingMoves: PublicKey,
+  mineValues: bigint[],
+  encCtx: EncryptionContext
+): Promise<{ computationOffset: BN }> {
+  const [gamePDA] = deriveGamePDA(gameId, program.programId);
+
+  const mineNonce = randomBytes(16);
+  const mineNonceValue = deserializeLE(mineNonce);
+  const { packed: minePacked } = encryptAndPack(encCtx.cipher, mineValues, mineNonce);
+
+  const computationOffset = await nextComputationOffset(program, gameId, payer.publicKey);
+  const arciumAccts = getArciumAccountAddresses(program, computationOffset, "mine_asteroid");
+
+  await program.methods
+    .queueMineAsteroid(
+      Buffer.from(minePacked) as any,
+      Array.from(encCtx.publicKey) as any,
+      new BN(mineNonceValue.toString())
+    )
+    .accountsPartial({
+      payer: payer.publicKey,
+      game: gamePDA,
+      celestialBody,
+      pendingMoves,
+      offsetTracker: deriveComputationOffsetTrackerPDA(gameId, payer.publicKey, program.programId)[0],
+      ...arciumAccts,
+    } as any)
+    .signers([payer])
+    .rpc({ skipPreflight: true, commitment: "confirmed" });
+
+  return { computationOffset };
+}
+
+/**
+ * Queue comet_collect MPC computation for a comet spawned on `celestialBody`.
+ */
+export async function queueCometCollect(
+  program: Program<EncryptedForest>,
+  payer: Keypair,
+  gameId: bigint,
+  celestialBody: PublicKey,
+  comet: PublicKey
+): Promise<{ computationOffset: BN }> {
+  const [gamePDA] = deriveGamePDA(gameId, program.programId);
+
+  const computationOffset = await nextComputationOffset(program, gameId, payer.publicKey);
+  const arciumAccts = getArciumAccountAddresses(program, computationOffset, "comet_collect");
+
+  await program.methods
+    .queueCometCollect()
+    .accountsPartial({
+      payer: payer.publicKey,
+      game: gamePDA,
+      celestialBody,
+      comet,
+      offsetTracker: deriveComputationOffsetTrackerPDA(gameId, payer.publicKey, program.programId)[0],
+      ...arciumAccts,
+    } as any)
+    .signers([payer])
+    .rpc({ skipPreflight: true, commitment: "confirmed" });
+
+  return { computationOffset };
+}
+
+/**
+ * Queue comet_drift MPC computation.
+ */
+export async function queueCometDrift(
+  program: Program<EncryptedForest>,
+  payer: Keypair,
+  gameId: bigint,
+  celestialBody: PublicKey,
+  pendingMoves: PublicKey
+): Promise<{ computationOffset: BN }> {
+  const [gamePDA] = deriveGamePDA(gameId, program.programId);
+
+  const computationOffset = await nextComputationOffset(program, gameId, payer.publicKey);
+  const arciumAccts = getArciumAccountAddresses(program, computationOffset, "comet_drift");
+
+  await program.methods
+    .queueCometDrift()
+    .accountsPartial({
+      payer: payer.publicKey,
+      game: gamePDA,
+      celestialBody,
+      pendingMoves,
+      offsetTracker: deriveComputationOffsetTrackerPDA(gameId, payer.publicKey, program.programId)[0],
+      ...arciumAccts,
+    } as any)
+    .signers([payer])
+    .rpc({ skipPreflight: true, commitment: "confirmed" });
+
+  return { computationOffset };
+}
+
+/**
+ * Queue check_spawn_lost (check_ownership circuit) for the payer's spawn.
+ */
+export async function queueCheckSpawnLost(
+  program: Program<EncryptedForest>,
+  payer: Keypair,
+  gameId: bigint,
+  celestialBody: PublicKey,
+  ownershipValues: bigint[],
+  encCtx: EncryptionContext
+): Promise<{ computationOffset: BN }> {
+  const [gamePDA] = deriveGamePDA(gameId, program.programId);
+  const [playerPDA] = derivePlayerPDA(gameId, payer.publicKey, program.programId);
+
+  const ownershipNonce = randomBytes(16);
+  const ownershipNonceValue = deserializeLE(ownershipNonce);
+  const { packed: ownershipPacked } = encryptAndPack(encCtx.cipher, ownershipValues, ownershipNonce);
+
+  const computationOffset = await nextComputationOffset(program, gameId, payer.publicKey);
+  const arciumAccts = getArciumAccountAddresses(program, computationOffset, "check_ownership");
+
+  await program.methods
+    .checkSpawnLost(
+      Buffer.from(ownershipPacked) as any,
+      Array.from(encCtx.publicKey) as any,
+      new BN(ownershipNonceValue.toString())
+    )
+    .accountsPartial({
+      payer: payer.publicKey,
+      game: gamePDA,
+      player: playerPDA,
+      celestialBody,
+      offsetTracker: deriveComputationOffsetTrackerPDA(gameId, payer.publicKey, program.programId)[0],
+      ...arciumAccts,
+    } as any)
+    .signers([payer])
+    .rpc({ skipPreflight: true, commitment: "confirmed" });
+
+  return { computationOffset };
+}
+
 // ---------------------------------------------------------------------------
 // Unique game ID generator
 // ---------------------------------------------------------------------------
@@ -0,0 +1,133 @@
+/**
+ * Leaderboard integration tests.
+ *
+ * Tests:
+ * 1. Admin creates a leaderboard; non-admin is rejected
+ * 2. Players rank themselves via update_leaderboard
+ * 3. Entries never exceed capacity
+ */
+
+import { describe, it, expect, beforeAll } from "vitest";
+import { Program, AnchorProvider, BN } from "@coral-xyz/anchor";
+import { Keypair, SystemProgram, PublicKey } from "@solana/web3.js";
+import type { EncryptedForest } from "../target/types/encrypted_forest";
+import {
+  getProviderAndProgram,
+  readKpJson,
+  airdrop,
+  createGame,
+  initPlayer,
+  defaultGameConfig,
+  deriveGamePDA,
+  derivePlayerPDA,
+  nextGameId,
+} from "./helpers";
+
+function deriveLeaderboardPDA(
+  gameId: bigint,
+  programId: PublicKey
+): [PublicKey, number] {
+  const gameIdBuf = Buffer.alloc(8);
+  gameIdBuf.writeBigUInt64LE(gameId);
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("leaderboard"), gameIdBuf],
+    programId
+  );
+}
+
+describe("Leaderboard", () => {
+  let provider: AnchorProvider;
+  let program: Program<EncryptedForest>;
+  let admin: Keypair;
+
+  beforeAll(async () => {
+    const setup = getProviderAndProgram();
+    provider = setup.provider;
+    program = setup.program;
+    admin = readKpJson(`${process.env.HOME}/.config/solana/id.json`);
+  });
+
+  async function createLeaderboard(
+    gameId: bigint,
+    capacity: number,
+    signer: Keypair = admin
+  ): Promise<PublicKey> {
+    const [gamePDA] = deriveGamePDA(gameId, program.programId);
+    const [leaderboardPDA] = deriveLeaderboardPDA(gameId, program.programId);
+    await program.methods
+      .createLeaderboard(new BN(gameId.toString()), capacity)
+      .accounts({
+        admin: signer.publicKey,
+        game: gamePDA,
+        leaderboard: leaderboardPDA,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .signers([signer])
+      .rpc({ commitment: "confirmed" });
+    return leaderboardPDA;
+  }
+
+  async function updateLeaderboard(gameId: bigint, owner: Keypair) {
+    const [playerPDA] = derivePlayerPDA(gameId, owner.publicKey, program.programId);
+    const [leaderboardPDA] = deriveLeaderboardPDA(gameId, program.programId);
+    await program.methods
+      .updateLeaderboard(new BN(gameId.toString()))
+      .accounts({
+        owner: owner.publicKey,
+        player: playerPDA,
+        leaderboard: leaderboardPDA,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .signers([owner])
+      .rpc({ commitment: "confirmed" });
+  }
+
+  it("lets only the admin create a leaderboard", async () => {
+    const gameId = nextGameId();
+    await createGame(program, admin, defaultGameConfig(gameId));
+
+    const outsider = Keypair.generate();
+    await airdrop(provider, outsider.publicKey, 1);
+    await expect(createLeaderboard(gameId, 5, outsider)).rejects.toThrow();
+
+    const leaderboardPDA = await createLeaderboard(gameId, 5);
+    const leaderboard = await program.account.leaderboard.fetch(leaderboardPDA);
+    expect(leaderboard.capacity).toBe(5);
+    expect(leaderboard.entries.length).toBe(0);
+  });
+
+  it("ranks players who update the leaderboard", async () => {
+    const gameId = nextGameId();
+    await createGame(program, admin, defaultGameConfig(gameId));
+    await initPlayer(program, admin, gameId);
+    const leaderboardPDA = await createLeaderboard(gameId, 5);
+
+    await updateLeaderboard(gameId, admin);
+    // Updating twice must not duplicate the entry
+    await updateLeaderboard(gameId, admin);
+
+    const leaderboard = await program.account.leaderboard.fetch(leaderboardPDA);
+    expect(leaderboard.entries.length).toBe(1);
+    expect(leaderboard.entries[0].owner.toString()).toBe(admin.publicKey.toString());
+    expect(leaderboard.entries[0].points.toString()).toBe("0");
+  });
+
+  it("never grows beyond capacity", async () => {
+    const gameId = nextGameId();
+    await createGame(program, admin, defaultGameConfig(gameId));
+    const leaderboardPDA = await createLeaderboard(gameId, 1);
+
+    const other = Keypair.generate();
+    await airdrop(provider, other.publicKey, 1);
+    await initPlayer(program, admin, gameId);
+    await initPlayer(program, other, gameId);
+
+    await updateLeaderboard(gameId, admin);
+    await updateLeaderboard(gameId, other);
+
+    // Ties keep the incumbent; the newcomer falls off the end
+    const leaderboard = await program.account.leaderboard.fetch(leaderboardPDA);
+    expect(leaderboard.entries.length).toBe(1);
+    expect(leaderboard.entries[0].owner.toString()).toBe(admin.publicKey.toString());
+  });
+});
@@ -30,9 +30,9 @@ import {
   computeLandingSlot,
   derivePlanetPDA,
   derivePendingMovesPDA,
+  deriveCombatLogPDA,
   derivePendingMoveAccountPDA,
   buildProcessMoveValues,
-  buildFlushPlanetValues,
   findSpawnPlanet,
   findPlanetOfType,
   nextGameId,
@@ -42,6 +42,7 @@ import {
   DEFAULT_THRESHOLDS,
   DEFAULT_GAME_SPEED,
   EncryptionContext,
+  playerIdFromPubkey,
 } from "./helpers";
 
 // ---------------------------------------------------------------------------
@@ -83,7 +84,7 @@ describe("Queue Process Move", () => {
     // Spawn at source (playerId=0n for first player, sourcePlanetId=0n for spawn)
     const source = findSpawnPlanet(gameId, DEFAULT_THRESHOLDS);
     const { computationOffset: spawnCO, planetPDA: sourcePDA } = await queueInitSpawnPlanet(
-      program, admin, gameId, source.x, source.y, 0n, 0n, encCtx
+      program, admin, gameId, source.x, source.y, playerIdFromPubkey(admin.publicKey), 0n, encCtx
     );
     await awaitComputationFinalization(provider, spawnCO, program.programId, "confirmed");
 
@@ -111,7 +112,7 @@ describe("Queue Process Move", () => {
     const landingSlot = computeLandingSlot(currentSlot, distance, launchVelocity, 1000n);
 
     const moveValues = buildProcessMoveValues(
-      1n, 0n,    // playerId, sourcePlanetId
+      playerIdFromPubkey(admin.publicKey), 0n, // playerId, sourcePlanetId
       3n, 0n,    // shipsToSend, metalToSend
       source.x, source.y, target.x, target.y,
     );
@@ -129,6 +130,13 @@ describe("Queue Process Move", () => {
     expect(pending.moves[0].landingSlot).toBeDefined();
     expect(pending.moves[0].moveId).toBeDefined();
     expect(pending.moves[0].payer).toBeDefined();
+
+    // The move account remembers where the fleet came from (for CombatEvent)
+    const [moveAccountPDA] = derivePendingMoveAccountPDA(
+      gameId, targetHash, BigInt(pending.moves[0].moveId.toString()), program.programId
+    );
+    const moveAccount = await program.account.pendingMoveAccount.fetch(moveAccountPDA);
+    expect(Buffer.from(moveAccount.sourcePlanetHash)).toEqual(Buffer.from(sourceHash));
   });
 
   it("updates source planet encrypted state after move", async () => {
@@ -143,7 +151,7 @@ describe("Queue Process Move", () => {
 
     const source = findSpawnPlanet(gameId, DEFAULT_THRESHOLDS);
     const { computationOffset: spawnCO, planetPDA: sourcePDA } = await queueInitSpawnPlanet(
-      program, admin, gameId, source.x, source.y, 0n, 0n, encCtx
+      program, admin, gameId, source.x, source.y, playerIdFromPubkey(admin.publicKey), 0n, encCtx
     );
     await awaitComputationFinalization(provider, spawnCO, program.programId, "confirmed");
 
@@ -172,7 +180,7 @@ describe("Queue Process Move", () => {
     const landingSlot = computeLandingSlot(currentSlot, distance, launchVelocity, 1000n);
 
     const moveValues = buildProcessMoveValues(
-      1n, 0n,    // playerId, sourcePlanetId
+      playerIdFromPubkey(admin.publicKey), 0n, // playerId, sourcePlanetId
       3n, 0n,    // shipsToSend, metalToSend
       source.x, source.y, target.x, target.y,
     );
@@ -239,7 +247,7 @@ describe("Queue Flush Planet", () => {
     // Set up: spawn + init target + move
     const source = findSpawnPlanet(gameId, DEFAULT_THRESHOLDS);
     const { computationOffset: spawnCO, planetPDA: sourcePDA } = await queueInitSpawnPlanet(
-      program, admin, gameId, source.x, source.y, 0n, 0n, encCtx
+      program, admin, gameId, source.x, source.y, playerIdFromPubkey(admin.publicKey), 0n, encCtx
     );
     await awaitComputationFinalization(provider, spawnCO, program.programId, "confirmed");
 
@@ -267,7 +275,7 @@ describe("Queue Flush Planet", () => {
     const landingSlot = computeLandingSlot(slot1, distance, launchVelocity, 1000n);
 
     const moveValues = buildProcessMoveValues(
-      1n, 0n,    // playerId, sourcePlanetId
+      playerIdFromPubkey(admin.publicKey), 0n, // playerId, sourcePlanetId
       5n, 0n,    // shipsToSend, metalToSend
       source.x, source.y, target.x, target.y,
     );
@@ -287,15 +295,9 @@ describe("Queue Flush Planet", () => {
 
     // Flush
     const targetBody = await program.account.encryptedCelestialBody.fetch(targetPlanetPDA);
-    const slot2 = BigInt(await provider.connection.getSlot("confirmed"));
-    const flushValues = buildFlushPlanetValues(
-      slot2, 1000n,
-      BigInt(targetBody.lastUpdatedSlot.toString()),
-      1n
-    );
     const { computationOffset: flushCO } = await queueFlushPlanet(
       program, admin, targetPlanetPDA, targetPendingPDA,
-      1, flushValues, [moveAccountPDA], encCtx
+      1, [moveAccountPDA], encCtx
     );
     await awaitComputationFinalization(provider, flushCO, program.programId, "confirmed");
 
@@ -306,5 +308,11 @@ describe("Queue Flush Planet", () => {
     // Verify last_flushed_slot was updated
     const bodyAfter = await program.account.encryptedCelestialBody.fetch(targetPlanetPDA);
     expect(Number(bodyAfter.lastFlushedSlot)).toBeGreaterThanOrEqual(Number(targetBody.lastFlushedSlot));
+
+    // Every flushed arrival is counted on the target's combat log
+    const [combatLogPDA] = deriveCombatLogPDA(gameId, targetHash, program.programId);
+    const combatLog = await program.account.combatLog.fetch(combatLogPDA);
+    expect(combatLog.totalAttacks).toBe(1);
+    expect(combatLog.totalDefenses + combatLog.totalCaptures).toBe(1);
   });
 });
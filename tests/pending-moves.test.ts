@@ -0,0 +1,292 @@
+/**
+ * Pending move maintenance tests.
+ *
+ * Tests:
+ * 1. abandon_move_batch closes the caller's unlanded move and drops its entry
+ * 2. abandon_move_batch rejects another player's move
+ * 3. cleanup_move_account closes an orphaned move after the game ends
+ * 4. cleanup_move_account rejects before the game ends
+ * 5. merge_pending_moves folds the source list into the destination
+ * 6. merge_pending_moves rejects a move its callback has not populated
+ *
+ * In-flight moves are staged with Surfpool's surfnet_setAccount cheatcode
+ * instead of a process_move round trip, so these run without Arcium.
+ *
+ * REQUIRES: Surfpool running
+ */
+
+import { describe, it, expect, beforeAll } from "vitest";
+import { Program, AnchorProvider, BN } from "@coral-xyz/anchor";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import { randomBytes } from "crypto";
+import type { EncryptedForest } from "../target/types/encrypted_forest";
+import {
+  getProviderAndProgram,
+  readKpJson,
+  airdrop,
+  createGame,
+  defaultGameConfig,
+  shortGameConfig,
+  advancePastSlot,
+  deriveGamePDA,
+  derivePendingMovesPDA,
+  derivePendingMoveAccountPDA,
+  stageProgramAccount,
+  nextGameId,
+} from "./helpers";
+
+interface StagedMove {
+  moveId: bigint;
+  landingSlot: bigint;
+  payer: PublicKey;
+  populated?: boolean;
+}
+
+describe("Pending Move Maintenance", () => {
+  let provider: AnchorProvider;
+  let program: Program<EncryptedForest>;
+  let admin: Keypair;
+
+  beforeAll(async () => {
+    const setup = getProviderAndProgram();
+    provider = setup.provider;
+    program = setup.program;
+    admin = readKpJson(`${process.env.HOME}/.config/solana/id.json`);
+  });
+
+  /** Stage a planet's pending list plus one PendingMoveAccount per move. */
+  async function stageMoves(
+    gameId: bigint,
+    planetHash: Uint8Array,
+    moves: StagedMove[]
+  ): Promise<{ pendingPDA: PublicKey; movePDAs: PublicKey[] }> {
+    const [pendingPDA] = derivePendingMovesPDA(gameId, planetHash, program.programId);
+    const sorted = [...moves].sort((a, b) =>
+      a.landingSlot === b.landingSlot
+        ? Number(a.moveId - b.moveId)
+        : Number(a.landingSlot - b.landingSlot)
+    );
+    await stageProgramAccount(provider, program, pendingPDA, "PendingMovesMetadata", {
+      gameId: new BN(gameId.toString()),
+      planetHash: Array.from(planetHash),
+      nextMoveId: new BN(moves.length),
+      moveCount: moves.length,
+      capacity: moves.length,
+      queuedCount: 0,
+      queuedLandingSlots: Array.from({ length: 8 }, () => new BN(0)),
+      lastMoveQueuedSlot: new BN(0),
+      moves: sorted.map((m) => ({
+        landingSlot: new BN(m.landingSlot.toString()),
+        moveId: new BN(m.moveId.toString()),
+      })),
+    });
+
+    const movePDAs: PublicKey[] = [];
+    for (const m of moves) {
+      const [movePDA] = derivePendingMoveAccountPDA(gameId, planetHash, m.moveId, program.programId);
+      await stageProgramAccount(provider, program, movePDA, "PendingMoveAccount", {
+        gameId: new BN(gameId.toString()),
+        planetHash: Array.from(planetHash),
+        moveId: new BN(m.moveId.toString()),
+        landingSlot: new BN(m.landingSlot.toString()),
+        payer: m.payer,
+        populated: m.populated ?? true,
+        encNonce: new BN(0),
+        encCiphertexts: Array.from({ length: 4 }, () => Array(32).fill(0)),
+        canonicalPlanetHash: Array.from(planetHash),
+        sourcePlanetHash: Array(32).fill(0),
+        flushQueuedSlot: new BN(0),
+      });
+      movePDAs.push(movePDA);
+    }
+    return { pendingPDA, movePDAs };
+  }
+
+  async function futureSlot(): Promise<bigint> {
+    return BigInt(await provider.connection.getSlot("confirmed")) + 100_000n;
+  }
+
+  it("abandons the caller's unlanded move", async () => {
+    const gameId = nextGameId();
+    await createGame(program, admin, defaultGameConfig(gameId));
+    const targetHash = new Uint8Array(randomBytes(32));
+    const landingSlot = await futureSlot();
+    const { pendingPDA, movePDAs } = await stageMoves(gameId, targetHash, [
+      { moveId: 0n, landingSlot, payer: admin.publicKey },
+      { moveId: 1n, landingSlot: landingSlot + 10n, payer: admin.publicKey },
+    ]);
+
+    await program.methods
+      .abandonMoveBatch(
+        new BN(gameId.toString()),
+        Array.from(targetHash) as any,
+        [new BN(0)]
+      )
+      .accounts({
+        payer: admin.publicKey,
+        game: deriveGamePDA(gameId, program.programId)[0],
+        pendingMoves: pendingPDA,
+      } as any)
+      .remainingAccounts([{ pubkey: movePDAs[0], isSigner: false, isWritable: true }])
+      .signers([admin])
+      .rpc({ commitment: "confirmed" });
+
+    expect(await provider.connection.getAccountInfo(movePDAs[0])).toBeNull();
+    const pending = await program.account.pendingMovesMetadata.fetch(pendingPDA);
+    expect(pending.moves.map((e: any) => e.moveId.toNumber())).toEqual([1]);
+    expect(pending.moveCount).toBe(1);
+  });
+
+  it("rejects abandoning another player's move", async () => {
+    const gameId = nextGameId();
+    await createGame(program, admin, defaultGameConfig(gameId));
+    const outsider = Keypair.generate();
+    await airdrop(provider, outsider.publicKey, 1);
+
+    const targetHash = new Uint8Array(randomBytes(32));
+    const { pendingPDA, movePDAs } = await stageMoves(gameId, targetHash, [
+      { moveId: 0n, landingSlot: await futureSlot(), payer: admin.publicKey },
+    ]);
+
+    await expect(
+      program.methods
+        .abandonMoveBatch(
+          new BN(gameId.toString()),
+          Array.from(targetHash) as any,
+          [new BN(0)]
+        )
+        .accounts({
+          payer: outsider.publicKey,
+          game: deriveGamePDA(gameId, program.programId)[0],
+          pendingMoves: pendingPDA,
+        } as any)
+        .remainingAccounts([{ pubkey: movePDAs[0], isSigner: false, isWritable: true }])
+        .signers([outsider])
+        .rpc({ commitment: "confirmed" })
+    ).rejects.toThrow();
+
+    expect(await provider.connection.getAccountInfo(movePDAs[0])).not.toBeNull();
+  });
+
+  it("cleans up an orphaned move account after the game ends", async () => {
+    const gameId = nextGameId();
+    const config = await shortGameConfig(provider, gameId);
+    await createGame(program, admin, config);
+    const planetHash = new Uint8Array(randomBytes(32));
+    const { pendingPDA, movePDAs } = await stageMoves(gameId, planetHash, [
+      { moveId: 0n, landingSlot: BigInt(config.endSlot.toString()), payer: admin.publicKey },
+    ]);
+    await advancePastSlot(provider, config.endSlot);
+
+    await program.methods
+      .cleanupMoveAccount(
+        new BN(gameId.toString()),
+        Array.from(planetHash) as any,
+        new BN(0)
+      )
+      .accounts({
+        closer: admin.publicKey,
+        game: deriveGamePDA(gameId, program.programId)[0],
+        pendingMoves: pendingPDA,
+        moveAccount: movePDAs[0],
+      } as any)
+      .signers([admin])
+      .rpc({ commitment: "confirmed" });
+
+    expect(await provider.connection.getAccountInfo(movePDAs[0])).toBeNull();
+    const pending = await program.account.pendingMovesMetadata.fetch(pendingPDA);
+    expect(pending.moves.length).toBe(0);
+  });
+
+  it("rejects move account cleanup before the game ends", async () => {
+    const gameId = nextGameId();
+    await createGame(program, admin, defaultGameConfig(gameId));
+    const planetHash = new Uint8Array(randomBytes(32));
+    const { pendingPDA, movePDAs } = await stageMoves(gameId, planetHash, [
+      { moveId: 0n, landingSlot: await futureSlot(), payer: admin.publicKey },
+    ]);
+
+    await expect(
+      program.methods
+        .cleanupMoveAccount(
+          new BN(gameId.toString()),
+          Array.from(planetHash) as any,
+          new BN(0)
+        )
+        .accounts({
+          closer: admin.publicKey,
+          game: deriveGamePDA(gameId, program.programId)[0],
+          pendingMoves: pendingPDA,
+          moveAccount: movePDAs[0],
+        } as any)
+        .signers([admin])
+        .rpc({ commitment: "confirmed" })
+    ).rejects.toThrow();
+  });
+
+  it("merges a planet's pending moves into another planet's list", async () => {
+    const gameId = nextGameId();
+    await createGame(program, admin, defaultGameConfig(gameId));
+    const sourceHash = new Uint8Array(randomBytes(32));
+    const destHash = new Uint8Array(randomBytes(32));
+    const landingSlot = await futureSlot();
+    const source = await stageMoves(gameId, sourceHash, [
+      { moveId: 0n, landingSlot, payer: admin.publicKey },
+    ]);
+    const dest = await stageMoves(gameId, destHash, []);
+
+    await program.methods
+      .mergePendingMoves(
+        new BN(gameId.toString()),
+        Array.from(sourceHash) as any,
+        Array.from(destHash) as any
+      )
+      .accounts({
+        admin: admin.publicKey,
+        game: deriveGamePDA(gameId, program.programId)[0],
+        sourcePending: source.pendingPDA,
+        destPending: dest.pendingPDA,
+      } as any)
+      .remainingAccounts([{ pubkey: source.movePDAs[0], isSigner: false, isWritable: true }])
+      .signers([admin])
+      .rpc({ commitment: "confirmed" });
+
+    expect(await provider.connection.getAccountInfo(source.pendingPDA)).toBeNull();
+    const merged = await program.account.pendingMovesMetadata.fetch(dest.pendingPDA);
+    expect(merged.moves.length).toBe(1);
+    expect(merged.moves[0].landingSlot.toString()).toBe(landingSlot.toString());
+    const moveAccount = await program.account.pendingMoveAccount.fetch(source.movePDAs[0]);
+    expect(Buffer.from(moveAccount.canonicalPlanetHash)).toEqual(Buffer.from(destHash));
+  });
+
+  it("rejects merging a move whose callback has not run", async () => {
+    const gameId = nextGameId();
+    await createGame(program, admin, defaultGameConfig(gameId));
+    const sourceHash = new Uint8Array(randomBytes(32));
+    const destHash = new Uint8Array(randomBytes(32));
+    const source = await stageMoves(gameId, sourceHash, [
+      { moveId: 0n, landingSlot: await futureSlot(), payer: admin.publicKey, populated: false },
+    ]);
+    const dest = await stageMoves(gameId, destHash, []);
+
+    await expect(
+      program.methods
+        .mergePendingMoves(
+          new BN(gameId.toString()),
+          Array.from(sourceHash) as any,
+          Array.from(destHash) as any
+        )
+        .accounts({
+          admin: admin.publicKey,
+          game: deriveGamePDA(gameId, program.programId)[0],
+          sourcePending: source.pendingPDA,
+          destPending: dest.pendingPDA,
+        } as any)
+        .remainingAccounts([{ pubkey: source.movePDAs[0], isSigner: false, isWritable: true }])
+        .signers([admin])
+        .rpc({ commitment: "confirmed" })
+    ).rejects.toThrow();
+
+    expect(await provider.connection.getAccountInfo(source.pendingPDA)).not.toBeNull();
+  });
+});
@@ -8,6 +8,8 @@
  * 4. Verify player account data
  * 5. Reject whitelist player without server signature
  * 6. Reject whitelist player with wrong server key
+ * 7. Enforce max_players and track current_player_count
+ * 8. Count games on a cross-game PlayerProfile
  */
 
 import { describe, it, expect, beforeAll } from "vitest";
@@ -148,6 +150,68 @@ describe("Player Initialization", () => {
     ).rejects.toThrow();
   });
 
+  it("rejects players beyond max_players", async () => {
+    const gameId = nextGameId();
+    const gamePDA = await createGame(
+      program,
+      admin,
+      defaultGameConfig(gameId, { maxPlayers: 1 })
+    );
+
+    const player2 = Keypair.generate();
+    await airdrop(provider, player2.publicKey, 2);
+
+    await initPlayer(program, admin, gameId);
+    await expect(initPlayer(program, player2, gameId)).rejects.toThrow();
+
+    const game = await program.account.game.fetch(gamePDA);
+    expect(game.maxPlayers).toBe(1);
+    expect(game.currentPlayerCount).toBe(1);
+    expect(game.planetCount.toNumber()).toBe(0);
+  });
+
+  it("counts joined games on the player's profile", async () => {
+    const player = Keypair.generate();
+    await airdrop(provider, player.publicKey, 2);
+    const [profilePDA] = PublicKey.findProgramAddressSync(
+      [Buffer.from("profile"), player.publicKey.toBuffer()],
+      program.programId
+    );
+
+    await program.methods
+      .initPlayerProfile()
+      .accounts({
+        owner: player.publicKey,
+        playerProfile: profilePDA,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .signers([player])
+      .rpc({ commitment: "confirmed" });
+
+    for (let i = 0; i < 2; i++) {
+      const gameId = nextGameId();
+      const [gamePDA] = deriveGamePDA(gameId, program.programId);
+      const [playerPDA] = derivePlayerPDA(gameId, player.publicKey, program.programId);
+      await createGame(program, admin, defaultGameConfig(gameId));
+      await program.methods
+        .initPlayer(new BN(gameId.toString()))
+        .accounts({
+          owner: player.publicKey,
+          game: gamePDA,
+          player: playerPDA,
+          playerProfile: profilePDA,
+          systemProgram: SystemProgram.programId,
+        } as any)
+        .signers([player])
+        .rpc({ commitment: "confirmed" });
+    }
+
+    const profile = await program.account.playerProfile.fetch(profilePDA);
+    expect(profile.owner.toString()).toBe(player.publicKey.toString());
+    expect(profile.totalGames).toBe(2);
+    expect(profile.totalWins).toBe(0);
+  });
+
   it("verifies player PDA derivation matches", async () => {
     const gameId = nextGameId();
     const config = defaultGameConfig(gameId);
@@ -0,0 +1,329 @@
+/**
+ * Planet resource instruction tests.
+ *
+ * Tests:
+ * 1. comet_collect absorbs or releases a comet spawned on the planet
+ * 2. comet_collect rejects a comet spawned elsewhere
+ * 3. mine_asteroid re-encrypts the planet; a non-belt keeps last_mined_slot at 0
+ * 4. mine_asteroid rejects a malformed input
+ * 5. comet_drift re-rolls an owned planet's comets
+ * 6. comet_drift rejects games with drift disabled
+ * 7. transfer_resources moves resources to an ally's planet
+ * 8. transfer_resources rejects a planet as its own destination
+ *
+ * REQUIRES: Surfpool + Arcium ARX nodes running
+ */
+
+import { describe, it, expect, beforeAll } from "vitest";
+import { Program, AnchorProvider, BN } from "@coral-xyz/anchor";
+import { Keypair, SystemProgram, PublicKey } from "@solana/web3.js";
+import type { EncryptedForest } from "../target/types/encrypted_forest";
+import {
+  getProviderAndProgram,
+  readKpJson,
+  airdrop,
+  createGame,
+  initPlayer,
+  defaultGameConfig,
+  setupEncryption,
+  queueInitSpawnPlanet,
+  queueCometCollect,
+  queueMineAsteroid,
+  queueCometDrift,
+  queueTransferResources,
+  buildMineAsteroidValues,
+  buildTransferResourcesValues,
+  deriveGamePDA,
+  findSpawnPlanet,
+  findPlanetOfType,
+  nextGameId,
+  awaitComputationFinalization,
+  getArciumEnv,
+  playerIdFromPubkey,
+  CelestialBodyType,
+  DEFAULT_THRESHOLDS,
+  EncryptionContext,
+} from "./helpers";
+
+function deriveCometPDA(gameId: bigint, cometId: bigint, programId: PublicKey): [PublicKey, number] {
+  const gameIdBuf = Buffer.alloc(8);
+  gameIdBuf.writeBigUInt64LE(gameId);
+  const cometIdBuf = Buffer.alloc(8);
+  cometIdBuf.writeBigUInt64LE(cometId);
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("comet"), gameIdBuf, cometIdBuf],
+    programId
+  );
+}
+
+function deriveAlliancePDA(gameId: bigint, allianceId: bigint, programId: PublicKey): [PublicKey, number] {
+  const gameIdBuf = Buffer.alloc(8);
+  gameIdBuf.writeBigUInt64LE(gameId);
+  const allianceIdBuf = Buffer.alloc(8);
+  allianceIdBuf.writeBigUInt64LE(allianceId);
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("alliance"), gameIdBuf, allianceIdBuf],
+    programId
+  );
+}
+
+function stateFingerprint(body: any): string {
+  return (
+    Buffer.from(body.stateEncNonce as any).toString("hex") +
+    body.stateEncCiphertexts.map((c: any) => Buffer.from(c).toString("hex")).join("")
+  );
+}
+
+describe("Planet Resources", () => {
+  let provider: AnchorProvider;
+  let program: Program<EncryptedForest>;
+  let admin: Keypair;
+  let encCtx: EncryptionContext;
+  let arciumAvailable = false;
+
+  beforeAll(async () => {
+    const setup = getProviderAndProgram();
+    provider = setup.provider;
+    program = setup.program;
+    admin = readKpJson(`${process.env.HOME}/.config/solana/id.json`);
+
+    try {
+      getArciumEnv();
+      encCtx = await setupEncryption(provider, program.programId);
+      arciumAvailable = true;
+    } catch {
+      console.log("Arcium environment not available");
+    }
+  });
+
+  /** Create a game, register `payer` and spawn them; returns the spawn. */
+  async function spawnIn(
+    gameId: bigint,
+    payer: Keypair,
+    spawn = findSpawnPlanet(gameId, DEFAULT_THRESHOLDS)
+  ): Promise<{ planetPDA: PublicKey; pendingPDA: PublicKey; hash: Uint8Array }> {
+    await initPlayer(program, payer, gameId);
+    const { computationOffset, planetPDA, pendingMovesPDA } = await queueInitSpawnPlanet(
+      program, payer, gameId, spawn.x, spawn.y, playerIdFromPubkey(payer.publicKey), 0n, encCtx
+    );
+    await awaitComputationFinalization(provider, computationOffset, program.programId, "confirmed");
+    return { planetPDA, pendingPDA: pendingMovesPDA, hash: spawn.hash };
+  }
+
+  async function spawnComet(gameId: bigint, cometId: bigint, locationHash: Uint8Array): Promise<PublicKey> {
+    const [cometPDA] = deriveCometPDA(gameId, cometId, program.programId);
+    await program.methods
+      .spawnComet(
+        new BN(gameId.toString()),
+        new BN(cometId.toString()),
+        { shipCapacity: {} } as any,
+        Array.from(locationHash) as any
+      )
+      .accounts({
+        admin: admin.publicKey,
+        game: deriveGamePDA(gameId, program.programId)[0],
+        comet: cometPDA,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .signers([admin])
+      .rpc({ commitment: "confirmed" });
+    return cometPDA;
+  }
+
+  it("collects a comet spawned on the planet", async () => {
+    if (!arciumAvailable) {
+      console.log("Skipping: Arcium not available");
+      return;
+    }
+
+    const gameId = nextGameId();
+    await createGame(program, admin, defaultGameConfig(gameId));
+    const { planetPDA, hash } = await spawnIn(gameId, admin);
+    const cometPDA = await spawnComet(gameId, 1n, hash);
+    const bodyBefore = await program.account.encryptedCelestialBody.fetch(planetPDA);
+
+    const { computationOffset } = await queueCometCollect(program, admin, gameId, planetPDA, cometPDA);
+    await awaitComputationFinalization(provider, computationOffset, program.programId, "confirmed");
+
+    const bodyAfter = await program.account.encryptedCelestialBody.fetch(planetPDA);
+    expect(stateFingerprint(bodyAfter)).not.toBe(stateFingerprint(bodyBefore));
+    // Collected comets close to the admin; with both slots taken it's released
+    const comet = await program.account.comet.fetchNullable(cometPDA);
+    if (comet) {
+      expect(comet.discoverer.toString()).toBe(PublicKey.default.toString());
+    }
+  });
+
+  it("rejects collecting a comet spawned on another planet", async () => {
+    if (!arciumAvailable) {
+      console.log("Skipping: Arcium not available");
+      return;
+    }
+
+    const gameId = nextGameId();
+    await createGame(program, admin, defaultGameConfig(gameId));
+    const { planetPDA } = await spawnIn(gameId, admin);
+    const cometPDA = await spawnComet(gameId, 1n, new Uint8Array(32).fill(9));
+
+    await expect(
+      queueCometCollect(program, admin, gameId, planetPDA, cometPDA)
+    ).rejects.toThrow();
+
+    const comet = await program.account.comet.fetch(cometPDA);
+    expect(comet.discoverer.toString()).toBe(PublicKey.default.toString());
+  });
+
+  it("mines a planet and leaves a non-belt's cooldown unset", async () => {
+    if (!arciumAvailable) {
+      console.log("Skipping: Arcium not available");
+      return;
+    }
+
+    const gameId = nextGameId();
+    await createGame(program, admin, defaultGameConfig(gameId));
+    const { planetPDA, pendingPDA } = await spawnIn(gameId, admin);
+    const bodyBefore = await program.account.encryptedCelestialBody.fetch(planetPDA);
+
+    const { computationOffset } = await queueMineAsteroid(
+      program, admin, gameId, planetPDA, pendingPDA,
+      buildMineAsteroidValues(playerIdFromPubkey(admin.publicKey)), encCtx
+    );
+    await awaitComputationFinalization(provider, computationOffset, program.programId, "confirmed");
+
+    const bodyAfter = await program.account.encryptedCelestialBody.fetch(planetPDA);
+    expect(stateFingerprint(bodyAfter)).not.toBe(stateFingerprint(bodyBefore));
+    // Spawns are planets, not asteroid belts, so the mine fails in MPC
+    expect(bodyAfter.lastMinedSlot.toNumber()).toBe(0);
+  });
+
+  it("rejects a malformed mine input", async () => {
+    if (!arciumAvailable) {
+      console.log("Skipping: Arcium not available");
+      return;
+    }
+
+    const gameId = nextGameId();
+    await createGame(program, admin, defaultGameConfig(gameId));
+    const { planetPDA, pendingPDA } = await spawnIn(gameId, admin);
+
+    // MineInput is a single ciphertext; two are rejected before queueing
+    await expect(
+      queueMineAsteroid(
+        program, admin, gameId, planetPDA, pendingPDA,
+        [...buildMineAsteroidValues(playerIdFromPubkey(admin.publicKey)), 0n], encCtx
+      )
+    ).rejects.toThrow();
+  });
+
+  it("drifts an owned planet's comets", async () => {
+    if (!arciumAvailable) {
+      console.log("Skipping: Arcium not available");
+      return;
+    }
+
+    const gameId = nextGameId();
+    await createGame(program, admin, defaultGameConfig(gameId, { cometDriftIntervalSlots: new BN(1) }));
+    const { planetPDA, pendingPDA } = await spawnIn(gameId, admin);
+    const bodyBefore = await program.account.encryptedCelestialBody.fetch(planetPDA);
+
+    const { computationOffset } = await queueCometDrift(program, admin, gameId, planetPDA, pendingPDA);
+    await awaitComputationFinalization(provider, computationOffset, program.programId, "confirmed");
+
+    const bodyAfter = await program.account.encryptedCelestialBody.fetch(planetPDA);
+    expect(stateFingerprint(bodyAfter)).not.toBe(stateFingerprint(bodyBefore));
+    expect(bodyAfter.lastUpdatedSlot.toNumber()).toBeGreaterThan(bodyBefore.lastUpdatedSlot.toNumber());
+  });
+
+  it("rejects comet drift when the game disables it", async () => {
+    if (!arciumAvailable) {
+      console.log("Skipping: Arcium not available");
+      return;
+    }
+
+    const gameId = nextGameId();
+    await createGame(program, admin, defaultGameConfig(gameId));
+    const { planetPDA, pendingPDA } = await spawnIn(gameId, admin);
+
+    await expect(
+      queueCometDrift(program, admin, gameId, planetPDA, pendingPDA)
+    ).rejects.toThrow();
+  });
+
+  it("transfers resources to an ally's planet", async () => {
+    if (!arciumAvailable) {
+      console.log("Skipping: Arcium not available");
+      return;
+    }
+
+    const gameId = nextGameId();
+    await createGame(program, admin, defaultGameConfig(gameId));
+    const ally = Keypair.generate();
+    await airdrop(provider, ally.publicKey, 2);
+
+    const source = await spawnIn(gameId, admin);
+    let allySpawn = findPlanetOfType(
+      gameId, DEFAULT_THRESHOLDS, CelestialBodyType.Planet, 1, 1000, 100_000, 50_000
+    );
+    while (allySpawn.properties!.size !== 1) {
+      const next = Number((allySpawn.y + 500n) * 1000n + (allySpawn.x + 500n)) + 1;
+      allySpawn = findPlanetOfType(
+        gameId, DEFAULT_THRESHOLDS, CelestialBodyType.Planet, 1, 1000, 100_000, next
+      );
+    }
+    const dest = await spawnIn(gameId, ally, allySpawn);
+
+    const [alliancePDA] = deriveAlliancePDA(gameId, 1n, program.programId);
+    await program.methods
+      .createAlliance(new BN(gameId.toString()), new BN(1), 2)
+      .accounts({
+        founder: admin.publicKey,
+        game: deriveGamePDA(gameId, program.programId)[0],
+        alliance: alliancePDA,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .signers([admin])
+      .rpc({ commitment: "confirmed" });
+    await program.methods
+      .addAllianceMember(new BN(gameId.toString()), new BN(1), ally.publicKey)
+      .accounts({ founder: admin.publicKey, alliance: alliancePDA } as any)
+      .signers([admin])
+      .rpc({ commitment: "confirmed" });
+
+    const sourceBefore = await program.account.encryptedCelestialBody.fetch(source.planetPDA);
+    const destBefore = await program.account.encryptedCelestialBody.fetch(dest.planetPDA);
+
+    const { computationOffset } = await queueTransferResources(
+      program, admin, gameId,
+      source.planetPDA, dest.planetPDA, source.pendingPDA, dest.pendingPDA,
+      buildTransferResourcesValues(
+        playerIdFromPubkey(admin.publicKey), playerIdFromPubkey(ally.publicKey), 1n, 0n
+      ),
+      encCtx, alliancePDA, ally.publicKey
+    );
+    await awaitComputationFinalization(provider, computationOffset, program.programId, "confirmed");
+
+    const sourceAfter = await program.account.encryptedCelestialBody.fetch(source.planetPDA);
+    const destAfter = await program.account.encryptedCelestialBody.fetch(dest.planetPDA);
+    expect(stateFingerprint(sourceAfter)).not.toBe(stateFingerprint(sourceBefore));
+    expect(stateFingerprint(destAfter)).not.toBe(stateFingerprint(destBefore));
+  });
+
+  it("rejects a transfer to the source planet itself", async () => {
+    if (!arciumAvailable) {
+      console.log("Skipping: Arcium not available");
+      return;
+    }
+
+    const gameId = nextGameId();
+    await createGame(program, admin, defaultGameConfig(gameId));
+    const { planetPDA, pendingPDA } = await spawnIn(gameId, admin);
+    const playerId = playerIdFromPubkey(admin.publicKey);
+
+    await expect(
+      queueTransferResources(
+        program, admin, gameId, planetPDA, planetPDA, pendingPDA, pendingPDA,
+        buildTransferResourcesValues(playerId, playerId, 1n, 0n), encCtx
+      )
+    ).rejects.toThrow();
+  });
+});
@@ -33,6 +33,7 @@ import {
   CelestialBodyType,
   DEFAULT_THRESHOLDS,
   EncryptionContext,
+  playerIdFromPubkey,
 } from "./helpers";
 
 // ---------------------------------------------------------------------------
@@ -84,7 +85,7 @@ describe("Queue Init Planet", () => {
     // Verify EncryptedCelestialBody account
     const body = await program.account.encryptedCelestialBody.fetch(planetPDA);
     expect(body.planetHash).toEqual(Array.from(coord.hash));
-    expect(body.stateEncCiphertexts.length).toBe(3);
+    expect(body.stateEncCiphertexts.length).toBe(4);
     expect(Number(body.lastUpdatedSlot)).toBeGreaterThan(0);
 
     // Verify PendingMovesMetadata account
@@ -155,7 +156,7 @@ describe("Queue Init Spawn Planet", () => {
     const spawn = findSpawnPlanet(gameId, DEFAULT_THRESHOLDS);
 
     const { computationOffset, playerPDA } = await queueInitSpawnPlanet(
-      program, admin, gameId, spawn.x, spawn.y, 0n, 0n, encCtx
+      program, admin, gameId, spawn.x, spawn.y, playerIdFromPubkey(admin.publicKey), 0n, encCtx
     );
 
     await awaitComputationFinalization(
@@ -179,7 +180,7 @@ describe("Queue Init Spawn Planet", () => {
     // First spawn
     const spawn1 = findSpawnPlanet(gameId, DEFAULT_THRESHOLDS);
     const { computationOffset: co1 } = await queueInitSpawnPlanet(
-      program, admin, gameId, spawn1.x, spawn1.y, 0n, 0n, encCtx
+      program, admin, gameId, spawn1.x, spawn1.y, playerIdFromPubkey(admin.publicKey), 0n, encCtx
     );
     await awaitComputationFinalization(provider, co1, program.programId, "confirmed");
 
@@ -190,7 +191,7 @@ describe("Queue Init Spawn Planet", () => {
       if (spawn2.x !== spawn1.x || spawn2.y !== spawn1.y) {
         await expect(
           queueInitSpawnPlanet(
-            program, admin, gameId, spawn2.x, spawn2.y, 0n, 0n, encCtx
+            program, admin, gameId, spawn2.x, spawn2.y, playerIdFromPubkey(admin.publicKey), 0n, encCtx
           )
         ).rejects.toThrow();
       }
@@ -214,7 +215,7 @@ describe("Queue Init Spawn Planet", () => {
     // Should fail because player PDA does not exist
     await expect(
       queueInitSpawnPlanet(
-        program, admin, gameId, spawn.x, spawn.y, 0n, 0n, encCtx
+        program, admin, gameId, spawn.x, spawn.y, playerIdFromPubkey(admin.publicKey), 0n, encCtx
       )
     ).rejects.toThrow();
   });
@@ -242,14 +243,14 @@ describe("Queue Init Spawn Planet", () => {
 
     // Player 1 spawns
     const { computationOffset: co1, playerPDA: p1PDA } = await queueInitSpawnPlanet(
-      program, admin, gameId, spawn1.x, spawn1.y, 0n, 0n, encCtx
+      program, admin, gameId, spawn1.x, spawn1.y, playerIdFromPubkey(admin.publicKey), 0n, encCtx
     );
     await awaitComputationFinalization(provider, co1, program.programId, "confirmed");
 
     // Player 2 spawns at different location
     if (spawn2.x !== spawn1.x || spawn2.y !== spawn1.y) {
       const { computationOffset: co2, playerPDA: p2PDA } = await queueInitSpawnPlanet(
-        program, player2, gameId, spawn2.x, spawn2.y, 0n, 0n, encCtx2
+        program, player2, gameId, spawn2.x, spawn2.y, playerIdFromPubkey(player2.publicKey), 0n, encCtx2
       );
       await awaitComputationFinalization(provider, co2, program.programId, "confirmed");
 
@@ -4,6 +4,7 @@
  * Tests:
  * 1. queue_upgrade_planet flow (requires Arcium)
  * 2. Verify encrypted state changes after upgrade
+ * 3. Reject a priority fee below MIN_PRIORITY_FEE
  *
  * REQUIRES: Surfpool + Arcium ARX nodes running
  */
@@ -30,6 +31,7 @@ import {
   getArciumEnv,
   UpgradeFocus,
   EncryptionContext,
+  playerIdFromPubkey,
 } from "./helpers";
 
 // ---------------------------------------------------------------------------
@@ -71,20 +73,16 @@ describe("Queue Upgrade Planet", () => {
     // Spawn to own a planet
     const spawn = findSpawnPlanet(gameId, defaultGameConfig(gameId).noiseThresholds);
     const { computationOffset: spawnCO, planetPDA } = await queueInitSpawnPlanet(
-      program, admin, gameId, spawn.x, spawn.y, 0n, 0n, encCtx
+      program, admin, gameId, spawn.x, spawn.y, playerIdFromPubkey(admin.publicKey), 0n, encCtx
     );
     await awaitComputationFinalization(provider, spawnCO, program.programId, "confirmed");
 
     // Read state before upgrade
     const bodyBefore = await program.account.encryptedCelestialBody.fetch(planetPDA);
-    const currentSlot = BigInt(await provider.connection.getSlot("confirmed"));
 
     const upgradeValues = buildUpgradePlanetValues(
-      1n,
+      playerIdFromPubkey(admin.publicKey),
       UpgradeFocus.Range,
-      currentSlot,
-      1000n,
-      BigInt(bodyBefore.lastUpdatedSlot.toString()),
       upgradeCost(1)
     );
 
@@ -125,19 +123,15 @@ describe("Queue Upgrade Planet", () => {
 
     const spawn = findSpawnPlanet(gameId, defaultGameConfig(gameId).noiseThresholds);
     const { computationOffset: spawnCO, planetPDA } = await queueInitSpawnPlanet(
-      program, admin, gameId, spawn.x, spawn.y, 0n, 0n, encCtx
+      program, admin, gameId, spawn.x, spawn.y, playerIdFromPubkey(admin.publicKey), 0n, encCtx
     );
     await awaitComputationFinalization(provider, spawnCO, program.programId, "confirmed");
 
     const bodyBefore = await program.account.encryptedCelestialBody.fetch(planetPDA);
-    const currentSlot = BigInt(await provider.connection.getSlot("confirmed"));
 
     const upgradeValues = buildUpgradePlanetValues(
-      1n,
+      playerIdFromPubkey(admin.publicKey),
       UpgradeFocus.LaunchVelocity,
-      currentSlot,
-      1000n,
-      BigInt(bodyBefore.lastUpdatedSlot.toString()),
       upgradeCost(1)
     );
 
@@ -155,4 +149,71 @@ describe("Queue Upgrade Planet", () => {
       Number(bodyBefore.lastUpdatedSlot)
     );
   });
+
+  it("queues upgrade with Balanced focus", async () => {
+    if (!arciumAvailable) {
+      console.log("Skipping: Arcium not available");
+      return;
+    }
+
+    const gameId = nextGameId();
+    await createGame(program, admin, defaultGameConfig(gameId));
+    await initPlayer(program, admin, gameId);
+
+    const spawn = findSpawnPlanet(gameId, defaultGameConfig(gameId).noiseThresholds);
+    const { computationOffset: spawnCO, planetPDA } = await queueInitSpawnPlanet(
+      program, admin, gameId, spawn.x, spawn.y, playerIdFromPubkey(admin.publicKey), 0n, encCtx
+    );
+    await awaitComputationFinalization(provider, spawnCO, program.programId, "confirmed");
+
+    const bodyBefore = await program.account.encryptedCelestialBody.fetch(planetPDA);
+
+    const upgradeValues = buildUpgradePlanetValues(
+      playerIdFromPubkey(admin.publicKey),
+      UpgradeFocus.Balanced,
+      upgradeCost(1)
+    );
+
+    const { computationOffset: upgradeCO } = await queueUpgradePlanet(
+      program, admin, gameId, planetPDA,
+      upgradeValues, encCtx
+    );
+
+    await awaitComputationFinalization(
+      provider, upgradeCO, program.programId, "confirmed"
+    );
+
+    const bodyAfter = await program.account.encryptedCelestialBody.fetch(planetPDA);
+    expect(Number(bodyAfter.lastUpdatedSlot)).toBeGreaterThanOrEqual(
+      Number(bodyBefore.lastUpdatedSlot)
+    );
+  });
+
+  it("rejects a non-zero priority fee below MIN_PRIORITY_FEE", async () => {
+    if (!arciumAvailable) {
+      console.log("Skipping: Arcium not available");
+      return;
+    }
+
+    const gameId = nextGameId();
+    await createGame(program, admin, defaultGameConfig(gameId));
+    await initPlayer(program, admin, gameId);
+
+    const spawn = findSpawnPlanet(gameId, defaultGameConfig(gameId).noiseThresholds);
+    const { computationOffset: spawnCO, planetPDA } = await queueInitSpawnPlanet(
+      program, admin, gameId, spawn.x, spawn.y, playerIdFromPubkey(admin.publicKey), 0n, encCtx
+    );
+    await awaitComputationFinalization(provider, spawnCO, program.programId, "confirmed");
+
+    const upgradeValues = buildUpgradePlanetValues(
+      playerIdFromPubkey(admin.publicKey),
+      UpgradeFocus.Balanced,
+      upgradeCost(1)
+    );
+
+    // MIN_PRIORITY_FEE is 5000 lamports; 0 means no fee
+    await expect(
+      queueUpgradePlanet(program, admin, gameId, planetPDA, upgradeValues, encCtx, 1n)
+    ).rejects.toThrow();
+  });
 });